@@ -0,0 +1,93 @@
+//! Packs small RGBA icons into the font atlas as glyphs, so they can be used in text.
+
+use imgui::*;
+
+mod support;
+
+const ICON_SIZE: u32 = 13;
+/// Codepoints from the Unicode private use area, which fonts leave empty
+const ICON_HEART: char = '\u{e000}';
+const ICON_CHECK: char = '\u{e001}';
+
+/// Returns the RGBA pixels of an icon, from the color of each pixel center in [-1, 1]²
+fn icon_pixels(color: impl Fn(f32, f32) -> Option<[u8; 3]>) -> Vec<u8> {
+    let mut pixels = Vec::new();
+    for y in 0..ICON_SIZE {
+        for x in 0..ICON_SIZE {
+            let to_unit = |v: u32| (v as f32 + 0.5) / ICON_SIZE as f32 * 2.0 - 1.0;
+            match color(to_unit(x), to_unit(y)) {
+                Some([r, g, b]) => pixels.extend_from_slice(&[r, g, b, 255]),
+                None => pixels.extend_from_slice(&[0, 0, 0, 0]),
+            }
+        }
+    }
+    pixels
+}
+
+fn heart(x: f32, y: f32) -> Option<[u8; 3]> {
+    // (x² + y² - 1)³ - x²y³ <= 0, flipped and scaled to fit the icon
+    let (x, y) = (x * 1.2, -y * 1.2 + 0.2);
+    let a = x * x + y * y - 1.0;
+    (a * a * a - x * x * y * y * y <= 0.0).then_some([220, 40, 60])
+}
+
+fn check(x: f32, y: f32) -> Option<[u8; 3]> {
+    let distance_to_segment = |(ax, ay): (f32, f32), (bx, by): (f32, f32)| {
+        let (dx, dy) = (bx - ax, by - ay);
+        let t = (((x - ax) * dx + (y - ay) * dy) / (dx * dx + dy * dy)).clamp(0.0, 1.0);
+        ((x - ax - t * dx).powi(2) + (y - ay - t * dy).powi(2)).sqrt()
+    };
+    let distance = distance_to_segment((-0.8, 0.0), (-0.3, 0.6))
+        .min(distance_to_segment((-0.3, 0.6), (0.8, -0.7)));
+    (distance < 0.2).then_some([60, 200, 80])
+}
+
+fn main() {
+    let mut system = support::init(file!());
+
+    let fonts = system.imgui.fonts();
+    let font = fonts.fonts()[0];
+    let icons = [
+        (ICON_HEART, icon_pixels(heart)),
+        (ICON_CHECK, icon_pixels(check)),
+    ]
+    .map(|(codepoint, pixels)| {
+        let rect = fonts.add_custom_rect_font_glyph(
+            font,
+            codepoint,
+            ICON_SIZE,
+            ICON_SIZE,
+            ICON_SIZE as f32 + 1.0,
+            [0.0, 0.0],
+        );
+        (rect, pixels)
+    });
+
+    // The rectangles only get a position when the atlas is built, and the pixels have to be
+    // written before the texture is uploaded
+    fonts.clear_tex_data();
+    fonts.build_rgba32_texture();
+    for (rect, pixels) in &icons {
+        fonts.write_custom_rect_pixels(*rect, pixels);
+    }
+    system
+        .renderer
+        .reload_font_texture(&mut system.imgui)
+        .expect("Failed to reload fonts");
+
+    system.main_loop(move |run, ui| {
+        ui.window("Custom glyphs")
+            .opened(run)
+            .size([300.0, 150.0], Condition::FirstUseEver)
+            .build(|| {
+                ui.text(format!("{} Favorite", ICON_HEART));
+                ui.text(format!("{} Done", ICON_CHECK));
+                ui.separator();
+                ui.text(format!(
+                    "Icons {} and {} are glyphs, so they flow with text",
+                    ICON_HEART, ICON_CHECK
+                ));
+                ui.button(format!("{} Like", ICON_HEART));
+            });
+    });
+}
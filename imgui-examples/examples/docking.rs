@@ -0,0 +1,48 @@
+//! Restricts where windows can dock with window classes: documents only dock into the
+//! documents dockspace, and tool windows can't be docked there.
+//!
+//! Requires the `docking` feature: `cargo run --example docking --features docking`
+
+use imgui::*;
+
+mod support;
+
+fn main() {
+    let mut system = support::init(file!());
+    system.imgui.io_mut().config_flags |= ConfigFlags::DOCKING_ENABLE;
+
+    system.main_loop(move |run, ui| {
+        // Windows without a class can't be dragged into the documents dockspace
+        let documents = WindowClass::new(ui.get_id("Documents")).docking_allow_unclassed(false);
+
+        let dockspace = ui
+            .window("Editor")
+            .opened(run)
+            .size([600.0, 400.0], Condition::FirstUseEver)
+            .build(|| {
+                let id = ui.get_id("Documents dockspace");
+                ui.dockspace(id, [0.0, 0.0], DockNodeFlags::empty(), Some(&documents))
+            });
+
+        for name in ["notes.txt", "todo.md", "main.rs"] {
+            let mut window = ui.window(name).window_class(&documents);
+            if let Some(dockspace) = dockspace {
+                // Start docked, the user can still move the documents around afterwards
+                window = window.dock_id(dockspace, Condition::FirstUseEver);
+            }
+            window.build(|| {
+                ui.text(format!("Contents of {}", name));
+                ui.text(format!("Docked: {}", ui.is_window_docked()));
+            });
+        }
+
+        for name in ["Properties", "Console"] {
+            ui.window(name)
+                .size([250.0, 150.0], Condition::FirstUseEver)
+                .build(|| {
+                    ui.text("Tool windows dock with each other,");
+                    ui.text("but not into the documents dockspace.");
+                });
+        }
+    });
+}
@@ -0,0 +1,106 @@
+use imgui::*;
+
+mod support;
+
+struct Document {
+    name: String,
+    open: bool,
+    unsaved: bool,
+}
+
+fn main() {
+    let mut documents: Vec<Document> = ["notes.txt", "todo.md", "main.rs"]
+        .iter()
+        .map(|name| Document {
+            name: name.to_string(),
+            open: true,
+            unsaved: false,
+        })
+        .collect();
+    let mut next_document = 1;
+    // Tab to select on the next frame, e.g. right after a document was opened
+    let mut select: Option<String> = None;
+    // Order of the tabs as arranged by the user, read back from their positions
+    let mut order: Vec<String> = Vec::new();
+    // Unsaved document the user asked to close
+    let mut confirm_close: Option<String> = None;
+
+    let system = support::init(file!());
+    system.main_loop(move |run, ui| {
+        ui.window("Document tabs")
+            .opened(run)
+            .size([500.0, 300.0], Condition::FirstUseEver)
+            .build(|| {
+                ui.text("Open:");
+                for document in &documents {
+                    ui.same_line();
+                    if ui.small_button(&document.name) {
+                        select = Some(document.name.clone());
+                    }
+                }
+                ui.text(format!("Tab order: {}", order.join(", ")));
+
+                let mut discard = None;
+                if let Some(name) = &confirm_close {
+                    ui.text(format!("{} has unsaved changes.", name));
+                    ui.same_line();
+                    if ui.small_button("Discard") {
+                        discard = confirm_close.take();
+                    }
+                    ui.same_line();
+                    if ui.small_button("Cancel") {
+                        confirm_close = None;
+                    }
+                }
+                ui.separator();
+
+                let flags = TabBarFlags::REORDERABLE | TabBarFlags::AUTO_SELECT_NEW_TABS;
+                if let Some(_tab_bar) = ui.tab_bar_with_flags("documents", flags) {
+                    // Closing the tab before it is submitted avoids a frame where the tab bar
+                    // still makes room for it.
+                    if let Some(name) = discard {
+                        ui.set_tab_item_closed(&name);
+                        documents.retain(|document| document.name != name);
+                    }
+
+                    if ui.tab_item_button("+", TabItemFlags::TRAILING | TabItemFlags::NO_TOOLTIP) {
+                        documents.push(Document {
+                            name: format!("untitled-{}", next_document),
+                            open: true,
+                            unsaved: true,
+                        });
+                        next_document += 1;
+                    }
+
+                    let mut positions = Vec::new();
+                    for document in documents.iter_mut() {
+                        let mut flags = TabItemFlags::empty();
+                        flags.set(TabItemFlags::UNSAVED_DOCUMENT, document.unsaved);
+                        let tab = TabItem::new(&document.name)
+                            .opened(&mut document.open)
+                            .flags(flags)
+                            .set_selected(select.as_ref() == Some(&document.name))
+                            .begin(ui);
+                        positions.push((ui.item_rect_min()[0], document.name.clone()));
+
+                        if let Some(_tab) = tab {
+                            ui.text(format!("Contents of {}", document.name));
+                            ui.checkbox("Unsaved changes", &mut document.unsaved);
+                        }
+                    }
+                    select = None;
+
+                    positions.sort_by(|a, b| a.0.partial_cmp(&b.0).unwrap());
+                    order = positions.into_iter().map(|(_, name)| name).collect();
+                }
+
+                // Unsaved documents keep their tab when the close button is pressed, so that
+                // the user can be asked what to do with the changes first.
+                for document in documents.iter_mut().filter(|d| !d.open && d.unsaved) {
+                    document.open = true;
+                    confirm_close = Some(document.name.clone());
+                }
+                documents.retain(|document| document.open);
+            });
+    });
+}
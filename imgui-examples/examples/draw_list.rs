@@ -1,4 +1,6 @@
+use imgui::draw_list::DrawFlags;
 use imgui::*;
+use std::f32::consts::PI;
 
 mod support;
 
@@ -133,5 +135,84 @@ fn main() {
                     .filled(true)
                     .build();
             });
+
+        ui.window("Paths and shapes")
+            .size([300.0, 250.0], Condition::FirstUseEver)
+            .position([400.0, 280.0], Condition::FirstUseEver)
+            .scroll_bar(false)
+            .build(|| {
+                let mut draw_list = ui.get_window_draw_list();
+                let o = ui.cursor_screen_pos();
+                let t = ui.time() as f32;
+
+                // A gauge: the track and the value are arcs, stroked as paths
+                let center = [o[0] + 60.0, o[1] + 60.0];
+                let (start, end) = (0.75 * PI, 2.25 * PI);
+                let value = start + (end - start) * (0.5 + 0.5 * t.sin());
+                draw_list.path().arc_to(center, 50.0, start, end).stroke(
+                    [0.3, 0.3, 0.3],
+                    DrawFlags::empty(),
+                    8.0,
+                );
+                draw_list.path().arc_to(center, 50.0, start, value).stroke(
+                    [0.2, 0.8, 0.2],
+                    DrawFlags::empty(),
+                    8.0,
+                );
+
+                // A closed shape mixing straight and curved segments
+                draw_list
+                    .path()
+                    .line_to([o[0] + 140.0, o[1] + 10.0])
+                    .line_to([o[0] + 260.0, o[1] + 10.0])
+                    .bezier_quadratic_curve_to(
+                        [o[0] + 280.0, o[1] + 60.0],
+                        [o[0] + 260.0, o[1] + 110.0],
+                    )
+                    .line_to([o[0] + 140.0, o[1] + 110.0])
+                    .stroke([1.0, 0.5, 0.0], DrawFlags::CLOSED, 2.0);
+
+                // A node-graph style link between two ports
+                let from = [o[0] + 10.0, o[1] + 190.0];
+                let to = [o[0] + 130.0, o[1] + 140.0];
+                draw_list
+                    .add_bezier_curve(
+                        from,
+                        [from[0] + 60.0, from[1]],
+                        [to[0] - 60.0, to[1]],
+                        to,
+                        [0.9, 0.9, 0.2],
+                    )
+                    .thickness(3.0)
+                    .build();
+                draw_list
+                    .add_circle(from, 5.0, [0.9, 0.9, 0.2])
+                    .filled(true)
+                    .build();
+                draw_list
+                    .add_circle(to, 5.0, [0.9, 0.9, 0.2])
+                    .filled(true)
+                    .build();
+
+                draw_list
+                    .add_bezier_quadratic(
+                        [o[0] + 10.0, o[1] + 130.0],
+                        [o[0] + 70.0, o[1] + 100.0],
+                        [o[0] + 130.0, o[1] + 130.0],
+                        [0.6, 0.6, 1.0],
+                    )
+                    .thickness(2.0)
+                    .build();
+
+                draw_list
+                    .add_ngon([o[0] + 170.0, o[1] + 165.0], 25.0, [0.0, 0.7, 1.0], 6)
+                    .filled(true)
+                    .build();
+                draw_list
+                    .add_ellipse([o[0] + 240.0, o[1] + 165.0], [35.0, 15.0], [1.0, 0.3, 0.6])
+                    .rotation(t)
+                    .thickness(2.0)
+                    .build();
+            });
     });
 }
@@ -0,0 +1,142 @@
+use imgui::*;
+
+mod support;
+
+struct FileNode {
+    name: &'static str,
+    kind: &'static str,
+    size: u32,
+    children: Vec<FileNode>,
+}
+
+impl FileNode {
+    fn dir(name: &'static str, children: Vec<FileNode>) -> Self {
+        FileNode {
+            name,
+            kind: "Folder",
+            size: 0,
+            children,
+        }
+    }
+
+    fn file(name: &'static str, kind: &'static str, size: u32) -> Self {
+        FileNode {
+            name,
+            kind,
+            size,
+            children: Vec::new(),
+        }
+    }
+}
+
+fn main() {
+    let root = vec![
+        FileNode::dir(
+            "src",
+            vec![
+                FileNode::file("main.rs", "Rust source", 1204),
+                FileNode::file("lib.rs", "Rust source", 8731),
+                FileNode::dir(
+                    "widget",
+                    vec![
+                        FileNode::file("tree.rs", "Rust source", 15320),
+                        FileNode::file("table.rs", "Rust source", 22018),
+                    ],
+                ),
+            ],
+        ),
+        FileNode::dir(
+            "assets",
+            vec![
+                FileNode::file("logo.png", "Image", 40312),
+                FileNode::dir(
+                    "fonts",
+                    vec![FileNode::file("Roboto-Regular.ttf", "Font", 171676)],
+                ),
+            ],
+        ),
+        FileNode::file("Cargo.toml", "TOML document", 614),
+        FileNode::file("README.md", "Markdown document", 2345),
+    ];
+
+    let system = support::init(file!());
+    system.main_loop(move |run, ui| {
+        ui.window("File tree")
+            .opened(run)
+            .size([600.0, 400.0], Condition::FirstUseEver)
+            .build(|| {
+                // Applied to every folder we visit this frame. Tree node state lives in the
+                // window storage, keyed by the id of the node, so it has to be set with the same
+                // ID stack as the node itself.
+                let mut set_open = None;
+                if ui.button("Expand all") {
+                    set_open = Some(true);
+                }
+                ui.same_line();
+                if ui.button("Collapse all") {
+                    set_open = Some(false);
+                }
+
+                let flags = TableFlags::BORDERS_V
+                    | TableFlags::BORDERS_OUTER_H
+                    | TableFlags::RESIZABLE
+                    | TableFlags::ROW_BG
+                    | TableFlags::NO_BORDERS_IN_BODY;
+                if let Some(_table) = ui.begin_table_with_flags("files", 3, flags) {
+                    let char_width = ui.calc_text_size("A")[0];
+                    ui.table_setup_column_with(TableColumnSetup {
+                        flags: TableColumnFlags::NO_HIDE,
+                        ..TableColumnSetup::new("Name")
+                    });
+                    ui.table_setup_column_with(TableColumnSetup {
+                        flags: TableColumnFlags::WIDTH_FIXED,
+                        init_width_or_weight: char_width * 10.0,
+                        ..TableColumnSetup::new("Size")
+                    });
+                    ui.table_setup_column_with(TableColumnSetup {
+                        flags: TableColumnFlags::WIDTH_FIXED,
+                        init_width_or_weight: char_width * 18.0,
+                        ..TableColumnSetup::new("Type")
+                    });
+                    ui.table_headers_row();
+
+                    for node in &root {
+                        show_node(ui, node, set_open);
+                    }
+                }
+            });
+    });
+}
+
+fn show_node(ui: &Ui, node: &FileNode, set_open: Option<bool>) {
+    ui.table_next_row();
+    ui.table_next_column();
+
+    if node.children.is_empty() {
+        ui.tree_node_config(node.name)
+            .leaf(true)
+            .bullet(true)
+            .tree_push_on_open(false)
+            .span_full_width(true)
+            .push();
+        ui.table_next_column();
+        ui.text(node.size.to_string());
+        ui.table_next_column();
+        ui.text(node.kind);
+    } else {
+        if let Some(open) = set_open {
+            ui.tree_node_set_open(ui.new_id_str(node.name), open);
+        }
+        let token = ui.tree_node_config(node.name).span_full_width(true).push();
+        ui.table_next_column();
+        ui.text_disabled("--");
+        ui.table_next_column();
+        ui.text(node.kind);
+
+        if let Some(_token) = token {
+            for child in &node.children {
+                show_node(ui, child, set_open);
+            }
+        }
+    }
+}
@@ -0,0 +1,135 @@
+//! Compares the stb_truetype and FreeType font rasterizers.
+//!
+//! Requires the `freetype` feature: `cargo run --example freetype --features freetype`
+
+use glium::glutin::event::{Event, WindowEvent};
+use glium::glutin::event_loop::ControlFlow;
+use glium::Surface;
+use imgui::*;
+use std::time::Instant;
+
+#[allow(dead_code)]
+mod support;
+
+const SAMPLE: &str = "The quick brown fox jumps over the lazy dog. 0123456789";
+const FONT_SIZE: f32 = 13.0;
+
+/// Variants of the same font, rasterized with different FreeType options
+const VARIANTS: &[(&str, FreeTypeBuilderFlags)] = &[
+    ("Default hinting", FreeTypeBuilderFlags::empty()),
+    ("No hinting", FreeTypeBuilderFlags::NO_HINTING),
+    ("Light hinting", FreeTypeBuilderFlags::LIGHT_HINTING),
+    (
+        "Monochrome",
+        FreeTypeBuilderFlags::MONO_HINTING.union(FreeTypeBuilderFlags::MONOCHROME),
+    ),
+    ("Bold", FreeTypeBuilderFlags::BOLD),
+    ("Oblique", FreeTypeBuilderFlags::OBLIQUE),
+];
+
+fn add_fonts(fonts: &mut FontAtlas) -> Vec<FontId> {
+    fonts.clear();
+    VARIANTS
+        .iter()
+        .map(|&(_, flags)| {
+            fonts.add_font(&[FontSource::TtfData {
+                data: include_bytes!("../../resources/Roboto-Regular.ttf"),
+                size_pixels: FONT_SIZE,
+                config: Some(FontConfig {
+                    font_builder_flags: flags.bits(),
+                    ..FontConfig::default()
+                }),
+            }])
+        })
+        .collect()
+}
+
+fn main() {
+    let support::System {
+        event_loop,
+        display,
+        mut imgui,
+        mut platform,
+        mut renderer,
+        ..
+    } = support::init(file!());
+
+    let font_ids = add_fonts(imgui.fonts());
+    renderer
+        .reload_font_texture(&mut imgui)
+        .expect("Failed to reload fonts");
+
+    let mut builder = FontBuilder::FreeType;
+    let mut rebuild = false;
+    let mut last_frame = Instant::now();
+
+    event_loop.run(move |event, _, control_flow| match event {
+        Event::NewEvents(_) => {
+            let now = Instant::now();
+            imgui.io_mut().update_delta_time(now - last_frame);
+            last_frame = now;
+        }
+        Event::MainEventsCleared => {
+            // The atlas can't be changed during a frame, so it is rebuilt between frames
+            if rebuild {
+                rebuild = false;
+                imgui.fonts().set_font_builder(builder);
+                imgui.fonts().clear_tex_data();
+                renderer
+                    .reload_font_texture(&mut imgui)
+                    .expect("Failed to reload fonts");
+            }
+
+            let gl_window = display.gl_window();
+            platform
+                .prepare_frame(imgui.io_mut(), gl_window.window())
+                .expect("Failed to prepare frame");
+            gl_window.window().request_redraw();
+        }
+        Event::RedrawRequested(_) => {
+            let ui = imgui.frame();
+
+            let mut run = true;
+            ui.window("Font rasterizers")
+                .opened(&mut run)
+                .size([600.0, 300.0], Condition::FirstUseEver)
+                .build(|| {
+                    rebuild |= ui.radio_button("FreeType", &mut builder, FontBuilder::FreeType);
+                    ui.same_line();
+                    rebuild |=
+                        ui.radio_button("stb_truetype", &mut builder, FontBuilder::StbTrueType);
+                    if builder == FontBuilder::StbTrueType {
+                        ui.text_disabled("stb_truetype ignores the FreeType options");
+                    }
+                    ui.separator();
+
+                    for (&(name, _), &font_id) in VARIANTS.iter().zip(&font_ids) {
+                        ui.text_disabled(name);
+                        let _font = ui.push_font(font_id);
+                        ui.text(SAMPLE);
+                    }
+                });
+            if !run {
+                *control_flow = ControlFlow::Exit;
+            }
+
+            let gl_window = display.gl_window();
+            let mut target = display.draw();
+            target.clear_color_srgb(1.0, 1.0, 1.0, 1.0);
+            platform.prepare_render(ui, gl_window.window());
+            let draw_data = imgui.render();
+            renderer
+                .render(&mut target, draw_data)
+                .expect("Rendering failed");
+            target.finish().expect("Failed to swap buffers");
+        }
+        Event::WindowEvent {
+            event: WindowEvent::CloseRequested,
+            ..
+        } => *control_flow = ControlFlow::Exit,
+        event => {
+            let gl_window = display.gl_window();
+            platform.handle_event(imgui.io_mut(), gl_window.window(), &event);
+        }
+    });
+}
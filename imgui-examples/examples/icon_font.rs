@@ -0,0 +1,86 @@
+//! Merges icons into the default font, so they can be used in any label.
+//!
+//! By default, a few symbols of the M+ font shipped with the examples are used as icons. To
+//! use a real icon font, pass the path of e.g. FontAwesome's `fa-solid-900.ttf` (under the SIL
+//! Open Font License): `cargo run --example icon_font -- path/to/fa-solid-900.ttf`
+
+use imgui::*;
+
+mod support;
+
+/// Icons with their codepoint in the M+ font, and in FontAwesome 6
+struct Icon {
+    mplus: char,
+    font_awesome: char,
+}
+
+const ICON_STAR: Icon = Icon {
+    mplus: '\u{2605}',
+    font_awesome: '\u{f005}',
+};
+const ICON_MUSIC: Icon = Icon {
+    mplus: '\u{266a}',
+    font_awesome: '\u{f001}',
+};
+const ICON_NEXT: Icon = Icon {
+    mplus: '\u{2192}',
+    font_awesome: '\u{f061}',
+};
+
+fn main() {
+    let mut system = support::init(file!());
+
+    let icon_font = std::env::args().nth(1).map(|path| {
+        std::fs::read(&path).unwrap_or_else(|err| panic!("Failed to read {}: {}", path, err))
+    });
+    let (ttf, glyph_ranges): (&[u8], _) = match &icon_font {
+        Some(ttf) => (ttf, FontGlyphRanges::from_slice(&[0xe000, 0xf8ff, 0])),
+        None => (
+            include_bytes!("../../resources/mplus-1p-regular.ttf"),
+            FontGlyphRanges::from_slice(&[0x2190, 0x21ff, 0x2600, 0x26ff, 0]),
+        ),
+    };
+    let icon = |icon: &Icon| {
+        if icon_font.is_some() {
+            icon.font_awesome
+        } else {
+            icon.mplus
+        }
+    };
+    let (star, music, next) = (icon(&ICON_STAR), icon(&ICON_MUSIC), icon(&ICON_NEXT));
+
+    system.imgui.fonts().add_merged_icon_font(
+        ttf,
+        system.font_size,
+        glyph_ranges,
+        IconFontConfig {
+            // Icon fonts are usually drawn a bit high compared to text
+            glyph_offset: [0.0, 1.0],
+            ..IconFontConfig::default()
+        },
+    );
+    system
+        .renderer
+        .reload_font_texture(&mut system.imgui)
+        .expect("Failed to reload fonts");
+
+    let mut favorites = 0;
+    let mut track = 1;
+    system.main_loop(move |run, ui| {
+        ui.window("Icon font")
+            .opened(run)
+            .size([300.0, 150.0], Condition::FirstUseEver)
+            .build(|| {
+                if ui.button(format!("{} Favorite", star)) {
+                    favorites += 1;
+                }
+                ui.same_line();
+                ui.text(format!("{} x{}", star, favorites));
+
+                ui.text(format!("{} Playing track {}", music, track));
+                if ui.button(format!("Next {}", next)) {
+                    track += 1;
+                }
+            });
+    });
+}
@@ -0,0 +1,43 @@
+//! Resolves a conflict between two users of the Space key with key ownership: Space toggles
+//! playback, unless a button has keyboard focus, in which case Space activates the button.
+//!
+//! Use Tab to focus the buttons. Requires the `docking` feature, whose bindings include the
+//! internal key ownership API: `cargo run --example key_owner --features docking`
+
+use imgui::*;
+
+mod support;
+
+fn main() {
+    let mut system = support::init(file!());
+    system.imgui.io_mut().config_flags |= ConfigFlags::NAV_ENABLE_KEYBOARD;
+
+    let mut playing = false;
+    let mut clicks = 0;
+    system.main_loop(move |run, ui| {
+        ui.window("Key owner")
+            .opened(run)
+            .size([300.0, 150.0], Condition::FirstUseEver)
+            .build(|| {
+                for label in ["Record", "Export"] {
+                    if ui.button(label) {
+                        clicks += 1;
+                    }
+                    // The button is submitted before the timeline reads the key, so that its
+                    // lock applies
+                    if ui.is_item_focused() {
+                        ui.set_key_owner(Key::Space, ui.item_id(), KeyOwnerFlags::LOCK_THIS_FRAME);
+                    }
+                    ui.same_line();
+                }
+                ui.new_line();
+                ui.text(format!("Button clicks: {}", clicks));
+
+                if ui.is_key_pressed_no_repeat(Key::Space) {
+                    playing = !playing;
+                }
+                ui.text(if playing { "Playing" } else { "Paused" });
+                ui.text("Press Space to toggle playback");
+            });
+    });
+}
@@ -0,0 +1,73 @@
+use imgui::*;
+
+mod support;
+
+struct Settings {
+    volume: f32,
+    fullscreen: bool,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Settings {
+            volume: 0.8,
+            fullscreen: false,
+        }
+    }
+}
+
+fn main() {
+    let mut settings = Settings::default();
+    let mut settings_open = false;
+    let mut status = String::from("Settings unchanged");
+
+    let system = support::init(file!());
+    system.main_loop(move |run, ui| {
+        ui.window("Modal popups")
+            .opened(run)
+            .size([400.0, 200.0], Condition::FirstUseEver)
+            .build(|| {
+                ui.text(&status);
+                if ui.button("Settings...") {
+                    settings_open = true;
+                    // Must be called with the same ID stack as `modal_popup_config` below
+                    ui.open_popup("Settings");
+                }
+
+                // The close button in the title bar sets `settings_open` to false
+                ui.modal_popup_config("Settings")
+                    .opened(&mut settings_open)
+                    .flags(WindowFlags::ALWAYS_AUTO_RESIZE)
+                    .build(|| {
+                        ui.slider("Volume", 0.0, 1.0, &mut settings.volume);
+                        ui.checkbox("Fullscreen", &mut settings.fullscreen);
+                        ui.separator();
+
+                        if ui.button("Reset...") {
+                            ui.open_popup("Reset settings?");
+                        }
+                        // The confirmation is nested in the settings modal: closing it leaves
+                        // the settings open.
+                        ui.modal_popup_config("Reset settings?")
+                            .always_auto_resize(true)
+                            .build(|| {
+                                ui.text("All settings will be reset to their defaults.");
+                                if ui.button("Reset") {
+                                    settings = Settings::default();
+                                    status = String::from("Settings were reset");
+                                    ui.close_current_popup();
+                                }
+                                ui.same_line();
+                                if ui.button("Cancel") {
+                                    ui.close_current_popup();
+                                }
+                            });
+
+                        ui.same_line();
+                        if ui.button("Close") {
+                            ui.close_current_popup();
+                        }
+                    });
+            });
+    });
+}
@@ -9,7 +9,7 @@ fn main() {
             .window("Progress bar")
             .opened(run)
             .position([20.0, 20.0], Condition::Appearing)
-            .size([700.0, 200.0], Condition::Appearing);
+            .size([700.0, 300.0], Condition::Appearing);
         w.build(|| {
             ui.text("This is a simple progress bar:");
             ProgressBar::new(0.5).build(ui);
@@ -21,6 +21,16 @@ fn main() {
             ui.separator();
             ui.text("This progress bar uses overlay text:");
             ProgressBar::new(0.8).overlay_text("Lorem ipsum").build(ui);
+
+            ui.separator();
+            ui.text("This progress bar hides the percentage:");
+            ProgressBar::new(0.4).show_overlay(false).build(ui);
+
+            ui.separator();
+            ui.text("This progress bar is indeterminate:");
+            ProgressBar::indeterminate()
+                .overlay_text("Working...")
+                .build(ui);
         });
     });
 }
@@ -0,0 +1,75 @@
+use imgui::*;
+
+mod support;
+
+fn main() {
+    let mut rows = vec![
+        ("Ada", "Engineer"),
+        ("Grace", "Admiral"),
+        ("Linus", "Maintainer"),
+        ("Margaret", "Director"),
+        ("Ken", "Researcher"),
+    ];
+    let mut selected: Option<usize> = None;
+
+    let system = support::init(file!());
+    system.main_loop(move |run, ui| {
+        ui.window("Table row selection")
+            .opened(run)
+            .size([500.0, 300.0], Condition::FirstUseEver)
+            .build(|| {
+                match selected {
+                    Some(index) => ui.text(format!("Selected: {}", rows[index].0)),
+                    None => ui.text("Click on a row to select it"),
+                }
+
+                let mut delete = None;
+                let flags = TableFlags::ROW_BG | TableFlags::BORDERS | TableFlags::RESIZABLE;
+                if let Some(_table) = ui.begin_table_with_flags("people", 3, flags) {
+                    ui.table_setup_column("Name");
+                    ui.table_setup_column("Role");
+                    ui.table_setup_column_with(TableColumnSetup {
+                        flags: TableColumnFlags::WIDTH_FIXED,
+                        ..TableColumnSetup::new("")
+                    });
+                    ui.table_headers_row();
+
+                    for (index, (name, role)) in rows.iter().enumerate() {
+                        // Rows may have the same name, so give each one its own id
+                        let _id = ui.push_id_usize(index);
+                        ui.table_next_row();
+
+                        // The selectable goes in the first column, spanning the whole row.
+                        // Allowing overlap keeps the button in the last column clickable.
+                        ui.table_next_column();
+                        if ui
+                            .selectable_config(name)
+                            .selected(selected == Some(index))
+                            .span_all_columns(true)
+                            .allow_overlap(true)
+                            .build()
+                        {
+                            selected = Some(index);
+                        }
+
+                        ui.table_next_column();
+                        ui.text(role);
+
+                        ui.table_next_column();
+                        if ui.small_button("Delete") {
+                            delete = Some(index);
+                        }
+                    }
+                }
+
+                if let Some(index) = delete {
+                    rows.remove(index);
+                    selected = match selected {
+                        Some(s) if s == index => None,
+                        Some(s) if s > index => Some(s - 1),
+                        s => s,
+                    };
+                }
+            });
+    });
+}
@@ -17,6 +17,8 @@ struct State {
     show_app_style_editor: bool,
     show_app_metrics: bool,
     show_app_about: bool,
+    show_app_id_stack_tool: bool,
+    show_app_debug_log: bool,
     no_titlebar: bool,
     no_resize: bool,
     no_move: bool,
@@ -82,6 +84,8 @@ impl Default for State {
             show_app_style_editor: false,
             show_app_metrics: false,
             show_app_about: false,
+            show_app_id_stack_tool: false,
+            show_app_debug_log: false,
             no_titlebar: false,
             no_resize: false,
             no_move: false,
@@ -243,11 +247,9 @@ fn main() {
 
 fn show_help_marker(ui: &Ui, desc: &str) {
     ui.text_disabled("(?)");
-    if ui.is_item_hovered() {
-        ui.tooltip(|| {
-            ui.text(desc);
-        });
-    }
+    ui.item_tooltip(|| {
+        ui.text(desc);
+    });
 }
 
 fn show_user_guide(ui: &Ui) {
@@ -291,6 +293,12 @@ fn show_test_window(ui: &Ui, state: &mut State, opened: &mut bool) {
     if state.show_app_metrics {
         ui.show_metrics_window(&mut state.show_app_metrics);
     }
+    if state.show_app_id_stack_tool {
+        ui.show_id_stack_tool_window(&mut state.show_app_id_stack_tool);
+    }
+    if state.show_app_debug_log {
+        ui.show_debug_log_window(&mut state.show_app_debug_log);
+    }
     if state.show_app_style_editor {
         ui.window("Style Editor")
             .opened(&mut state.show_app_style_editor)
@@ -361,6 +369,26 @@ fn show_test_window(ui: &Ui, state: &mut State, opened: &mut bool) {
                 ui.menu_item_config("Style Editor").build_with_ref(&mut state.show_app_style_editor);
                 ui.menu_item_config("About ImGui").build_with_ref(&mut state.show_app_about);
             }
+            if let Some(_menu) = ui.begin_menu("Debug") {
+                ui.menu_item_config("ID Stack Tool").build_with_ref(&mut state.show_app_id_stack_tool);
+                ui.menu_item_config("Debug Log").build_with_ref(&mut state.show_app_debug_log);
+                // The item picker is part of the internal API, only bound with docking
+                #[cfg(feature = "docking")]
+                {
+                    if ui.menu_item("Item Picker") {
+                        ui.debug_start_item_picker();
+                    }
+                    ui.same_line();
+                    show_help_marker(
+                        ui,
+                        "Will call the IM_DEBUG_BREAK() macro to break in debugger.\n\
+                         Warning: If you don't have a debugger attached, this will probably crash.",
+                    );
+                }
+                if let Some(_encoding) = ui.begin_menu("Text Encoding") {
+                    ui.debug_text_encoding("Hello, ¡Hola!, 20 €");
+                }
+            }
         }
         ui.spacing();
         if CollapsingHeader::new("Help").build(ui) {
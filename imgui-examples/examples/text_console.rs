@@ -0,0 +1,152 @@
+//! A small console, showing how to implement Tab-completion and Up/Down
+//! history navigation with input text callbacks.
+
+use imgui::*;
+
+mod support;
+
+const COMMANDS: &[&str] = &["help", "history", "clear", "classify", "close"];
+
+/// Everything the callbacks need, kept alive across frames.
+#[derive(Default)]
+struct ConsoleState {
+    log: Vec<String>,
+    history: Vec<String>,
+    /// `None` when editing a new line, otherwise the index of the recalled history entry.
+    history_pos: Option<usize>,
+}
+
+impl ConsoleState {
+    fn execute(&mut self, command: &str) {
+        self.log.push(format!("# {}", command));
+        self.history.retain(|entry| entry != command);
+        self.history.push(command.to_owned());
+        self.history_pos = None;
+
+        match command {
+            "help" => self.log.push(format!("Commands: {}", COMMANDS.join(", "))),
+            "history" => {
+                let start = self.history.len().saturating_sub(10);
+                for (i, entry) in self.history.iter().enumerate().skip(start) {
+                    self.log.push(format!("{:3}: {}", i, entry));
+                }
+            }
+            "clear" => self.log.clear(),
+            _ => self.log.push(format!("Unknown command: '{}'", command)),
+        }
+    }
+}
+
+impl InputTextCallbackHandler for ConsoleState {
+    fn on_completion(&mut self, mut data: TextCallbackData) {
+        // Locate the beginning of the word under the cursor
+        let cursor = data.cursor_pos();
+        let word_start = data.str()[..cursor]
+            .rfind(|c: char| c.is_whitespace() || c == ',' || c == ';')
+            .map_or(0, |i| i + 1);
+        let word = data.str()[word_start..cursor].to_owned();
+
+        let candidates: Vec<&str> = COMMANDS
+            .iter()
+            .copied()
+            .filter(|command| command.starts_with(&word))
+            .collect();
+
+        match candidates.as_slice() {
+            [] => self.log.push(format!("No match for \"{}\"!", word)),
+            [single] => {
+                // Single match: replace the word, and add a space so the user can keep typing
+                data.remove_chars(word_start, word.chars().count());
+                data.insert_chars(word_start, single);
+                data.insert_chars(word_start + single.len(), " ");
+            }
+            multiple => {
+                // Multiple matches: complete as much as we can, and list the candidates
+                let mut prefix_len = word.len();
+                while let Some(c) = multiple[0][prefix_len..].chars().next() {
+                    if multiple
+                        .iter()
+                        .all(|candidate| candidate[prefix_len..].starts_with(c))
+                    {
+                        prefix_len += c.len_utf8();
+                    } else {
+                        break;
+                    }
+                }
+                if prefix_len > word.len() {
+                    data.insert_chars(cursor, &multiple[0][word.len()..prefix_len]);
+                }
+
+                self.log.push("Possible matches:".to_owned());
+                for candidate in multiple {
+                    self.log.push(format!("- {}", candidate));
+                }
+            }
+        }
+    }
+
+    fn on_history(&mut self, dir: HistoryDirection, mut data: TextCallbackData) {
+        let prev_pos = self.history_pos;
+        self.history_pos = match (dir, self.history_pos) {
+            (HistoryDirection::Up, None) => self.history.len().checked_sub(1),
+            (HistoryDirection::Up, Some(pos)) => Some(pos.saturating_sub(1)),
+            (HistoryDirection::Down, Some(pos)) if pos + 1 < self.history.len() => Some(pos + 1),
+            (HistoryDirection::Down, _) => None,
+        };
+
+        if prev_pos != self.history_pos {
+            let entry = self
+                .history_pos
+                .map_or("", |pos| self.history[pos].as_str());
+            data.clear();
+            data.push_str(entry);
+        }
+    }
+}
+
+fn main() {
+    let system = support::init(file!());
+    let mut state = ConsoleState::default();
+    let mut input = String::new();
+
+    system.main_loop(move |_, ui| {
+        ui.window("Console")
+            .size([520.0, 400.0], Condition::FirstUseEver)
+            .build(|| {
+                ui.text_wrapped("Enter 'help' for help. Press Tab to complete commands, and Up/Down to navigate the history.");
+                ui.separator();
+
+                let footer_height = ui.frame_height_with_spacing();
+                ui.child_window("scrolling")
+                    .size([0.0, -footer_height])
+                    .build(|| {
+                        for line in &state.log {
+                            ui.text(line);
+                        }
+                        if ui.scroll_y() >= ui.scroll_max_y() {
+                            ui.set_scroll_here_y_with_ratio(1.0);
+                        }
+                    });
+                ui.separator();
+
+                let submitted = ui
+                    .input_text("Input", &mut input)
+                    .enter_returns_true(true)
+                    .callback(
+                        InputTextCallback::COMPLETION | InputTextCallback::HISTORY,
+                        &mut state,
+                    )
+                    .build();
+                if submitted {
+                    let command = input.trim().to_owned();
+                    if !command.is_empty() {
+                        state.execute(&command);
+                    }
+                    input.clear();
+                    // Keep focus on the input after pressing Enter
+                    ui.set_keyboard_focus_here_with_offset(FocusedWidget::Previous);
+                }
+                ui.set_item_default_focus();
+            });
+    });
+}
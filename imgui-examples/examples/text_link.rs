@@ -0,0 +1,38 @@
+use imgui::*;
+
+mod support;
+
+fn main() {
+    let mut clicks = 0;
+
+    let system = support::init(file!());
+    system.main_loop(move |run, ui| {
+        ui.window("About")
+            .opened(run)
+            .size([400.0, 200.0], Condition::FirstUseEver)
+            .build(|| {
+                ui.text("imgui-rs: Rust bindings for Dear ImGui");
+                ui.spacing();
+
+                // Opens the URL in the browser, thanks to the `open-url` feature
+                ui.text("Source code:");
+                ui.same_line();
+                ui.text_link_open_url(
+                    "github.com/imgui-rs/imgui-rs",
+                    "https://github.com/imgui-rs/imgui-rs",
+                );
+
+                ui.text("Based on:");
+                ui.same_line();
+                ui.text_link_open_url("Dear ImGui", "https://github.com/ocornut/imgui");
+
+                ui.separator();
+                // A plain link just reports clicks
+                if ui.text_link("Click me") {
+                    clicks += 1;
+                }
+                ui.same_line();
+                ui.text(format!("Clicked {} times", clicks));
+            });
+    });
+}
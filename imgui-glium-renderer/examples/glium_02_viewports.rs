@@ -0,0 +1,423 @@
+//! Renders imgui windows dragged outside of the main window into their own
+//! OS windows, with `Renderer::render_viewports`. Requires the `docking`
+//! feature.
+//!
+//! The platform side of the viewports is a minimal implementation of
+//! `imgui::PlatformViewportBackend`: each viewport gets a window with its own
+//! glium `Display`. User textures are only drawn in the main window. Windows
+//! can't be positioned on Wayland, where this example doesn't work well.
+
+use std::cell::RefCell;
+use std::collections::{HashMap, VecDeque};
+use std::rc::Rc;
+use std::time::Instant;
+
+use glium::backend::{Context, Facade};
+use glium::glutin::dpi::{LogicalSize, PhysicalPosition, PhysicalSize};
+use glium::glutin::event::{Event, WindowEvent};
+use glium::glutin::event_loop::{ControlFlow, EventLoop, EventLoopWindowTarget};
+use glium::glutin::window::WindowBuilder;
+use glium::{Display, Frame, Surface};
+use imgui::{BackendFlags, ConfigFlags, Id, Viewport, ViewportFlags};
+use imgui_glium_renderer::{Renderer, ViewportDisplays};
+use imgui_winit_support::{HiDpiMode, WinitPlatform};
+
+const TITLE: &str = "Viewports";
+
+fn main() {
+    let event_loop = EventLoop::new();
+    let context = glium::glutin::ContextBuilder::new().with_vsync(true);
+    let builder = WindowBuilder::new()
+        .with_title(TITLE.to_owned())
+        .with_inner_size(LogicalSize::new(1024f64, 768f64));
+    let display =
+        Display::new(builder, context, &event_loop).expect("Failed to initialize display");
+
+    let mut imgui_context = imgui::Context::create();
+    imgui_context.set_ini_filename(None);
+    imgui_context
+        .fonts()
+        .add_font(&[imgui::FontSource::DefaultFontData { config: None }]);
+    let io = imgui_context.io_mut();
+    io.config_flags |= ConfigFlags::DOCKING_ENABLE | ConfigFlags::VIEWPORTS_ENABLE;
+    io.backend_flags |= BackendFlags::PLATFORM_HAS_VIEWPORTS;
+
+    // Viewports are positioned in physical pixels, so the DPI factor is locked
+    let mut winit_platform = WinitPlatform::init(&mut imgui_context);
+    winit_platform.attach_window(
+        imgui_context.io_mut(),
+        display.gl_window().window(),
+        HiDpiMode::Locked(1.0),
+    );
+
+    let monitors = display
+        .gl_window()
+        .window()
+        .available_monitors()
+        .map(|monitor| {
+            let pos = [monitor.position().x as f32, monitor.position().y as f32];
+            let size = [monitor.size().width as f32, monitor.size().height as f32];
+            imgui::PlatformMonitor {
+                main_pos: pos,
+                main_size: size,
+                work_pos: pos,
+                work_size: size,
+                dpi_scale: 1.0,
+            }
+        })
+        .collect::<Vec<_>>();
+    imgui_context
+        .platform_io_mut()
+        .monitors
+        .replace_from_slice(&monitors);
+
+    let platform = Rc::new(RefCell::new(PlatformState::default()));
+    let main_viewport_id = imgui_context.main_viewport().id;
+    platform
+        .borrow_mut()
+        .update_window(main_viewport_id, display.gl_window().window());
+    imgui_context.set_platform_backend(PlatformBackend(platform.clone()));
+
+    let mut renderer =
+        Renderer::init(&mut imgui_context, &display).expect("Failed to initialize renderer");
+    let windows = Windows::default();
+    renderer.enable_viewports(&mut imgui_context, windows.clone());
+
+    let mut last_frame = Instant::now();
+    event_loop.run(move |event, window_target, control_flow| match event {
+        Event::NewEvents(_) => {
+            let now = Instant::now();
+            imgui_context.io_mut().update_delta_time(now - last_frame);
+            last_frame = now;
+        }
+        Event::MainEventsCleared => {
+            let gl_window = display.gl_window();
+            winit_platform
+                .prepare_frame(imgui_context.io_mut(), gl_window.window())
+                .expect("Failed to prepare frame");
+            gl_window.window().request_redraw();
+        }
+        Event::RedrawRequested(_) => {
+            let ui = imgui_context.frame();
+            ui.dockspace_over_main_viewport();
+            ui.show_demo_window(&mut true);
+            ui.window("Drag me outside").build(|| {
+                ui.text("Drag this window outside of the main window");
+                ui.text("to render it into its own OS window.");
+            });
+
+            winit_platform.prepare_render(ui, display.gl_window().window());
+            let draw_data = imgui_context.render();
+            let mut target = display.draw();
+            target.clear_color_srgb(1.0, 1.0, 1.0, 1.0);
+            renderer
+                .render(&mut target, draw_data)
+                .expect("Rendering failed");
+            target.finish().expect("Failed to swap buffers");
+
+            imgui_context.update_platform_windows();
+            apply_platform_events(&platform, &mut imgui_context, &windows, window_target);
+            renderer
+                .render_viewports(&imgui_context)
+                .expect("Rendering the viewports failed");
+        }
+        Event::WindowEvent { window_id, event } => {
+            let main_window_id = display.gl_window().window().id();
+            let (viewport_id, window_display) = if window_id == main_window_id {
+                (main_viewport_id, display.clone())
+            } else if let Some(found) = windows.find(window_id) {
+                found
+            } else {
+                return;
+            };
+            let gl_window = window_display.gl_window();
+            let window = gl_window.window();
+
+            match event {
+                WindowEvent::CursorMoved { position, .. } => {
+                    // With viewports, imgui expects the mouse position on the
+                    // desktop rather than in the window
+                    let window_pos = window.inner_position().unwrap_or_default();
+                    imgui_context.io_mut().add_mouse_pos_event([
+                        (window_pos.x as f64 + position.x) as f32,
+                        (window_pos.y as f64 + position.y) as f32,
+                    ]);
+                    return;
+                }
+                WindowEvent::Moved(_) | WindowEvent::Resized(_) | WindowEvent::Focused(_) => {
+                    let mut platform = platform.borrow_mut();
+                    platform.update_window(viewport_id, window);
+                    if let WindowEvent::Focused(focused) = event {
+                        platform.windows.get_mut(&viewport_id).unwrap().focused = focused;
+                    }
+                }
+                WindowEvent::CloseRequested if viewport_id == main_viewport_id => {
+                    *control_flow = ControlFlow::Exit;
+                }
+                _ => {}
+            }
+
+            if viewport_id != main_viewport_id {
+                if let Some(viewport) = imgui_context.viewport_by_id_mut(viewport_id) {
+                    match event {
+                        WindowEvent::Moved(_) => viewport.platform_request_move = true,
+                        WindowEvent::Resized(_) => viewport.platform_request_resize = true,
+                        WindowEvent::CloseRequested => viewport.platform_request_close = true,
+                        _ => {}
+                    }
+                }
+                // Input from the other windows is handled as if it was
+                // received by the main window
+                if !matches!(
+                    event,
+                    WindowEvent::KeyboardInput { .. }
+                        | WindowEvent::ReceivedCharacter(_)
+                        | WindowEvent::ModifiersChanged(_)
+                        | WindowEvent::MouseInput { .. }
+                        | WindowEvent::MouseWheel { .. }
+                        | WindowEvent::Focused(_)
+                ) {
+                    return;
+                }
+            }
+            let main_gl_window = display.gl_window();
+            let event = Event::WindowEvent::<()> {
+                window_id: main_window_id,
+                event,
+            };
+            winit_platform.handle_event(imgui_context.io_mut(), main_gl_window.window(), &event);
+        }
+        event => {
+            let gl_window = display.gl_window();
+            winit_platform.handle_event(imgui_context.io_mut(), gl_window.window(), &event);
+        }
+    });
+}
+
+/// Requests of imgui to the platform windows, applied in the event loop
+enum PlatformEvent {
+    Create(Id),
+    Destroy(Id),
+    Show(Id),
+    SetPos(Id, [f32; 2]),
+    SetSize(Id, [f32; 2]),
+    Focus(Id),
+    SetTitle(Id, String),
+}
+
+/// State of a platform window, as reported to imgui
+#[derive(Clone, Copy, Default)]
+struct WindowState {
+    pos: [f32; 2],
+    size: [f32; 2],
+    focused: bool,
+    minimized: bool,
+}
+
+#[derive(Default)]
+struct PlatformState {
+    events: VecDeque<PlatformEvent>,
+    windows: HashMap<Id, WindowState>,
+}
+
+impl PlatformState {
+    fn update_window(&mut self, id: Id, window: &glium::glutin::window::Window) {
+        let pos = window.inner_position().unwrap_or_default();
+        let size = window.inner_size();
+        let state = self.windows.entry(id).or_default();
+        state.pos = [pos.x as f32, pos.y as f32];
+        state.size = [size.width as f32, size.height as f32];
+        state.minimized = size.width == 0 || size.height == 0;
+    }
+
+    fn window(&self, id: Id) -> WindowState {
+        self.windows.get(&id).copied().unwrap_or_default()
+    }
+}
+
+struct PlatformBackend(Rc<RefCell<PlatformState>>);
+
+impl PlatformBackend {
+    fn push(&self, event: PlatformEvent) {
+        self.0.borrow_mut().events.push_back(event);
+    }
+}
+
+impl imgui::PlatformViewportBackend for PlatformBackend {
+    fn create_window(&mut self, viewport: &mut Viewport) {
+        let state = WindowState {
+            pos: viewport.pos,
+            size: viewport.size,
+            ..WindowState::default()
+        };
+        let mut platform = self.0.borrow_mut();
+        platform.windows.insert(viewport.id, state);
+        platform
+            .events
+            .push_back(PlatformEvent::Create(viewport.id));
+    }
+
+    fn destroy_window(&mut self, viewport: &mut Viewport) {
+        let mut platform = self.0.borrow_mut();
+        platform.windows.remove(&viewport.id);
+        platform
+            .events
+            .push_back(PlatformEvent::Destroy(viewport.id));
+    }
+
+    fn show_window(&mut self, viewport: &mut Viewport) {
+        self.push(PlatformEvent::Show(viewport.id));
+    }
+
+    fn set_window_pos(&mut self, viewport: &mut Viewport, pos: [f32; 2]) {
+        self.push(PlatformEvent::SetPos(viewport.id, pos));
+    }
+
+    fn get_window_pos(&mut self, viewport: &mut Viewport) -> [f32; 2] {
+        self.0.borrow().window(viewport.id).pos
+    }
+
+    fn set_window_size(&mut self, viewport: &mut Viewport, size: [f32; 2]) {
+        self.push(PlatformEvent::SetSize(viewport.id, size));
+    }
+
+    fn get_window_size(&mut self, viewport: &mut Viewport) -> [f32; 2] {
+        self.0.borrow().window(viewport.id).size
+    }
+
+    fn set_window_focus(&mut self, viewport: &mut Viewport) {
+        self.push(PlatformEvent::Focus(viewport.id));
+    }
+
+    fn get_window_focus(&mut self, viewport: &mut Viewport) -> bool {
+        self.0.borrow().window(viewport.id).focused
+    }
+
+    fn get_window_minimized(&mut self, viewport: &mut Viewport) -> bool {
+        self.0.borrow().window(viewport.id).minimized
+    }
+
+    fn set_window_title(&mut self, viewport: &mut Viewport, title: &str) {
+        self.push(PlatformEvent::SetTitle(viewport.id, title.to_owned()));
+    }
+
+    fn set_window_alpha(&mut self, _viewport: &mut Viewport, _alpha: f32) {}
+
+    fn update_window(&mut self, _viewport: &mut Viewport) {}
+
+    fn render_window(&mut self, _viewport: &mut Viewport) {}
+
+    fn swap_buffers(&mut self, _viewport: &mut Viewport) {}
+
+    fn create_vk_surface(
+        &mut self,
+        _viewport: &mut Viewport,
+        _instance: u64,
+        _out_surface: &mut u64,
+    ) -> i32 {
+        0
+    }
+}
+
+/// The displays of the viewports other than the main one, shared between the
+/// event loop and the renderer
+#[derive(Clone, Default)]
+struct Windows(Rc<RefCell<HashMap<Id, Display>>>);
+
+impl Windows {
+    fn get(&self, id: Id) -> Option<Display> {
+        self.0.borrow().get(&id).cloned()
+    }
+
+    fn find(&self, window_id: glium::glutin::window::WindowId) -> Option<(Id, Display)> {
+        self.0.borrow().iter().find_map(|(&id, display)| {
+            (display.gl_window().window().id() == window_id).then(|| (id, display.clone()))
+        })
+    }
+}
+
+impl ViewportDisplays for Windows {
+    fn draw(&mut self, viewport: &Viewport) -> Option<(Rc<Context>, Frame)> {
+        let display = self.get(viewport.id)?;
+        Some((display.get_context().clone(), display.draw()))
+    }
+}
+
+/// Creates, updates and destroys the windows of the viewports as requested
+/// by imgui
+fn apply_platform_events(
+    platform: &RefCell<PlatformState>,
+    imgui_context: &mut imgui::Context,
+    windows: &Windows,
+    window_target: &EventLoopWindowTarget<()>,
+) {
+    loop {
+        let event = platform.borrow_mut().events.pop_front();
+        let event = match event {
+            Some(event) => event,
+            None => break,
+        };
+        match event {
+            PlatformEvent::Create(id) => {
+                if let Some(viewport) = imgui_context.viewport_by_id(id) {
+                    let display = create_viewport_display(viewport, window_target);
+                    windows.0.borrow_mut().insert(id, display);
+                }
+            }
+            PlatformEvent::Destroy(id) => {
+                // The renderer already dropped the objects of this context
+                windows.0.borrow_mut().remove(&id);
+            }
+            PlatformEvent::Show(id) => {
+                if let Some(display) = windows.get(id) {
+                    display.gl_window().window().set_visible(true);
+                }
+            }
+            PlatformEvent::SetPos(id, pos) => {
+                if let Some(display) = windows.get(id) {
+                    // Viewport windows are created without decorations, so
+                    // their outer and inner positions are the same
+                    display
+                        .gl_window()
+                        .window()
+                        .set_outer_position(PhysicalPosition::new(pos[0], pos[1]));
+                }
+            }
+            PlatformEvent::SetSize(id, size) => {
+                if let Some(display) = windows.get(id) {
+                    // The display resizes its context when drawing the next
+                    // frame
+                    display
+                        .gl_window()
+                        .window()
+                        .set_inner_size(PhysicalSize::new(size[0] as u32, size[1] as u32));
+                }
+            }
+            PlatformEvent::Focus(id) => {
+                if let Some(display) = windows.get(id) {
+                    display.gl_window().window().focus_window();
+                }
+            }
+            PlatformEvent::SetTitle(id, title) => {
+                if let Some(display) = windows.get(id) {
+                    display.gl_window().window().set_title(&title);
+                }
+            }
+        }
+    }
+}
+
+fn create_viewport_display(
+    viewport: &Viewport,
+    window_target: &EventLoopWindowTarget<()>,
+) -> Display {
+    let builder = WindowBuilder::new()
+        .with_position(PhysicalPosition::new(viewport.pos[0], viewport.pos[1]))
+        .with_inner_size(PhysicalSize::new(viewport.size[0], viewport.size[1]))
+        .with_visible(false)
+        .with_decorations(!viewport.flags.contains(ViewportFlags::NO_DECORATION));
+    // Only the main window waits for vsync, or the frame rate would be divided
+    // by the number of windows
+    let context = glium::glutin::ContextBuilder::new().with_vsync(false);
+    Display::new(builder, context, window_target).expect("Failed to create viewport display")
+}
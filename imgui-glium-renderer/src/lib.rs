@@ -10,16 +10,22 @@ use glium::uniforms::{
 };
 use glium::{
     program, uniform, vertex, Blend, DrawError, DrawParameters, IndexBuffer, Program, Rect,
-    Surface, Texture2d, VertexBuffer,
+    Surface, SwapBuffersError, Texture2d, VertexBuffer,
 };
 use imgui::internal::RawWrapper;
-use imgui::{BackendFlags, DrawCmd, DrawCmdParams, DrawData, TextureId, Textures};
+use imgui::{BackendFlags, DrawCmd, DrawCmdParams, DrawDataSource, TextureId, Textures};
 use std::borrow::Cow;
 use std::error::Error;
 use std::fmt;
 use std::rc::Rc;
 use std::usize;
 
+#[cfg(feature = "docking")]
+mod viewports;
+
+#[cfg(feature = "docking")]
+pub use viewports::ViewportDisplays;
+
 #[derive(Clone, Debug)]
 pub enum RendererError {
     Vertex(vertex::BufferCreationError),
@@ -28,6 +34,7 @@ pub enum RendererError {
     Texture(TextureCreationError),
     Draw(DrawError),
     BadTexture(TextureId),
+    SwapBuffers(SwapBuffersError),
 }
 
 impl Error for RendererError {
@@ -40,6 +47,7 @@ impl Error for RendererError {
             Texture(ref e) => Some(e),
             Draw(ref e) => Some(e),
             BadTexture(_) => None,
+            SwapBuffers(ref e) => Some(e),
         }
     }
 }
@@ -54,6 +62,7 @@ impl fmt::Display for RendererError {
             Texture(_) => write!(f, "Texture creation failed"),
             Draw(ref e) => write!(f, "Drawing failed: {}", e),
             BadTexture(ref t) => write!(f, "Bad texture ID: {}", t.id()),
+            SwapBuffers(ref e) => write!(f, "Swapping buffers failed: {}", e),
         }
     }
 }
@@ -88,6 +97,12 @@ impl From<DrawError> for RendererError {
     }
 }
 
+impl From<SwapBuffersError> for RendererError {
+    fn from(e: SwapBuffersError) -> RendererError {
+        RendererError::SwapBuffers(e)
+    }
+}
+
 pub struct Texture {
     pub texture: Rc<Texture2d>,
     pub sampler: SamplerBehavior,
@@ -98,6 +113,8 @@ pub struct Renderer {
     program: Program,
     font_texture: Texture,
     textures: Textures<Texture>,
+    #[cfg(feature = "docking")]
+    viewports: Option<viewports::Viewports>,
 }
 
 #[repr(C)]
@@ -158,39 +175,77 @@ impl Renderer {
             program,
             font_texture,
             textures: Textures::new(),
+            #[cfg(feature = "docking")]
+            viewports: None,
         })
     }
     pub fn reload_font_texture(&mut self, ctx: &mut imgui::Context) -> Result<(), RendererError> {
         self.font_texture = upload_font_texture(ctx.fonts(), &self.ctx)?;
+        #[cfg(feature = "docking")]
+        if let Some(viewports) = &mut self.viewports {
+            viewports.reload_font_atlas(ctx.fonts());
+        }
         Ok(())
     }
     pub fn textures(&mut self) -> &mut Textures<Texture> {
         &mut self.textures
     }
-    fn lookup_texture(&self, texture_id: TextureId) -> Result<&Texture, RendererError> {
+    /// Renders the draw data of a frame, or an [`imgui::OwnedDrawData`] snapshot of it.
+    pub fn render<T: Surface, D: DrawDataSource + ?Sized>(
+        &mut self,
+        target: &mut T,
+        draw_data: &D,
+    ) -> Result<(), RendererError> {
+        let resources = DrawResources {
+            ctx: &self.ctx,
+            program: &self.program,
+            font_texture: &self.font_texture,
+            textures: Some(&self.textures),
+        };
+        resources.render(target, draw_data)
+    }
+}
+
+/// The OpenGL objects used to render draw data into the surfaces of a context
+struct DrawResources<'a> {
+    ctx: &'a Rc<Context>,
+    program: &'a Program,
+    font_texture: &'a Texture,
+    /// `None` if the user textures can't be used in `ctx`, to skip them
+    textures: Option<&'a Textures<Texture>>,
+}
+
+impl DrawResources<'_> {
+    fn lookup_texture(&self, texture_id: TextureId) -> Result<Option<&Texture>, RendererError> {
         if texture_id.id() == usize::MAX {
-            Ok(&self.font_texture)
-        } else if let Some(texture) = self.textures.get(texture_id) {
-            Ok(texture)
+            Ok(Some(self.font_texture))
+        } else if let Some(textures) = self.textures {
+            match textures.get(texture_id) {
+                Some(texture) => Ok(Some(texture)),
+                None => Err(RendererError::BadTexture(texture_id)),
+            }
         } else {
-            Err(RendererError::BadTexture(texture_id))
+            Ok(None)
         }
     }
-    pub fn render<T: Surface>(
-        &mut self,
+    fn render<T: Surface, D: DrawDataSource + ?Sized>(
+        &self,
         target: &mut T,
-        draw_data: &DrawData,
+        draw_data: &D,
     ) -> Result<(), RendererError> {
-        let fb_width = draw_data.display_size[0] * draw_data.framebuffer_scale[0];
-        let fb_height = draw_data.display_size[1] * draw_data.framebuffer_scale[1];
+        let display_pos = draw_data.display_pos();
+        let display_size = draw_data.display_size();
+        let framebuffer_scale = draw_data.framebuffer_scale();
+        let fb_width = display_size[0] * framebuffer_scale[0];
+        let fb_height = display_size[1] * framebuffer_scale[1];
         if !(fb_width > 0.0 && fb_height > 0.0) {
             return Ok(());
         }
         let _ = self.ctx.insert_debug_marker("imgui-rs: starting rendering");
-        let left = draw_data.display_pos[0];
-        let right = draw_data.display_pos[0] + draw_data.display_size[0];
-        let top = draw_data.display_pos[1];
-        let bottom = draw_data.display_pos[1] + draw_data.display_size[1];
+        let left = display_pos[0];
+        let right = display_pos[0] + display_size[0];
+        let top = display_pos[1];
+        let bottom = display_pos[1] + display_size[1];
         let matrix = [
             [(2.0 / (right - left)), 0.0, 0.0, 0.0],
             [0.0, (2.0 / (top - bottom)), 0.0, 0.0],
@@ -202,14 +257,14 @@ impl Renderer {
                 1.0,
             ],
         ];
-        let clip_off = draw_data.display_pos;
-        let clip_scale = draw_data.framebuffer_scale;
-        for draw_list in draw_data.draw_lists() {
-            let vtx_buffer = VertexBuffer::immutable(&self.ctx, unsafe {
+        let clip_off = display_pos;
+        let clip_scale = framebuffer_scale;
+        for draw_list in draw_data.draw_list_refs() {
+            let vtx_buffer = VertexBuffer::immutable(self.ctx, unsafe {
                 draw_list.transmute_vtx_buffer::<GliumDrawVert>()
             })?;
             let idx_buffer = IndexBuffer::immutable(
-                &self.ctx,
+                self.ctx,
                 PrimitiveType::TrianglesList,
                 draw_list.idx_buffer(),
             )?;
@@ -226,19 +281,13 @@ impl Renderer {
                                 ..
                             },
                     } => {
-                        let clip_rect = [
-                            (clip_rect[0] - clip_off[0]) * clip_scale[0],
-                            (clip_rect[1] - clip_off[1]) * clip_scale[1],
-                            (clip_rect[2] - clip_off[0]) * clip_scale[0],
-                            (clip_rect[3] - clip_off[1]) * clip_scale[1],
-                        ];
-
-                        if clip_rect[0] < fb_width
-                            && clip_rect[1] < fb_height
-                            && clip_rect[2] >= 0.0
-                            && clip_rect[3] >= 0.0
+                        if let Some(scissor) =
+                            scissor_rect(clip_rect, clip_off, clip_scale, [fb_width, fb_height])
                         {
-                            let texture = self.lookup_texture(texture_id)?;
+                            let texture = match self.lookup_texture(texture_id)? {
+                                Some(texture) => texture,
+                                None => continue,
+                            };
 
                             target.draw(
                                 vtx_buffer
@@ -247,29 +296,28 @@ impl Renderer {
                                 idx_buffer
                                     .slice(idx_offset..(idx_offset + count))
                                     .expect("Invalid index buffer range"),
-                                &self.program,
+                                self.program,
                                 &uniform! {
                                     matrix: matrix,
                                     tex: Sampler(texture.texture.as_ref(), texture.sampler)
                                 },
                                 &DrawParameters {
                                     blend: Blend::alpha_blending(),
-                                    scissor: Some(Rect {
-                                        left: f32::max(0.0, clip_rect[0]).floor() as u32,
-                                        bottom: f32::max(0.0, fb_height - clip_rect[3]).floor()
-                                            as u32,
-                                        width: (clip_rect[2] - clip_rect[0]).abs().ceil() as u32,
-                                        height: (clip_rect[3] - clip_rect[1]).abs().ceil() as u32,
-                                    }),
+                                    scissor: Some(scissor),
                                     ..DrawParameters::default()
                                 },
                             )?;
                         }
                     }
-                    DrawCmd::ResetRenderState => (), // TODO
-                    DrawCmd::RawCallback { callback, raw_cmd } => unsafe {
-                        callback(draw_list.raw(), raw_cmd)
-                    },
+                    // The whole render state is given to glium with each draw call, so there
+                    // is nothing to reset
+                    DrawCmd::ResetRenderState => (),
+                    DrawCmd::RawCallback { callback, raw_cmd } => {
+                        // Snapshots have no callbacks
+                        if let Some(draw_list) = draw_list.as_draw_list() {
+                            unsafe { callback(draw_list.raw(), raw_cmd) }
+                        }
+                    }
                 }
             }
         }
@@ -278,19 +326,67 @@ impl Renderer {
     }
 }
 
+/// Computes the scissor rectangle of a clip rectangle, offset by the display
+/// position and scaled to framebuffer pixels.
+///
+/// The rectangle is rounded outward, so that adjacent clip rectangles leave no
+/// gap between them at fractional scales, and clamped to the framebuffer
+/// (whose size is truncated). Returns `None` if the rectangle is empty.
+fn scissor_rect(
+    clip_rect: [f32; 4],
+    clip_off: [f32; 2],
+    scale: [f32; 2],
+    fb_size: [f32; 2],
+) -> Option<Rect> {
+    // `max` and `min` would replace NaN coordinates with the bounds
+    if clip_rect.iter().any(|c| c.is_nan()) {
+        return None;
+    }
+    let fb_width = fb_size[0].floor();
+    let fb_height = fb_size[1].floor();
+    let x1 = ((clip_rect[0] - clip_off[0]) * scale[0]).floor().max(0.0);
+    let y1 = ((clip_rect[1] - clip_off[1]) * scale[1]).floor().max(0.0);
+    let x2 = ((clip_rect[2] - clip_off[0]) * scale[0])
+        .ceil()
+        .min(fb_width);
+    let y2 = ((clip_rect[3] - clip_off[1]) * scale[1])
+        .ceil()
+        .min(fb_height);
+    if x2 <= x1 || y2 <= y1 {
+        return None;
+    }
+    Some(Rect {
+        left: x1 as u32,
+        bottom: (fb_height - y2) as u32,
+        width: (x2 - x1) as u32,
+        height: (y2 - y1) as u32,
+    })
+}
+
 fn upload_font_texture(
     fonts: &mut imgui::FontAtlas,
     ctx: &Rc<Context>,
 ) -> Result<Texture, RendererError> {
     let texture = fonts.build_rgba32_texture();
+    let font_texture = create_font_texture(texture.data, texture.width, texture.height, ctx)?;
+    fonts.tex_id = TextureId::from(usize::MAX);
+    Ok(font_texture)
+}
+
+/// Creates the font texture from the RGBA pixels of the font atlas
+fn create_font_texture(
+    pixels: &[u8],
+    width: u32,
+    height: u32,
+    ctx: &Rc<Context>,
+) -> Result<Texture, RendererError> {
     let data = RawImage2d {
-        data: Cow::Borrowed(texture.data),
-        width: texture.width,
-        height: texture.height,
+        data: Cow::Borrowed(pixels),
+        width,
+        height,
         format: ClientFormat::U8U8U8U8,
     };
     let font_texture = Texture2d::with_mipmaps(ctx, data, MipmapsOption::NoMipmap)?;
-    fonts.tex_id = TextureId::from(usize::MAX);
     Ok(Texture {
         texture: Rc::new(font_texture),
         sampler: SamplerBehavior {
@@ -341,3 +437,93 @@ fn compile_default_program<F: Facade>(facade: &F) -> Result<Program, ProgramChoo
         },
     )
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rect(left: u32, bottom: u32, width: u32, height: u32) -> Option<Rect> {
+        Some(Rect {
+            left,
+            bottom,
+            width,
+            height,
+        })
+    }
+
+    #[test]
+    fn test_scissor_rect_fractional_scale() {
+        let fb_size = [100.0, 100.0];
+        assert_eq!(
+            scissor_rect([0.0, 0.0, 10.0, 10.0], [0.0, 0.0], [1.25, 1.25], fb_size),
+            rect(0, 87, 13, 13)
+        );
+        assert_eq!(
+            scissor_rect([1.0, 1.0, 3.0, 3.0], [0.0, 0.0], [1.5, 1.5], fb_size),
+            rect(1, 95, 4, 4)
+        );
+    }
+
+    #[test]
+    fn test_scissor_rect_adjacent_rects_leave_no_gap() {
+        let fb_size = [100.0, 100.0];
+        for scale in [1.25, 1.5] {
+            let left =
+                scissor_rect([0.0, 0.0, 10.0, 10.0], [0.0, 0.0], [scale; 2], fb_size).unwrap();
+            let right =
+                scissor_rect([10.0, 0.0, 20.0, 10.0], [0.0, 0.0], [scale; 2], fb_size).unwrap();
+            assert!(
+                right.left <= left.left + left.width,
+                "gap at scale {}",
+                scale
+            );
+        }
+    }
+
+    #[test]
+    fn test_scissor_rect_clip_offset() {
+        assert_eq!(
+            scissor_rect(
+                [10.0, 20.0, 30.0, 40.0],
+                [10.0, 20.0],
+                [1.0, 1.0],
+                [100.0, 100.0]
+            ),
+            rect(0, 80, 20, 20)
+        );
+    }
+
+    #[test]
+    fn test_scissor_rect_clamped_to_framebuffer() {
+        assert_eq!(
+            scissor_rect(
+                [-5.0, -5.0, 200.0, 200.0],
+                [0.0, 0.0],
+                [1.0, 1.0],
+                [99.6, 50.4]
+            ),
+            rect(0, 0, 99, 50)
+        );
+    }
+
+    #[test]
+    fn test_scissor_rect_empty() {
+        let fb_size = [100.0, 100.0];
+        let scale = [1.0, 1.0];
+        for clip_rect in [
+            [5.0, 5.0, 5.0, 10.0],
+            [5.0, 5.0, 10.0, 5.0],
+            [10.0, 10.0, 5.0, 5.0],
+            [200.0, 0.0, 300.0, 10.0],
+            [f32::NAN, 0.0, 10.0, 10.0],
+            [0.0, 0.0, 10.0, f32::NAN],
+        ] {
+            assert_eq!(
+                scissor_rect(clip_rect, [0.0, 0.0], scale, fb_size),
+                None,
+                "{:?}",
+                clip_rect
+            );
+        }
+    }
+}
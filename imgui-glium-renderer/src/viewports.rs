@@ -0,0 +1,212 @@
+//! Rendering of the extra viewports (platform windows) created by imgui when
+//! `ConfigFlags::VIEWPORTS_ENABLE` is set, with the `docking` feature.
+//!
+//! The platform integration creates an OS window for each viewport, with its
+//! own glium `Display`. Once enabled with [`Renderer::enable_viewports`],
+//! [`Renderer::render_viewports`] renders the draw data of each viewport after
+//! the main window, into the frames given by the [`ViewportDisplays`]
+//! implemented by the application.
+//!
+//! # OpenGL contexts
+//!
+//! glium objects can't be shared between the contexts of different
+//! `Display`s, so the shader program and the font texture are created again
+//! in the context of each viewport, the first time it is rendered. They are
+//! dropped when imgui destroys the viewport.
+//!
+//! The user textures of [`Renderer::textures`] belong to the main context.
+//! glium can't tell whether another context shares objects with it (e.g.
+//! with `ContextBuilder::with_shared_lists`), so the draw commands using user
+//! textures are skipped in the viewports whose context isn't the main one.
+
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::rc::Rc;
+
+use glium::backend::Context;
+use glium::{Frame, Program, Surface};
+use imgui::{BackendFlags, Id, RendererViewportBackend, Viewport, ViewportFlags};
+
+use crate::{
+    compile_default_program, create_font_texture, DrawResources, Renderer, RendererError, Texture,
+};
+
+/// Access to the windows of the viewports, implemented by the application or
+/// its platform integration.
+pub trait ViewportDisplays {
+    /// Starts drawing into the window of `viewport`, returning the context of
+    /// its `Display` and the frame to draw into (e.g. `display.draw()`).
+    ///
+    /// Returns `None` if the viewport has no window (e.g. it is still being
+    /// created), to skip it.
+    fn draw(&mut self, viewport: &Viewport) -> Option<(Rc<Context>, Frame)>;
+}
+
+/// The viewports state of the renderer, see [`Renderer::enable_viewports`]
+pub(crate) struct Viewports {
+    displays: Box<dyn ViewportDisplays>,
+    font_atlas: FontAtlasPixels,
+    /// Shared with the renderer hooks of imgui's platform IO, which drop the
+    /// resources of destroyed viewports
+    resources: Rc<RefCell<HashMap<Id, ViewportResources>>>,
+}
+
+impl Viewports {
+    /// Keeps the pixels of the rebuilt font atlas, and drops the font
+    /// textures of the viewports so that they are created again
+    pub(crate) fn reload_font_atlas(&mut self, fonts: &mut imgui::FontAtlas) {
+        self.font_atlas = FontAtlasPixels::new(fonts);
+        self.resources.borrow_mut().clear();
+    }
+}
+
+/// Copy of the font atlas, to upload it into the context of each viewport
+struct FontAtlasPixels {
+    data: Vec<u8>,
+    width: u32,
+    height: u32,
+}
+
+impl FontAtlasPixels {
+    fn new(fonts: &mut imgui::FontAtlas) -> Self {
+        let texture = fonts.build_rgba32_texture();
+        FontAtlasPixels {
+            data: texture.data.to_vec(),
+            width: texture.width,
+            height: texture.height,
+        }
+    }
+}
+
+/// The objects created in the context of the window of a viewport
+struct ViewportResources {
+    ctx: Rc<Context>,
+    program: Program,
+    font_texture: Texture,
+}
+
+impl ViewportResources {
+    fn new(ctx: Rc<Context>, font_atlas: &FontAtlasPixels) -> Result<Self, RendererError> {
+        let program = compile_default_program(&ctx)?;
+        let font_texture =
+            create_font_texture(&font_atlas.data, font_atlas.width, font_atlas.height, &ctx)?;
+        Ok(ViewportResources {
+            ctx,
+            program,
+            font_texture,
+        })
+    }
+}
+
+impl Renderer {
+    /// Renders the viewports of imgui into the windows given by `displays`,
+    /// from now on. See the [module documentation](self).
+    ///
+    /// This installs the renderer hooks in imgui's platform IO, and sets
+    /// [`BackendFlags::RENDERER_HAS_VIEWPORTS`].
+    pub fn enable_viewports<D: ViewportDisplays + 'static>(
+        &mut self,
+        ctx: &mut imgui::Context,
+        displays: D,
+    ) {
+        let resources = Rc::new(RefCell::new(HashMap::new()));
+        self.viewports = Some(Viewports {
+            displays: Box::new(displays),
+            font_atlas: FontAtlasPixels::new(ctx.fonts()),
+            resources: resources.clone(),
+        });
+        ctx.set_renderer_backend(RendererBackend { resources });
+        ctx.io_mut()
+            .backend_flags
+            .insert(BackendFlags::RENDERER_HAS_VIEWPORTS);
+    }
+
+    /// Renders the extra viewports, each into the window of its own
+    /// `Display`, and swaps their buffers.
+    ///
+    /// Call this after rendering the main viewport and
+    /// [`imgui::Context::update_platform_windows`]. Minimized viewports are
+    /// skipped, and viewports are cleared unless they have the
+    /// [`ViewportFlags::NO_RENDERER_CLEAR`] flag. Does nothing if
+    /// [`Renderer::enable_viewports`] wasn't called.
+    pub fn render_viewports(&mut self, ctx: &imgui::Context) -> Result<(), RendererError> {
+        let viewports = match &mut self.viewports {
+            Some(viewports) => viewports,
+            None => return Ok(()),
+        };
+        let main_ctx = &self.ctx;
+        let textures = &self.textures;
+        let main_viewport_id = ctx.main_viewport().id;
+        for viewport in ctx.viewports() {
+            if viewport.id == main_viewport_id
+                || !viewport.platform_window_created
+                || viewport.flags.contains(ViewportFlags::MINIMIZED)
+            {
+                continue;
+            }
+            let (viewport_ctx, mut frame) = match viewports.displays.draw(viewport) {
+                Some(target) => target,
+                None => continue,
+            };
+
+            let mut resources = viewports.resources.borrow_mut();
+            // The window may have been created again with a new context
+            let outdated = match resources.get(&viewport.id) {
+                Some(resources) => !Rc::ptr_eq(&resources.ctx, &viewport_ctx),
+                None => true,
+            };
+            let result = if outdated {
+                ViewportResources::new(viewport_ctx, &viewports.font_atlas).map(|created| {
+                    resources.insert(viewport.id, created);
+                })
+            } else {
+                Ok(())
+            };
+            let result = result.and_then(|()| {
+                let viewport_resources = &resources[&viewport.id];
+                if !viewport.flags.contains(ViewportFlags::NO_RENDERER_CLEAR) {
+                    frame.clear_color(0.0, 0.0, 0.0, 1.0);
+                }
+                DrawResources {
+                    ctx: &viewport_resources.ctx,
+                    program: &viewport_resources.program,
+                    font_texture: &viewport_resources.font_texture,
+                    // The user textures can only be used in the main context
+                    textures: Rc::ptr_eq(&viewport_resources.ctx, main_ctx).then_some(textures),
+                }
+                .render(&mut frame, viewport.draw_data())
+            });
+            // The frame must be finished even if rendering failed
+            let swapped = frame.finish();
+            result?;
+            swapped?;
+        }
+        Ok(())
+    }
+}
+
+/// The renderer hooks of imgui's platform IO, see
+/// [`Renderer::enable_viewports`]
+struct RendererBackend {
+    resources: Rc<RefCell<HashMap<Id, ViewportResources>>>,
+}
+
+impl RendererViewportBackend for RendererBackend {
+    // The resources of a viewport are created when it is first rendered, once
+    // the platform integration created its window
+    fn create_window(&mut self, _viewport: &mut Viewport) {}
+
+    // Called before the platform integration destroys the window, so that the
+    // OpenGL objects are deleted while its context is alive
+    fn destroy_window(&mut self, viewport: &mut Viewport) {
+        self.resources.borrow_mut().remove(&viewport.id);
+    }
+
+    // glium frames take the size of the window when they are created
+    fn set_window_size(&mut self, _viewport: &mut Viewport, _size: [f32; 2]) {}
+
+    // Viewports are rendered by `Renderer::render_viewports`
+    fn render_window(&mut self, _viewport: &mut Viewport) {}
+
+    fn swap_buffers(&mut self, _viewport: &mut Viewport) {}
+}
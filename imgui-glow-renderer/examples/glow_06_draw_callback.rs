@@ -0,0 +1,200 @@
+//! An example showing custom OpenGL rendering inside of an imgui window, using a draw list
+//! callback to draw a spinning triangle in the middle of imgui's draw order.
+
+use std::{rc::Rc, time::Instant};
+
+use glow::HasContext;
+
+#[allow(dead_code)]
+mod utils;
+
+const VERTEX_SHADER_SOURCE: &str = r#"#version 330
+const vec2 verts[3] = vec2[3](
+    vec2(0.0f, 0.8f),
+    vec2(-0.7f, -0.4f),
+    vec2(0.7f, -0.4f)
+);
+const vec3 colors[3] = vec3[3](
+    vec3(1.0f, 0.0f, 0.0f),
+    vec3(0.0f, 1.0f, 0.0f),
+    vec3(0.0f, 0.0f, 1.0f)
+);
+
+uniform float angle;
+
+out vec3 color;
+
+void main() {
+    vec2 vert = verts[gl_VertexID];
+    float c = cos(angle);
+    float s = sin(angle);
+    color = colors[gl_VertexID];
+    gl_Position = vec4(c * vert.x - s * vert.y, s * vert.x + c * vert.y, 0.0, 1.0);
+}
+"#;
+
+const FRAGMENT_SHADER_SOURCE: &str = r#"#version 330
+in vec3 color;
+
+out vec4 frag_color;
+
+void main() {
+    frag_color = vec4(color, 1.0);
+}
+"#;
+
+/// The OpenGL objects used to draw the triangle
+#[derive(Clone, Copy)]
+struct Spinner {
+    program: glow::Program,
+    vertex_array: glow::VertexArray,
+    angle: Option<glow::UniformLocation>,
+}
+
+impl Spinner {
+    fn new(gl: &glow::Context) -> Self {
+        unsafe {
+            let program = gl.create_program().expect("Cannot create program");
+            let mut shaders = Vec::new();
+            for (kind, source) in [
+                (glow::VERTEX_SHADER, VERTEX_SHADER_SOURCE),
+                (glow::FRAGMENT_SHADER, FRAGMENT_SHADER_SOURCE),
+            ] {
+                let shader = gl.create_shader(kind).expect("Cannot create shader");
+                gl.shader_source(shader, source);
+                gl.compile_shader(shader);
+                if !gl.get_shader_compile_status(shader) {
+                    panic!("{}", gl.get_shader_info_log(shader));
+                }
+                gl.attach_shader(program, shader);
+                shaders.push(shader);
+            }
+            gl.link_program(program);
+            if !gl.get_program_link_status(program) {
+                panic!("{}", gl.get_program_info_log(program));
+            }
+            for shader in shaders {
+                gl.detach_shader(program, shader);
+                gl.delete_shader(shader);
+            }
+
+            Self {
+                program,
+                vertex_array: gl
+                    .create_vertex_array()
+                    .expect("Cannot create vertex array"),
+                angle: gl.get_uniform_location(program, "angle"),
+            }
+        }
+    }
+
+    /// Draws the triangle in the given rectangle, in framebuffer pixels from the bottom left
+    fn render(&self, gl: &glow::Context, rect: [i32; 4], angle: f32) {
+        unsafe {
+            gl.viewport(rect[0], rect[1], rect[2], rect[3]);
+            gl.scissor(rect[0], rect[1], rect[2], rect[3]);
+            gl.enable(glow::SCISSOR_TEST);
+            gl.use_program(Some(self.program));
+            gl.uniform_1_f32(self.angle.as_ref(), angle);
+            gl.bind_vertex_array(Some(self.vertex_array));
+            gl.draw_arrays(glow::TRIANGLES, 0, 3);
+        }
+    }
+
+    fn destroy(&self, gl: &glow::Context) {
+        unsafe {
+            gl.delete_program(self.program);
+            gl.delete_vertex_array(self.vertex_array);
+        }
+    }
+}
+
+fn main() {
+    let (event_loop, window) = utils::create_window("Draw callback", glutin::GlRequest::Latest);
+    let (mut winit_platform, mut imgui_context) = utils::imgui_init(&window);
+    let gl = utils::glow_context(&window);
+
+    let mut ig_renderer = imgui_glow_renderer::AutoRenderer::initialize(gl, &mut imgui_context)
+        .expect("failed to create renderer");
+    let spinner = Spinner::new(ig_renderer.gl_context());
+
+    let start = Instant::now();
+    let mut last_frame = Instant::now();
+    event_loop.run(move |event, _, control_flow| {
+        match event {
+            glutin::event::Event::NewEvents(_) => {
+                let now = Instant::now();
+                imgui_context
+                    .io_mut()
+                    .update_delta_time(now.duration_since(last_frame));
+                last_frame = now;
+            }
+            glutin::event::Event::MainEventsCleared => {
+                winit_platform
+                    .prepare_frame(imgui_context.io_mut(), window.window())
+                    .unwrap();
+
+                window.window().request_redraw();
+            }
+            glutin::event::Event::RedrawRequested(_) => {
+                unsafe {
+                    ig_renderer.gl_context().clear_color(0.05, 0.05, 0.1, 1.0);
+                    ig_renderer.gl_context().clear(glow::COLOR_BUFFER_BIT);
+                }
+
+                let ui = imgui_context.frame();
+                ui.window("Spinning triangle")
+                    .size([300.0, 300.0], imgui::Condition::FirstUseEver)
+                    .build(|| {
+                        // Use the remaining space of the window for the triangle
+                        let pos = ui.cursor_screen_pos();
+                        let size = ui.content_region_avail();
+                        ui.invisible_button("triangle", size);
+
+                        // The callback runs while the draw data is rendered, so it can't
+                        // borrow anything from the frame: compute everything it needs now
+                        let scale = ui.io().display_framebuffer_scale;
+                        let display_height = ui.io().display_size[1];
+                        let rect = [
+                            (pos[0] * scale[0]) as i32,
+                            ((display_height - pos[1] - size[1]) * scale[1]) as i32,
+                            (size[0] * scale[0]) as i32,
+                            (size[1] * scale[1]) as i32,
+                        ];
+                        let angle = start.elapsed().as_secs_f32();
+
+                        let draw_list = ui.get_window_draw_list();
+                        draw_list
+                            .add_callback({
+                                let gl = Rc::clone(ig_renderer.gl_context());
+                                move || spinner.render(&gl, rect, angle)
+                            })
+                            .build();
+                        // The callback changed the viewport, program and vertex array
+                        draw_list.add_reset_render_state();
+                    });
+
+                winit_platform.prepare_render(ui, window.window());
+                let draw_data = imgui_context.render();
+
+                ig_renderer
+                    .render(draw_data)
+                    .expect("error rendering imgui");
+
+                window.swap_buffers().unwrap();
+            }
+            glutin::event::Event::WindowEvent {
+                event: glutin::event::WindowEvent::CloseRequested,
+                ..
+            } => {
+                *control_flow = glutin::event_loop::ControlFlow::Exit;
+            }
+            glutin::event::Event::LoopDestroyed => {
+                spinner.destroy(ig_renderer.gl_context());
+            }
+            event => {
+                winit_platform.handle_event(imgui_context.io_mut(), window.window(), &event);
+            }
+        }
+    });
+}
@@ -0,0 +1,151 @@
+//! Keeps text sharp when the window moves between displays with different DPI factors, by
+//! rasterizing the fonts again with `imgui::FontManager`.
+//!
+//! On startup, the fonts are swapped between two rendered frames, to check that the font
+//! texture is reloaded in place, and that an atlas larger than `GL_MAX_TEXTURE_SIZE` is
+//! rejected.
+
+use std::{cell::Cell, rc::Rc, time::Instant};
+
+use glow::HasContext;
+use imgui::{FontManager, FontSource};
+use imgui_glow_renderer::{AutoRenderer, InitError};
+
+#[allow(dead_code)]
+mod utils;
+
+fn main() {
+    let (event_loop, window) = utils::create_window("DPI-aware fonts", glutin::GlRequest::Latest);
+    let (mut winit_platform, mut imgui_context) = utils::imgui_init(&window);
+    let gl = utils::glow_context(&window);
+
+    let mut font_manager = FontManager::new();
+    font_manager.add_font(&[FontSource::DefaultFontData { config: None }]);
+    let roboto = font_manager.add_font(&[FontSource::TtfData {
+        data: include_bytes!("../../resources/Roboto-Regular.ttf"),
+        size_pixels: 16.0,
+        config: None,
+    }]);
+    font_manager.rebuild(&mut imgui_context, winit_platform.hidpi_factor());
+
+    // Set by the platform while handling events, applied before the next frame
+    let new_scale_factor = Rc::new(Cell::new(None));
+    winit_platform.set_scale_factor_handler(Some(Box::new({
+        let new_scale_factor = Rc::clone(&new_scale_factor);
+        move |factor| new_scale_factor.set(Some(factor))
+    })));
+
+    let mut ig_renderer =
+        AutoRenderer::initialize(gl, &mut imgui_context).expect("failed to create renderer");
+    check_font_reload(&mut ig_renderer, &mut imgui_context, &mut font_manager);
+    let mut last_frame = Instant::now();
+    event_loop.run(move |event, _, control_flow| match event {
+        glutin::event::Event::NewEvents(_) => {
+            let now = Instant::now();
+            imgui_context
+                .io_mut()
+                .update_delta_time(now.duration_since(last_frame));
+            last_frame = now;
+        }
+        glutin::event::Event::MainEventsCleared => {
+            if let Some(factor) = new_scale_factor.take() {
+                if font_manager.rebuild(&mut imgui_context, factor) {
+                    ig_renderer
+                        .reload_font_texture(&mut imgui_context)
+                        .expect("failed to reload fonts");
+                }
+            }
+            winit_platform
+                .prepare_frame(imgui_context.io_mut(), window.window())
+                .unwrap();
+
+            window.window().request_redraw();
+        }
+        glutin::event::Event::RedrawRequested(_) => {
+            unsafe { ig_renderer.gl_context().clear(glow::COLOR_BUFFER_BIT) };
+
+            let ui = imgui_context.frame();
+            ui.window("DPI-aware fonts")
+                .size([400.0, 200.0], imgui::Condition::FirstUseEver)
+                .build(|| {
+                    ui.text(format!(
+                        "DPI factor: {}",
+                        font_manager.scale_factor().unwrap_or(1.0)
+                    ));
+                    ui.text("Move the window to another display");
+                    if let Some(roboto) = font_manager.font(roboto) {
+                        let _font = ui.push_font(roboto);
+                        ui.text("Roboto stays sharp at any DPI factor");
+                    }
+                });
+
+            winit_platform.prepare_render(ui, window.window());
+            let draw_data = imgui_context.render();
+            ig_renderer
+                .render(draw_data)
+                .expect("error rendering imgui");
+
+            window.swap_buffers().unwrap();
+        }
+        glutin::event::Event::WindowEvent {
+            event: glutin::event::WindowEvent::CloseRequested,
+            ..
+        } => {
+            *control_flow = glutin::event_loop::ControlFlow::Exit;
+        }
+        event => {
+            winit_platform.handle_event(imgui_context.io_mut(), window.window(), &event);
+        }
+    });
+}
+
+/// Renders a frame of text, without showing it
+fn render_text(renderer: &mut AutoRenderer, imgui_context: &mut imgui::Context) {
+    let ui = imgui_context.frame();
+    ui.text("Font reload");
+    let draw_data = imgui_context.render();
+    renderer.render(draw_data).expect("error rendering imgui");
+}
+
+/// Swaps the fonts between two frames, and checks the reloads of the font texture
+fn check_font_reload(
+    renderer: &mut AutoRenderer,
+    imgui_context: &mut imgui::Context,
+    font_manager: &mut FontManager,
+) {
+    let scale_factor = font_manager.scale_factor().unwrap_or(1.0);
+    let tex_id = imgui_context.fonts().tex_id;
+    render_text(renderer, imgui_context);
+    assert!(font_manager.rebuild(imgui_context, scale_factor * 2.0));
+    renderer
+        .reload_font_texture(imgui_context)
+        .expect("failed to reload fonts");
+    render_text(renderer, imgui_context);
+    assert_eq!(imgui_context.fonts().tex_id, tex_id);
+    assert_eq!(unsafe { renderer.gl_context().get_error() }, glow::NO_ERROR);
+
+    // An atlas wider than the maximum texture size is rejected
+    let max_size = unsafe {
+        renderer
+            .gl_context()
+            .get_parameter_i32(glow::MAX_TEXTURE_SIZE)
+    } as u32;
+    let fonts = imgui_context.fonts();
+    fonts.clear_tex_data();
+    fonts.tex_desired_width = (max_size * 2) as i32;
+    match renderer.reload_font_texture(imgui_context) {
+        Err(InitError::FontAtlasTooLarge { size, .. }) => assert_eq!(size[0], max_size * 2),
+        result => panic!("unexpected result: {:?}", result),
+    }
+    assert_eq!(imgui_context.fonts().tex_id, tex_id);
+
+    // The fonts of both DPI factors are still in the atlas, only its texture is built again
+    let fonts = imgui_context.fonts();
+    fonts.clear_tex_data();
+    fonts.tex_desired_width = 0;
+    assert!(!font_manager.rebuild(imgui_context, scale_factor));
+    renderer
+        .reload_font_texture(imgui_context)
+        .expect("failed to reload fonts");
+    render_text(renderer, imgui_context);
+}
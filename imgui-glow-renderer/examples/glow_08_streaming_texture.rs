@@ -0,0 +1,112 @@
+//! Streams an animated gradient into a texture every frame, with
+//! `AutoRenderer::create_texture` and `AutoRenderer::update_texture`.
+//!
+//! Only the rows of a moving band are uploaded each frame, to show partial
+//! updates. See `Renderer::update_texture_strided` to upload part of the rows.
+
+use std::time::Instant;
+
+use glow::HasContext;
+use imgui::Condition;
+use imgui_glow_renderer::TextureFormat;
+
+#[allow(dead_code)]
+mod utils;
+
+const WIDTH: u32 = 256;
+const HEIGHT: u32 = 256;
+/// Rows uploaded each frame
+const BAND_HEIGHT: u32 = 32;
+
+fn main() {
+    let (event_loop, window) = utils::create_window("Streaming texture", glutin::GlRequest::Latest);
+    let (mut winit_platform, mut imgui_context) = utils::imgui_init(&window);
+    let gl = utils::glow_context(&window);
+
+    let mut ig_renderer = imgui_glow_renderer::AutoRenderer::initialize(gl, &mut imgui_context)
+        .expect("failed to create renderer");
+    let texture_id = ig_renderer
+        .create_texture(WIDTH, HEIGHT, TextureFormat::Rgba8)
+        .expect("failed to create texture");
+
+    // Pixels of the whole texture, of which a band of rows is uploaded each frame
+    let mut pixels = vec![0; (WIDTH * HEIGHT) as usize * TextureFormat::Rgba8.bytes_per_pixel()];
+    let mut band = 0;
+    let start = Instant::now();
+    let mut last_frame = Instant::now();
+    event_loop.run(move |event, _, control_flow| match event {
+        glutin::event::Event::NewEvents(_) => {
+            let now = Instant::now();
+            imgui_context
+                .io_mut()
+                .update_delta_time(now.duration_since(last_frame));
+            last_frame = now;
+        }
+        glutin::event::Event::MainEventsCleared => {
+            winit_platform
+                .prepare_frame(imgui_context.io_mut(), window.window())
+                .unwrap();
+
+            window.window().request_redraw();
+        }
+        glutin::event::Event::RedrawRequested(_) => {
+            let time = start.elapsed().as_secs_f32();
+            let y = band * BAND_HEIGHT;
+            fill_gradient(&mut pixels, y..y + BAND_HEIGHT, time);
+            let row_size = WIDTH as usize * TextureFormat::Rgba8.bytes_per_pixel();
+            ig_renderer
+                .update_texture(
+                    texture_id,
+                    0,
+                    y,
+                    WIDTH,
+                    BAND_HEIGHT,
+                    &pixels[y as usize * row_size..],
+                    TextureFormat::Rgba8,
+                )
+                .expect("failed to update texture");
+            band = (band + 1) % (HEIGHT / BAND_HEIGHT);
+
+            unsafe { ig_renderer.gl_context().clear(glow::COLOR_BUFFER_BIT) };
+
+            let ui = imgui_context.frame();
+            ui.window("Streaming texture")
+                .size([300.0, 320.0], Condition::FirstUseEver)
+                .build(|| {
+                    ui.text(format!("Updating rows {} to {}", y, y + BAND_HEIGHT));
+                    imgui::Image::new(texture_id, [WIDTH as f32, HEIGHT as f32]).build(ui);
+                });
+
+            winit_platform.prepare_render(ui, window.window());
+            let draw_data = imgui_context.render();
+            ig_renderer
+                .render(draw_data)
+                .expect("error rendering imgui");
+
+            window.swap_buffers().unwrap();
+        }
+        glutin::event::Event::WindowEvent {
+            event: glutin::event::WindowEvent::CloseRequested,
+            ..
+        } => {
+            *control_flow = glutin::event_loop::ControlFlow::Exit;
+        }
+        event => {
+            winit_platform.handle_event(imgui_context.io_mut(), window.window(), &event);
+        }
+    });
+}
+
+/// Fills the given rows with a gradient scrolling over time
+fn fill_gradient(pixels: &mut [u8], rows: std::ops::Range<u32>, time: f32) {
+    for y in rows {
+        for x in 0..WIDTH {
+            let i = (y * WIDTH + x) as usize * 4;
+            let phase = (x + y) as f32 / (WIDTH + HEIGHT) as f32 + time * 0.25;
+            pixels[i] = (x * 255 / WIDTH) as u8;
+            pixels[i + 1] = (y * 255 / HEIGHT) as u8;
+            pixels[i + 2] = ((phase * std::f32::consts::TAU).sin() * 127.5 + 127.5) as u8;
+            pixels[i + 3] = 255;
+        }
+    }
+}
@@ -0,0 +1,195 @@
+//! Shows the effect of `OutputColorSpace` and `Renderer::framebuffer_srgb`.
+//!
+//! On startup, a mid-gray swatch is rendered into an ordinary and an sRGB
+//! framebuffer object with the matching color space, and read back to check
+//! that both store the sRGB value imgui was given.
+
+use std::time::Instant;
+
+use glow::HasContext;
+use imgui::Condition;
+use imgui_glow_renderer::{OutputColorSpace, Renderer, SimpleTextureMap};
+
+#[allow(dead_code)]
+mod utils;
+
+/// Size of the framebuffer objects the swatch is rendered into
+const SWATCH_SIZE: i32 = 16;
+/// sRGB gray of the swatch, stored as 128 in an 8 bit framebuffer
+const SWATCH_COLOR: [f32; 4] = [0.5, 0.5, 0.5, 1.0];
+
+fn main() {
+    let (event_loop, window) = utils::create_window("Color space", glutin::GlRequest::Latest);
+    let (mut winit_platform, mut imgui_context) = utils::imgui_init(&window);
+    let gl = utils::glow_context(&window);
+
+    let mut texture_map = SimpleTextureMap::default();
+    let mut ig_renderer = Renderer::initialize_with_color_space(
+        &gl,
+        &mut imgui_context,
+        &mut texture_map,
+        OutputColorSpace::Auto,
+    )
+    .expect("failed to create renderer");
+
+    let checks = [
+        (glow::RGBA8, OutputColorSpace::Srgb, false),
+        (glow::SRGB8_ALPHA8, OutputColorSpace::Linear, true),
+        (glow::RGBA8, OutputColorSpace::Auto, false),
+        (glow::SRGB8_ALPHA8, OutputColorSpace::Auto, true),
+    ]
+    .iter()
+    .map(|&(internal_format, color_space, framebuffer_srgb)| {
+        ig_renderer.output_color_space = color_space;
+        ig_renderer.framebuffer_srgb = Some(framebuffer_srgb);
+        let pixel = render_swatch(
+            &gl,
+            &mut ig_renderer,
+            &texture_map,
+            &mut imgui_context,
+            internal_format,
+        );
+        let result = format!(
+            "{:?} into {} framebuffer: read {:?}",
+            color_space,
+            if framebuffer_srgb { "an sRGB" } else { "a" },
+            pixel
+        );
+        println!("{}", result);
+        assert!(
+            pixel[..3].iter().all(|&c| (127..=129).contains(&c)),
+            "unexpected swatch color"
+        );
+        result
+    })
+    .collect::<Vec<_>>();
+
+    ig_renderer.output_color_space = OutputColorSpace::Auto;
+    ig_renderer.framebuffer_srgb = None;
+    let mut framebuffer_srgb = false;
+    let mut last_frame = Instant::now();
+    event_loop.run(move |event, _, control_flow| match event {
+        glutin::event::Event::NewEvents(_) => {
+            let now = Instant::now();
+            imgui_context
+                .io_mut()
+                .update_delta_time(now.duration_since(last_frame));
+            last_frame = now;
+        }
+        glutin::event::Event::MainEventsCleared => {
+            winit_platform
+                .prepare_frame(imgui_context.io_mut(), window.window())
+                .unwrap();
+
+            window.window().request_redraw();
+        }
+        glutin::event::Event::RedrawRequested(_) => {
+            unsafe {
+                // `OutputColorSpace::Auto` follows this state. The default
+                // framebuffer is usually not sRGB, in which case enabling it
+                // makes the UI too dark with `Auto` and `Linear`.
+                if framebuffer_srgb {
+                    gl.enable(glow::FRAMEBUFFER_SRGB);
+                } else {
+                    gl.disable(glow::FRAMEBUFFER_SRGB);
+                }
+                gl.clear(glow::COLOR_BUFFER_BIT);
+            }
+
+            let ui = imgui_context.frame();
+            ui.window("Color space")
+                .size([400.0, 300.0], Condition::FirstUseEver)
+                .build(|| {
+                    for check in &checks {
+                        ui.text(check);
+                    }
+                    ui.separator();
+                    let color_space = &mut ig_renderer.output_color_space;
+                    ui.radio_button("sRGB", color_space, OutputColorSpace::Srgb);
+                    ui.radio_button("Linear", color_space, OutputColorSpace::Linear);
+                    ui.radio_button("Auto", color_space, OutputColorSpace::Auto);
+                    ui.checkbox("GL_FRAMEBUFFER_SRGB", &mut framebuffer_srgb);
+                    ui.color_button("Swatch", SWATCH_COLOR);
+                });
+
+            winit_platform.prepare_render(ui, window.window());
+            let draw_data = imgui_context.render();
+            ig_renderer
+                .render(&gl, &texture_map, draw_data)
+                .expect("error rendering imgui");
+
+            window.swap_buffers().unwrap();
+        }
+        glutin::event::Event::WindowEvent {
+            event: glutin::event::WindowEvent::CloseRequested,
+            ..
+        } => {
+            *control_flow = glutin::event_loop::ControlFlow::Exit;
+        }
+        glutin::event::Event::LoopDestroyed => {
+            ig_renderer.destroy(&gl);
+        }
+        event => {
+            winit_platform.handle_event(imgui_context.io_mut(), window.window(), &event);
+        }
+    });
+}
+
+/// Renders the swatch into a framebuffer object with the given internal
+/// format, and reads back the color of a pixel
+fn render_swatch(
+    gl: &glow::Context,
+    renderer: &mut Renderer,
+    texture_map: &SimpleTextureMap,
+    imgui_context: &mut imgui::Context,
+    internal_format: u32,
+) -> [u8; 4] {
+    let io = imgui_context.io_mut();
+    io.display_size = [SWATCH_SIZE as f32; 2];
+    io.display_framebuffer_scale = [1.0, 1.0];
+    let ui = imgui_context.frame();
+    ui.get_background_draw_list()
+        .add_rect([0.0, 0.0], [SWATCH_SIZE as f32; 2], SWATCH_COLOR)
+        .filled(true)
+        .build();
+    let draw_data = imgui_context.render();
+
+    let mut pixel = [0; 4];
+    unsafe {
+        let fbo = gl.create_framebuffer().unwrap();
+        let rbo = gl.create_renderbuffer().unwrap();
+        gl.bind_framebuffer(glow::FRAMEBUFFER, Some(fbo));
+        gl.bind_renderbuffer(glow::RENDERBUFFER, Some(rbo));
+        gl.renderbuffer_storage(
+            glow::RENDERBUFFER,
+            internal_format,
+            SWATCH_SIZE,
+            SWATCH_SIZE,
+        );
+        gl.framebuffer_renderbuffer(
+            glow::FRAMEBUFFER,
+            glow::COLOR_ATTACHMENT0,
+            glow::RENDERBUFFER,
+            Some(rbo),
+        );
+
+        renderer
+            .render(gl, texture_map, draw_data)
+            .expect("error rendering swatch");
+        gl.read_pixels(
+            SWATCH_SIZE / 2,
+            SWATCH_SIZE / 2,
+            1,
+            1,
+            glow::RGBA,
+            glow::UNSIGNED_BYTE,
+            glow::PixelPackData::Slice(&mut pixel),
+        );
+
+        gl.bind_framebuffer(glow::FRAMEBUFFER, None);
+        gl.bind_renderbuffer(glow::RENDERBUFFER, None);
+        gl.delete_framebuffer(fbo);
+        gl.delete_renderbuffer(rbo);
+    }
+    pixel
+}
@@ -0,0 +1,493 @@
+//! Renders imgui windows dragged outside of the main window into their own
+//! OS windows, with `AutoRenderer::render_viewports`. Requires the `docking`
+//! feature.
+//!
+//! The platform side of the viewports is a minimal implementation of
+//! `imgui::PlatformViewportBackend` on top of glutin 0.29: each viewport gets
+//! a window with its own context, sharing objects with the main context so the
+//! font atlas can be used from all of them. Windows can't be positioned on
+//! Wayland, where this example doesn't work well.
+
+use std::{
+    cell::RefCell,
+    collections::{HashMap, VecDeque},
+    rc::Rc,
+    time::Instant,
+};
+
+use glow::HasContext;
+use glutin::{
+    dpi::{PhysicalPosition, PhysicalSize},
+    event::{Event, WindowEvent},
+    event_loop::{ControlFlow, EventLoopWindowTarget},
+    window::{WindowBuilder, WindowId},
+};
+use imgui::{BackendFlags, ConfigFlags, Id, Viewport, ViewportFlags};
+use imgui_glow_renderer::{AutoRenderer, ViewportSurfaces};
+use imgui_winit_support::{HiDpiMode, WinitPlatform};
+
+#[allow(dead_code)]
+mod utils;
+
+use utils::Window;
+
+fn main() {
+    let (event_loop, window) = utils::create_window("Viewports", glutin::GlRequest::Latest);
+    let gl = utils::glow_context(&window);
+
+    let mut imgui_context = imgui::Context::create();
+    imgui_context.set_ini_filename(None);
+    imgui_context
+        .fonts()
+        .add_font(&[imgui::FontSource::DefaultFontData { config: None }]);
+    let io = imgui_context.io_mut();
+    io.config_flags |= ConfigFlags::DOCKING_ENABLE | ConfigFlags::VIEWPORTS_ENABLE;
+    io.backend_flags |= BackendFlags::PLATFORM_HAS_VIEWPORTS;
+
+    // Viewports are positioned in physical pixels, so the DPI factor is locked
+    let mut winit_platform = WinitPlatform::init(&mut imgui_context);
+    winit_platform.attach_window(
+        imgui_context.io_mut(),
+        window.window(),
+        HiDpiMode::Locked(1.0),
+    );
+
+    let monitors = window
+        .window()
+        .available_monitors()
+        .map(|monitor| {
+            let pos = [monitor.position().x as f32, monitor.position().y as f32];
+            let size = [monitor.size().width as f32, monitor.size().height as f32];
+            imgui::PlatformMonitor {
+                main_pos: pos,
+                main_size: size,
+                work_pos: pos,
+                work_size: size,
+                dpi_scale: 1.0,
+            }
+        })
+        .collect::<Vec<_>>();
+    imgui_context
+        .platform_io_mut()
+        .monitors
+        .replace_from_slice(&monitors);
+
+    let platform = Rc::new(RefCell::new(PlatformState::default()));
+    let main_viewport_id = imgui_context.main_viewport().id;
+    platform
+        .borrow_mut()
+        .update_window(main_viewport_id, window.window());
+    imgui_context.set_platform_backend(PlatformBackend(platform.clone()));
+
+    let windows = Rc::new(RefCell::new(Windows {
+        main: Some(window),
+        extra: HashMap::new(),
+    }));
+    let mut ig_renderer =
+        AutoRenderer::initialize(gl, &mut imgui_context).expect("failed to create renderer");
+    ig_renderer.enable_viewports(&mut imgui_context, SharedWindows(windows.clone()));
+
+    let mut last_frame = Instant::now();
+    event_loop.run(move |event, window_target, control_flow| match event {
+        Event::NewEvents(_) => {
+            let now = Instant::now();
+            imgui_context
+                .io_mut()
+                .update_delta_time(now.duration_since(last_frame));
+            last_frame = now;
+        }
+        Event::MainEventsCleared => {
+            let windows = windows.borrow();
+            let main_window = windows.main().window();
+            winit_platform
+                .prepare_frame(imgui_context.io_mut(), main_window)
+                .unwrap();
+
+            main_window.request_redraw();
+        }
+        Event::RedrawRequested(_) => {
+            let ui = imgui_context.frame();
+            ui.dockspace_over_main_viewport();
+            ui.show_demo_window(&mut true);
+            ui.window("Drag me outside").build(|| {
+                ui.text("Drag this window outside of the main window");
+                ui.text("to render it into its own OS window.");
+            });
+
+            winit_platform.prepare_render(ui, windows.borrow().main().window());
+            let draw_data = imgui_context.render();
+            unsafe { ig_renderer.gl_context().clear(glow::COLOR_BUFFER_BIT) };
+            ig_renderer
+                .render(draw_data)
+                .expect("error rendering imgui");
+            windows.borrow().main().swap_buffers().unwrap();
+
+            imgui_context.update_platform_windows();
+            apply_platform_events(
+                &platform,
+                &mut imgui_context,
+                &mut windows.borrow_mut(),
+                window_target,
+            );
+            ig_renderer
+                .render_viewports(&imgui_context)
+                .expect("error rendering viewports");
+        }
+        Event::WindowEvent { window_id, event } => {
+            let windows = windows.borrow();
+            let main_window = windows.main().window();
+            let (viewport_id, window) = if window_id == main_window.id() {
+                (main_viewport_id, main_window)
+            } else if let Some((id, window)) = windows.find(window_id) {
+                (id, window)
+            } else {
+                return;
+            };
+
+            match event {
+                WindowEvent::CursorMoved { position, .. } => {
+                    // With viewports, imgui expects the mouse position on the
+                    // desktop rather than in the window
+                    let window_pos = window.inner_position().unwrap_or_default();
+                    imgui_context.io_mut().add_mouse_pos_event([
+                        (window_pos.x as f64 + position.x) as f32,
+                        (window_pos.y as f64 + position.y) as f32,
+                    ]);
+                    return;
+                }
+                WindowEvent::Moved(_) | WindowEvent::Resized(_) | WindowEvent::Focused(_) => {
+                    let mut platform = platform.borrow_mut();
+                    platform.update_window(viewport_id, window);
+                    if let WindowEvent::Focused(focused) = event {
+                        platform.windows.get_mut(&viewport_id).unwrap().focused = focused;
+                    }
+                }
+                WindowEvent::CloseRequested if viewport_id == main_viewport_id => {
+                    *control_flow = ControlFlow::Exit;
+                }
+                _ => {}
+            }
+
+            if viewport_id != main_viewport_id {
+                if let WindowEvent::Resized(size) = event {
+                    windows.get(viewport_id).unwrap().resize(size);
+                }
+                if let Some(viewport) = imgui_context.viewport_by_id_mut(viewport_id) {
+                    match event {
+                        WindowEvent::Moved(_) => viewport.platform_request_move = true,
+                        WindowEvent::Resized(_) => viewport.platform_request_resize = true,
+                        WindowEvent::CloseRequested => viewport.platform_request_close = true,
+                        _ => {}
+                    }
+                }
+                // Input from the other windows is handled as if it was
+                // received by the main window
+                if !matches!(
+                    event,
+                    WindowEvent::KeyboardInput { .. }
+                        | WindowEvent::ReceivedCharacter(_)
+                        | WindowEvent::ModifiersChanged(_)
+                        | WindowEvent::MouseInput { .. }
+                        | WindowEvent::MouseWheel { .. }
+                        | WindowEvent::Focused(_)
+                ) {
+                    return;
+                }
+            }
+            let main_window = windows.main().window();
+            let event = Event::WindowEvent::<()> {
+                window_id: main_window.id(),
+                event,
+            };
+            winit_platform.handle_event(imgui_context.io_mut(), main_window, &event);
+        }
+        event => {
+            let windows = windows.borrow();
+            let main_window = windows.main().window();
+            winit_platform.handle_event(imgui_context.io_mut(), main_window, &event);
+        }
+    });
+}
+
+/// Requests of imgui to the platform windows, applied in the event loop
+enum PlatformEvent {
+    Create(Id),
+    Destroy(Id),
+    Show(Id),
+    SetPos(Id, [f32; 2]),
+    SetSize(Id, [f32; 2]),
+    Focus(Id),
+    SetTitle(Id, String),
+}
+
+/// State of a platform window, as reported to imgui
+#[derive(Clone, Copy, Default)]
+struct WindowState {
+    pos: [f32; 2],
+    size: [f32; 2],
+    focused: bool,
+    minimized: bool,
+}
+
+#[derive(Default)]
+struct PlatformState {
+    events: VecDeque<PlatformEvent>,
+    windows: HashMap<Id, WindowState>,
+}
+
+impl PlatformState {
+    fn update_window(&mut self, id: Id, window: &glutin::window::Window) {
+        let pos = window.inner_position().unwrap_or_default();
+        let size = window.inner_size();
+        let state = self.windows.entry(id).or_default();
+        state.pos = [pos.x as f32, pos.y as f32];
+        state.size = [size.width as f32, size.height as f32];
+        state.minimized = size.width == 0 || size.height == 0;
+    }
+
+    fn window(&self, id: Id) -> WindowState {
+        self.windows.get(&id).copied().unwrap_or_default()
+    }
+}
+
+struct PlatformBackend(Rc<RefCell<PlatformState>>);
+
+impl PlatformBackend {
+    fn push(&self, event: PlatformEvent) {
+        self.0.borrow_mut().events.push_back(event);
+    }
+}
+
+impl imgui::PlatformViewportBackend for PlatformBackend {
+    fn create_window(&mut self, viewport: &mut Viewport) {
+        let state = WindowState {
+            pos: viewport.pos,
+            size: viewport.size,
+            ..WindowState::default()
+        };
+        let mut platform = self.0.borrow_mut();
+        platform.windows.insert(viewport.id, state);
+        platform
+            .events
+            .push_back(PlatformEvent::Create(viewport.id));
+    }
+
+    fn destroy_window(&mut self, viewport: &mut Viewport) {
+        let mut platform = self.0.borrow_mut();
+        platform.windows.remove(&viewport.id);
+        platform
+            .events
+            .push_back(PlatformEvent::Destroy(viewport.id));
+    }
+
+    fn show_window(&mut self, viewport: &mut Viewport) {
+        self.push(PlatformEvent::Show(viewport.id));
+    }
+
+    fn set_window_pos(&mut self, viewport: &mut Viewport, pos: [f32; 2]) {
+        self.push(PlatformEvent::SetPos(viewport.id, pos));
+    }
+
+    fn get_window_pos(&mut self, viewport: &mut Viewport) -> [f32; 2] {
+        self.0.borrow().window(viewport.id).pos
+    }
+
+    fn set_window_size(&mut self, viewport: &mut Viewport, size: [f32; 2]) {
+        self.push(PlatformEvent::SetSize(viewport.id, size));
+    }
+
+    fn get_window_size(&mut self, viewport: &mut Viewport) -> [f32; 2] {
+        self.0.borrow().window(viewport.id).size
+    }
+
+    fn set_window_focus(&mut self, viewport: &mut Viewport) {
+        self.push(PlatformEvent::Focus(viewport.id));
+    }
+
+    fn get_window_focus(&mut self, viewport: &mut Viewport) -> bool {
+        self.0.borrow().window(viewport.id).focused
+    }
+
+    fn get_window_minimized(&mut self, viewport: &mut Viewport) -> bool {
+        self.0.borrow().window(viewport.id).minimized
+    }
+
+    fn set_window_title(&mut self, viewport: &mut Viewport, title: &str) {
+        self.push(PlatformEvent::SetTitle(viewport.id, title.to_owned()));
+    }
+
+    fn set_window_alpha(&mut self, _viewport: &mut Viewport, _alpha: f32) {}
+
+    fn update_window(&mut self, _viewport: &mut Viewport) {}
+
+    fn render_window(&mut self, _viewport: &mut Viewport) {}
+
+    fn swap_buffers(&mut self, _viewport: &mut Viewport) {}
+
+    fn create_vk_surface(
+        &mut self,
+        _viewport: &mut Viewport,
+        _instance: u64,
+        _out_surface: &mut u64,
+    ) -> i32 {
+        0
+    }
+}
+
+/// The windows of the main viewport and of the other viewports. Contexts are
+/// taken out of their `Option` while being made current.
+struct Windows {
+    main: Option<Window>,
+    extra: HashMap<Id, Option<Window>>,
+}
+
+impl Windows {
+    fn main(&self) -> &Window {
+        self.main.as_ref().unwrap()
+    }
+
+    fn get(&self, id: Id) -> Option<&Window> {
+        self.extra.get(&id).and_then(Option::as_ref)
+    }
+
+    fn find(&self, window_id: WindowId) -> Option<(Id, &glutin::window::Window)> {
+        self.extra.iter().find_map(|(&id, window)| {
+            let window = window.as_ref()?.window();
+            (window.id() == window_id).then_some((id, window))
+        })
+    }
+}
+
+fn make_current(window: &mut Option<Window>) {
+    let current = unsafe { window.take().unwrap().make_current() };
+    *window = Some(
+        current
+            .map_err(|(_, error)| error)
+            .expect("could not make window context current"),
+    );
+}
+
+impl ViewportSurfaces for Windows {
+    fn make_current(&mut self, viewport: &Viewport) -> bool {
+        match self.extra.get_mut(&viewport.id) {
+            Some(window) => {
+                make_current(window);
+                true
+            }
+            None => false,
+        }
+    }
+
+    fn swap_buffers(&mut self, viewport: &Viewport) {
+        if let Some(window) = self.get(viewport.id) {
+            window.swap_buffers().expect("could not swap buffers");
+        }
+    }
+
+    fn make_main_current(&mut self) {
+        make_current(&mut self.main);
+    }
+}
+
+/// The windows, shared between the event loop and the renderer
+struct SharedWindows(Rc<RefCell<Windows>>);
+
+impl ViewportSurfaces for SharedWindows {
+    fn make_current(&mut self, viewport: &Viewport) -> bool {
+        self.0.borrow_mut().make_current(viewport)
+    }
+
+    fn swap_buffers(&mut self, viewport: &Viewport) {
+        self.0.borrow_mut().swap_buffers(viewport);
+    }
+
+    fn make_main_current(&mut self) {
+        self.0.borrow_mut().make_main_current();
+    }
+}
+
+/// Creates, updates and destroys the windows of the viewports as requested
+/// by imgui
+fn apply_platform_events(
+    platform: &RefCell<PlatformState>,
+    imgui_context: &mut imgui::Context,
+    windows: &mut Windows,
+    window_target: &EventLoopWindowTarget<()>,
+) {
+    loop {
+        let event = platform.borrow_mut().events.pop_front();
+        let event = match event {
+            Some(event) => event,
+            None => break,
+        };
+        match event {
+            PlatformEvent::Create(id) => {
+                if let Some(viewport) = imgui_context.viewport_by_id(id) {
+                    let window = create_viewport_window(viewport, windows.main(), window_target);
+                    windows.extra.insert(id, Some(window));
+                    // The main context stays current outside of rendering
+                    windows.make_main_current();
+                }
+            }
+            PlatformEvent::Destroy(id) => {
+                windows.extra.remove(&id);
+            }
+            PlatformEvent::Show(id) => {
+                if let Some(window) = windows.get(id) {
+                    window.window().set_visible(true);
+                }
+            }
+            PlatformEvent::SetPos(id, pos) => {
+                if let Some(window) = windows.get(id) {
+                    // Viewport windows are created without decorations, so
+                    // their outer and inner positions are the same
+                    window
+                        .window()
+                        .set_outer_position(PhysicalPosition::new(pos[0], pos[1]));
+                }
+            }
+            PlatformEvent::SetSize(id, size) => {
+                if let Some(window) = windows.get(id) {
+                    let size = PhysicalSize::new(size[0] as u32, size[1] as u32);
+                    window.window().set_inner_size(size);
+                    window.resize(size);
+                }
+            }
+            PlatformEvent::Focus(id) => {
+                if let Some(window) = windows.get(id) {
+                    window.window().focus_window();
+                }
+            }
+            PlatformEvent::SetTitle(id, title) => {
+                if let Some(window) = windows.get(id) {
+                    window.window().set_title(&title);
+                }
+            }
+        }
+    }
+}
+
+fn create_viewport_window(
+    viewport: &Viewport,
+    main_window: &Window,
+    window_target: &EventLoopWindowTarget<()>,
+) -> Window {
+    let window = WindowBuilder::new()
+        .with_position(PhysicalPosition::new(viewport.pos[0], viewport.pos[1]))
+        .with_inner_size(PhysicalSize::new(viewport.size[0], viewport.size[1]))
+        .with_visible(false)
+        .with_decorations(!viewport.flags.contains(ViewportFlags::NO_DECORATION));
+    // Sharing objects with the main context makes the font atlas and the
+    // textures of the renderer available. Only the main window waits for
+    // vsync, or the frame rate would be divided by the number of windows.
+    let window = glutin::ContextBuilder::new()
+        .with_shared_lists(main_window.context())
+        .with_vsync(false)
+        .build_windowed(window, window_target)
+        .expect("could not create viewport window");
+    unsafe {
+        window
+            .make_current()
+            .expect("could not make viewport context current")
+    }
+}
@@ -0,0 +1,221 @@
+//! Replaces the fragment shader of the renderer with `ShaderBodies`, to apply
+//! a color grade and CRT-style scanlines to the UI only, with uniforms set by
+//! `Renderer::set_uniform_callback`.
+//!
+//! On startup, a shader lacking a required attribute is checked to be
+//! rejected, and a swatch is rendered into a framebuffer object and read back
+//! to check the effect.
+
+use std::{cell::Cell, rc::Rc, time::Instant};
+
+use glow::HasContext;
+use imgui::Condition;
+use imgui_glow_renderer::{
+    InitError, OutputColorSpace, Renderer, ShaderBodies, ShaderError, SimpleTextureMap,
+    FRAGMENT_SHADER_BODY, VERTEX_SHADER_BODY,
+};
+
+#[allow(dead_code)]
+mod utils;
+
+/// Size of the framebuffer object the swatch is rendered into
+const SWATCH_SIZE: i32 = 16;
+/// sRGB gray of the swatch, stored as 128 in an 8 bit framebuffer
+const SWATCH_COLOR: [f32; 4] = [0.5, 0.5, 0.5, 1.0];
+
+const GRADE_FUNCTION: &str = r#"
+uniform vec3 tint;
+uniform float scanlines;
+
+vec4 grade(vec4 color) {
+    // Darken every other row of pixels
+    float scanline = 1.0 - scanlines * mod(floor(gl_FragCoord.y), 2.0);
+    return vec4(color.rgb * tint * scanline, color.a);
+}
+
+void main() {"#;
+
+/// Uniforms of the color grade, shared with the uniform callback
+#[derive(Copy, Clone)]
+struct Grade {
+    tint: [f32; 3],
+    scanlines: f32,
+}
+
+fn main() {
+    let (event_loop, window) = utils::create_window("Custom shader", glutin::GlRequest::Latest);
+    let (mut winit_platform, mut imgui_context) = utils::imgui_init(&window);
+    let gl = utils::glow_context(&window);
+    let mut texture_map = SimpleTextureMap::default();
+
+    // The renderer requires the attributes and uniforms it sets to be used
+    let missing_uv = ShaderBodies {
+        vertex: VERTEX_SHADER_BODY
+            .replace("fragment_uv = uv;", "fragment_uv = vec2(0.0);")
+            .into(),
+        ..ShaderBodies::default()
+    };
+    match Renderer::initialize_with_shaders(
+        &gl,
+        &mut imgui_context,
+        &mut texture_map,
+        OutputColorSpace::Srgb,
+        Some(&missing_uv),
+    ) {
+        Err(InitError::Shader(ShaderError::AttributeNotFound(name))) if name == "uv" => {}
+        Err(error) => panic!("unexpected error: {}", error),
+        Ok(_) => panic!("a shader without `uv` was accepted"),
+    }
+
+    let main_start = "void main() {";
+    let texture_sample = "fragment_color * texture(tex, fragment_uv.st)";
+    assert!(
+        FRAGMENT_SHADER_BODY.contains(main_start) && FRAGMENT_SHADER_BODY.contains(texture_sample)
+    );
+    let graded = ShaderBodies {
+        fragment: FRAGMENT_SHADER_BODY
+            .replace(main_start, GRADE_FUNCTION)
+            .replace(texture_sample, &format!("grade({})", texture_sample))
+            .into(),
+        ..ShaderBodies::default()
+    };
+    let mut ig_renderer = Renderer::initialize_with_shaders(
+        &gl,
+        &mut imgui_context,
+        &mut texture_map,
+        OutputColorSpace::Srgb,
+        Some(&graded),
+    )
+    .expect("failed to create renderer");
+
+    let grade = Rc::new(Cell::new(Grade {
+        tint: [1.0, 0.0, 0.0],
+        scanlines: 1.0,
+    }));
+    let uniforms = grade.clone();
+    ig_renderer.set_uniform_callback(Some(Box::new(move |gl, program| unsafe {
+        let grade = uniforms.get();
+        let tint = gl.get_uniform_location(*program, "tint");
+        gl.uniform_3_f32_slice(tint.as_ref(), &grade.tint);
+        let scanlines = gl.get_uniform_location(*program, "scanlines");
+        gl.uniform_1_f32(scanlines.as_ref(), grade.scanlines);
+    })));
+
+    // An even row keeps the red of the swatch, an odd row is darkened
+    let rows = render_swatch(&gl, &mut ig_renderer, &texture_map, &mut imgui_context);
+    println!("Swatch rows with the color grade: {:?}", rows);
+    assert!((127..=129).contains(&rows[0][0]) && rows[0][1] == 0 && rows[0][2] == 0);
+    assert_eq!(rows[1][..3], [0, 0, 0]);
+
+    grade.set(Grade {
+        tint: [1.0, 0.9, 0.7],
+        scanlines: 0.3,
+    });
+    let mut last_frame = Instant::now();
+    event_loop.run(move |event, _, control_flow| match event {
+        glutin::event::Event::NewEvents(_) => {
+            let now = Instant::now();
+            imgui_context
+                .io_mut()
+                .update_delta_time(now.duration_since(last_frame));
+            last_frame = now;
+        }
+        glutin::event::Event::MainEventsCleared => {
+            winit_platform
+                .prepare_frame(imgui_context.io_mut(), window.window())
+                .unwrap();
+
+            window.window().request_redraw();
+        }
+        glutin::event::Event::RedrawRequested(_) => {
+            unsafe { gl.clear(glow::COLOR_BUFFER_BIT) };
+
+            let ui = imgui_context.frame();
+            ui.window("Custom shader")
+                .size([400.0, 200.0], Condition::FirstUseEver)
+                .build(|| {
+                    let mut current = grade.get();
+                    ui.color_edit3("Tint", &mut current.tint);
+                    ui.slider("Scanlines", 0.0, 1.0, &mut current.scanlines);
+                    grade.set(current);
+                });
+            ui.show_demo_window(&mut true);
+
+            winit_platform.prepare_render(ui, window.window());
+            let draw_data = imgui_context.render();
+            ig_renderer
+                .render(&gl, &texture_map, draw_data)
+                .expect("error rendering imgui");
+
+            window.swap_buffers().unwrap();
+        }
+        glutin::event::Event::WindowEvent {
+            event: glutin::event::WindowEvent::CloseRequested,
+            ..
+        } => {
+            *control_flow = glutin::event_loop::ControlFlow::Exit;
+        }
+        glutin::event::Event::LoopDestroyed => {
+            ig_renderer.destroy(&gl);
+        }
+        event => {
+            winit_platform.handle_event(imgui_context.io_mut(), window.window(), &event);
+        }
+    });
+}
+
+/// Renders the swatch into a framebuffer object, and reads back the color of
+/// a pixel in an even and an odd row
+fn render_swatch(
+    gl: &glow::Context,
+    renderer: &mut Renderer,
+    texture_map: &SimpleTextureMap,
+    imgui_context: &mut imgui::Context,
+) -> [[u8; 4]; 2] {
+    let io = imgui_context.io_mut();
+    io.display_size = [SWATCH_SIZE as f32; 2];
+    io.display_framebuffer_scale = [1.0, 1.0];
+    let ui = imgui_context.frame();
+    ui.get_background_draw_list()
+        .add_rect([0.0, 0.0], [SWATCH_SIZE as f32; 2], SWATCH_COLOR)
+        .filled(true)
+        .build();
+    let draw_data = imgui_context.render();
+
+    let mut pixels = [0; 8];
+    unsafe {
+        let fbo = gl.create_framebuffer().unwrap();
+        let rbo = gl.create_renderbuffer().unwrap();
+        gl.bind_framebuffer(glow::FRAMEBUFFER, Some(fbo));
+        gl.bind_renderbuffer(glow::RENDERBUFFER, Some(rbo));
+        gl.renderbuffer_storage(glow::RENDERBUFFER, glow::RGBA8, SWATCH_SIZE, SWATCH_SIZE);
+        gl.framebuffer_renderbuffer(
+            glow::FRAMEBUFFER,
+            glow::COLOR_ATTACHMENT0,
+            glow::RENDERBUFFER,
+            Some(rbo),
+        );
+
+        renderer
+            .render(gl, texture_map, draw_data)
+            .expect("error rendering swatch");
+        gl.read_pixels(
+            SWATCH_SIZE / 2,
+            SWATCH_SIZE / 2,
+            1,
+            2,
+            glow::RGBA,
+            glow::UNSIGNED_BYTE,
+            glow::PixelPackData::Slice(&mut pixels),
+        );
+
+        gl.bind_framebuffer(glow::FRAMEBUFFER, None);
+        gl.bind_renderbuffer(glow::RENDERBUFFER, None);
+        gl.delete_framebuffer(fbo);
+        gl.delete_renderbuffer(rbo);
+    }
+    [
+        [pixels[0], pixels[1], pixels[2], pixels[3]],
+        [pixels[4], pixels[5], pixels[6], pixels[7]],
+    ]
+}
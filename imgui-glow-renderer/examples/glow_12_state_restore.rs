@@ -0,0 +1,133 @@
+//! Draws a triangle with an unusual blend function over the imgui demo window,
+//! relying on the renderer to restore the OpenGL state it changes
+//! (`Renderer::restore_state`, enabled by default).
+//!
+//! On startup, the blend function is checked to be left as is by rendering,
+//! and to be left as set up for imgui with `restore_state` disabled.
+
+use std::time::Instant;
+
+use glow::HasContext;
+use imgui_glow_renderer::{Renderer, SimpleTextureMap};
+
+mod utils;
+
+use utils::Triangler;
+
+/// Blend function of the application: `[src_rgb, dst_rgb, src_alpha, dst_alpha]`
+const APP_BLEND_FUNC: [u32; 4] = [glow::DST_COLOR, glow::ZERO, glow::ONE, glow::ZERO];
+
+fn main() {
+    let (event_loop, window) = utils::create_window("State restore", glutin::GlRequest::Latest);
+    let (mut winit_platform, mut imgui_context) = utils::imgui_init(&window);
+    let gl = utils::glow_context(&window);
+    let mut texture_map = SimpleTextureMap::default();
+    let mut ig_renderer = Renderer::initialize(&gl, &mut imgui_context, &mut texture_map, true)
+        .expect("failed to create renderer");
+    let tri_renderer = Triangler::new(&gl, "#version 330");
+
+    assert!(ig_renderer.restore_state);
+    set_app_blend_func(&gl);
+    render_frame(&gl, &mut ig_renderer, &texture_map, &mut imgui_context);
+    assert_eq!(blend_func(&gl), APP_BLEND_FUNC);
+
+    ig_renderer.restore_state = false;
+    render_frame(&gl, &mut ig_renderer, &texture_map, &mut imgui_context);
+    assert_eq!(
+        blend_func(&gl),
+        [
+            glow::SRC_ALPHA,
+            glow::ONE_MINUS_SRC_ALPHA,
+            glow::ONE,
+            glow::ONE_MINUS_SRC_ALPHA
+        ]
+    );
+    ig_renderer.restore_state = true;
+    println!("The blend function was restored after rendering");
+
+    let mut last_frame = Instant::now();
+    event_loop.run(move |event, _, control_flow| match event {
+        glutin::event::Event::NewEvents(_) => {
+            let now = Instant::now();
+            imgui_context
+                .io_mut()
+                .update_delta_time(now.duration_since(last_frame));
+            last_frame = now;
+        }
+        glutin::event::Event::MainEventsCleared => {
+            winit_platform
+                .prepare_frame(imgui_context.io_mut(), window.window())
+                .unwrap();
+
+            window.window().request_redraw();
+        }
+        glutin::event::Event::RedrawRequested(_) => {
+            unsafe {
+                gl.clear_color(1.0, 1.0, 1.0, 1.0);
+                gl.clear(glow::COLOR_BUFFER_BIT);
+                set_app_blend_func(&gl);
+            }
+
+            let ui = imgui_context.frame();
+            ui.show_demo_window(&mut true);
+
+            winit_platform.prepare_render(ui, window.window());
+            let draw_data = imgui_context.render();
+            ig_renderer
+                .render(&gl, &texture_map, draw_data)
+                .expect("error rendering imgui");
+
+            // Tints what is below the triangle, if the blend function was
+            // restored
+            tri_renderer.render(&gl);
+
+            window.swap_buffers().unwrap();
+        }
+        glutin::event::Event::WindowEvent {
+            event: glutin::event::WindowEvent::CloseRequested,
+            ..
+        } => {
+            *control_flow = glutin::event_loop::ControlFlow::Exit;
+        }
+        glutin::event::Event::LoopDestroyed => {
+            tri_renderer.destroy(&gl);
+            ig_renderer.destroy(&gl);
+        }
+        event => {
+            winit_platform.handle_event(imgui_context.io_mut(), window.window(), &event);
+        }
+    });
+}
+
+fn set_app_blend_func(gl: &glow::Context) {
+    let [src_rgb, dst_rgb, src_alpha, dst_alpha] = APP_BLEND_FUNC;
+    unsafe {
+        gl.enable(glow::BLEND);
+        gl.blend_func_separate(src_rgb, dst_rgb, src_alpha, dst_alpha);
+    }
+}
+
+fn blend_func(gl: &glow::Context) -> [u32; 4] {
+    unsafe {
+        [
+            gl.get_parameter_i32(glow::BLEND_SRC_RGB) as u32,
+            gl.get_parameter_i32(glow::BLEND_DST_RGB) as u32,
+            gl.get_parameter_i32(glow::BLEND_SRC_ALPHA) as u32,
+            gl.get_parameter_i32(glow::BLEND_DST_ALPHA) as u32,
+        ]
+    }
+}
+
+/// Renders a frame of an empty UI
+fn render_frame(
+    gl: &glow::Context,
+    renderer: &mut Renderer,
+    texture_map: &SimpleTextureMap,
+    imgui_context: &mut imgui::Context,
+) {
+    imgui_context.frame();
+    let draw_data = imgui_context.render();
+    renderer
+        .render(gl, texture_map, draw_data)
+        .expect("error rendering imgui");
+}
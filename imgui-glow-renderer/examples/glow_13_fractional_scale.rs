@@ -0,0 +1,164 @@
+//! Shows the demo window with a framebuffer scale of 1.5, whatever the DPI
+//! factor of the monitor, to check that clip rectangles are rounded outward
+//! to whole framebuffer pixels.
+//!
+//! On startup, two adjacent clip rectangles ending in the middle of a pixel
+//! are rendered into a framebuffer object and read back, to check that no
+//! column of pixels is left out along their boundary.
+
+use std::time::Instant;
+
+use glow::HasContext;
+use imgui::Condition;
+use imgui_glow_renderer::{Renderer, SimpleTextureMap};
+
+#[allow(dead_code)]
+mod utils;
+
+const FRAMEBUFFER_SCALE: f32 = 1.5;
+/// Logical size of the UI rendered into the framebuffer object
+const SIZE: f32 = 16.0;
+/// Right edge of each of the two adjacent clip rectangles, the first one
+/// ending in the middle of a pixel
+const CLIP_EDGES: [f32; 2] = [5.0, 10.0];
+
+fn main() {
+    let (event_loop, window) = utils::create_window("Fractional scale", glutin::GlRequest::Latest);
+    let (mut winit_platform, mut imgui_context) = utils::imgui_init(&window);
+    let gl = utils::glow_context(&window);
+    let mut texture_map = SimpleTextureMap::default();
+    let mut ig_renderer = Renderer::initialize(&gl, &mut imgui_context, &mut texture_map, true)
+        .expect("failed to create renderer");
+
+    let row = render_clip_rects(&gl, &mut ig_renderer, &texture_map, &mut imgui_context);
+    println!("Row of pixels: {:?}", row);
+    let covered = (CLIP_EDGES[1] * FRAMEBUFFER_SCALE) as usize;
+    assert!(row[..covered].iter().all(|&red| red == 255));
+    assert!(row[covered..].iter().all(|&red| red == 0));
+
+    let mut last_frame = Instant::now();
+    event_loop.run(move |event, _, control_flow| match event {
+        glutin::event::Event::NewEvents(_) => {
+            let now = Instant::now();
+            imgui_context
+                .io_mut()
+                .update_delta_time(now.duration_since(last_frame));
+            last_frame = now;
+        }
+        glutin::event::Event::MainEventsCleared => {
+            winit_platform
+                .prepare_frame(imgui_context.io_mut(), window.window())
+                .unwrap();
+
+            window.window().request_redraw();
+        }
+        glutin::event::Event::RedrawRequested(_) => {
+            unsafe { gl.clear(glow::COLOR_BUFFER_BIT) };
+
+            // Render the UI at a fractional scale of the window size
+            let size = window.window().inner_size();
+            let io = imgui_context.io_mut();
+            io.display_size = [
+                size.width as f32 / FRAMEBUFFER_SCALE,
+                size.height as f32 / FRAMEBUFFER_SCALE,
+            ];
+            io.display_framebuffer_scale = [FRAMEBUFFER_SCALE; 2];
+
+            let ui = imgui_context.frame();
+            ui.window("Fractional scale")
+                .size([300.0, 200.0], Condition::FirstUseEver)
+                .build(|| {
+                    ui.child_window("Child").border(true).build(|| {
+                        for line in 0..20 {
+                            ui.text(format!("Clipped line {}", line));
+                        }
+                    });
+                });
+            ui.show_demo_window(&mut true);
+
+            winit_platform.prepare_render(ui, window.window());
+            let draw_data = imgui_context.render();
+            ig_renderer
+                .render(&gl, &texture_map, draw_data)
+                .expect("error rendering imgui");
+
+            window.swap_buffers().unwrap();
+        }
+        glutin::event::Event::WindowEvent {
+            event: glutin::event::WindowEvent::CloseRequested,
+            ..
+        } => {
+            *control_flow = glutin::event_loop::ControlFlow::Exit;
+        }
+        glutin::event::Event::LoopDestroyed => {
+            ig_renderer.destroy(&gl);
+        }
+        event => {
+            winit_platform.handle_event(imgui_context.io_mut(), window.window(), &event);
+        }
+    });
+}
+
+/// Renders a red rectangle covering the framebuffer object through each of
+/// the two clip rectangles, and reads back the red channel of a row of pixels
+fn render_clip_rects(
+    gl: &glow::Context,
+    renderer: &mut Renderer,
+    texture_map: &SimpleTextureMap,
+    imgui_context: &mut imgui::Context,
+) -> Vec<u8> {
+    let io = imgui_context.io_mut();
+    io.display_size = [SIZE; 2];
+    io.display_framebuffer_scale = [FRAMEBUFFER_SCALE; 2];
+    let ui = imgui_context.frame();
+    let draw_list = ui.get_background_draw_list();
+    let mut left = 0.0;
+    for right in CLIP_EDGES {
+        draw_list.with_clip_rect([left, 0.0], [right, SIZE], || {
+            draw_list
+                .add_rect([0.0, 0.0], [SIZE; 2], [1.0, 0.0, 0.0, 1.0])
+                .filled(true)
+                .build();
+        });
+        left = right;
+    }
+    drop(draw_list);
+    let draw_data = imgui_context.render();
+
+    let fb_size = (SIZE * FRAMEBUFFER_SCALE) as i32;
+    let mut pixels = vec![0; fb_size as usize * 4];
+    unsafe {
+        let fbo = gl.create_framebuffer().unwrap();
+        let rbo = gl.create_renderbuffer().unwrap();
+        gl.bind_framebuffer(glow::FRAMEBUFFER, Some(fbo));
+        gl.bind_renderbuffer(glow::RENDERBUFFER, Some(rbo));
+        gl.renderbuffer_storage(glow::RENDERBUFFER, glow::RGBA8, fb_size, fb_size);
+        gl.framebuffer_renderbuffer(
+            glow::FRAMEBUFFER,
+            glow::COLOR_ATTACHMENT0,
+            glow::RENDERBUFFER,
+            Some(rbo),
+        );
+        gl.clear_color(0.0, 0.0, 0.0, 1.0);
+        gl.clear(glow::COLOR_BUFFER_BIT);
+
+        renderer
+            .render(gl, texture_map, draw_data)
+            .expect("error rendering clip rectangles");
+        gl.read_pixels(
+            0,
+            fb_size / 2,
+            fb_size,
+            1,
+            glow::RGBA,
+            glow::UNSIGNED_BYTE,
+            glow::PixelPackData::Slice(&mut pixels),
+        );
+
+        gl.bind_framebuffer(glow::FRAMEBUFFER, None);
+        gl.bind_renderbuffer(glow::RENDERBUFFER, None);
+        gl.delete_framebuffer(fbo);
+        gl.delete_renderbuffer(rbo);
+    }
+    pixels.chunks(4).map(|pixel| pixel[0]).collect()
+}
@@ -0,0 +1,195 @@
+//! Shows an image through an `AutoRenderer` created with
+//! `AutoRenderer::with_texture_map`, which looks up textures with an
+//! `imgui::Textures` instead of the default `SimpleTextureMap`.
+//!
+//! On startup, the image is rendered into a framebuffer object with each of
+//! the two texture maps and read back, to check that both give the same
+//! pixels.
+
+use std::time::Instant;
+
+use glow::HasContext;
+use imgui::{Condition, TextureId};
+use imgui_glow_renderer::{AutoRenderer, Renderer, SimpleTextureMap, TextureMap};
+
+#[allow(dead_code)]
+mod utils;
+
+/// Size of the framebuffer object the image is rendered into
+const SIZE: i32 = 16;
+/// Texels of the 2x2 image, row by row
+const TEXELS: [[u8; 4]; 4] = [
+    [255, 0, 0, 255],
+    [0, 255, 0, 255],
+    [0, 0, 255, 255],
+    [255, 255, 255, 255],
+];
+
+fn main() {
+    let (event_loop, window) = utils::create_window("Texture maps", glutin::GlRequest::Latest);
+    let (mut winit_platform, mut imgui_context) = utils::imgui_init(&window);
+    let gl = utils::glow_context(&window);
+    let image = create_image(&gl);
+
+    let simple = render_image(&gl, SimpleTextureMap::default(), image, &mut imgui_context);
+    let textures = render_image(
+        &gl,
+        imgui::Textures::<glow::Texture>::default(),
+        image,
+        &mut imgui_context,
+    );
+    assert!(simple == textures, "the texture maps gave different pixels");
+    // Each texel covers a quarter of the framebuffer object
+    for texel in TEXELS {
+        let count = simple.chunks(4).filter(|&pixel| pixel == texel).count();
+        assert_eq!(count, (SIZE * SIZE / 4) as usize);
+    }
+    println!("Both texture maps rendered the same image");
+
+    let mut ig_renderer =
+        AutoRenderer::with_texture_map(gl, &mut imgui_context, imgui::Textures::default())
+            .expect("failed to create renderer");
+    // The renderer doesn't delete the textures registered by the application
+    let image_id = ig_renderer.texture_map_mut().register(image).unwrap();
+
+    let mut last_frame = Instant::now();
+    event_loop.run(move |event, _, control_flow| match event {
+        glutin::event::Event::NewEvents(_) => {
+            let now = Instant::now();
+            imgui_context
+                .io_mut()
+                .update_delta_time(now.duration_since(last_frame));
+            last_frame = now;
+        }
+        glutin::event::Event::MainEventsCleared => {
+            winit_platform
+                .prepare_frame(imgui_context.io_mut(), window.window())
+                .unwrap();
+
+            window.window().request_redraw();
+        }
+        glutin::event::Event::RedrawRequested(_) => {
+            unsafe { ig_renderer.gl_context().clear(glow::COLOR_BUFFER_BIT) };
+
+            let ui = imgui_context.frame();
+            ui.window("Texture maps")
+                .size([300.0, 300.0], Condition::FirstUseEver)
+                .build(|| {
+                    ui.text("Texture registered in an imgui::Textures");
+                    imgui::Image::new(image_id, [200.0, 200.0]).build(ui);
+                });
+
+            winit_platform.prepare_render(ui, window.window());
+            let draw_data = imgui_context.render();
+            ig_renderer
+                .render(draw_data)
+                .expect("error rendering imgui");
+
+            window.swap_buffers().unwrap();
+        }
+        glutin::event::Event::WindowEvent {
+            event: glutin::event::WindowEvent::CloseRequested,
+            ..
+        } => {
+            *control_flow = glutin::event_loop::ControlFlow::Exit;
+        }
+        glutin::event::Event::LoopDestroyed => {
+            ig_renderer.texture_map_mut().remove(image_id);
+            unsafe { ig_renderer.gl_context().delete_texture(image) };
+        }
+        event => {
+            winit_platform.handle_event(imgui_context.io_mut(), window.window(), &event);
+        }
+    });
+}
+
+/// Uploads the 2x2 image, sampled without filtering
+fn create_image(gl: &glow::Context) -> glow::Texture {
+    let data: Vec<u8> = TEXELS.iter().flatten().copied().collect();
+    unsafe {
+        let texture = gl.create_texture().expect("unable to create GL texture");
+        gl.bind_texture(glow::TEXTURE_2D, Some(texture));
+        gl.tex_parameter_i32(
+            glow::TEXTURE_2D,
+            glow::TEXTURE_MIN_FILTER,
+            glow::NEAREST as _,
+        );
+        gl.tex_parameter_i32(
+            glow::TEXTURE_2D,
+            glow::TEXTURE_MAG_FILTER,
+            glow::NEAREST as _,
+        );
+        gl.tex_image_2d(
+            glow::TEXTURE_2D,
+            0,
+            glow::RGBA as _,
+            2,
+            2,
+            0,
+            glow::RGBA,
+            glow::UNSIGNED_BYTE,
+            Some(&data),
+        );
+        texture
+    }
+}
+
+/// Renders `image` over the whole framebuffer object, looking it up with
+/// `texture_map`, and reads back the pixels
+fn render_image<T: TextureMap>(
+    gl: &glow::Context,
+    mut texture_map: T,
+    image: glow::Texture,
+    imgui_context: &mut imgui::Context,
+) -> Vec<u8> {
+    let mut renderer = Renderer::initialize(gl, imgui_context, &mut texture_map, false)
+        .expect("failed to create renderer");
+    let image_id: TextureId = texture_map.register(image).unwrap();
+
+    let io = imgui_context.io_mut();
+    io.display_size = [SIZE as f32; 2];
+    io.display_framebuffer_scale = [1.0, 1.0];
+    let ui = imgui_context.frame();
+    ui.get_background_draw_list()
+        .add_image(image_id, [0.0, 0.0], [SIZE as f32; 2])
+        .build();
+    let draw_data = imgui_context.render();
+
+    let mut pixels = vec![0; (SIZE * SIZE * 4) as usize];
+    unsafe {
+        let fbo = gl.create_framebuffer().unwrap();
+        let rbo = gl.create_renderbuffer().unwrap();
+        gl.bind_framebuffer(glow::FRAMEBUFFER, Some(fbo));
+        gl.bind_renderbuffer(glow::RENDERBUFFER, Some(rbo));
+        gl.renderbuffer_storage(glow::RENDERBUFFER, glow::RGBA8, SIZE, SIZE);
+        gl.framebuffer_renderbuffer(
+            glow::FRAMEBUFFER,
+            glow::COLOR_ATTACHMENT0,
+            glow::RENDERBUFFER,
+            Some(rbo),
+        );
+        gl.clear_color(0.0, 0.0, 0.0, 0.0);
+        gl.clear(glow::COLOR_BUFFER_BIT);
+
+        renderer
+            .render(gl, &texture_map, draw_data)
+            .expect("error rendering image");
+        gl.read_pixels(
+            0,
+            0,
+            SIZE,
+            SIZE,
+            glow::RGBA,
+            glow::UNSIGNED_BYTE,
+            glow::PixelPackData::Slice(&mut pixels),
+        );
+
+        gl.bind_framebuffer(glow::FRAMEBUFFER, None);
+        gl.bind_renderbuffer(glow::RENDERBUFFER, None);
+        gl.delete_framebuffer(fbo);
+        gl.delete_renderbuffer(rbo);
+    }
+    // Only deletes the font atlas texture, not the image
+    renderer.destroy(gl);
+    pixels
+}
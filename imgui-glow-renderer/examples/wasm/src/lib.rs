@@ -0,0 +1,111 @@
+//! Renders the imgui demo window into a WebGL 2 canvas, created and fed with
+//! events by winit's web backend.
+//!
+//! `imgui-winit-support` measures time with `std::time::Instant`, which isn't
+//! available on `wasm32-unknown-unknown`, so the few inputs needed by the demo
+//! are forwarded to imgui here, and time is measured with `performance.now()`.
+
+use std::time::Duration;
+
+use imgui::Condition;
+use imgui_glow_renderer::{glow::HasContext, AutoRenderer};
+use wasm_bindgen::{prelude::*, JsCast};
+use winit::{
+    dpi::LogicalSize,
+    event::{ElementState, Event, MouseButton, MouseScrollDelta, WindowEvent},
+    event_loop::EventLoop,
+    platform::web::WindowExtWebSys,
+    window::WindowBuilder,
+};
+
+#[wasm_bindgen(start)]
+pub fn start() -> Result<(), JsValue> {
+    let event_loop = EventLoop::new();
+    let window = WindowBuilder::new()
+        .with_inner_size(LogicalSize::new(1024.0, 768.0))
+        .build(&event_loop)
+        .map_err(|error| error.to_string())?;
+
+    let web_window = web_sys::window().ok_or("no global `window`")?;
+    let canvas = window.canvas();
+    web_window
+        .document()
+        .and_then(|document| document.body())
+        .ok_or("no document body")?
+        .append_child(&canvas)?;
+    let webgl2_context = canvas
+        .get_context("webgl2")?
+        .ok_or("WebGL 2 isn't supported")?
+        .dyn_into::<web_sys::WebGl2RenderingContext>()?;
+    let gl = imgui_glow_renderer::glow::Context::from_webgl2_context(webgl2_context);
+
+    let mut imgui_context = imgui::Context::create();
+    imgui_context.set_ini_filename(None);
+    let mut renderer =
+        AutoRenderer::initialize(gl, &mut imgui_context).map_err(|error| error.to_string())?;
+
+    let performance = web_window.performance().ok_or("no `performance`")?;
+    let mut last_frame = performance.now();
+    event_loop.run(move |event, _, _| match event {
+        Event::MainEventsCleared => window.request_redraw(),
+        Event::RedrawRequested(_) => {
+            let now = performance.now();
+            let io = imgui_context.io_mut();
+            io.update_delta_time(Duration::from_secs_f64(
+                (now - last_frame).max(1.0) / 1000.0,
+            ));
+            last_frame = now;
+
+            let scale_factor = window.scale_factor();
+            let size: LogicalSize<f32> = window.inner_size().to_logical(scale_factor);
+            io.display_size = [size.width, size.height];
+            io.display_framebuffer_scale = [scale_factor as f32; 2];
+
+            let ui = imgui_context.frame();
+            ui.window("WebGL 2")
+                .size([300.0, 100.0], Condition::FirstUseEver)
+                .build(|| {
+                    ui.text("Hello from imgui-glow-renderer!");
+                    ui.text(format!("{:.1} FPS", ui.io().framerate));
+                });
+            ui.show_demo_window(&mut true);
+
+            unsafe {
+                let gl = renderer.gl_context();
+                gl.clear_color(0.1, 0.1, 0.1, 1.0);
+                gl.clear(imgui_glow_renderer::glow::COLOR_BUFFER_BIT);
+            }
+            let draw_data = imgui_context.render();
+            renderer.render(draw_data).expect("error rendering imgui");
+        }
+        Event::WindowEvent { event, .. } => {
+            let io = imgui_context.io_mut();
+            match event {
+                WindowEvent::CursorMoved { position, .. } => {
+                    let position = position.to_logical(window.scale_factor());
+                    io.add_mouse_pos_event([position.x, position.y]);
+                }
+                WindowEvent::MouseInput { state, button, .. } => {
+                    let button = match button {
+                        MouseButton::Left => imgui::MouseButton::Left,
+                        MouseButton::Right => imgui::MouseButton::Right,
+                        MouseButton::Middle => imgui::MouseButton::Middle,
+                        MouseButton::Other(_) => return,
+                    };
+                    io.add_mouse_button_event(button, state == ElementState::Pressed);
+                }
+                WindowEvent::MouseWheel { delta, .. } => match delta {
+                    MouseScrollDelta::LineDelta(h, v) => io.add_mouse_wheel_event([h, v]),
+                    MouseScrollDelta::PixelDelta(position) => {
+                        // Scroll by one line per 20 pixels
+                        let position = position.to_logical::<f32>(window.scale_factor());
+                        io.add_mouse_wheel_event([position.x / 20.0, position.y / 20.0]);
+                    }
+                },
+                WindowEvent::ReceivedCharacter(character) => io.add_input_character(character),
+                _ => {}
+            }
+        }
+        _ => {}
+    });
+}
@@ -21,6 +21,10 @@
 //! found with these OpenGL versions, pull requests to extend support to earlier
 //! versions are welcomed.
 //!
+//! WebGL 2 is supported on `wasm32`, with a [`glow::Context`] created by
+//! `glow::Context::from_webgl2_context`. It is detected from its version
+//! string and treated as OpenGL ES 3.0 (see the `examples/wasm` crate).
+//!
 //! # Scope
 //!
 //! Consider this an example renderer. It is intended to be sufficent for simple
@@ -38,16 +42,19 @@
 //! When using the [`AutoRenderer`], this library will convert colors to sRGB
 //! as a step in the shader. When initialising a [`Renderer`], you can choose
 //! whether or not to include this step in the shader or not when calling
-//! [`Renderer::initialize`].
+//! [`Renderer::initialize`], or pick an [`OutputColorSpace`] with
+//! [`Renderer::initialize_with_color_space`].
 //!
 //! This library also assumes that textures have their internal format
 //! set appropriately when uploaded to OpenGL. That is, assuming your texture
 //! is sRGB (if you don't know, it probably is) the `internal_format` is
 //! one of the `SRGB*` values.
 
-use std::{borrow::Cow, error::Error, fmt::Display, mem::size_of, num::NonZeroU32, rc::Rc};
+#[cfg(not(target_arch = "wasm32"))]
+use std::num::NonZeroU32;
+use std::{borrow::Cow, collections::HashMap, error::Error, fmt::Display, mem::size_of, rc::Rc};
 
-use imgui::{internal::RawWrapper, DrawCmd, DrawData, DrawVert};
+use imgui::{internal::RawWrapper, DrawCmd, DrawDataSource, DrawVert};
 
 use crate::versions::{GlVersion, GlslVersion};
 
@@ -56,12 +63,19 @@ pub use glow;
 use glow::{Context, HasContext};
 
 pub mod versions;
+#[cfg(feature = "docking")]
+pub mod viewports;
+
+#[cfg(feature = "docking")]
+pub use viewports::ViewportSurfaces;
 
 pub type GlBuffer = <Context as HasContext>::Buffer;
 pub type GlTexture = <Context as HasContext>::Texture;
 pub type GlVertexArray = <Context as HasContext>::VertexArray;
-type GlProgram = <Context as HasContext>::Program;
+pub type GlProgram = <Context as HasContext>::Program;
 type GlUniformLocation = <Context as HasContext>::UniformLocation;
+/// Callback setting additional uniforms, see [`Renderer::set_uniform_callback`]
+pub type UniformCallback = Box<dyn FnMut(&Context, &GlProgram)>;
 
 /// Renderer which owns the OpenGL context and handles textures itself. Also
 /// converts all output colors to sRGB for display. Useful for simple applications,
@@ -70,10 +84,18 @@ type GlUniformLocation = <Context as HasContext>::UniformLocation;
 ///
 /// OpenGL context is still available to the rest of the application through
 /// the [`gl_context`](Self::gl_context) method.
-pub struct AutoRenderer {
+///
+/// Textures are looked up with a [`SimpleTextureMap`] by default, or with the
+/// [`TextureMap`] given to [`AutoRenderer::with_texture_map`].
+///
+/// With the `docking` feature, it can also render the extra viewports of
+/// imgui, see the [`viewports`] module.
+pub struct AutoRenderer<T: TextureMap = SimpleTextureMap> {
     gl: Rc<glow::Context>,
-    texture_map: SimpleTextureMap,
+    texture_map: T,
     renderer: Renderer,
+    #[cfg(feature = "docking")]
+    viewports: Option<Box<dyn ViewportSurfaces>>,
 }
 
 impl AutoRenderer {
@@ -84,12 +106,33 @@ impl AutoRenderer {
         gl: glow::Context,
         imgui_context: &mut imgui::Context,
     ) -> Result<Self, InitError> {
-        let mut texture_map = SimpleTextureMap::default();
+        Self::with_texture_map(gl, imgui_context, SimpleTextureMap::default())
+    }
+}
+
+impl<T: TextureMap> AutoRenderer<T> {
+    /// Creates the renderer like [`AutoRenderer::initialize`], looking up
+    /// textures with `texture_map`, e.g. an application's own map from
+    /// [`imgui::TextureId`] to its OpenGL textures.
+    ///
+    /// The font atlas texture is registered in the map. See [`TextureMap`]
+    /// for the textures the renderer deletes.
+    ///
+    /// # Errors
+    /// Any error initialising the OpenGL objects (including shaders) will
+    /// result in an error.
+    pub fn with_texture_map(
+        gl: glow::Context,
+        imgui_context: &mut imgui::Context,
+        mut texture_map: T,
+    ) -> Result<Self, InitError> {
         let renderer = Renderer::initialize(&gl, imgui_context, &mut texture_map, true)?;
         Ok(Self {
             gl: Rc::new(gl),
             texture_map,
             renderer,
+            #[cfg(feature = "docking")]
+            viewports: None,
         })
     }
 
@@ -101,12 +144,12 @@ impl AutoRenderer {
     }
 
     #[inline]
-    pub fn texture_map(&self) -> &SimpleTextureMap {
+    pub fn texture_map(&self) -> &T {
         &self.texture_map
     }
 
     #[inline]
-    pub fn texture_map_mut(&mut self) -> &mut SimpleTextureMap {
+    pub fn texture_map_mut(&mut self) -> &mut T {
         &mut self.texture_map
     }
 
@@ -115,16 +158,66 @@ impl AutoRenderer {
         &self.renderer
     }
 
+    /// Renders the draw data of a frame, or an [`imgui::OwnedDrawData`] snapshot of it.
+    ///
     /// # Errors
     /// Some OpenGL errors trigger an error (few are explicitly checked,
     /// however)
     #[inline]
-    pub fn render(&mut self, draw_data: &DrawData) -> Result<(), RenderError> {
+    pub fn render<D: DrawDataSource + ?Sized>(&mut self, draw_data: &D) -> Result<(), RenderError> {
         self.renderer.render(&self.gl, &self.texture_map, draw_data)
     }
+
+    /// See [`Renderer::reload_font_texture`].
+    ///
+    /// # Errors
+    /// Creating the texture fails if the renderer was destroyed, or if the
+    /// atlas is larger than `GL_MAX_TEXTURE_SIZE`.
+    #[inline]
+    pub fn reload_font_texture(
+        &mut self,
+        imgui_context: &mut imgui::Context,
+    ) -> Result<(), InitError> {
+        self.renderer.reload_font_texture(&self.gl, imgui_context)
+    }
+
+    /// See [`Renderer::create_texture`].
+    ///
+    /// # Errors
+    /// Creating the OpenGL texture can fail.
+    #[inline]
+    pub fn create_texture(
+        &mut self,
+        width: u32,
+        height: u32,
+        format: TextureFormat,
+    ) -> Result<imgui::TextureId, TextureError> {
+        self.renderer
+            .create_texture(&self.gl, &mut self.texture_map, width, height, format)
+    }
+
+    /// See [`Renderer::update_texture`].
+    ///
+    /// # Errors
+    /// See [`Renderer::update_texture`].
+    #[allow(clippy::too_many_arguments)]
+    #[inline]
+    pub fn update_texture(
+        &mut self,
+        texture_id: imgui::TextureId,
+        x: u32,
+        y: u32,
+        width: u32,
+        height: u32,
+        data: &[u8],
+        format: TextureFormat,
+    ) -> Result<(), TextureError> {
+        self.renderer
+            .update_texture(&self.gl, texture_id, x, y, width, height, data, format)
+    }
 }
 
-impl Drop for AutoRenderer {
+impl<T: TextureMap> Drop for AutoRenderer<T> {
     fn drop(&mut self) {
         self.renderer.destroy(&self.gl);
     }
@@ -138,11 +231,40 @@ pub struct Renderer {
     pub vbo_handle: Option<GlBuffer>,
     pub ebo_handle: Option<GlBuffer>,
     pub font_atlas_texture: Option<GlTexture>,
+    font_atlas_texture_id: imgui::TextureId,
     #[cfg(feature = "bind_vertex_array_support")]
     pub vertex_array_object: Option<GlVertexArray>,
     pub gl_version: GlVersion,
+    /// Whether the context is a WebGL context, whose `gl_version` is that of
+    /// the OpenGL ES version it is based on. Desktop-only state isn't used
+    /// with WebGL, and object bindings can't be queried from it, so they are
+    /// left unbound after rendering.
+    pub is_webgl: bool,
     pub has_clip_origin_support: bool,
+    /// Color space of the colors written to the framebuffer, can be changed between frames
+    pub output_color_space: OutputColorSpace,
+    /// Whether to enable (`Some(true)`) or disable (`Some(false)`) `GL_FRAMEBUFFER_SRGB`
+    /// while rendering, restoring its previous state afterwards. Left as is if `None` (the
+    /// default), or if the OpenGL version doesn't support it.
+    pub framebuffer_srgb: Option<bool>,
+    /// Whether to back up the OpenGL state changed while rendering, and
+    /// restore it afterwards (`true` by default, like the upstream backends).
+    /// See [`GlStateBackup`] for the state that is captured. When `false`, the
+    /// state is neither queried nor restored, and is left as set up for
+    /// imgui, except for the vertex array object, which is unbound.
+    pub restore_state: bool,
     pub is_destroyed: bool,
+    textures: HashMap<imgui::TextureId, CreatedTexture>,
+    uniform_callback: Option<UniformCallback>,
+}
+
+/// A texture created with [`Renderer::create_texture`]
+#[derive(Copy, Clone, Debug)]
+struct CreatedTexture {
+    gl_texture: GlTexture,
+    width: u32,
+    height: u32,
+    format: TextureFormat,
 }
 
 impl Renderer {
@@ -172,6 +294,44 @@ impl Renderer {
         imgui_context: &mut imgui::Context,
         texture_map: &mut T,
         output_srgb: bool,
+    ) -> Result<Self, InitError> {
+        let output_color_space = if output_srgb {
+            OutputColorSpace::Srgb
+        } else {
+            OutputColorSpace::Linear
+        };
+        Self::initialize_with_color_space(gl, imgui_context, texture_map, output_color_space)
+    }
+
+    /// Create the renderer like [`Renderer::initialize`], choosing the color
+    /// space of the output with an [`OutputColorSpace`].
+    ///
+    /// # Errors
+    /// Any error initialising the OpenGL objects (including shaders) will
+    /// result in an error.
+    pub fn initialize_with_color_space<T: TextureMap>(
+        gl: &Context,
+        imgui_context: &mut imgui::Context,
+        texture_map: &mut T,
+        output_color_space: OutputColorSpace,
+    ) -> Result<Self, InitError> {
+        Self::initialize_with_shaders(gl, imgui_context, texture_map, output_color_space, None)
+    }
+
+    /// Create the renderer like [`Renderer::initialize_with_color_space`],
+    /// with the shaders supplied by `shader_provider` instead of the built-in
+    /// ones (if `Some`).
+    ///
+    /// # Errors
+    /// Any error initialising the OpenGL objects (including shaders) will
+    /// result in an error, in particular if the shaders lack an attribute or
+    /// uniform required by the renderer (see [`ShaderProvider`]).
+    pub fn initialize_with_shaders<T: TextureMap>(
+        gl: &Context,
+        imgui_context: &mut imgui::Context,
+        texture_map: &mut T,
+        output_color_space: OutputColorSpace,
+        shader_provider: Option<&dyn ShaderProvider>,
     ) -> Result<Self, InitError> {
         #![allow(
             clippy::similar_names,
@@ -180,6 +340,24 @@ impl Renderer {
         )]
 
         let gl_version = GlVersion::read(gl);
+        let is_webgl = GlVersion::is_webgl(gl);
+
+        if size_of::<imgui::DrawIdx>() == 4 && !gl_version.element_index_uint_support() {
+            #[cfg(feature = "gl_extensions_support")]
+            let support = {
+                let extensions = gl.supported_extensions();
+                extensions.contains("OES_element_index_uint")
+                    || extensions.contains("GL_OES_element_index_uint")
+            };
+            #[cfg(not(feature = "gl_extensions_support"))]
+            let support = false;
+            if !support {
+                return Err(InitError::UserError(
+                    "32 bit draw indices require OpenGL ES 3.0, WebGL 2 or OES_element_index_uint"
+                        .to_owned(),
+                ));
+            }
+        }
 
         #[cfg(feature = "clip_origin_support")]
         let has_clip_origin_support = {
@@ -189,12 +367,7 @@ impl Renderer {
             if support {
                 support
             } else {
-                let extensions_count = unsafe { gl.get_parameter_i32(glow::NUM_EXTENSIONS) } as u32;
-                (0..extensions_count).any(|index| {
-                    let extension_name =
-                        unsafe { gl.get_parameter_indexed_string(glow::EXTENSIONS, index) };
-                    extension_name == "GL_ARB_clip_control"
-                })
+                gl.supported_extensions().contains("GL_ARB_clip_control")
             }
             #[cfg(not(feature = "gl_extensions_support"))]
             support
@@ -207,7 +380,7 @@ impl Renderer {
 
         let font_atlas_texture = prepare_font_atlas(gl, imgui_context.fonts(), texture_map)?;
 
-        let shaders = Shaders::new(gl, gl_version, output_srgb)?;
+        let shaders = Shaders::new(gl, gl_version, shader_provider)?;
         let vbo_handle = unsafe { gl.create_buffer() }.map_err(InitError::CreateBufferObject)?;
         let ebo_handle = unsafe { gl.create_buffer() }.map_err(InitError::CreateBufferObject)?;
 
@@ -219,11 +392,18 @@ impl Renderer {
             vbo_handle: Some(vbo_handle),
             ebo_handle: Some(ebo_handle),
             font_atlas_texture: Some(font_atlas_texture),
+            font_atlas_texture_id: imgui_context.fonts().tex_id,
             #[cfg(feature = "bind_vertex_array_support")]
             vertex_array_object: None,
             gl_version,
+            is_webgl,
             has_clip_origin_support,
+            output_color_space,
+            framebuffer_srgb: None,
+            restore_state: true,
             is_destroyed: false,
+            textures: HashMap::new(),
+            uniform_callback: None,
         };
 
         // Leave this until the end of the function to avoid changing state if
@@ -233,6 +413,257 @@ impl Renderer {
         Ok(out)
     }
 
+    /// Uploads the font atlas texture again, e.g. after fonts were added or
+    /// rebuilt for another DPI factor, building the atlas first if needed.
+    ///
+    /// The storage of the font texture is replaced by the RGBA32 pixels of the
+    /// current atlas, and its [`imgui::TextureId`] is kept and set on the
+    /// atlas again. Other textures (e.g. the user textures of the texture map)
+    /// are left untouched.
+    ///
+    /// # Errors
+    /// Creating the texture fails if the renderer was destroyed, or if the
+    /// atlas is larger than `GL_MAX_TEXTURE_SIZE`
+    /// ([`InitError::FontAtlasTooLarge`]), in which case the previous texture
+    /// is kept.
+    pub fn reload_font_texture(
+        &mut self,
+        gl: &Context,
+        imgui_context: &mut imgui::Context,
+    ) -> Result<(), InitError> {
+        let gl_texture = self
+            .font_atlas_texture
+            .ok_or_else(|| InitError::CreateTexture("renderer is destroyed".into()))?;
+        self.state_backup.pre_init(gl);
+        let result = upload_font_atlas(gl, imgui_context.fonts(), gl_texture);
+        self.state_backup.post_init(gl);
+        imgui_context.fonts().tex_id = self.font_atlas_texture_id;
+        result
+    }
+
+    /// Creates an empty texture, to be filled with [`Renderer::update_texture`], e.g. to
+    /// stream video frames or other images generated every frame.
+    ///
+    /// The texture is registered in `texture_map`, and is deleted with
+    /// [`Renderer::delete_texture`] or when the renderer is destroyed.
+    ///
+    /// # Errors
+    /// Creating the OpenGL texture or registering it in the texture map can fail.
+    pub fn create_texture<T: TextureMap>(
+        &mut self,
+        gl: &Context,
+        texture_map: &mut T,
+        width: u32,
+        height: u32,
+        format: TextureFormat,
+    ) -> Result<imgui::TextureId, TextureError> {
+        #![allow(clippy::cast_possible_wrap)]
+
+        let gl_texture = unsafe { gl.create_texture() }.map_err(TextureError::CreateTexture)?;
+        self.state_backup.pre_init(gl);
+        unsafe {
+            gl.bind_texture(glow::TEXTURE_2D, Some(gl_texture));
+            gl.tex_parameter_i32(
+                glow::TEXTURE_2D,
+                glow::TEXTURE_MIN_FILTER,
+                glow::LINEAR as _,
+            );
+            gl.tex_parameter_i32(
+                glow::TEXTURE_2D,
+                glow::TEXTURE_MAG_FILTER,
+                glow::LINEAR as _,
+            );
+            gl.tex_image_2d(
+                glow::TEXTURE_2D,
+                0,
+                format.gl_internal_format() as _,
+                width as _,
+                height as _,
+                0,
+                format.gl_format(),
+                glow::UNSIGNED_BYTE,
+                None,
+            );
+        }
+        self.state_backup.post_init(gl);
+
+        let texture_id = match texture_map.register(gl_texture) {
+            Some(texture_id) => texture_id,
+            None => {
+                unsafe { gl.delete_texture(gl_texture) };
+                return Err(TextureError::RegisterTexture);
+            }
+        };
+        self.textures.insert(
+            texture_id,
+            CreatedTexture {
+                gl_texture,
+                width,
+                height,
+                format,
+            },
+        );
+        Ok(texture_id)
+    }
+
+    /// Uploads tightly packed pixels to the `width` x `height` rectangle at `x`, `y` of a
+    /// texture created with [`Renderer::create_texture`].
+    ///
+    /// # Errors
+    /// See [`Renderer::update_texture_strided`].
+    #[allow(clippy::too_many_arguments)]
+    pub fn update_texture(
+        &mut self,
+        gl: &Context,
+        texture_id: imgui::TextureId,
+        x: u32,
+        y: u32,
+        width: u32,
+        height: u32,
+        data: &[u8],
+        format: TextureFormat,
+    ) -> Result<(), TextureError> {
+        let stride = width as usize * format.bytes_per_pixel();
+        self.update_texture_strided(
+            gl,
+            texture_id,
+            [x, y],
+            [width, height],
+            data,
+            stride,
+            format,
+        )
+    }
+
+    /// Uploads pixels to the rectangle of a texture created with
+    /// [`Renderer::create_texture`], where `stride` is the number of bytes between the start
+    /// of each row in `data`, e.g. to upload part of a larger image.
+    ///
+    /// Rows of any length are supported, regardless of `GL_UNPACK_ALIGNMENT`. Strided data
+    /// is uploaded with `GL_UNPACK_ROW_LENGTH` where available, and repacked otherwise
+    /// (OpenGL ES 2.0 and WebGL 1).
+    ///
+    /// # Errors
+    /// Fails if the texture wasn't created by this renderer, if the rectangle doesn't fit in
+    /// the texture, or if `format`, `stride` or the length of `data` don't match the
+    /// rectangle.
+    #[allow(clippy::too_many_arguments)]
+    pub fn update_texture_strided(
+        &mut self,
+        gl: &Context,
+        texture_id: imgui::TextureId,
+        position: [u32; 2],
+        size: [u32; 2],
+        data: &[u8],
+        stride: usize,
+        format: TextureFormat,
+    ) -> Result<(), TextureError> {
+        #![allow(clippy::cast_possible_wrap)]
+
+        let texture = *self
+            .textures
+            .get(&texture_id)
+            .ok_or(TextureError::UnknownTexture(texture_id))?;
+        check_texture_update(&texture, position, size, data.len(), stride, format)?;
+        if size[0] == 0 || size[1] == 0 {
+            return Ok(());
+        }
+
+        let bpp = format.bytes_per_pixel();
+        let row_size = size[0] as usize * bpp;
+        // Stride in pixels, used if it is a whole number of pixels
+        let row_length = stride / bpp;
+        let len = stride * (size[1] as usize - 1) + row_size;
+        let repacked;
+        let (data, row_length) = if stride == row_size {
+            (&data[..len], None)
+        } else if self.unpack_row_length_support() && row_length * bpp == stride {
+            (&data[..len], Some(row_length))
+        } else {
+            repacked = data
+                .chunks(stride)
+                .take(size[1] as usize)
+                .flat_map(|row| &row[..row_size])
+                .copied()
+                .collect::<Vec<_>>();
+            (&repacked[..], None)
+        };
+
+        self.state_backup.pre_init(gl);
+        unsafe {
+            let alignment = gl.get_parameter_i32(glow::UNPACK_ALIGNMENT);
+            gl.pixel_store_i32(glow::UNPACK_ALIGNMENT, 1);
+            if let Some(row_length) = row_length {
+                gl.pixel_store_i32(glow::UNPACK_ROW_LENGTH, row_length as _);
+            }
+            gl.bind_texture(glow::TEXTURE_2D, Some(texture.gl_texture));
+            gl.tex_sub_image_2d(
+                glow::TEXTURE_2D,
+                0,
+                position[0] as _,
+                position[1] as _,
+                size[0] as _,
+                size[1] as _,
+                format.gl_format(),
+                glow::UNSIGNED_BYTE,
+                glow::PixelUnpackData::Slice(data),
+            );
+            if row_length.is_some() {
+                gl.pixel_store_i32(glow::UNPACK_ROW_LENGTH, 0);
+            }
+            gl.pixel_store_i32(glow::UNPACK_ALIGNMENT, alignment);
+        }
+        self.state_backup.post_init(gl);
+        Ok(())
+    }
+
+    /// Deletes a texture created with [`Renderer::create_texture`]. It must also be removed
+    /// from the texture map, if the map supports it.
+    ///
+    /// # Errors
+    /// Fails if the texture wasn't created by this renderer.
+    pub fn delete_texture(
+        &mut self,
+        gl: &Context,
+        texture_id: imgui::TextureId,
+    ) -> Result<(), TextureError> {
+        let texture = self
+            .textures
+            .remove(&texture_id)
+            .ok_or(TextureError::UnknownTexture(texture_id))?;
+        unsafe { gl.delete_texture(texture.gl_texture) };
+        Ok(())
+    }
+
+    #[cfg(feature = "framebuffer_srgb_support")]
+    fn is_framebuffer_srgb_enabled(&self, gl: &Context) -> bool {
+        self.gl_version.framebuffer_srgb_support()
+            && unsafe { gl.is_enabled(glow::FRAMEBUFFER_SRGB) }
+    }
+
+    #[cfg(not(feature = "framebuffer_srgb_support"))]
+    fn is_framebuffer_srgb_enabled(&self, _gl: &Context) -> bool {
+        false
+    }
+
+    #[cfg(feature = "unpack_row_length_support")]
+    fn unpack_row_length_support(&self) -> bool {
+        !self.is_webgl && self.gl_version.unpack_row_length_support()
+    }
+
+    #[cfg(not(feature = "unpack_row_length_support"))]
+    fn unpack_row_length_support(&self) -> bool {
+        false
+    }
+
+    /// Sets a callback called with the shader program each time it is set up
+    /// for rendering, after the renderer set its own uniforms. This allows
+    /// setting the additional uniforms of custom shaders (see
+    /// [`ShaderProvider`]), or `None` to remove the callback.
+    pub fn set_uniform_callback(&mut self, callback: Option<UniformCallback>) {
+        self.uniform_callback = callback;
+    }
+
     /// This must be called before being dropped to properly free OpenGL
     /// resources.
     pub fn destroy(&mut self, gl: &Context) {
@@ -256,31 +687,40 @@ impl Renderer {
             unsafe { gl.delete_texture(h) };
             self.font_atlas_texture = None;
         }
+        for (_, texture) in self.textures.drain() {
+            unsafe { gl.delete_texture(texture.gl_texture) };
+        }
 
         self.is_destroyed = true;
     }
 
+    /// Renders the draw data of a frame, or an [`imgui::OwnedDrawData`] snapshot of it.
+    ///
     /// # Errors
     /// Some OpenGL errors trigger an error (few are explicitly checked,
     /// however)
-    pub fn render<T: TextureMap>(
+    pub fn render<T: TextureMap, D: DrawDataSource + ?Sized>(
         &mut self,
         gl: &Context,
         texture_map: &T,
-        draw_data: &DrawData,
+        draw_data: &D,
     ) -> Result<(), RenderError> {
         if self.is_destroyed {
             return Err(Self::renderer_destroyed());
         }
 
-        let fb_width = draw_data.display_size[0] * draw_data.framebuffer_scale[0];
-        let fb_height = draw_data.display_size[1] * draw_data.framebuffer_scale[1];
+        let display_size = draw_data.display_size();
+        let framebuffer_scale = draw_data.framebuffer_scale();
+        let fb_width = display_size[0] * framebuffer_scale[0];
+        let fb_height = display_size[1] * framebuffer_scale[1];
         if !(fb_width > 0.0 && fb_height > 0.0) {
             return Ok(());
         }
 
         gl_debug_message(gl, "imgui-rs-glow: start render");
-        self.state_backup.pre_render(gl, self.gl_version);
+        if self.restore_state {
+            self.state_backup.pre_render(gl, self.gl_version);
+        }
 
         #[cfg(feature = "bind_vertex_array_support")]
         if self.gl_version.bind_vertex_array_support() {
@@ -296,7 +736,7 @@ impl Renderer {
         self.set_up_render_state(gl, draw_data, fb_width, fb_height)?;
 
         gl_debug_message(gl, "start loop over draw lists");
-        for draw_list in draw_data.draw_lists() {
+        for draw_list in draw_data.draw_list_refs() {
             unsafe {
                 gl.buffer_data_u8_slice(
                     glow::ARRAY_BUFFER,
@@ -322,9 +762,12 @@ impl Renderer {
                         fb_width,
                         fb_height,
                     ),
-                    DrawCmd::RawCallback { callback, raw_cmd } => unsafe {
-                        callback(draw_list.raw(), raw_cmd)
-                    },
+                    DrawCmd::RawCallback { callback, raw_cmd } => {
+                        // Snapshots have no callbacks
+                        if let Some(draw_list) = draw_list.as_draw_list() {
+                            unsafe { callback(draw_list.raw(), raw_cmd) }
+                        }
+                    }
                     DrawCmd::ResetRenderState => {
                         self.set_up_render_state(gl, draw_data, fb_width, fb_height)?
                     }
@@ -338,7 +781,9 @@ impl Renderer {
             self.vertex_array_object = None;
         }
 
-        self.state_backup.post_render(gl, self.gl_version);
+        if self.restore_state {
+            self.state_backup.post_render(gl, self.gl_version);
+        }
         gl_debug_message(gl, "imgui-rs-glow: complete render");
         Ok(())
     }
@@ -347,10 +792,10 @@ impl Renderer {
     /// Few GL calls are checked for errors, but any that are found will result
     /// in an error. Errors from the state manager lifecycle callbacks will also
     /// result in an error.
-    pub fn set_up_render_state(
+    pub fn set_up_render_state<D: DrawDataSource + ?Sized>(
         &mut self,
         gl: &Context,
-        draw_data: &DrawData,
+        draw_data: &D,
         fb_width: f32,
         fb_height: f32,
     ) -> Result<(), RenderError> {
@@ -388,6 +833,24 @@ impl Renderer {
             gl.viewport(0, 0, fb_width as _, fb_height as _);
         }
 
+        #[cfg(feature = "framebuffer_srgb_support")]
+        if let Some(enable) = self.framebuffer_srgb {
+            if self.gl_version.framebuffer_srgb_support() {
+                unsafe {
+                    if enable {
+                        gl.enable(glow::FRAMEBUFFER_SRGB);
+                    } else {
+                        gl.disable(glow::FRAMEBUFFER_SRGB);
+                    }
+                }
+            }
+        }
+        let output_srgb = match self.output_color_space {
+            OutputColorSpace::Srgb => true,
+            OutputColorSpace::Linear => false,
+            OutputColorSpace::Auto => !self.is_framebuffer_srgb_enabled(gl),
+        };
+
         #[cfg(feature = "clip_origin_support")]
         let clip_origin_is_lower_left = if self.has_clip_origin_support {
             unsafe { gl.get_parameter_i32(glow::CLIP_ORIGIN) != glow::UPPER_LEFT as i32 }
@@ -402,12 +865,20 @@ impl Renderer {
         unsafe {
             gl.use_program(self.shaders.program);
             gl.uniform_1_i32(Some(&self.shaders.texture_uniform_location), 0);
+            gl.uniform_1_i32(
+                Some(&self.shaders.output_srgb_uniform_location),
+                i32::from(output_srgb),
+            );
             gl.uniform_matrix_4_f32_slice(
                 Some(&self.shaders.matrix_uniform_location),
                 false,
                 &projection_matrix,
             );
         }
+        if let (Some(callback), Some(program)) = (&mut self.uniform_callback, &self.shaders.program)
+        {
+            callback(gl, program);
+        }
 
         #[cfg(feature = "bind_sampler_support")]
         if self.gl_version.bind_sampler_support() {
@@ -455,13 +926,13 @@ impl Renderer {
     }
 
     #[allow(clippy::too_many_arguments)]
-    fn render_elements<T: TextureMap>(
+    fn render_elements<T: TextureMap, D: DrawDataSource + ?Sized>(
         &self,
         gl: &Context,
         texture_map: &T,
         element_count: usize,
         element_params: imgui::DrawCmdParams,
-        draw_data: &DrawData,
+        draw_data: &D,
         fb_width: f32,
         fb_height: f32,
     ) {
@@ -477,25 +948,18 @@ impl Renderer {
             vtx_offset,
             idx_offset,
         } = element_params;
-        let clip_off = draw_data.display_pos;
-        let scale = draw_data.framebuffer_scale;
-
-        let clip_x1 = (clip_rect[0] - clip_off[0]) * scale[0];
-        let clip_y1 = (clip_rect[1] - clip_off[1]) * scale[1];
-        let clip_x2 = (clip_rect[2] - clip_off[0]) * scale[0];
-        let clip_y2 = (clip_rect[3] - clip_off[1]) * scale[1];
-
-        if clip_x1 >= fb_width || clip_y1 >= fb_height || clip_x2 < 0.0 || clip_y2 < 0.0 {
-            return;
-        }
+        let [x, y, width, height] = match scissor_box(
+            clip_rect,
+            draw_data.display_pos(),
+            draw_data.framebuffer_scale(),
+            [fb_width, fb_height],
+        ) {
+            Some(scissor_box) => scissor_box,
+            None => return,
+        };
 
         unsafe {
-            gl.scissor(
-                clip_x1 as i32,
-                (fb_height - clip_y2) as i32,
-                (clip_x2 - clip_x1) as i32,
-                (clip_y2 - clip_y1) as i32,
-            );
+            gl.scissor(x, y, width, height);
             gl.bind_texture(glow::TEXTURE_2D, texture_map.gl_texture(texture_id));
 
             #[cfg(feature = "vertex_offset_support")]
@@ -550,9 +1014,25 @@ impl Renderer {
 /// [`register`]: Self::register
 ///
 /// Then [`gl_texture`] can be called to find the OpenGL texture corresponding to
-/// that [`imgui::TextureId`].
+/// that [`imgui::TextureId`]. The renderer calls it for each draw command.
 ///
 /// [`gl_texture`]: Self::gl_texture
+///
+/// Two implementations are provided: [`SimpleTextureMap`], where the texture
+/// ID is the OpenGL name of the texture (so nothing is stored), and
+/// [`imgui::Textures`]. Applications which already keep track of their
+/// textures can implement this trait on their own handle system instead, so
+/// that texture IDs map straight onto their textures.
+///
+/// # Ownership
+///
+/// The map only gives access to textures, it doesn't own them. The renderer
+/// only deletes the textures it created: the font atlas texture, and the
+/// textures created with [`Renderer::create_texture`] (when they are deleted
+/// with [`Renderer::delete_texture`], or when the renderer is destroyed).
+/// Textures registered by the application are never deleted by the renderer,
+/// so they have to be deleted by the application, after the last frame using
+/// them was rendered.
 pub trait TextureMap {
     fn register(&mut self, gl_texture: GlTexture) -> Option<imgui::TextureId>;
 
@@ -560,10 +1040,14 @@ pub trait TextureMap {
 }
 
 /// Texture map where the imgui texture ID is simply numerically equal to the
-/// OpenGL texture ID.
+/// OpenGL texture ID, so that textures don't have to be stored.
+///
+/// WebGL textures have no numeric ID, so on `wasm32` the textures are stored
+/// in an [`imgui::Textures`] instead.
 #[derive(Default)]
-pub struct SimpleTextureMap();
+pub struct SimpleTextureMap(#[cfg(target_arch = "wasm32")] imgui::Textures<glow::Texture>);
 
+#[cfg(not(target_arch = "wasm32"))]
 impl TextureMap for SimpleTextureMap {
     #[inline(always)]
     fn register(&mut self, gl_texture: glow::Texture) -> Option<imgui::TextureId> {
@@ -579,6 +1063,19 @@ impl TextureMap for SimpleTextureMap {
     }
 }
 
+#[cfg(target_arch = "wasm32")]
+impl TextureMap for SimpleTextureMap {
+    #[inline(always)]
+    fn register(&mut self, gl_texture: glow::Texture) -> Option<imgui::TextureId> {
+        self.0.register(gl_texture)
+    }
+
+    #[inline(always)]
+    fn gl_texture(&self, imgui_texture: imgui::TextureId) -> Option<glow::Texture> {
+        self.0.gl_texture(imgui_texture)
+    }
+}
+
 /// [`imgui::Textures`] is a simple choice for a texture map.
 impl TextureMap for imgui::Textures<glow::Texture> {
     fn register(&mut self, gl_texture: glow::Texture) -> Option<imgui::TextureId> {
@@ -603,6 +1100,20 @@ impl TextureMap for imgui::Textures<glow::Texture> {
 /// restored, it is probably best to do this manually before/after calling
 /// the render method rather than opening an issue to add more to this
 /// struct.
+///
+/// The state captured around rendering (unless [`Renderer::restore_state`] is
+/// `false`) is:
+/// - the active texture unit, the current program, the texture bound to
+///   `GL_TEXTURE_2D`, the sampler bound to unit 0, the buffer bound to
+///   `GL_ARRAY_BUFFER` and the bound vertex array object,
+/// - the polygon mode, the viewport and the scissor box,
+/// - the blend functions and equations,
+/// - whether `GL_BLEND`, `GL_CULL_FACE`, `GL_DEPTH_TEST`, `GL_STENCIL_TEST`,
+///   `GL_SCISSOR_TEST`, `GL_PRIMITIVE_RESTART` and `GL_FRAMEBUFFER_SRGB` are
+///   enabled.
+///
+/// State that the OpenGL version doesn't support, or whose feature is
+/// disabled, is skipped. On WebGL, objects are unbound instead of restored.
 #[allow(clippy::struct_excessive_bools)]
 #[derive(Default)]
 pub struct GlStateBackup {
@@ -629,10 +1140,13 @@ pub struct GlStateBackup {
     scissor_test_enabled: bool,
     #[cfg(feature = "primitive_restart_support")]
     primitive_restart_enabled: Option<bool>,
+    #[cfg(feature = "framebuffer_srgb_support")]
+    framebuffer_srgb_enabled: Option<bool>,
     #[cfg(feature = "bind_vertex_array_support")]
     vertex_array_object: Option<u32>,
 }
 
+#[cfg(not(target_arch = "wasm32"))]
 fn to_native_gl<T>(handle: u32, constructor: fn(NonZeroU32) -> T) -> Option<T> {
     if handle != 0 {
         Some(constructor(NonZeroU32::new(handle).unwrap()))
@@ -641,6 +1155,21 @@ fn to_native_gl<T>(handle: u32, constructor: fn(NonZeroU32) -> T) -> Option<T> {
     }
 }
 
+/// Converts an object name queried with `glGet*` back to a glow object. WebGL
+/// objects can't be queried this way, so they are always `None` on `wasm32`.
+macro_rules! queried_object {
+    ($handle:expr, $native:ident) => {{
+        #[cfg(not(target_arch = "wasm32"))]
+        let object = to_native_gl($handle, glow::$native);
+        #[cfg(target_arch = "wasm32")]
+        let object = {
+            let _ = $handle;
+            None
+        };
+        object
+    }};
+}
+
 impl GlStateBackup {
     fn pre_init(&mut self, gl: &Context) {
         self.texture = unsafe { gl.get_parameter_i32(glow::TEXTURE_BINDING_2D) as _ };
@@ -651,7 +1180,7 @@ impl GlStateBackup {
         unsafe {
             gl.bind_texture(
                 glow::TEXTURE_2D,
-                to_native_gl(self.texture, glow::NativeTexture),
+                queried_object!(self.texture, NativeTexture),
             );
         }
     }
@@ -704,29 +1233,35 @@ impl GlStateBackup {
             } else {
                 self.primitive_restart_enabled = None;
             }
+            #[cfg(feature = "framebuffer_srgb_support")]
+            if gl_version.framebuffer_srgb_support() {
+                self.framebuffer_srgb_enabled = Some(gl.is_enabled(glow::FRAMEBUFFER_SRGB));
+            } else {
+                self.framebuffer_srgb_enabled = None;
+            }
         }
     }
 
     fn post_render(&mut self, gl: &Context, _gl_version: GlVersion) {
         #![allow(clippy::cast_sign_loss)]
         unsafe {
-            gl.use_program(to_native_gl(self.program, glow::NativeProgram));
+            gl.use_program(queried_object!(self.program, NativeProgram));
             gl.bind_texture(
                 glow::TEXTURE_2D,
-                to_native_gl(self.texture, glow::NativeTexture),
+                queried_object!(self.texture, NativeTexture),
             );
             #[cfg(feature = "bind_sampler_support")]
             if let Some(sampler) = self.sampler {
-                gl.bind_sampler(0, to_native_gl(sampler, glow::NativeSampler));
+                gl.bind_sampler(0, queried_object!(sampler, NativeSampler));
             }
             gl.active_texture(self.active_texture as _);
             #[cfg(feature = "bind_vertex_array_support")]
             if let Some(vao) = self.vertex_array_object {
-                gl.bind_vertex_array(to_native_gl(vao, glow::NativeVertexArray));
+                gl.bind_vertex_array(queried_object!(vao, NativeVertexArray));
             }
             gl.bind_buffer(
                 glow::ARRAY_BUFFER,
-                to_native_gl(self.array_buffer, glow::NativeBuffer),
+                queried_object!(self.array_buffer, NativeBuffer),
             );
             gl.blend_equation_separate(
                 self.blend_equation_rgb as _,
@@ -771,6 +1306,14 @@ impl GlStateBackup {
                     gl.disable(glow::PRIMITIVE_RESTART)
                 }
             }
+            #[cfg(feature = "framebuffer_srgb_support")]
+            if let Some(framebuffer_srgb_enabled) = self.framebuffer_srgb_enabled {
+                if framebuffer_srgb_enabled {
+                    gl.enable(glow::FRAMEBUFFER_SRGB)
+                } else {
+                    gl.disable(glow::FRAMEBUFFER_SRGB)
+                }
+            }
             #[cfg(feature = "polygon_mode_support")]
             if let Some([mode, _]) = self.polygon_mode {
                 gl.polygon_mode(glow::FRONT_AND_BACK, mode as _);
@@ -798,15 +1341,22 @@ struct Shaders {
     program: Option<GlProgram>,
     texture_uniform_location: GlUniformLocation,
     matrix_uniform_location: GlUniformLocation,
+    output_srgb_uniform_location: GlUniformLocation,
     position_attribute_index: u32,
     uv_attribute_index: u32,
     color_attribute_index: u32,
 }
 
 impl Shaders {
-    fn new(gl: &Context, gl_version: GlVersion, output_srgb: bool) -> Result<Self, ShaderError> {
-        let (vertex_source, fragment_source) =
-            Self::get_shader_sources(gl, gl_version, output_srgb)?;
+    fn new(
+        gl: &Context,
+        gl_version: GlVersion,
+        provider: Option<&dyn ShaderProvider>,
+    ) -> Result<Self, ShaderError> {
+        let header = Self::get_shader_header(gl, gl_version)?;
+        let (vertex_source, fragment_source) = provider
+            .unwrap_or(&ShaderBodies::default())
+            .shader_sources(&header);
 
         let vertex_shader =
             unsafe { gl.create_shader(glow::VERTEX_SHADER) }.map_err(ShaderError::CreateShader)?;
@@ -857,6 +1407,9 @@ impl Shaders {
                 matrix_uniform_location: gl
                     .get_uniform_location(program, "matrix")
                     .ok_or_else(|| ShaderError::UniformNotFound("matrix".into()))?,
+                output_srgb_uniform_location: gl
+                    .get_uniform_location(program, "output_srgb")
+                    .ok_or_else(|| ShaderError::UniformNotFound("output_srgb".into()))?,
                 position_attribute_index: gl
                     .get_attrib_location(program, "position")
                     .ok_or_else(|| ShaderError::AttributeNotFound("position".into()))?,
@@ -870,12 +1423,50 @@ impl Shaders {
         })
     }
 
-    fn get_shader_sources(
-        gl: &Context,
-        gl_version: GlVersion,
-        output_srgb: bool,
-    ) -> Result<(String, String), ShaderError> {
-        const VERTEX_BODY: &str = r#"
+    fn get_shader_header(gl: &Context, gl_version: GlVersion) -> Result<String, ShaderError> {
+        let glsl_version = GlslVersion::read(gl);
+
+        // Find the lowest common denominator version
+        let is_gles = gl_version.is_gles || glsl_version.is_gles;
+        let (major, minor) = if let std::cmp::Ordering::Less = gl_version
+            .major
+            .cmp(&glsl_version.major)
+            .then(gl_version.minor.cmp(&glsl_version.minor))
+        {
+            (gl_version.major, gl_version.minor)
+        } else {
+            (glsl_version.major, glsl_version.minor)
+        };
+
+        if is_gles && major < 2 {
+            return Err(ShaderError::IncompatibleVersion(format!(
+                "This auto-shader OpenGL version 3.0 or OpenGL ES version 2.0 or higher, found: ES {}.{}",
+                major, minor
+            )));
+        }
+        if !is_gles && major < 3 {
+            return Err(ShaderError::IncompatibleVersion(format!(
+                "This auto-shader OpenGL version 3.0 or OpenGL ES version 2.0 or higher, found: {}.{}",
+                major, minor
+            )));
+        }
+
+        Ok(format!(
+            "#version {version}{es_extras}",
+            version = major * 100 + minor * 10,
+            es_extras = if is_gles {
+                " es\nprecision mediump float;"
+            } else {
+                ""
+            },
+        ))
+    }
+}
+
+/// Body of the built-in vertex shader, without the `#version` header. See
+/// [`ShaderProvider`] for the inputs and outputs replacement shaders must
+/// have.
+pub const VERTEX_SHADER_BODY: &str = r#"
 layout (location = 0) in vec2 position;
 layout (location = 1) in vec2 uv;
 layout (location = 2) in vec4 color;
@@ -903,11 +1494,14 @@ void main() {
     gl_Position = matrix * vec4(position.xy, 0, 1);
 }
 "#;
-        const FRAGMENT_BODY: &str = r#"
+/// Body of the built-in fragment shader, without the `#version` header, which
+/// can be edited to make a replacement shader (see [`ShaderProvider`]).
+pub const FRAGMENT_SHADER_BODY: &str = r#"
 in vec2 fragment_uv;
 in vec4 fragment_color;
 
 uniform sampler2D tex;
+uniform bool output_srgb;
 layout (location = 0) out vec4 out_color;
 
 vec4 linear_to_srgb(vec4 linear_color) {
@@ -923,68 +1517,63 @@ vec4 linear_to_srgb(vec4 linear_color) {
 
 void main() {
     vec4 linear_color = fragment_color * texture(tex, fragment_uv.st);
-#ifdef OUTPUT_SRGB
-    out_color = linear_to_srgb(linear_color);
-#else
-    out_color = linear_color;
-#endif
+    if (output_srgb) {
+        out_color = linear_to_srgb(linear_color);
+    } else {
+        out_color = linear_color;
+    }
 }
 "#;
 
-        let glsl_version = GlslVersion::read(gl);
+/// Supplies replacement shader sources, see
+/// [`Renderer::initialize_with_shaders`].
+///
+/// The program is used like the built-in one (see [`VERTEX_SHADER_BODY`] and
+/// [`FRAGMENT_SHADER_BODY`]), so it must declare and use:
+/// - the vertex attributes `vec2 position`, `vec2 uv` and `vec4 color`, the
+///   latter with sRGB components in the 0 to 1 range,
+/// - the uniforms `mat4 matrix` (the projection of `position`),
+///   `sampler2D tex` (the texture of the draw command) and
+///   `bool output_srgb` (whether to write sRGB colors, see
+///   [`OutputColorSpace`]).
+///
+/// Attributes and uniforms which aren't used may be removed by the GLSL
+/// compiler, in which case the renderer fails to initialize with
+/// [`ShaderError::AttributeNotFound`] or [`ShaderError::UniformNotFound`].
+/// Additional uniforms can be set with [`Renderer::set_uniform_callback`].
+pub trait ShaderProvider {
+    /// Returns the vertex and fragment shader sources.
+    ///
+    /// `header` is the `#version` line (and precision statement on OpenGL
+    /// ES) matching the context, as used by the built-in shaders.
+    fn shader_sources(&self, header: &str) -> (String, String);
+}
 
-        // Find the lowest common denominator version
-        let is_gles = gl_version.is_gles || glsl_version.is_gles;
-        let (major, minor) = if let std::cmp::Ordering::Less = gl_version
-            .major
-            .cmp(&glsl_version.major)
-            .then(gl_version.minor.cmp(&glsl_version.minor))
-        {
-            (gl_version.major, gl_version.minor)
-        } else {
-            (glsl_version.major, glsl_version.minor)
-        };
+/// Shader bodies to which the `#version` header is prepended, e.g. an edited
+/// copy of [`FRAGMENT_SHADER_BODY`].
+///
+/// The default value holds the built-in shader bodies.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ShaderBodies {
+    pub vertex: Cow<'static, str>,
+    pub fragment: Cow<'static, str>,
+}
 
-        if is_gles && major < 2 {
-            return Err(ShaderError::IncompatibleVersion(format!(
-                "This auto-shader OpenGL version 3.0 or OpenGL ES version 2.0 or higher, found: ES {}.{}",
-                major, minor
-            )));
+impl Default for ShaderBodies {
+    fn default() -> Self {
+        Self {
+            vertex: Cow::Borrowed(VERTEX_SHADER_BODY),
+            fragment: Cow::Borrowed(FRAGMENT_SHADER_BODY),
         }
-        if !is_gles && major < 3 {
-            return Err(ShaderError::IncompatibleVersion(format!(
-                "This auto-shader OpenGL version 3.0 or OpenGL ES version 2.0 or higher, found: {}.{}",
-                major, minor
-            )));
-        }
-
-        let vertex_source = format!(
-            "#version {version}{es_extras}\n{body}",
-            version = major * 100 + minor * 10,
-            es_extras = if is_gles {
-                " es\nprecision mediump float;"
-            } else {
-                ""
-            },
-            body = VERTEX_BODY,
-        );
-        let fragment_source = format!(
-            "#version {version}{es_extras}{defines}\n{body}",
-            version = major * 100 + minor * 10,
-            es_extras = if is_gles {
-                " es\nprecision mediump float;"
-            } else {
-                ""
-            },
-            defines = if output_srgb {
-                "\n#define OUTPUT_SRGB"
-            } else {
-                ""
-            },
-            body = FRAGMENT_BODY,
-        );
+    }
+}
 
-        Ok((vertex_source, fragment_source))
+impl ShaderProvider for ShaderBodies {
+    fn shader_sources(&self, header: &str) -> (String, String) {
+        (
+            format!("{}\n{}", header, self.vertex),
+            format!("{}\n{}", header, self.fragment),
+        )
     }
 }
 
@@ -1014,12 +1603,16 @@ impl Display for ShaderError {
             Self::CompileShader(msg) => write!(f, "Error compiling shader: {}", msg),
             Self::LinkProgram(msg) => write!(f, "Error linking shader program: {}", msg),
             Self::UniformNotFound(uniform_name) => {
-                write!(f, "Uniform `{}` not found in shader program", uniform_name)
+                write!(
+                    f,
+                    "Uniform `{}` not found in shader program, it must be declared and used",
+                    uniform_name
+                )
             }
             Self::AttributeNotFound(attribute_name) => {
                 write!(
                     f,
-                    "Attribute `{}` not found in shader program",
+                    "Attribute `{}` not found in shader program, it must be declared and used",
                     attribute_name
                 )
             }
@@ -1033,6 +1626,11 @@ pub enum InitError {
     CreateBufferObject(String),
     CreateTexture(String),
     RegisterTexture,
+    /// The font atlas is larger than `GL_MAX_TEXTURE_SIZE` in either dimension
+    FontAtlasTooLarge {
+        size: [u32; 2],
+        max_size: u32,
+    },
     UserError(String),
 }
 
@@ -1052,6 +1650,11 @@ impl Display for InitError {
             Self::CreateBufferObject(msg) => write!(f, "Error creating buffer object: {}", msg),
             Self::CreateTexture(msg) => write!(f, "Error creating texture object: {}", msg),
             Self::RegisterTexture => write!(f, "Error registering texture in texture map"),
+            Self::FontAtlasTooLarge { size, max_size } => write!(
+                f,
+                "Font atlas of size {:?} is larger than the maximum texture size {}",
+                size, max_size
+            ),
             Self::UserError(msg) => write!(f, "Initialization error: {}", msg),
         }
     }
@@ -1063,6 +1666,132 @@ impl From<ShaderError> for InitError {
     }
 }
 
+/// Color space of the colors the renderer writes to the framebuffer.
+///
+/// imgui colors are sRGB, and textures are expected to use an `SRGB*`
+/// internal format, so the shader blends colors in linear space before
+/// writing them out in the chosen color space.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum OutputColorSpace {
+    /// Write sRGB colors, for framebuffers which store colors as is. This is
+    /// usually the case of the default framebuffer, unless
+    /// `GL_FRAMEBUFFER_SRGB` is enabled.
+    Srgb,
+    /// Write linear colors, for sRGB framebuffers with `GL_FRAMEBUFFER_SRGB`
+    /// enabled (which convert them to sRGB), or intermediate framebuffers
+    /// which keep colors in linear space.
+    Linear,
+    /// Write linear colors if `GL_FRAMEBUFFER_SRGB` is enabled when rendering,
+    /// sRGB colors otherwise.
+    ///
+    /// glow doesn't expose the color encoding of the framebuffer, so this
+    /// assumes that enabling `GL_FRAMEBUFFER_SRGB` means the framebuffer is
+    /// sRGB. OpenGL ES can't toggle the conversion, so this always writes sRGB
+    /// colors there.
+    Auto,
+}
+
+/// Pixel format of the textures created with [`Renderer::create_texture`], with 8 bits per
+/// channel.
+///
+/// Colors are assumed to be sRGB, so the textures use an `SRGB*` internal format.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum TextureFormat {
+    Rgba8,
+    Rgb8,
+}
+
+impl TextureFormat {
+    /// Size of a pixel in bytes
+    pub const fn bytes_per_pixel(self) -> usize {
+        match self {
+            Self::Rgba8 => 4,
+            Self::Rgb8 => 3,
+        }
+    }
+
+    const fn gl_format(self) -> u32 {
+        match self {
+            Self::Rgba8 => glow::RGBA,
+            Self::Rgb8 => glow::RGB,
+        }
+    }
+
+    const fn gl_internal_format(self) -> u32 {
+        match self {
+            Self::Rgba8 => glow::SRGB8_ALPHA8,
+            Self::Rgb8 => glow::SRGB8,
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum TextureError {
+    CreateTexture(String),
+    RegisterTexture,
+    /// The texture wasn't created with [`Renderer::create_texture`], or was deleted
+    UnknownTexture(imgui::TextureId),
+    /// The updated rectangle doesn't fit in the texture
+    OutOfBounds {
+        position: [u32; 2],
+        size: [u32; 2],
+        texture_size: [u32; 2],
+    },
+    FormatMismatch {
+        expected: TextureFormat,
+        found: TextureFormat,
+    },
+    /// The stride is smaller than a row of the updated rectangle
+    InvalidStride {
+        stride: usize,
+        row_size: usize,
+    },
+    DataTooShort {
+        expected: usize,
+        found: usize,
+    },
+}
+
+impl Error for TextureError {}
+
+impl Display for TextureError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::CreateTexture(msg) => write!(f, "Error creating texture object: {}", msg),
+            Self::RegisterTexture => write!(f, "Error registering texture in texture map"),
+            Self::UnknownTexture(texture_id) => write!(
+                f,
+                "Texture {} was not created by the renderer",
+                texture_id.id()
+            ),
+            Self::OutOfBounds {
+                position,
+                size,
+                texture_size,
+            } => write!(
+                f,
+                "Rectangle at {:?} of size {:?} is out of the bounds of the texture of size {:?}",
+                position, size, texture_size
+            ),
+            Self::FormatMismatch { expected, found } => write!(
+                f,
+                "Texture has format {:?}, but data has format {:?}",
+                expected, found
+            ),
+            Self::InvalidStride { stride, row_size } => write!(
+                f,
+                "Stride of {} bytes is smaller than a row of {} bytes",
+                stride, row_size
+            ),
+            Self::DataTooShort { expected, found } => write!(
+                f,
+                "Expected at least {} bytes of data, found {}",
+                expected, found
+            ),
+        }
+    }
+}
+
 pub type RenderError = String;
 
 fn prepare_font_atlas<T: TextureMap>(
@@ -1070,11 +1799,75 @@ fn prepare_font_atlas<T: TextureMap>(
     fonts: &mut imgui::FontAtlas,
     texture_map: &mut T,
 ) -> Result<GlTexture, InitError> {
-    #![allow(clippy::cast_possible_wrap)]
+    let gl_texture = unsafe { gl.create_texture() }.map_err(InitError::CreateTexture)?;
+    if let Err(error) = upload_font_atlas(gl, fonts, gl_texture) {
+        unsafe { gl.delete_texture(gl_texture) };
+        return Err(error);
+    }
 
-    let atlas_texture = fonts.build_rgba32_texture();
+    fonts.tex_id = texture_map
+        .register(gl_texture)
+        .ok_or(InitError::RegisterTexture)?;
 
-    let gl_texture = unsafe { gl.create_texture() }.map_err(InitError::CreateTexture)?;
+    Ok(gl_texture)
+}
+
+/// Checks that an update of a texture created by the renderer is valid
+fn check_texture_update(
+    texture: &CreatedTexture,
+    position: [u32; 2],
+    size: [u32; 2],
+    data_len: usize,
+    stride: usize,
+    format: TextureFormat,
+) -> Result<(), TextureError> {
+    if format != texture.format {
+        return Err(TextureError::FormatMismatch {
+            expected: texture.format,
+            found: format,
+        });
+    }
+    let fits =
+        |start: u32, len: u32, max: u32| matches!(start.checked_add(len), Some(end) if end <= max);
+    if !fits(position[0], size[0], texture.width) || !fits(position[1], size[1], texture.height) {
+        return Err(TextureError::OutOfBounds {
+            position,
+            size,
+            texture_size: [texture.width, texture.height],
+        });
+    }
+    let row_size = size[0] as usize * format.bytes_per_pixel();
+    if stride < row_size {
+        return Err(TextureError::InvalidStride { stride, row_size });
+    }
+    if size[1] > 0 {
+        let expected = stride * (size[1] as usize - 1) + row_size;
+        if data_len < expected {
+            return Err(TextureError::DataTooShort {
+                expected,
+                found: data_len,
+            });
+        }
+    }
+    Ok(())
+}
+
+/// Builds the font atlas texture if needed, and uploads it to `gl_texture`
+fn upload_font_atlas(
+    gl: &Context,
+    fonts: &mut imgui::FontAtlas,
+    gl_texture: GlTexture,
+) -> Result<(), InitError> {
+    #![allow(clippy::cast_possible_wrap, clippy::cast_sign_loss)]
+
+    let atlas_texture = fonts.build_rgba32_texture();
+    let max_size = unsafe { gl.get_parameter_i32(glow::MAX_TEXTURE_SIZE) } as u32;
+    if atlas_texture.width > max_size || atlas_texture.height > max_size {
+        return Err(InitError::FontAtlasTooLarge {
+            size: [atlas_texture.width, atlas_texture.height],
+            max_size,
+        });
+    }
 
     unsafe {
         gl.bind_texture(glow::TEXTURE_2D, Some(gl_texture));
@@ -1100,12 +1893,7 @@ fn prepare_font_atlas<T: TextureMap>(
             Some(atlas_texture.data),
         );
     }
-
-    fonts.tex_id = texture_map
-        .register(gl_texture)
-        .ok_or(InitError::RegisterTexture)?;
-
-    Ok(gl_texture)
+    Ok(())
 }
 
 // this CFG guard disables apple usage of this function -- apple only has supported up to opengl 3.3
@@ -1125,13 +1913,55 @@ fn gl_debug_message<G: glow::HasContext>(gl: &G, message: impl AsRef<str>) {
 #[cfg(any(target_vendor = "apple", not(feature = "debug_message_insert_support")))]
 fn gl_debug_message<G: glow::HasContext>(_gl: &G, _message: impl AsRef<str>) {}
 
-fn calculate_matrix(draw_data: &DrawData, clip_origin_is_lower_left: bool) -> [f32; 16] {
+/// Computes the scissor box (`[x, y, width, height]`, with `y` from the
+/// bottom) of a clip rectangle, offset by the display position and scaled to
+/// framebuffer pixels.
+///
+/// The box is rounded outward, so that adjacent clip rectangles leave no gap
+/// between them at fractional scales, and clamped to the framebuffer (whose
+/// size is truncated, like the viewport). Returns `None` if the box is empty.
+#[allow(clippy::cast_possible_truncation)]
+fn scissor_box(
+    clip_rect: [f32; 4],
+    clip_off: [f32; 2],
+    scale: [f32; 2],
+    fb_size: [f32; 2],
+) -> Option<[i32; 4]> {
+    // `max` and `min` would replace NaN coordinates with the bounds
+    if clip_rect.iter().any(|c| c.is_nan()) {
+        return None;
+    }
+    let fb_width = fb_size[0].floor();
+    let fb_height = fb_size[1].floor();
+    let x1 = ((clip_rect[0] - clip_off[0]) * scale[0]).floor().max(0.0);
+    let y1 = ((clip_rect[1] - clip_off[1]) * scale[1]).floor().max(0.0);
+    let x2 = ((clip_rect[2] - clip_off[0]) * scale[0])
+        .ceil()
+        .min(fb_width);
+    let y2 = ((clip_rect[3] - clip_off[1]) * scale[1])
+        .ceil()
+        .min(fb_height);
+    if x2 <= x1 || y2 <= y1 {
+        return None;
+    }
+    Some([
+        x1 as i32,
+        (fb_height - y2) as i32,
+        (x2 - x1) as i32,
+        (y2 - y1) as i32,
+    ])
+}
+
+fn calculate_matrix<D: DrawDataSource + ?Sized>(
+    draw_data: &D,
+    clip_origin_is_lower_left: bool,
+) -> [f32; 16] {
     #![allow(clippy::deprecated_cfg_attr)]
 
-    let left = draw_data.display_pos[0];
-    let right = draw_data.display_pos[0] + draw_data.display_size[0];
-    let top = draw_data.display_pos[1];
-    let bottom = draw_data.display_pos[1] + draw_data.display_size[1];
+    let [left, top] = draw_data.display_pos();
+    let display_size = draw_data.display_size();
+    let right = left + display_size[0];
+    let bottom = top + display_size[1];
 
     #[cfg(feature = "clip_origin_support")]
     let (top, bottom) = if clip_origin_is_lower_left {
@@ -1162,3 +1992,186 @@ const fn imgui_index_type_as_gl() -> u32 {
         _ => glow::UNSIGNED_INT,
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::num::NonZeroU32;
+
+    use super::*;
+
+    /// A 4x2 RGBA texture, which is never given to OpenGL
+    fn texture() -> CreatedTexture {
+        CreatedTexture {
+            gl_texture: glow::NativeTexture(NonZeroU32::new(1).unwrap()),
+            width: 4,
+            height: 2,
+            format: TextureFormat::Rgba8,
+        }
+    }
+
+    #[test]
+    fn test_texture_update_valid() {
+        let texture = texture();
+        assert!(
+            check_texture_update(&texture, [0, 0], [4, 2], 32, 16, TextureFormat::Rgba8).is_ok()
+        );
+        assert!(
+            check_texture_update(&texture, [1, 1], [3, 1], 12, 12, TextureFormat::Rgba8).is_ok()
+        );
+        // The last row doesn't need the padding of the stride
+        assert!(
+            check_texture_update(&texture, [0, 0], [2, 2], 24, 16, TextureFormat::Rgba8).is_ok()
+        );
+    }
+
+    #[test]
+    fn test_texture_update_out_of_bounds() {
+        let texture = texture();
+        for (position, size) in [
+            ([0, 0], [5, 1]),
+            ([0, 0], [1, 3]),
+            ([4, 0], [1, 1]),
+            ([1, 1], [4, 1]),
+            ([u32::MAX, 0], [2, 1]),
+        ] {
+            let result =
+                check_texture_update(&texture, position, size, 1024, 64, TextureFormat::Rgba8);
+            assert!(
+                matches!(
+                    result,
+                    Err(TextureError::OutOfBounds {
+                        texture_size: [4, 2],
+                        ..
+                    })
+                ),
+                "{:?} {:?}: {:?}",
+                position,
+                size,
+                result
+            );
+        }
+    }
+
+    #[test]
+    fn test_texture_update_format_mismatch() {
+        let result = check_texture_update(&texture(), [0, 0], [1, 1], 3, 3, TextureFormat::Rgb8);
+        assert!(matches!(
+            result,
+            Err(TextureError::FormatMismatch {
+                expected: TextureFormat::Rgba8,
+                found: TextureFormat::Rgb8,
+            })
+        ));
+    }
+
+    #[test]
+    fn test_texture_update_short_data() {
+        let result = check_texture_update(&texture(), [0, 0], [2, 2], 23, 16, TextureFormat::Rgba8);
+        assert!(matches!(
+            result,
+            Err(TextureError::DataTooShort {
+                expected: 24,
+                found: 23,
+            })
+        ));
+    }
+
+    #[test]
+    fn test_texture_update_small_stride() {
+        let result = check_texture_update(&texture(), [0, 0], [3, 1], 12, 11, TextureFormat::Rgba8);
+        assert!(matches!(
+            result,
+            Err(TextureError::InvalidStride {
+                stride: 11,
+                row_size: 12,
+            })
+        ));
+    }
+
+    #[test]
+    fn test_texture_update_zero_size() {
+        let texture = texture();
+        assert!(check_texture_update(&texture, [0, 0], [0, 0], 0, 0, TextureFormat::Rgba8).is_ok());
+        assert!(check_texture_update(&texture, [4, 2], [0, 0], 0, 0, TextureFormat::Rgba8).is_ok());
+        // No rows, so no data is needed
+        assert!(
+            check_texture_update(&texture, [0, 0], [4, 0], 0, 16, TextureFormat::Rgba8).is_ok()
+        );
+        // Still out of bounds
+        assert!(
+            check_texture_update(&texture, [5, 0], [0, 0], 0, 0, TextureFormat::Rgba8).is_err()
+        );
+    }
+
+    #[test]
+    fn test_scissor_box_fractional_scale() {
+        let fb_size = [100.0, 100.0];
+        assert_eq!(
+            scissor_box([0.0, 0.0, 10.0, 10.0], [0.0, 0.0], [1.25, 1.25], fb_size),
+            Some([0, 87, 13, 13])
+        );
+        assert_eq!(
+            scissor_box([1.0, 1.0, 3.0, 3.0], [0.0, 0.0], [1.5, 1.5], fb_size),
+            Some([1, 95, 4, 4])
+        );
+    }
+
+    #[test]
+    fn test_scissor_box_adjacent_rects_leave_no_gap() {
+        let fb_size = [100.0, 100.0];
+        for scale in [1.25, 1.5] {
+            let [left_x, _, left_width, _] =
+                scissor_box([0.0, 0.0, 10.0, 10.0], [0.0, 0.0], [scale; 2], fb_size).unwrap();
+            let [right_x, _, _, _] =
+                scissor_box([10.0, 0.0, 20.0, 10.0], [0.0, 0.0], [scale; 2], fb_size).unwrap();
+            assert!(right_x <= left_x + left_width, "gap at scale {}", scale);
+        }
+    }
+
+    #[test]
+    fn test_scissor_box_clip_offset() {
+        assert_eq!(
+            scissor_box(
+                [10.0, 20.0, 30.0, 40.0],
+                [10.0, 20.0],
+                [1.0, 1.0],
+                [100.0, 100.0]
+            ),
+            Some([0, 80, 20, 20])
+        );
+    }
+
+    #[test]
+    fn test_scissor_box_clamped_to_framebuffer() {
+        assert_eq!(
+            scissor_box(
+                [-5.0, -5.0, 200.0, 200.0],
+                [0.0, 0.0],
+                [1.0, 1.0],
+                [99.6, 50.4]
+            ),
+            Some([0, 0, 99, 50])
+        );
+    }
+
+    #[test]
+    fn test_scissor_box_empty() {
+        let fb_size = [100.0, 100.0];
+        let scale = [1.0, 1.0];
+        for clip_rect in [
+            [5.0, 5.0, 5.0, 10.0],
+            [5.0, 5.0, 10.0, 5.0],
+            [10.0, 10.0, 5.0, 5.0],
+            [200.0, 0.0, 300.0, 10.0],
+            [f32::NAN, 0.0, 10.0, 10.0],
+            [0.0, 0.0, 10.0, f32::NAN],
+        ] {
+            assert_eq!(
+                scissor_box(clip_rect, [0.0, 0.0], scale, fb_size),
+                None,
+                "{:?}",
+                clip_rect
+            );
+        }
+    }
+}
@@ -28,6 +28,12 @@ impl GlVersion {
         Self::parse(&unsafe { gl.get_parameter_string(glow::VERSION) })
     }
 
+    /// Whether the context is a WebGL context, whose version string is
+    /// `WebGL <major>.<minor>[ <vendor specific information>]`
+    pub fn is_webgl<G: glow::HasContext>(gl: &G) -> bool {
+        unsafe { gl.get_parameter_string(glow::VERSION) }.starts_with("WebGL ")
+    }
+
     /// Parse the OpenGL version from the version string queried from the driver
     /// via the `GL_VERSION` enum.
     ///
@@ -35,7 +41,9 @@ impl GlVersion {
     /// `<major>.<minor>[.<release>][ <vendor specific information>]`
     /// for full-fat OpenGL, and
     /// `OpenGL ES <major>.<minor>[.<release>][ <vendor specific information>]`
-    /// for OpenGL ES.
+    /// for OpenGL ES. WebGL strings, in the form
+    /// `WebGL <major>.<minor>[ <vendor specific information>]`, are parsed
+    /// as the OpenGL ES version they are based on (WebGL 2.0 is OpenGL ES 3.0).
     ///
     /// Examples based on strings found in the wild:
     /// ```rust
@@ -48,8 +56,19 @@ impl GlVersion {
     /// assert!(version.is_gles);
     /// assert_eq!(version.major, 3);
     /// assert_eq!(version.minor, 2);
+    /// let version = GlVersion::parse("WebGL 2.0 (OpenGL ES 3.0 Chromium)");
+    /// assert!(version.is_gles);
+    /// assert_eq!(version.major, 3);
+    /// assert_eq!(version.minor, 0);
     /// ```
     pub fn parse(gl_version_string: &str) -> Self {
+        if let Some(version_string) = gl_version_string.strip_prefix("WebGL ") {
+            let mut parts = version_string.split(|c: char| !c.is_numeric());
+            let major: u16 = parts.next().unwrap_or("0").parse().unwrap_or(0);
+            let minor = parts.next().unwrap_or("0").parse().unwrap_or(0);
+            return Self::gles(major + 1, minor);
+        }
+
         let (version_string, is_gles) = gl_version_string
             .strip_prefix("OpenGL ES ")
             .map_or_else(|| (gl_version_string, false), |version| (version, true));
@@ -115,6 +134,28 @@ impl GlVersion {
     pub fn primitive_restart_support(self) -> bool {
         self >= GlVersion::gl(3, 1)
     }
+
+    /// Toggling the conversion to sRGB of sRGB framebuffers
+    /// (`GL_FRAMEBUFFER_SRGB`) is supported from OpenGL 3.0, not by OpenGL ES
+    #[cfg(feature = "framebuffer_srgb_support")]
+    pub fn framebuffer_srgb_support(self) -> bool {
+        self >= GlVersion::gl(3, 0)
+    }
+
+    /// Uploading part of the rows of an image (`GL_UNPACK_ROW_LENGTH`) is
+    /// supported by all OpenGL versions and from OpenGL ES 3.0. The renderer
+    /// doesn't use it with WebGL, see `Renderer::is_webgl`.
+    #[cfg(feature = "unpack_row_length_support")]
+    pub fn unpack_row_length_support(self) -> bool {
+        self >= GlVersion::gl(1, 0) || self >= GlVersion::gles(3, 0)
+    }
+
+    /// 32 bit indices (`GL_UNSIGNED_INT`) are supported by all OpenGL
+    /// versions and from OpenGL ES 3.0, and by earlier OpenGL ES (and WebGL 1)
+    /// with the `OES_element_index_uint` extension
+    pub fn element_index_uint_support(self) -> bool {
+        self >= GlVersion::gl(1, 0) || self >= GlVersion::gles(3, 0)
+    }
 }
 
 impl PartialOrd for GlVersion {
@@ -149,7 +190,9 @@ impl GlslVersion {
     /// `<major>.<minor>[.<release>][ <vendor specific information>]`
     /// for full-fat OpenGL, and
     /// `OpenGL ES GLSL ES <major>.<minor>[.<release>][ <vendor specific information>]`
-    /// for OpenGL ES (however, strings omitting that prefix have been observed).
+    /// for OpenGL ES (however, strings omitting that prefix have been observed),
+    /// and `WebGL GLSL ES <major>.<minor>[ <vendor specific information>]` for
+    /// WebGL.
     ///
     /// Examples based on strings found in the wild:
     /// ```rust
@@ -162,10 +205,15 @@ impl GlslVersion {
     /// assert!(version.is_gles);
     /// assert_eq!(version.major, 3);
     /// assert_eq!(version.minor, 2);
+    /// let version = GlslVersion::parse("WebGL GLSL ES 3.00 (OpenGL ES GLSL ES 3.0 Chromium)");
+    /// assert!(version.is_gles);
+    /// assert_eq!(version.major, 3);
+    /// assert_eq!(version.minor, 0);
     /// ```
     pub fn parse(gl_shading_language_version: &str) -> Self {
         let (version_string, is_gles) = gl_shading_language_version
             .strip_prefix("OpenGL ES GLSL ES ")
+            .or_else(|| gl_shading_language_version.strip_prefix("WebGL GLSL ES "))
             .map_or_else(
                 || (gl_shading_language_version, false),
                 |version| (version, true),
@@ -0,0 +1,154 @@
+//! Rendering of the extra viewports (platform windows) created by imgui when
+//! `ConfigFlags::VIEWPORTS_ENABLE` is set, with the `docking` feature.
+//!
+//! The platform integration creates an OS window for each viewport, and an
+//! OpenGL surface to draw into it. Once enabled with
+//! [`AutoRenderer::enable_viewports`], [`AutoRenderer::render_viewports`]
+//! renders the draw data of each viewport after the main window, making each
+//! surface current in turn through the [`ViewportSurfaces`] implemented by the
+//! application. This is the same shape as the viewport support of
+//! `imgui-glium-renderer`.
+//!
+//! # Relation to `imgui-winit-glow-renderer-viewports`
+//!
+//! The `imgui-winit-glow-renderer-viewports` crate is a complete platform and
+//! renderer backend: it creates the windows of the viewports itself, with
+//! glutin 0.30, and handles their events. This module only renders, so it
+//! can be used with any windowing library and platform integration, at the
+//! cost of implementing `imgui::PlatformViewportBackend` and
+//! [`ViewportSurfaces`] in the application (see the `glow_10_viewports`
+//! example). The other crate is simpler to set up when glutin 0.30 suits the
+//! application.
+//!
+//! # Sharing OpenGL objects
+//!
+//! All viewports are rendered with the same [`Renderer`](crate::Renderer), so
+//! the font atlas texture, the shader program, the buffers and the user
+//! textures of the texture map must be usable from every surface. This is the
+//! case if either:
+//! - a single OpenGL context is made current with the surface of each window
+//!   (e.g. one glutin 0.30 context with one `Surface` per window), or
+//! - each window has its own context, created sharing objects with the
+//!   context of the main window (e.g. `ContextBuilder::with_shared_lists`
+//!   with glutin 0.29, or the `share_context` of `eglCreateContext`).
+//!
+//! Contexts which don't share objects with the main context are not
+//! supported: the font atlas would have to be uploaded again in each of them.
+//! Vertex array objects are never shared between contexts, which is fine as
+//! the renderer creates one for each render. The [`glow::Context`] is loaded
+//! from the main context and used with all of them, which requires the
+//! contexts to have the same pixel format on platforms where OpenGL function
+//! pointers depend on it (WGL).
+
+use glow::HasContext;
+use imgui::{BackendFlags, RendererViewportBackend, Viewport, ViewportFlags};
+
+use crate::{AutoRenderer, RenderError, TextureMap};
+
+/// Access to the OpenGL surfaces of the platform windows of the viewports,
+/// implemented by the application or its platform integration.
+pub trait ViewportSurfaces {
+    /// Makes the surface of the platform window of `viewport` current, so
+    /// that it is rendered into.
+    ///
+    /// Returns false if the viewport has no surface (e.g. its window is still
+    /// being created), to skip it.
+    fn make_current(&mut self, viewport: &Viewport) -> bool;
+
+    /// Presents what was rendered into the surface of `viewport`.
+    fn swap_buffers(&mut self, viewport: &Viewport);
+
+    /// Makes the surface of the main window current again, once the other
+    /// viewports were rendered.
+    fn make_main_current(&mut self);
+}
+
+impl<T: TextureMap> AutoRenderer<T> {
+    /// Renders the viewports of imgui into the surfaces given by `surfaces`,
+    /// from now on. See the [module documentation](self) for the requirements
+    /// on the OpenGL contexts of the viewports.
+    ///
+    /// This installs the renderer hooks in imgui's platform IO, and sets
+    /// [`BackendFlags::RENDERER_HAS_VIEWPORTS`].
+    pub fn enable_viewports<S: ViewportSurfaces + 'static>(
+        &mut self,
+        imgui_context: &mut imgui::Context,
+        surfaces: S,
+    ) {
+        self.viewports = Some(Box::new(surfaces));
+        imgui_context.set_renderer_backend(RendererBackend);
+        imgui_context
+            .io_mut()
+            .backend_flags
+            .insert(BackendFlags::RENDERER_HAS_VIEWPORTS);
+    }
+
+    /// Renders the extra viewports, each into its own surface, and makes the
+    /// surface of the main window current again.
+    ///
+    /// Call this after rendering the main viewport and
+    /// [`imgui::Context::update_platform_windows`]. Minimized viewports are
+    /// skipped, and viewports are cleared unless they have the
+    /// [`ViewportFlags::NO_RENDERER_CLEAR`] flag. Does nothing if
+    /// [`AutoRenderer::enable_viewports`] wasn't called.
+    ///
+    /// # Errors
+    /// Some OpenGL errors trigger an error (few are explicitly checked,
+    /// however)
+    pub fn render_viewports(&mut self, imgui_context: &imgui::Context) -> Result<(), RenderError> {
+        let surfaces = match &mut self.viewports {
+            Some(surfaces) => surfaces,
+            None => return Ok(()),
+        };
+        let main_viewport_id = imgui_context.main_viewport().id;
+        let mut rendered = false;
+        let mut result = Ok(());
+        for viewport in imgui_context.viewports() {
+            if viewport.id == main_viewport_id
+                || !viewport.platform_window_created
+                || viewport.flags.contains(ViewportFlags::MINIMIZED)
+                || !surfaces.make_current(viewport)
+            {
+                continue;
+            }
+            rendered = true;
+            if !viewport.flags.contains(ViewportFlags::NO_RENDERER_CLEAR) {
+                unsafe {
+                    self.gl.disable(glow::SCISSOR_TEST);
+                    self.gl.clear_color(0.0, 0.0, 0.0, 1.0);
+                    self.gl.clear(glow::COLOR_BUFFER_BIT);
+                }
+            }
+            result = self
+                .renderer
+                .render(&self.gl, &self.texture_map, viewport.draw_data());
+            if result.is_err() {
+                break;
+            }
+            surfaces.swap_buffers(viewport);
+        }
+        if rendered {
+            surfaces.make_main_current();
+        }
+        result
+    }
+}
+
+/// The renderer hooks of imgui's platform IO, see
+/// [`AutoRenderer::enable_viewports`]
+struct RendererBackend;
+
+// Surfaces are created and resized by the platform integration, and the
+// viewports are rendered by `AutoRenderer::render_viewports`, so the hooks
+// have nothing to do
+impl RendererViewportBackend for RendererBackend {
+    fn create_window(&mut self, _viewport: &mut Viewport) {}
+
+    fn destroy_window(&mut self, _viewport: &mut Viewport) {}
+
+    fn set_window_size(&mut self, _viewport: &mut Viewport, _size: [f32; 2]) {}
+
+    fn render_window(&mut self, _viewport: &mut Viewport) {}
+
+    fn swap_buffers(&mut self, _viewport: &mut Viewport) {}
+}
@@ -82,8 +82,14 @@ fn main() -> std::io::Result<()> {
             }
 
             // Set flag for dear imgui
-            build.define("CIMGUI_FREETYPE", None); // Sets IMGUI_ENABLE_FREETYPE
+            build.define("CIMGUI_FREETYPE", None);
+            // cimgui.cpp defines IMGUI_ENABLE_FREETYPE too, but it is included after
+            // imgui_draw.cpp, which then wouldn't select FreeType as the default font builder
+            build.define("IMGUI_ENABLE_FREETYPE", Some(""));
             println!("cargo:DEFINE_IMGUI_ENABLE_FREETYPE=");
+            // Keep stb_truetype available, so it can still be selected at runtime
+            build.define("IMGUI_ENABLE_STB_TRUETYPE", None);
+            println!("cargo:DEFINE_IMGUI_ENABLE_STB_TRUETYPE=");
 
             // imgui_freetype.cpp needs access to `#include "imgui.h"`.
             // So we include something like '[...]/third-party/imgui-master/imgui/'
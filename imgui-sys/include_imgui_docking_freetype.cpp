@@ -10,3 +10,9 @@
 #include "./third-party/imgui-docking-freetype/cimgui.cpp"
 
 #include "./third-party/imgui-docking-freetype/imgui/misc/freetype/imgui_freetype.cpp"
+
+// Not exposed by cimgui, this allows switching back to stb_truetype at runtime
+extern "C" const ImFontBuilderIO* ImGuiRs_GetBuilderForStbTruetype()
+{
+    return ImFontAtlasGetBuilderForStbTruetype();
+}
@@ -72,6 +72,13 @@ cfg_if::cfg_if! {
     }
 }
 
+#[cfg(all(feature = "freetype", not(feature = "wasm")))]
+extern "C" {
+    /// Returns the stb_truetype font builder, which is otherwise only used when the freetype
+    /// feature is disabled.
+    pub fn ImGuiRs_GetBuilderForStbTruetype() -> *const ImFontBuilderIO;
+}
+
 impl ImVec2 {
     #[inline]
     pub const fn new(x: f32, y: f32) -> ImVec2 {
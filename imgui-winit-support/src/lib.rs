@@ -18,7 +18,6 @@
 //! ```no_run
 //! use imgui::Context;
 //! use imgui_winit_support::{HiDpiMode, WinitPlatform};
-//! use std::time::Instant;
 //! use winit::event::{Event, WindowEvent};
 //! use winit::event_loop::{ControlFlow, EventLoop};
 //! use winit::window::Window;
@@ -32,15 +31,12 @@
 //! let mut platform = WinitPlatform::init(&mut imgui); // step 1
 //! platform.attach_window(imgui.io_mut(), &window, HiDpiMode::Default); // step 2
 //!
-//! let mut last_frame = Instant::now();
 //! let mut run = true;
 //! event_loop.run(move |event, _, control_flow| {
 //!     match event {
 //!         Event::NewEvents(_) => {
 //!             // other application-specific logic
-//!             let now = Instant::now();
-//!             imgui.io_mut().update_delta_time(now - last_frame);
-//!             last_frame = now;
+//!             platform.update_delta_time(imgui.io_mut());
 //!         },
 //!         Event::MainEventsCleared => {
 //!             // other application-specific logic
@@ -72,29 +68,157 @@
 //!     }
 //! })
 //! ```
+//!
+//! ## Logging
+//!
+//! With the `log` feature, the handled events and the changes made to the OS cursor are
+//! logged at the trace level with the [`log`](https://docs.rs/log) crate, which helps finding
+//! out where input gets lost. Without the feature, no logging code is compiled.
 
 use imgui::{self, BackendFlags, ConfigFlags, Context, Io, Key, Ui};
 use std::cmp::Ordering;
+use std::collections::VecDeque;
+use std::fmt;
+use std::time::{Duration, Instant};
 
 // Re-export winit to make it easier for users to use the correct version.
+use raw_window_handle::{HasRawWindowHandle, RawWindowHandle};
 pub use winit;
-use winit::dpi::{LogicalPosition, LogicalSize};
+use winit::dpi::{LogicalPosition, LogicalSize, PhysicalPosition, PhysicalSize};
 
 use winit::{
     error::ExternalError,
     event::{
-        DeviceEvent, ElementState, Event, KeyboardInput, MouseButton, MouseScrollDelta, TouchPhase,
-        VirtualKeyCode, WindowEvent,
+        AxisId, DeviceEvent, ElementState, Event, KeyboardInput, ModifiersState, MouseButton,
+        MouseScrollDelta, TouchPhase, VirtualKeyCode, WindowEvent,
     },
-    window::{CursorIcon as MouseCursor, Window},
+    window::{CursorIcon as MouseCursor, Window, WindowId},
 };
 
+/// Logs at the trace level with the `log` feature, and expands to nothing without it
+macro_rules! trace {
+    ($($arg:tt)*) => {{
+        #[cfg(feature = "log")]
+        log::trace!($($arg)*);
+    }};
+}
+
 /// winit backend platform state
 #[derive(Debug)]
 pub struct WinitPlatform {
     hidpi_mode: ActiveHiDpiMode,
-    hidpi_factor: f64,
+    /// Horizontal and vertical DPI factors, which only differ with `set_hidpi_factor_xy`
+    hidpi_factor: [f64; 2],
     cursor_cache: Option<CursorSettings>,
+    applied_cursor: AppliedCursor,
+    extra_mouse_button_handler: Option<Handler<dyn FnMut(u16, bool)>>,
+    unmapped_key_handler: Option<Handler<dyn FnMut(VirtualKeyCode, bool)>>,
+    scale_factor_handler: Option<Handler<dyn FnMut(f64)>>,
+    /// The DPI mode to apply again on `Event::Resumed`, while suspended
+    suspended: Option<HiDpiMode>,
+    tablet: TabletState,
+    safe_area: SafeAreaInsets,
+    mouse_offset: [f32; 2],
+    key_debounce: Option<Duration>,
+    /// Key releases held back by debouncing, with the time they were received
+    pending_key_releases: Vec<(VirtualKeyCode, Instant)>,
+    /// Keys whose press was forwarded to imgui and whose release wasn't yet
+    pressed_keys: Vec<VirtualKeyCode>,
+    use_logical_keys: bool,
+    /// A key press held back until the character it produces is received
+    pending_logical_key: Option<VirtualKeyCode>,
+    /// Pressed keys which were forwarded as the imgui key of their character
+    logical_keys: Vec<(VirtualKeyCode, Key)>,
+    /// Whether only text and modifier keys are forwarded
+    text_only_mode: bool,
+    pressed_mouse_buttons: MouseButtons,
+    require_focus: bool,
+    cursor_grabbed: bool,
+    /// Icon applied instead of the one requested by imgui
+    cursor_icon_override: Option<MouseCursor>,
+    mouse_passthrough_key: Option<VirtualKeyCode>,
+    /// Whether the horizontal and vertical scroll directions are inverted
+    invert_scroll: [bool; 2],
+    wheel_scale: [f32; 2],
+    scroll_mode: ScrollMode,
+    /// Pixel deltas not yet converted to lines, with `ScrollMode::Accumulate`
+    scroll_accumulator: [f64; 2],
+    /// Magnification not yet taken with `take_magnify_delta`
+    magnify_delta: f64,
+    magnify_as_wheel: bool,
+    pinch_scales_fonts: bool,
+    /// Bounds of `Io::font_global_scale` when it is changed by magnification gestures
+    pinch_font_scale_range: [f32; 2],
+    /// Rotation in degrees not yet taken with `take_rotate_delta`
+    rotate_delta: f32,
+    /// Whether Ctrl is held, as last reported by winit
+    ctrl_held: bool,
+    /// Smallest display size given to imgui
+    min_display_size: [f32; 2],
+    /// Logical size the display size was last computed from, to skip identical resizes
+    last_logical_size: Option<LogicalSize<f64>>,
+    /// Number of writes to `io.display_size`
+    #[cfg(test)]
+    display_size_updates: u64,
+    /// Whether the window has focus, as last reported by winit
+    focused: bool,
+    /// Windowing system of the attached window
+    windowing_backend: WindowingBackend,
+    window_position: Option<PhysicalPosition<i32>>,
+    /// Time of the last call to `update_delta_time`
+    last_frame: Option<Instant>,
+    max_delta: Duration,
+    /// Number of calls to `mark_redraw`
+    redraw_count: u64,
+    /// Times of the calls to `mark_redraw` during the last `REDRAW_HISTORY`
+    redraw_times: VecDeque<Instant>,
+}
+
+/// How long redraws are remembered by [`WinitPlatform::mark_redraw`]
+const REDRAW_HISTORY: Duration = Duration::from_secs(1);
+
+/// Logical insets of the area where imgui can be displayed, see
+/// [`WinitPlatform::set_safe_area_insets`]
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+struct SafeAreaInsets {
+    top: f64,
+    bottom: f64,
+    left: f64,
+    right: f64,
+}
+
+/// Absolute tablet input, see [`WinitPlatform::set_tablet_mode`]
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct TabletState {
+    enabled: bool,
+    axes: [AxisId; 2],
+    /// Range of the values of each axis, mapped to the width and height of the window. `None`
+    /// if the values are already physical pixels in the window.
+    ranges: Option<[[f64; 2]; 2]>,
+    /// Last reported value on each axis
+    pos: [Option<f64>; 2],
+}
+
+impl Default for TabletState {
+    fn default() -> Self {
+        TabletState {
+            enabled: false,
+            axes: [0, 1],
+            ranges: None,
+            pos: [None, None],
+        }
+    }
+}
+
+/// User-provided event handler.
+///
+/// Wrapped so `WinitPlatform` can keep deriving `Debug`.
+struct Handler<F: ?Sized>(Box<F>);
+
+impl<F: ?Sized> fmt::Debug for Handler<F> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("Handler")
+    }
 }
 
 #[derive(Debug, Copy, Clone, Eq, PartialEq)]
@@ -118,13 +242,165 @@ fn to_winit_cursor(cursor: imgui::MouseCursor) -> MouseCursor {
 }
 
 impl CursorSettings {
-    fn apply(&self, window: &Window) {
+    fn from_ui(ui: &Ui) -> Option<CursorSettings> {
+        let io = ui.io();
+        if io
+            .config_flags
+            .contains(ConfigFlags::NO_MOUSE_CURSOR_CHANGE)
+        {
+            return None;
+        }
+        Some(CursorSettings {
+            cursor: ui.mouse_cursor(),
+            draw_cursor: io.mouse_draw_cursor,
+        })
+    }
+    fn to_command(self) -> CursorCommand {
         match self.cursor {
-            Some(mouse_cursor) if !self.draw_cursor => {
-                window.set_cursor_visible(true);
-                window.set_cursor_icon(to_winit_cursor(mouse_cursor));
+            Some(mouse_cursor) if !self.draw_cursor => CursorCommand {
+                visible: true,
+                icon: Some(to_winit_cursor(mouse_cursor)),
+            },
+            _ => CursorCommand {
+                visible: false,
+                icon: None,
+            },
+        }
+    }
+}
+
+/// OS cursor changes requested by imgui, see [`WinitPlatform::compute_cursor`].
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct CursorCommand {
+    /// Whether the OS cursor should be visible
+    pub visible: bool,
+    /// Icon to use for the OS cursor, or `None` to leave it unchanged (when the cursor is
+    /// hidden)
+    pub icon: Option<MouseCursor>,
+}
+
+/// Cursor properties last set on the window.
+///
+/// Visibility and icon are tracked independently, so changing one doesn't re-issue the other
+/// (on X11 each call is a round-trip to the server).
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq)]
+struct AppliedCursor {
+    visible: Option<bool>,
+    icon: Option<MouseCursor>,
+}
+
+/// Window operations used by the platform.
+///
+/// Implemented for winit's `Window`, and abstracted so the platform logic can be tested without
+/// creating a real window.
+trait PlatformWindow {
+    fn id(&self) -> WindowId;
+    fn scale_factor(&self) -> f64;
+    fn inner_size(&self) -> PhysicalSize<u32>;
+    fn set_cursor_visible(&self, visible: bool);
+    fn set_cursor_icon(&self, icon: MouseCursor);
+    fn set_cursor_position(&self, position: LogicalPosition<f64>) -> Result<(), ExternalError>;
+    fn windowing_backend(&self) -> WindowingBackend;
+}
+
+impl PlatformWindow for Window {
+    fn id(&self) -> WindowId {
+        Window::id(self)
+    }
+    fn scale_factor(&self) -> f64 {
+        Window::scale_factor(self)
+    }
+    fn inner_size(&self) -> PhysicalSize<u32> {
+        Window::inner_size(self)
+    }
+    fn set_cursor_visible(&self, visible: bool) {
+        Window::set_cursor_visible(self, visible)
+    }
+    fn set_cursor_icon(&self, icon: MouseCursor) {
+        Window::set_cursor_icon(self, icon)
+    }
+    fn set_cursor_position(&self, position: LogicalPosition<f64>) -> Result<(), ExternalError> {
+        Window::set_cursor_position(self, position)
+    }
+    fn windowing_backend(&self) -> WindowingBackend {
+        WindowingBackend::from_raw_handle(self.raw_window_handle())
+    }
+}
+
+/// A set of mouse buttons, see [`WinitPlatform::pressed_mouse_buttons`]
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct MouseButtons(u8);
+
+impl MouseButtons {
+    /// Returns true if the set contains the button.
+    pub fn contains(self, button: imgui::MouseButton) -> bool {
+        self.0 & (1 << button as u8) != 0
+    }
+    /// Returns true if the set contains no button.
+    pub fn is_empty(self) -> bool {
+        self.0 == 0
+    }
+    /// Returns an iterator over the buttons of the set.
+    pub fn iter(self) -> impl Iterator<Item = imgui::MouseButton> {
+        imgui::MouseButton::VARIANTS
+            .iter()
+            .copied()
+            .filter(move |&button| self.contains(button))
+    }
+    fn set(&mut self, button: imgui::MouseButton, value: bool) {
+        if value {
+            self.0 |= 1 << button as u8;
+        } else {
+            self.0 &= !(1 << button as u8);
+        }
+    }
+}
+
+/// Reason why a window can't be attached, see [`WinitPlatform::attach_window_checked`]
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum AttachWindowError {
+    /// The window has no area, typically because it isn't visible yet
+    ZeroSize(PhysicalSize<u32>),
+    /// The window reports a scale factor that isn't a positive finite number
+    InvalidScaleFactor(f64),
+}
+
+impl std::error::Error for AttachWindowError {}
+
+impl fmt::Display for AttachWindowError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::ZeroSize(size) => write!(
+                f,
+                "Window has a size of {}x{}, was it attached before being shown?",
+                size.width, size.height
+            ),
+            Self::InvalidScaleFactor(scale_factor) => {
+                write!(f, "Window has an invalid scale factor: {}", scale_factor)
             }
-            _ => window.set_cursor_visible(false),
+        }
+    }
+}
+
+/// The windowing system a window belongs to, see [`WinitPlatform::windowing_backend`]
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum WindowingBackend {
+    X11,
+    Wayland,
+    Windows,
+    MacOS,
+    /// Any other system, such as the web, Android or iOS
+    Other,
+}
+
+impl WindowingBackend {
+    fn from_raw_handle(handle: RawWindowHandle) -> Self {
+        match handle {
+            RawWindowHandle::Xlib(_) | RawWindowHandle::Xcb(_) => WindowingBackend::X11,
+            RawWindowHandle::Wayland(_) => WindowingBackend::Wayland,
+            RawWindowHandle::Win32(_) | RawWindowHandle::WinRt(_) => WindowingBackend::Windows,
+            RawWindowHandle::AppKit(_) => WindowingBackend::MacOS,
+            _ => WindowingBackend::Other,
         }
     }
 }
@@ -133,6 +409,7 @@ impl CursorSettings {
 enum ActiveHiDpiMode {
     Default,
     Rounded,
+    Snapped(f64),
     Locked,
 }
 
@@ -145,6 +422,7 @@ enum ActiveHiDpiMode {
 /// will use different logical coordinates, so be careful if you pass around logical size or
 /// position values.**
 #[derive(Copy, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum HiDpiMode {
     /// The DPI factor from winit is used directly without adjustment
     Default,
@@ -152,6 +430,12 @@ pub enum HiDpiMode {
     ///
     /// This prevents the user interface from becoming blurry with non-integer scaling.
     Rounded,
+    /// The DPI factor from winit is rounded to the nearest multiple of the included step.
+    ///
+    /// This is a middle ground between `Default` and `Rounded` for fractional scaling setups:
+    /// for example, with a step of 0.25, a factor of 1.37 becomes 1.25. The result is never
+    /// smaller than the step.
+    Snapped(f64),
     /// The DPI factor from winit is ignored, and the included value is used instead.
     ///
     /// This is useful if you want to force some DPI factor (e.g. 1.0) and not care about the value
@@ -164,11 +448,166 @@ impl HiDpiMode {
         match *self {
             HiDpiMode::Default => (ActiveHiDpiMode::Default, hidpi_factor),
             HiDpiMode::Rounded => (ActiveHiDpiMode::Rounded, hidpi_factor.round()),
+            HiDpiMode::Snapped(step) => (
+                ActiveHiDpiMode::Snapped(step),
+                snap_hidpi_factor(hidpi_factor, step),
+            ),
             HiDpiMode::Locked(value) => (ActiveHiDpiMode::Locked, value),
         }
     }
 }
 
+fn snap_hidpi_factor(hidpi_factor: f64, step: f64) -> f64 {
+    if step > 0.0 {
+        ((hidpi_factor / step).round() * step).max(step)
+    } else {
+        hidpi_factor
+    }
+}
+
+/// How mouse wheel events with a pixel delta, sent by touchpads and some mice, are converted
+/// to the line deltas imgui expects.
+///
+/// Events with a line delta are passed to imgui as they are.
+#[derive(Copy, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ScrollMode {
+    /// Each event scrolls by one line in the direction of the delta, whatever its size.
+    ///
+    /// This gives step scrolling, e.g. for lists, but touchpads send many small events and
+    /// scroll very fast.
+    SignOnly,
+    /// The delta, in logical pixels, is divided by the included number of pixels per line, so
+    /// touchpads scroll smoothly. This is the default, with 50 pixels per line.
+    Lines {
+        /// Number of logical pixels giving one line of scrolling
+        pixels_per_line: f64,
+    },
+    /// Deltas are summed up, and one line is scrolled each time the sum reaches the included
+    /// number of logical pixels.
+    ///
+    /// This gives step scrolling which follows the speed of the touchpad.
+    Accumulate {
+        /// Number of logical pixels giving one line of scrolling
+        threshold: f64,
+    },
+}
+
+impl Default for ScrollMode {
+    fn default() -> Self {
+        ScrollMode::Lines {
+            pixels_per_line: 50.0,
+        }
+    }
+}
+
+/// Suggested reaction to an event, returned by [`WinitPlatform::handle_event_with_result`].
+///
+/// This is only advisory: it is derived from the event alone and doesn't know about any other
+/// state of the application, which is free to ignore it or combine it with its own logic.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum EventHint {
+    /// The window was asked to redraw, or received input that may change the UI. For example,
+    /// request a redraw with `Window::request_redraw`.
+    Redraw,
+    /// The window was asked to close. For example, set the control flow to `ControlFlow::Exit`.
+    Exit,
+    /// The event doesn't concern the UI.
+    Nothing,
+}
+
+impl EventHint {
+    fn from_event<T>(window_id: WindowId, event: &Event<T>) -> EventHint {
+        match *event {
+            Event::WindowEvent {
+                window_id: id,
+                event: WindowEvent::CloseRequested,
+            } if id == window_id => EventHint::Exit,
+            Event::WindowEvent { window_id: id, .. } | Event::RedrawRequested(id)
+                if id == window_id =>
+            {
+                EventHint::Redraw
+            }
+            _ => EventHint::Nothing,
+        }
+    }
+}
+
+/// Snapshot of the platform state that can be saved and restored later.
+///
+/// Obtained with [`WinitPlatform::save_state`] and applied with
+/// [`WinitPlatform::restore_state`]. With the `serde` feature enabled, this type implements
+/// `Serialize` and `Deserialize`, so it can be persisted across restarts or hot reloads.
+#[derive(Copy, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PlatformState {
+    /// DPI mode in use. A locked mode carries its locked factor.
+    pub hidpi_mode: HiDpiMode,
+    /// DPI factor in use when the state was saved
+    pub hidpi_factor: f64,
+    /// Last cursor state applied to the window, if any
+    pub cursor: Option<CursorState>,
+}
+
+/// Cursor state applied to the window by [`WinitPlatform::prepare_render`].
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CursorState {
+    /// Cursor requested by imgui, or `None` if the cursor is hidden
+    #[cfg_attr(feature = "serde", serde(with = "serde_mouse_cursor"))]
+    pub cursor: Option<imgui::MouseCursor>,
+    /// Whether imgui draws the cursor by itself (see `Io::mouse_draw_cursor`)
+    pub draw_cursor: bool,
+}
+
+impl From<CursorSettings> for CursorState {
+    fn from(settings: CursorSettings) -> CursorState {
+        CursorState {
+            cursor: settings.cursor,
+            draw_cursor: settings.draw_cursor,
+        }
+    }
+}
+
+impl From<CursorState> for CursorSettings {
+    fn from(state: CursorState) -> CursorSettings {
+        CursorSettings {
+            cursor: state.cursor,
+            draw_cursor: state.draw_cursor,
+        }
+    }
+}
+
+/// Serializes `imgui::MouseCursor` by variant name, so saved states don't depend on the
+/// numeric values used by Dear ImGui.
+#[cfg(feature = "serde")]
+mod serde_mouse_cursor {
+    use serde::{de::Error, Deserialize, Deserializer, Serialize, Serializer};
+
+    pub fn serialize<S: Serializer>(
+        cursor: &Option<imgui::MouseCursor>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        cursor
+            .map(|cursor| format!("{:?}", cursor))
+            .serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Option<imgui::MouseCursor>, D::Error> {
+        match Option::<String>::deserialize(deserializer)? {
+            Some(name) => imgui::MouseCursor::VARIANTS
+                .iter()
+                .copied()
+                .find(|cursor| format!("{:?}", cursor) == name)
+                .map(Some)
+                .ok_or_else(|| D::Error::custom(format!("unknown mouse cursor `{}`", name))),
+            None => Ok(None),
+        }
+    }
+}
+
 fn to_imgui_mouse_button(button: MouseButton) -> Option<imgui::MouseButton> {
     match button {
         MouseButton::Left | MouseButton::Other(0) => Some(imgui::MouseButton::Left),
@@ -180,117 +619,251 @@ fn to_imgui_mouse_button(button: MouseButton) -> Option<imgui::MouseButton> {
     }
 }
 
+/// The winit keys forwarded to imgui, with the imgui key each one is mapped to.
+///
+/// This is the table used to translate keyboard events, so it can be used to list the keys
+/// an application can bind, e.g. in a settings UI. Keys missing from it are passed to the
+/// handler set with [`WinitPlatform::set_unmapped_key_handler`] instead.
+pub const KEY_MAPPING: &[(VirtualKeyCode, Key)] = &[
+    (VirtualKeyCode::Tab, Key::Tab),
+    (VirtualKeyCode::Left, Key::LeftArrow),
+    (VirtualKeyCode::Right, Key::RightArrow),
+    (VirtualKeyCode::Up, Key::UpArrow),
+    (VirtualKeyCode::Down, Key::DownArrow),
+    (VirtualKeyCode::PageUp, Key::PageUp),
+    (VirtualKeyCode::PageDown, Key::PageDown),
+    (VirtualKeyCode::Home, Key::Home),
+    (VirtualKeyCode::End, Key::End),
+    (VirtualKeyCode::Insert, Key::Insert),
+    (VirtualKeyCode::Delete, Key::Delete),
+    (VirtualKeyCode::Back, Key::Backspace),
+    (VirtualKeyCode::Space, Key::Space),
+    (VirtualKeyCode::Return, Key::Enter),
+    (VirtualKeyCode::Escape, Key::Escape),
+    (VirtualKeyCode::LControl, Key::LeftCtrl),
+    (VirtualKeyCode::LShift, Key::LeftShift),
+    (VirtualKeyCode::LAlt, Key::LeftAlt),
+    (VirtualKeyCode::LWin, Key::LeftSuper),
+    (VirtualKeyCode::RControl, Key::RightCtrl),
+    (VirtualKeyCode::RShift, Key::RightShift),
+    (VirtualKeyCode::RAlt, Key::RightAlt),
+    (VirtualKeyCode::RWin, Key::RightSuper),
+    // TODO: find out if there is a Menu key in winit
+    (VirtualKeyCode::Key0, Key::Alpha0),
+    (VirtualKeyCode::Key1, Key::Alpha1),
+    (VirtualKeyCode::Key2, Key::Alpha2),
+    (VirtualKeyCode::Key3, Key::Alpha3),
+    (VirtualKeyCode::Key4, Key::Alpha4),
+    (VirtualKeyCode::Key5, Key::Alpha5),
+    (VirtualKeyCode::Key6, Key::Alpha6),
+    (VirtualKeyCode::Key7, Key::Alpha7),
+    (VirtualKeyCode::Key8, Key::Alpha8),
+    (VirtualKeyCode::Key9, Key::Alpha9),
+    (VirtualKeyCode::A, Key::A),
+    (VirtualKeyCode::B, Key::B),
+    (VirtualKeyCode::C, Key::C),
+    (VirtualKeyCode::D, Key::D),
+    (VirtualKeyCode::E, Key::E),
+    (VirtualKeyCode::F, Key::F),
+    (VirtualKeyCode::G, Key::G),
+    (VirtualKeyCode::H, Key::H),
+    (VirtualKeyCode::I, Key::I),
+    (VirtualKeyCode::J, Key::J),
+    (VirtualKeyCode::K, Key::K),
+    (VirtualKeyCode::L, Key::L),
+    (VirtualKeyCode::M, Key::M),
+    (VirtualKeyCode::N, Key::N),
+    (VirtualKeyCode::O, Key::O),
+    (VirtualKeyCode::P, Key::P),
+    (VirtualKeyCode::Q, Key::Q),
+    (VirtualKeyCode::R, Key::R),
+    (VirtualKeyCode::S, Key::S),
+    (VirtualKeyCode::T, Key::T),
+    (VirtualKeyCode::U, Key::U),
+    (VirtualKeyCode::V, Key::V),
+    (VirtualKeyCode::W, Key::W),
+    (VirtualKeyCode::X, Key::X),
+    (VirtualKeyCode::Y, Key::Y),
+    (VirtualKeyCode::Z, Key::Z),
+    (VirtualKeyCode::F1, Key::F1),
+    (VirtualKeyCode::F2, Key::F2),
+    (VirtualKeyCode::F3, Key::F3),
+    (VirtualKeyCode::F4, Key::F4),
+    (VirtualKeyCode::F5, Key::F5),
+    (VirtualKeyCode::F6, Key::F6),
+    (VirtualKeyCode::F7, Key::F7),
+    (VirtualKeyCode::F8, Key::F8),
+    (VirtualKeyCode::F9, Key::F9),
+    (VirtualKeyCode::F10, Key::F10),
+    (VirtualKeyCode::F11, Key::F11),
+    (VirtualKeyCode::F12, Key::F12),
+    (VirtualKeyCode::Apostrophe, Key::Apostrophe),
+    (VirtualKeyCode::Comma, Key::Comma),
+    (VirtualKeyCode::Minus, Key::Minus),
+    (VirtualKeyCode::Period, Key::Period),
+    (VirtualKeyCode::Slash, Key::Slash),
+    (VirtualKeyCode::Semicolon, Key::Semicolon),
+    (VirtualKeyCode::Equals, Key::Equal),
+    (VirtualKeyCode::LBracket, Key::LeftBracket),
+    (VirtualKeyCode::Backslash, Key::Backslash),
+    (VirtualKeyCode::RBracket, Key::RightBracket),
+    (VirtualKeyCode::Grave, Key::GraveAccent),
+    (VirtualKeyCode::Capital, Key::CapsLock),
+    (VirtualKeyCode::Scroll, Key::ScrollLock),
+    (VirtualKeyCode::Numlock, Key::NumLock),
+    (VirtualKeyCode::Snapshot, Key::PrintScreen),
+    (VirtualKeyCode::Pause, Key::Pause),
+    (VirtualKeyCode::Numpad0, Key::Keypad0),
+    (VirtualKeyCode::Numpad1, Key::Keypad1),
+    (VirtualKeyCode::Numpad2, Key::Keypad2),
+    (VirtualKeyCode::Numpad3, Key::Keypad3),
+    (VirtualKeyCode::Numpad4, Key::Keypad4),
+    (VirtualKeyCode::Numpad5, Key::Keypad5),
+    (VirtualKeyCode::Numpad6, Key::Keypad6),
+    (VirtualKeyCode::Numpad7, Key::Keypad7),
+    (VirtualKeyCode::Numpad8, Key::Keypad8),
+    (VirtualKeyCode::Numpad9, Key::Keypad9),
+    (VirtualKeyCode::NumpadDecimal, Key::KeypadDecimal),
+    (VirtualKeyCode::NumpadDivide, Key::KeypadDivide),
+    (VirtualKeyCode::NumpadMultiply, Key::KeypadMultiply),
+    (VirtualKeyCode::NumpadSubtract, Key::KeypadSubtract),
+    (VirtualKeyCode::NumpadAdd, Key::KeypadAdd),
+    (VirtualKeyCode::NumpadEnter, Key::KeypadEnter),
+    (VirtualKeyCode::NumpadEquals, Key::KeypadEqual),
+];
+
 fn to_imgui_key(keycode: VirtualKeyCode) -> Option<Key> {
-    match keycode {
-        VirtualKeyCode::Tab => Some(Key::Tab),
-        VirtualKeyCode::Left => Some(Key::LeftArrow),
-        VirtualKeyCode::Right => Some(Key::RightArrow),
-        VirtualKeyCode::Up => Some(Key::UpArrow),
-        VirtualKeyCode::Down => Some(Key::DownArrow),
-        VirtualKeyCode::PageUp => Some(Key::PageUp),
-        VirtualKeyCode::PageDown => Some(Key::PageDown),
-        VirtualKeyCode::Home => Some(Key::Home),
-        VirtualKeyCode::End => Some(Key::End),
-        VirtualKeyCode::Insert => Some(Key::Insert),
-        VirtualKeyCode::Delete => Some(Key::Delete),
-        VirtualKeyCode::Back => Some(Key::Backspace),
-        VirtualKeyCode::Space => Some(Key::Space),
-        VirtualKeyCode::Return => Some(Key::Enter),
-        VirtualKeyCode::Escape => Some(Key::Escape),
-        VirtualKeyCode::LControl => Some(Key::LeftCtrl),
-        VirtualKeyCode::LShift => Some(Key::LeftShift),
-        VirtualKeyCode::LAlt => Some(Key::LeftAlt),
-        VirtualKeyCode::LWin => Some(Key::LeftSuper),
-        VirtualKeyCode::RControl => Some(Key::RightCtrl),
-        VirtualKeyCode::RShift => Some(Key::RightShift),
-        VirtualKeyCode::RAlt => Some(Key::RightAlt),
-        VirtualKeyCode::RWin => Some(Key::RightSuper),
-        //VirtualKeyCode::Menu => Some(Key::Menu), // TODO: find out if there is a Menu key in winit
-        VirtualKeyCode::Key0 => Some(Key::Alpha0),
-        VirtualKeyCode::Key1 => Some(Key::Alpha1),
-        VirtualKeyCode::Key2 => Some(Key::Alpha2),
-        VirtualKeyCode::Key3 => Some(Key::Alpha3),
-        VirtualKeyCode::Key4 => Some(Key::Alpha4),
-        VirtualKeyCode::Key5 => Some(Key::Alpha5),
-        VirtualKeyCode::Key6 => Some(Key::Alpha6),
-        VirtualKeyCode::Key7 => Some(Key::Alpha7),
-        VirtualKeyCode::Key8 => Some(Key::Alpha8),
-        VirtualKeyCode::Key9 => Some(Key::Alpha9),
-        VirtualKeyCode::A => Some(Key::A),
-        VirtualKeyCode::B => Some(Key::B),
-        VirtualKeyCode::C => Some(Key::C),
-        VirtualKeyCode::D => Some(Key::D),
-        VirtualKeyCode::E => Some(Key::E),
-        VirtualKeyCode::F => Some(Key::F),
-        VirtualKeyCode::G => Some(Key::G),
-        VirtualKeyCode::H => Some(Key::H),
-        VirtualKeyCode::I => Some(Key::I),
-        VirtualKeyCode::J => Some(Key::J),
-        VirtualKeyCode::K => Some(Key::K),
-        VirtualKeyCode::L => Some(Key::L),
-        VirtualKeyCode::M => Some(Key::M),
-        VirtualKeyCode::N => Some(Key::N),
-        VirtualKeyCode::O => Some(Key::O),
-        VirtualKeyCode::P => Some(Key::P),
-        VirtualKeyCode::Q => Some(Key::Q),
-        VirtualKeyCode::R => Some(Key::R),
-        VirtualKeyCode::S => Some(Key::S),
-        VirtualKeyCode::T => Some(Key::T),
-        VirtualKeyCode::U => Some(Key::U),
-        VirtualKeyCode::V => Some(Key::V),
-        VirtualKeyCode::W => Some(Key::W),
-        VirtualKeyCode::X => Some(Key::X),
-        VirtualKeyCode::Y => Some(Key::Y),
-        VirtualKeyCode::Z => Some(Key::Z),
-        VirtualKeyCode::F1 => Some(Key::F1),
-        VirtualKeyCode::F2 => Some(Key::F2),
-        VirtualKeyCode::F3 => Some(Key::F3),
-        VirtualKeyCode::F4 => Some(Key::F4),
-        VirtualKeyCode::F5 => Some(Key::F5),
-        VirtualKeyCode::F6 => Some(Key::F6),
-        VirtualKeyCode::F7 => Some(Key::F7),
-        VirtualKeyCode::F8 => Some(Key::F8),
-        VirtualKeyCode::F9 => Some(Key::F9),
-        VirtualKeyCode::F10 => Some(Key::F10),
-        VirtualKeyCode::F11 => Some(Key::F11),
-        VirtualKeyCode::F12 => Some(Key::F12),
-        VirtualKeyCode::Apostrophe => Some(Key::Apostrophe),
-        VirtualKeyCode::Comma => Some(Key::Comma),
-        VirtualKeyCode::Minus => Some(Key::Minus),
-        VirtualKeyCode::Period => Some(Key::Period),
-        VirtualKeyCode::Slash => Some(Key::Slash),
-        VirtualKeyCode::Semicolon => Some(Key::Semicolon),
-        VirtualKeyCode::Equals => Some(Key::Equal),
-        VirtualKeyCode::LBracket => Some(Key::LeftBracket),
-        VirtualKeyCode::Backslash => Some(Key::Backslash),
-        VirtualKeyCode::RBracket => Some(Key::RightBracket),
-        VirtualKeyCode::Grave => Some(Key::GraveAccent),
-        VirtualKeyCode::Capital => Some(Key::CapsLock),
-        VirtualKeyCode::Scroll => Some(Key::ScrollLock),
-        VirtualKeyCode::Numlock => Some(Key::NumLock),
-        VirtualKeyCode::Snapshot => Some(Key::PrintScreen),
-        VirtualKeyCode::Pause => Some(Key::Pause),
-        VirtualKeyCode::Numpad0 => Some(Key::Keypad0),
-        VirtualKeyCode::Numpad1 => Some(Key::Keypad1),
-        VirtualKeyCode::Numpad2 => Some(Key::Keypad2),
-        VirtualKeyCode::Numpad3 => Some(Key::Keypad3),
-        VirtualKeyCode::Numpad4 => Some(Key::Keypad4),
-        VirtualKeyCode::Numpad5 => Some(Key::Keypad5),
-        VirtualKeyCode::Numpad6 => Some(Key::Keypad6),
-        VirtualKeyCode::Numpad7 => Some(Key::Keypad7),
-        VirtualKeyCode::Numpad8 => Some(Key::Keypad8),
-        VirtualKeyCode::Numpad9 => Some(Key::Keypad9),
-        VirtualKeyCode::NumpadDecimal => Some(Key::KeypadDecimal),
-        VirtualKeyCode::NumpadDivide => Some(Key::KeypadDivide),
-        VirtualKeyCode::NumpadMultiply => Some(Key::KeypadMultiply),
-        VirtualKeyCode::NumpadSubtract => Some(Key::KeypadSubtract),
-        VirtualKeyCode::NumpadAdd => Some(Key::KeypadAdd),
-        VirtualKeyCode::NumpadEnter => Some(Key::KeypadEnter),
-        VirtualKeyCode::NumpadEquals => Some(Key::KeypadEqual),
-        _ => None,
+    KEY_MAPPING
+        .iter()
+        .find(|&&(winit_key, _)| winit_key == keycode)
+        .map(|&(_, key)| key)
+}
+
+/// Returns true for keys which produce a character on some keyboard layouts
+fn may_produce_character(keycode: VirtualKeyCode) -> bool {
+    match to_imgui_key(keycode) {
+        Some(key) => char_keys().any(|(_, k)| k == key),
+        None => matches!(
+            keycode,
+            VirtualKeyCode::Plus
+                | VirtualKeyCode::Asterisk
+                | VirtualKeyCode::At
+                | VirtualKeyCode::Caret
+                | VirtualKeyCode::Colon
+                | VirtualKeyCode::Underline
+                | VirtualKeyCode::OEM102
+        ),
+    }
+}
+
+/// Characters of a US layout, with the imgui key which produces them
+fn char_keys() -> impl Iterator<Item = (char, Key)> {
+    const PUNCTUATION: [(char, char, Key); 11] = [
+        ('\'', '"', Key::Apostrophe),
+        (',', '<', Key::Comma),
+        ('-', '_', Key::Minus),
+        ('.', '>', Key::Period),
+        ('/', '?', Key::Slash),
+        (';', ':', Key::Semicolon),
+        ('=', '+', Key::Equal),
+        ('[', '{', Key::LeftBracket),
+        ('\\', '|', Key::Backslash),
+        (']', '}', Key::RightBracket),
+        ('`', '~', Key::GraveAccent),
+    ];
+    // `Key::VARIANTS` is in the order of the imgui key values, which starts with Tab
+    let index = |key: Key| key as usize - Key::Tab as usize;
+    let letters = ('a'..='z').zip(&Key::VARIANTS[index(Key::A)..=index(Key::Z)]);
+    let digits = ('0'..='9').zip(&Key::VARIANTS[index(Key::Alpha0)..=index(Key::Alpha9)]);
+    letters.chain(digits).map(|(ch, &key)| (ch, key)).chain(
+        PUNCTUATION
+            .iter()
+            .flat_map(|&(ch, shifted, key)| [(ch, key), (shifted, key)]),
+    )
+}
+
+/// Returns the imgui key of a character, for [`WinitPlatform::set_use_logical_keys`]
+fn char_to_imgui_key(ch: char) -> Option<Key> {
+    // With Ctrl held, letters are reported as control characters on some platforms
+    let ch = match ch {
+        '\u{1}'..='\u{1a}' => (b'a' + ch as u8 - 1) as char,
+        _ => ch.to_ascii_lowercase(),
+    };
+    char_keys().find(|&(c, _)| c == ch).map(|(_, key)| key)
+}
+
+/// Returns true for mouse and keyboard input events
+fn is_input_event(event: &WindowEvent) -> bool {
+    matches!(
+        event,
+        WindowEvent::ModifiersChanged(_)
+            | WindowEvent::KeyboardInput { .. }
+            | WindowEvent::ReceivedCharacter(_)
+            | WindowEvent::CursorMoved { .. }
+            | WindowEvent::AxisMotion { .. }
+            | WindowEvent::MouseWheel { .. }
+            | WindowEvent::MouseInput { .. }
+    )
+}
+
+#[cfg(feature = "log")]
+fn press_name(pressed: bool) -> &'static str {
+    if pressed {
+        "pressed"
+    } else {
+        "released"
+    }
+}
+
+fn handle_received_character(io: &mut Io, ch: char) {
+    // Exclude the backspace key ('\u{7f}'). Otherwise we will insert this char and then
+    // delete it.
+    if ch != '\u{7f}' {
+        io.add_input_character(ch)
     }
 }
 
+/// Feeds text to imgui as if it had been typed, with the same filtering as the characters
+/// received from winit.
+///
+/// Unlike feeding the characters one by one, the text is passed to imgui in bulk, which is
+/// faster for large pastes (e.g. from an IME commit or the clipboard).
+pub fn handle_received_text(io: &mut Io, text: &str) {
+    for chunk in text.split('\u{7f}') {
+        if !chunk.is_empty() {
+            io.add_input_characters(chunk);
+        }
+    }
+}
+
+/// Converts the modifiers held according to winit to the state of imgui's modifier keys, as
+/// `(shift, ctrl, alt, super)`.
+///
+/// This is what [`WinitPlatform`] feeds to imgui on `WindowEvent::ModifiersChanged`, and can be
+/// used by applications checking their own shortcuts against the same state as imgui.
+pub fn imgui_modifiers_from_winit(modifiers: ModifiersState) -> (bool, bool, bool, bool) {
+    (
+        modifiers.shift(),
+        modifiers.ctrl(),
+        modifiers.alt(),
+        modifiers.logo(),
+    )
+}
+
+fn is_modifier_key(key: VirtualKeyCode) -> bool {
+    matches!(
+        key,
+        VirtualKeyCode::LShift
+            | VirtualKeyCode::RShift
+            | VirtualKeyCode::LControl
+            | VirtualKeyCode::RControl
+            | VirtualKeyCode::LAlt
+            | VirtualKeyCode::RAlt
+            | VirtualKeyCode::LWin
+            | VirtualKeyCode::RWin
+    )
+}
+
 fn handle_key_modifier(io: &mut Io, key: VirtualKeyCode, down: bool) {
     if key == VirtualKeyCode::LShift || key == VirtualKeyCode::RShift {
         io.add_key_event(imgui::Key::ModShift, down);
@@ -303,7 +876,25 @@ fn handle_key_modifier(io: &mut Io, key: VirtualKeyCode, down: bool) {
     }
 }
 
+/// Platform name set on the imgui context
+const PLATFORM_NAME: &str = concat!("imgui-winit-support ", env!("CARGO_PKG_VERSION"));
+
 impl WinitPlatform {
+    /// Returns the name and version of this platform backend, e.g. `"imgui-winit-support 0.11.0"`,
+    /// to be shown in about boxes or diagnostics.
+    ///
+    /// This is also the context's platform name, set by [`WinitPlatform::init`].
+    pub fn backend_name() -> &'static str {
+        PLATFORM_NAME
+    }
+    /// Returns the windowing system of a window.
+    ///
+    /// Some features depend on it: for example, Wayland doesn't let applications move the
+    /// cursor, so [`WinitPlatform::attach_window`] clears `BackendFlags::HAS_SET_MOUSE_POS` on
+    /// Wayland and imgui never asks to move it.
+    pub fn windowing_backend(window: &Window) -> WindowingBackend {
+        PlatformWindow::windowing_backend(window)
+    }
     /// Initializes a winit platform instance and configures imgui.
     ///
     /// This function configures imgui-rs in the following ways:
@@ -315,16 +906,76 @@ impl WinitPlatform {
         let io = imgui.io_mut();
         io.backend_flags.insert(BackendFlags::HAS_MOUSE_CURSORS);
         io.backend_flags.insert(BackendFlags::HAS_SET_MOUSE_POS);
-        imgui.set_platform_name(Some(format!(
-            "imgui-winit-support {}",
-            env!("CARGO_PKG_VERSION")
-        )));
+        imgui.set_platform_name(Some(PLATFORM_NAME.to_owned()));
         WinitPlatform {
             hidpi_mode: ActiveHiDpiMode::Default,
-            hidpi_factor: 1.0,
+            hidpi_factor: [1.0, 1.0],
             cursor_cache: None,
+            applied_cursor: AppliedCursor::default(),
+            extra_mouse_button_handler: None,
+            unmapped_key_handler: None,
+            scale_factor_handler: None,
+            suspended: None,
+            tablet: TabletState::default(),
+            safe_area: SafeAreaInsets::default(),
+            mouse_offset: [0.0, 0.0],
+            key_debounce: None,
+            pending_key_releases: Vec::new(),
+            pressed_keys: Vec::new(),
+            use_logical_keys: false,
+            pending_logical_key: None,
+            logical_keys: Vec::new(),
+            text_only_mode: false,
+            pressed_mouse_buttons: MouseButtons::default(),
+            require_focus: false,
+            cursor_grabbed: false,
+            cursor_icon_override: None,
+            mouse_passthrough_key: None,
+            invert_scroll: [false, false],
+            wheel_scale: [1.0, 1.0],
+            scroll_mode: ScrollMode::default(),
+            scroll_accumulator: [0.0, 0.0],
+            magnify_delta: 0.0,
+            magnify_as_wheel: false,
+            pinch_scales_fonts: false,
+            pinch_font_scale_range: [0.5, 3.0],
+            rotate_delta: 0.0,
+            ctrl_held: false,
+            min_display_size: [1.0, 1.0],
+            last_logical_size: None,
+            #[cfg(test)]
+            display_size_updates: 0,
+            focused: true,
+            windowing_backend: WindowingBackend::Other,
+            window_position: None,
+            last_frame: None,
+            max_delta: Duration::from_millis(100),
+            redraw_count: 0,
+            redraw_times: VecDeque::new(),
         }
     }
+    /// Configures a new imgui context for this platform instance, e.g. after recreating the
+    /// context to reload a theme or rebuild the font atlas.
+    ///
+    /// This configures the context like [`WinitPlatform::init`] and
+    /// [`WinitPlatform::attach_window`] did for the previous one: the backend flags, the
+    /// platform name and `io.display_framebuffer_scale` are set again, from the DPI factor and
+    /// windowing system of the attached window. The rest of the platform state is kept. The
+    /// display size is only known from the window, so call
+    /// [`WinitPlatform::refresh_display_size`] before the next frame.
+    pub fn reinit(&mut self, imgui: &mut Context) {
+        let io = imgui.io_mut();
+        io.backend_flags.insert(BackendFlags::HAS_MOUSE_CURSORS);
+        // Wayland doesn't allow moving the cursor
+        io.backend_flags.set(
+            BackendFlags::HAS_SET_MOUSE_POS,
+            self.windowing_backend != WindowingBackend::Wayland,
+        );
+        io.display_framebuffer_scale = self.hidpi_factor.map(|f| f as f32);
+        imgui.set_platform_name(Some(PLATFORM_NAME.to_owned()));
+        // The cursor of the new context has to be applied again
+        self.cursor_cache = None;
+    }
     /// Attaches the platform instance to a winit window.
     ///
     /// This function configures imgui-rs in the following ways:
@@ -332,137 +983,1179 @@ impl WinitPlatform {
     /// * framebuffer scale (= DPI factor) is set
     /// * display size is set
     pub fn attach_window(&mut self, io: &mut Io, window: &Window, hidpi_mode: HiDpiMode) {
+        self.attach(io, window, hidpi_mode);
+    }
+    /// Attaches the platform instance to a winit window like
+    /// [`WinitPlatform::attach_window`], after checking that the window reports a usable size
+    /// and scale factor.
+    ///
+    /// Attaching a window too early (e.g. before it is shown on some platforms) gives imgui an
+    /// empty display or a meaningless DPI factor, which otherwise only shows up as rendering
+    /// problems later on. Nothing is changed if an error is returned.
+    pub fn attach_window_checked(
+        &mut self,
+        io: &mut Io,
+        window: &Window,
+        hidpi_mode: HiDpiMode,
+    ) -> Result<(), AttachWindowError> {
+        self.attach_checked(io, window, hidpi_mode)
+    }
+    fn attach_checked<W: PlatformWindow + ?Sized>(
+        &mut self,
+        io: &mut Io,
+        window: &W,
+        hidpi_mode: HiDpiMode,
+    ) -> Result<(), AttachWindowError> {
+        let scale_factor = window.scale_factor();
+        if !(scale_factor.is_finite() && scale_factor > 0.0) {
+            return Err(AttachWindowError::InvalidScaleFactor(scale_factor));
+        }
+        let size = window.inner_size();
+        if size.width == 0 || size.height == 0 {
+            return Err(AttachWindowError::ZeroSize(size));
+        }
+        self.attach(io, window, hidpi_mode);
+        Ok(())
+    }
+    fn attach<W: PlatformWindow + ?Sized>(
+        &mut self,
+        io: &mut Io,
+        window: &W,
+        hidpi_mode: HiDpiMode,
+    ) {
+        self.windowing_backend = window.windowing_backend();
+        // Wayland doesn't allow moving the cursor
+        io.backend_flags.set(
+            BackendFlags::HAS_SET_MOUSE_POS,
+            self.windowing_backend != WindowingBackend::Wayland,
+        );
         let (hidpi_mode, hidpi_factor) = hidpi_mode.apply(window.scale_factor());
         self.hidpi_mode = hidpi_mode;
-        self.hidpi_factor = hidpi_factor;
+        self.hidpi_factor = [hidpi_factor, hidpi_factor];
         io.display_framebuffer_scale = [hidpi_factor as f32, hidpi_factor as f32];
         let logical_size = window.inner_size().to_logical(hidpi_factor);
-        let logical_size = self.scale_size_from_winit(window, logical_size);
-        io.display_size = [logical_size.width as f32, logical_size.height as f32];
+        let logical_size = self.scale_size(window.scale_factor(), logical_size);
+        self.set_logical_display_size(io, logical_size);
     }
-    /// Returns the current DPI factor.
+    /// Switches to another DPI mode, e.g. to let users pick the UI scale with
+    /// [`HiDpiMode::Locked`].
     ///
-    /// The value might not be the same as the winit DPI factor (depends on the used DPI mode)
-    pub fn hidpi_factor(&self) -> f64 {
-        self.hidpi_factor
+    /// The DPI factor, `io.display_framebuffer_scale` and `io.display_size` are recomputed for
+    /// the window, like on a `ScaleFactorChanged` event. The window keeps its size in physical
+    /// pixels, so the current mouse position is scaled to stay under the cursor.
+    pub fn set_hidpi_mode(&mut self, io: &mut Io, window: &Window, hidpi_mode: HiDpiMode) {
+        self.set_hidpi_mode_with(io, window, hidpi_mode);
     }
-    /// Scales a logical size coming from winit using the current DPI mode.
+    fn set_hidpi_mode_with<W: PlatformWindow + ?Sized>(
+        &mut self,
+        io: &mut Io,
+        window: &W,
+        hidpi_mode: HiDpiMode,
+    ) {
+        let (hidpi_mode, hidpi_factor) = hidpi_mode.apply(window.scale_factor());
+        self.hidpi_mode = hidpi_mode;
+        self.set_hidpi_factor(io, [hidpi_factor, hidpi_factor]);
+        self.refresh_display_size_with(io, window);
+    }
+    /// Sets a handler for mouse buttons that imgui has no equivalent for.
     ///
-    /// This utility function is useful if you are using a DPI mode other than default, and want
-    /// your application to use the same logical coordinates as imgui-rs.
-    pub fn scale_size_from_winit(
-        &self,
-        window: &Window,
-        logical_size: LogicalSize<f64>,
-    ) -> LogicalSize<f64> {
-        match self.hidpi_mode {
-            ActiveHiDpiMode::Default => logical_size,
-            _ => logical_size
-                .to_physical::<f64>(window.scale_factor())
-                .to_logical(self.hidpi_factor),
+    /// imgui only knows about 5 mouse buttons, so events from other buttons (for example
+    /// `MouseButton::Other(5)` and above on mice with many side buttons) are not forwarded to
+    /// imgui. Instead, the handler is called with the button number and whether the button was
+    /// pressed, so applications can bind them to their own actions.
+    pub fn set_extra_mouse_button_handler(&mut self, handler: Option<Box<dyn FnMut(u16, bool)>>) {
+        self.extra_mouse_button_handler = handler.map(Handler);
+    }
+    /// Sets a handler for keys that imgui has no equivalent for.
+    ///
+    /// Keys such as `VirtualKeyCode::MyComputer`, `Calculator` or `Mail` (the "launch app"
+    /// keys of many keyboards) are not forwarded to imgui. Instead, the handler is called with
+    /// the key and whether it was pressed, so applications can bind them to their own actions.
+    /// Keys that winit can't identify at all (such as `Fn` on most laptops) don't reach the
+    /// handler either.
+    pub fn set_unmapped_key_handler(
+        &mut self,
+        handler: Option<Box<dyn FnMut(VirtualKeyCode, bool)>>,
+    ) {
+        self.unmapped_key_handler = handler.map(Handler);
+    }
+    /// Sets a handler called with the new DPI factor when it changes.
+    ///
+    /// The handler is called from `WindowEvent::ScaleFactorChanged`, once the DPI mode is
+    /// applied (so never with [`HiDpiMode::Locked`]). This is the time to rasterize the fonts
+    /// again, e.g. with `imgui::FontManager`. Since the imgui context is borrowed while the
+    /// event is handled, the handler should only record the new factor, and the fonts be
+    /// rebuilt before the next frame.
+    ///
+    /// Fonts rebuilt by `FontManager` keep their logical size, and so do the style sizes. An
+    /// application that enlarges its fonts by the DPI factor instead should also scale a copy
+    /// of its unscaled style with `imgui::Style::scale_all_sizes`.
+    pub fn set_scale_factor_handler(&mut self, handler: Option<Box<dyn FnMut(f64)>>) {
+        self.scale_factor_handler = handler.map(Handler);
+    }
+    /// Returns a snapshot of the DPI and cursor state.
+    ///
+    /// The snapshot can be applied again later with [`WinitPlatform::restore_state`].
+    pub fn save_state(&self) -> PlatformState {
+        let hidpi_mode = match self.hidpi_mode {
+            ActiveHiDpiMode::Default => HiDpiMode::Default,
+            ActiveHiDpiMode::Rounded => HiDpiMode::Rounded,
+            ActiveHiDpiMode::Snapped(step) => HiDpiMode::Snapped(step),
+            ActiveHiDpiMode::Locked => HiDpiMode::Locked(self.hidpi_factor[0]),
+        };
+        PlatformState {
+            hidpi_mode,
+            hidpi_factor: self.hidpi_factor[0],
+            cursor: self.cursor_cache.map(CursorState::from),
         }
     }
-    /// Scales a logical position coming from winit using the current DPI mode.
+    /// Restores a snapshot previously taken with [`WinitPlatform::save_state`].
     ///
-    /// This utility function is useful if you are using a DPI mode other than default, and want
-    /// your application to use the same logical coordinates as imgui-rs.
-    pub fn scale_pos_from_winit(
-        &self,
-        window: &Window,
-        logical_pos: LogicalPosition<f64>,
-    ) -> LogicalPosition<f64> {
-        match self.hidpi_mode {
-            ActiveHiDpiMode::Default => logical_pos,
-            _ => logical_pos
-                .to_physical::<f64>(window.scale_factor())
-                .to_logical(self.hidpi_factor),
+    /// The saved DPI mode is re-applied to the given window like in `attach_window` (so a
+    /// `Default` or `Rounded` mode picks up the window's current DPI factor), and the saved
+    /// cursor, if any, is applied to the window.
+    pub fn restore_state(&mut self, io: &mut Io, window: &Window, state: &PlatformState) {
+        self.attach_window(io, window, state.hidpi_mode);
+        self.cursor_cache = None;
+        self.applied_cursor = AppliedCursor::default();
+        if let Some(cursor) = state.cursor {
+            self.apply_cursor(cursor.into(), window);
         }
     }
-    /// Scales a logical position for winit using the current DPI mode.
+    /// Enables or disables absolute tablet mode.
     ///
-    /// This utility function is useful if you are using a DPI mode other than default, and want
-    /// your application to use the same logical coordinates as imgui-rs.
-    pub fn scale_pos_for_winit(
-        &self,
-        window: &Window,
-        logical_pos: LogicalPosition<f64>,
-    ) -> LogicalPosition<f64> {
-        match self.hidpi_mode {
-            ActiveHiDpiMode::Default => logical_pos,
-            _ => logical_pos
-                .to_physical::<f64>(self.hidpi_factor)
-                .to_logical(window.scale_factor()),
+    /// Graphics tablets often report the pen position through axis motion events
+    /// (`DeviceEvent::Motion` or `WindowEvent::AxisMotion`) instead of `CursorMoved`. In tablet
+    /// mode, the values of the axes chosen with [`WinitPlatform::set_tablet_axes`] are used as
+    /// the mouse position. Disabled by default.
+    ///
+    /// By default, the values must already be physical pixels relative to the window. Drivers
+    /// often report them in device units instead (e.g. 0 to 32767 on X11 with XInput2), which
+    /// can be mapped to the window with [`WinitPlatform::set_tablet_axis_ranges`].
+    pub fn set_tablet_mode(&mut self, enabled: bool) {
+        self.tablet.enabled = enabled;
+        self.tablet.pos = [None, None];
+    }
+    /// Returns true if absolute tablet mode is enabled.
+    pub fn tablet_mode(&self) -> bool {
+        self.tablet.enabled
+    }
+    /// Sets which axes correspond to the X and Y position in tablet mode.
+    ///
+    /// Defaults to axes 0 and 1.
+    pub fn set_tablet_axes(&mut self, x_axis: AxisId, y_axis: AxisId) {
+        self.tablet.axes = [x_axis, y_axis];
+        self.tablet.pos = [None, None];
+    }
+    /// Sets the range of the values of the X and Y axes in tablet mode, as
+    /// `[[x_min, x_max], [y_min, y_max]]`.
+    ///
+    /// The range of each axis is mapped to the width or height of the window, so that the
+    /// corners of the tablet match the corners of the window. With `None` (the default), the
+    /// values are used as physical pixels in the window.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a minimum isn't smaller than its maximum, or if a bound isn't finite.
+    pub fn set_tablet_axis_ranges(&mut self, ranges: Option<[[f64; 2]; 2]>) {
+        if let Some(ranges) = ranges {
+            assert!(
+                ranges
+                    .iter()
+                    .all(|&[min, max]| min.is_finite() && max.is_finite() && min < max),
+                "invalid tablet axis range"
+            );
         }
+        self.tablet.ranges = ranges;
     }
-    /// Handles a winit event.
+    /// Feeds text to imgui as if it had been typed.
     ///
-    /// This function performs the following actions (depends on the event):
+    /// This is meant for text which is synthesized by the application (e.g. by a virtual
+    /// keyboard or a script), and goes through the same filtering as the characters received
+    /// from winit. See also [`handle_received_text`], which doesn't need the platform.
+    pub fn input_text(&self, io: &mut Io, s: &str) {
+        handle_received_text(io, s);
+    }
+    /// Sets the logical insets of the area where imgui is displayed.
     ///
-    /// * window size / dpi factor changes are applied
-    /// * keyboard state is updated
-    /// * mouse state is updated
-    pub fn handle_event<T>(&mut self, io: &mut Io, window: &Window, event: &Event<T>) {
-        match *event {
-            Event::WindowEvent {
-                window_id,
-                ref event,
-            } if window_id == window.id() => {
-                self.handle_window_event(io, window, event);
-            }
-            // Track key release events outside our window. If we don't do this,
-            // we might never see the release event if some other window gets focus.
-            Event::DeviceEvent {
-                event:
-                    DeviceEvent::Key(KeyboardInput {
-                        state: ElementState::Released,
-                        virtual_keycode: Some(key),
-                        ..
-                    }),
-                ..
-            } => {
-                if let Some(key) = to_imgui_key(key) {
-                    io.add_key_event(key, false);
-                }
-            }
-            _ => (),
+    /// On mobile devices, the content must avoid notches and rounded corners. The insets are
+    /// subtracted from `io.display_size`, and mouse positions are offset so that imgui
+    /// coordinates start at the top left corner of the safe area. Note that imgui still renders
+    /// to the whole window, so the renderer must be set up to offset the drawn content (for
+    /// example by adjusting `DrawData::display_pos`).
+    ///
+    /// The display size is updated on the next resize; call
+    /// [`WinitPlatform::refresh_display_size`] to apply the insets right away.
+    pub fn set_safe_area_insets(&mut self, top: f64, bottom: f64, left: f64, right: f64) {
+        self.safe_area = SafeAreaInsets {
+            top,
+            bottom,
+            left,
+            right,
+        };
+        // Applied by the next resize, even if the window keeps its size
+        self.last_logical_size = None;
+    }
+    /// Sets the logical position, in the window, of the area where imgui is displayed.
+    ///
+    /// This is useful when imgui is rendered into a part of the window (e.g. a viewport panel
+    /// of a game editor): the offset is subtracted from the mouse positions, so that imgui's
+    /// (0, 0) matches the top left corner of the area. It applies on top of the safe area insets
+    /// (see [`WinitPlatform::set_safe_area_insets`]), and also to the positions given to
+    /// [`WinitPlatform::scale_pos_for_winit`].
+    ///
+    /// `io.display_size` is not affected and still covers the whole window, so set it to the
+    /// size of the area after calling [`WinitPlatform::prepare_frame`] if imgui windows should
+    /// stay inside of it. Defaults to `[0.0, 0.0]`.
+    pub fn set_mouse_offset(&mut self, offset: [f32; 2]) {
+        self.mouse_offset = offset;
+    }
+    /// Enables or disables key debouncing.
+    ///
+    /// On some X11 setups, key auto-repeat is reported as release and press pairs a few
+    /// milliseconds apart, which imgui sees as the key being released. With debouncing, a key
+    /// release is held back for the given duration, and dropped along with the following
+    /// press if the same key is pressed again in the meantime, so the key stays down.
+    ///
+    /// Held back releases are forwarded by [`WinitPlatform::handle_event`] or
+    /// [`WinitPlatform::prepare_frame`] once the duration has elapsed. Disabled by default.
+    pub fn set_key_debounce(&mut self, debounce: Option<Duration>) {
+        self.key_debounce = debounce;
+    }
+    /// Enables or disables layout-aware mapping of character keys.
+    ///
+    /// By default, letter, digit and punctuation keys are mapped to imgui keys by their
+    /// virtual key code, which follows the position of the key on a US layout on most
+    /// platforms. This suits positional bindings such as WASD movement, but breaks shortcuts
+    /// bound to characters on other layouts: on a German layout, the key labeled `Z` is
+    /// reported as `Y`. With logical keys, the press of a character key is held back until
+    /// winit reports the character it produced, and the key is forwarded as the imgui key of
+    /// that character, e.g. `Key::Z` for `z`, or `Key::Equal` for `+`, which shares its key
+    /// with `=` on US layouts. Keys which produce no known character keep their usual mapping.
+    ///
+    /// winit 0.27 has no logical key in its keyboard events, so the character is taken from the
+    /// `ReceivedCharacter` event which follows the key press. Disabled by default.
+    pub fn set_use_logical_keys(&mut self, use_logical_keys: bool) {
+        self.use_logical_keys = use_logical_keys;
+    }
+    /// Enables or disables forwarding only text to imgui, e.g. while the application handles a
+    /// password field itself.
+    ///
+    /// In text only mode, received characters are forwarded as usual, but presses of keys other
+    /// than modifiers are not, so they don't trigger imgui navigation or shortcuts. Releases of
+    /// keys imgui saw being pressed are still forwarded so they don't get stuck. Disabled by
+    /// default.
+    pub fn set_text_only_mode(&mut self, text_only_mode: bool) {
+        self.text_only_mode = text_only_mode;
+        if text_only_mode {
+            self.pending_logical_key = None;
         }
     }
-    fn handle_window_event(&mut self, io: &mut Io, window: &Window, event: &WindowEvent) {
-        match *event {
-            WindowEvent::Resized(physical_size) => {
-                let logical_size = physical_size.to_logical(window.scale_factor());
-                let logical_size = self.scale_size_from_winit(window, logical_size);
-                io.display_size = [logical_size.width as f32, logical_size.height as f32];
-            }
-            WindowEvent::ScaleFactorChanged { scale_factor, .. } => {
-                let hidpi_factor = match self.hidpi_mode {
-                    ActiveHiDpiMode::Default => scale_factor,
-                    ActiveHiDpiMode::Rounded => scale_factor.round(),
-                    _ => return,
-                };
-                // Mouse position needs to be changed while we still have both the old and the new
-                // values
-                if io.mouse_pos[0].is_finite() && io.mouse_pos[1].is_finite() {
-                    io.mouse_pos = [
-                        io.mouse_pos[0] * (hidpi_factor / self.hidpi_factor) as f32,
-                        io.mouse_pos[1] * (hidpi_factor / self.hidpi_factor) as f32,
-                    ];
-                }
-                self.hidpi_factor = hidpi_factor;
-                io.display_framebuffer_scale = [hidpi_factor as f32, hidpi_factor as f32];
+    /// Returns true if only text is forwarded to imgui, see
+    /// [`WinitPlatform::set_text_only_mode`].
+    pub fn text_only_mode(&self) -> bool {
+        self.text_only_mode
+    }
+    /// Sets a key which, while held down, stops mouse input from being forwarded to imgui.
+    ///
+    /// This lets the application handle the mouse even over imgui windows, e.g. to orbit a 3D
+    /// view while Space is held. Mouse moves, wheel events and button presses are dropped, but
+    /// releases of buttons that imgui saw being pressed are still forwarded so they don't get
+    /// stuck. The key itself is forwarded to imgui as usual. Disabled by default.
+    pub fn set_mouse_passthrough_key(&mut self, key: Option<VirtualKeyCode>) {
+        self.mouse_passthrough_key = key;
+    }
+    /// Enables/disables ignoring mouse and keyboard input while the window is unfocused.
+    ///
+    /// This is useful when events from several windows are forwarded to the same imgui context,
+    /// so background windows don't affect the UI. Other events, like resizes and DPI changes,
+    /// are still handled. The focus is tracked from `WindowEvent::Focused`, and the window is
+    /// assumed to be focused until told otherwise. Disabled by default.
+    pub fn set_require_focus(&mut self, require_focus: bool) {
+        self.require_focus = require_focus;
+    }
+    /// Tells the platform whether the application grabbed the cursor, e.g. for camera control.
+    ///
+    /// While the cursor is grabbed, [`WinitPlatform::prepare_render`] doesn't change the cursor
+    /// icon or visibility, so it doesn't fight with the application. Once released, the next
+    /// call applies imgui's cursor again, since the application may have changed it meanwhile.
+    pub fn set_cursor_grabbed(&mut self, grabbed: bool) {
+        if self.cursor_grabbed && !grabbed {
+            self.cursor_cache = None;
+            self.applied_cursor = AppliedCursor::default();
+        }
+        self.cursor_grabbed = grabbed;
+    }
+    /// Sets an icon for [`WinitPlatform::prepare_render`] to apply instead of the one requested
+    /// by imgui, e.g. to match the theme of the application, or `None` to use imgui's icon.
+    ///
+    /// The visibility of the cursor still follows imgui, so the icon is only applied when the
+    /// OS cursor is shown. winit 0.27 only supports the system icons of [`CursorIcon`]; custom
+    /// cursor images require winit 0.29.
+    ///
+    /// [`CursorIcon`]: winit::window::CursorIcon
+    pub fn set_cursor_icon_override(&mut self, icon: Option<MouseCursor>) {
+        self.cursor_icon_override = icon;
+        self.cursor_cache = None;
+    }
+    /// Returns the icon applied instead of the one requested by imgui, if any.
+    pub fn cursor_icon_override(&self) -> Option<MouseCursor> {
+        self.cursor_icon_override
+    }
+    /// Inverts the horizontal and/or vertical mouse wheel direction.
+    ///
+    /// Dear ImGui has no such option, and platforms disagree on the expected direction (e.g.
+    /// with "natural scrolling" on trackpads), so this can be used to match user expectations.
+    /// Disabled by default.
+    pub fn set_invert_scroll(&mut self, invert_x: bool, invert_y: bool) {
+        self.invert_scroll = [invert_x, invert_y];
+    }
+    /// Multiplies the horizontal and vertical mouse wheel deltas, e.g. to scroll faster.
+    ///
+    /// This applies to both line deltas and pixel deltas (once converted to lines, see
+    /// [`WinitPlatform::set_scroll_mode`]). Defaults to `[1.0, 1.0]`.
+    pub fn set_wheel_scale(&mut self, scale: [f32; 2]) {
+        self.wheel_scale = scale;
+    }
+    /// Sets how mouse wheel events with a pixel delta are converted to lines.
+    ///
+    /// Defaults to [`ScrollMode::Lines`] with 50 pixels per line.
+    pub fn set_scroll_mode(&mut self, scroll_mode: ScrollMode) {
+        self.scroll_mode = scroll_mode;
+        self.scroll_accumulator = [0.0, 0.0];
+    }
+    /// Returns how mouse wheel events with a pixel delta are converted to lines.
+    pub fn scroll_mode(&self) -> ScrollMode {
+        self.scroll_mode
+    }
+    /// Handles a magnification gesture, such as a pinch on a macOS touchpad.
+    ///
+    /// The delta is the relative change of the zoom level, positive when zooming in. It is
+    /// accumulated until [`WinitPlatform::take_magnify_delta`] is called. winit 0.27 doesn't
+    /// report these gestures, so until the upgrade to winit 0.28 (which adds
+    /// `WindowEvent::TouchpadMagnify`) they have to be read from the platform directly.
+    ///
+    /// Non-finite deltas are ignored.
+    pub fn handle_magnify(&mut self, io: &mut Io, delta: f64) {
+        if !delta.is_finite() {
+            trace!("ignored invalid magnification by {}", delta);
+            return;
+        }
+        if !self.accepts_input() {
+            trace!(
+                "ignored magnification by {}, imgui doesn't accept it",
+                delta
+            );
+            return;
+        }
+        trace!("magnified by {}", delta);
+        self.magnify_delta += delta;
+        if self.magnify_as_wheel && io.font_allow_user_scaling {
+            // imgui scales the window font by 0.1 per wheel line
+            io.add_key_event(Key::ModCtrl, true);
+            io.add_mouse_wheel_event([0.0, (delta * 10.0) as f32]);
+            io.add_key_event(Key::ModCtrl, self.ctrl_held);
+        }
+        if self.pinch_scales_fonts {
+            let [min, max] = self.pinch_font_scale_range;
+            io.font_global_scale = (io.font_global_scale * (1.0 + delta as f32)).clamp(min, max);
+        }
+    }
+    /// Returns the magnification accumulated since the last call, and resets it.
+    pub fn take_magnify_delta(&mut self) -> f64 {
+        std::mem::take(&mut self.magnify_delta)
+    }
+    /// Handles a rotation gesture, such as a two-finger rotation on a macOS touchpad.
+    ///
+    /// The delta is in degrees, positive when rotating counterclockwise. It is accumulated
+    /// until [`WinitPlatform::take_rotate_delta`] is called. Like magnification gestures (see
+    /// [`WinitPlatform::handle_magnify`]), winit 0.27 doesn't report them, so until the upgrade
+    /// to winit 0.28 (which adds `WindowEvent::TouchpadRotate`) they have to be read from the
+    /// platform directly.
+    pub fn handle_rotate(&mut self, delta: f32) {
+        if !self.accepts_input() {
+            trace!("ignored rotation by {}, imgui doesn't accept it", delta);
+            return;
+        }
+        trace!("rotated by {}", delta);
+        self.rotate_delta += delta;
+    }
+    /// Returns the rotation in degrees accumulated since the last call, and resets it.
+    ///
+    /// Positive values are counterclockwise.
+    pub fn take_rotate_delta(&mut self) -> f32 {
+        std::mem::take(&mut self.rotate_delta)
+    }
+    /// Sets whether magnification gestures are also fed to imgui as Ctrl+mouse wheel.
+    ///
+    /// When `Io::font_allow_user_scaling` is enabled, this lets the user zoom the font of the
+    /// hovered window with a pinch. Disabled by default.
+    pub fn set_magnify_as_wheel(&mut self, enabled: bool) {
+        self.magnify_as_wheel = enabled;
+    }
+    /// Sets whether magnification gestures scale `Io::font_global_scale`, zooming all the text
+    /// of the UI with a pinch.
+    ///
+    /// Each delta multiplies the scale by `1 + delta`, and the scale is kept within the range
+    /// set with [`WinitPlatform::set_pinch_font_scale_range`]. Disabled by default.
+    pub fn set_pinch_scales_fonts(&mut self, enabled: bool) {
+        self.pinch_scales_fonts = enabled;
+    }
+    /// Sets the bounds of `Io::font_global_scale` when it is scaled by magnification gestures
+    /// (see [`WinitPlatform::set_pinch_scales_fonts`]). Defaults to 0.5 to 3.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `min` isn't positive or is greater than `max`, or if either bound is NaN.
+    pub fn set_pinch_font_scale_range(&mut self, min: f32, max: f32) {
+        assert!(min > 0.0 && min <= max, "invalid font scale range");
+        self.pinch_font_scale_range = [min, max];
+    }
+    /// Converts a pixel delta to lines, returning `None` if there is nothing to scroll yet
+    fn pixel_delta_to_lines(&mut self, delta: [f64; 2]) -> Option<(f32, f32)> {
+        let lines = match self.scroll_mode {
+            ScrollMode::SignOnly => delta.map(|d| match d.partial_cmp(&0.0) {
+                Some(Ordering::Greater) => 1.0,
+                Some(Ordering::Less) => -1.0,
+                _ => 0.0,
+            }),
+            ScrollMode::Lines { pixels_per_line } => delta.map(|d| d / pixels_per_line),
+            ScrollMode::Accumulate { threshold } => {
+                let mut lines = [0.0; 2];
+                for axis in 0..2 {
+                    let sum = self.scroll_accumulator[axis] + delta[axis];
+                    lines[axis] = (sum / threshold).trunc();
+                    self.scroll_accumulator[axis] = sum - lines[axis] * threshold;
+                }
+                if lines == [0.0, 0.0] {
+                    return None;
+                }
+                lines
+            }
+        };
+        Some((lines[0] as f32, lines[1] as f32))
+    }
+    /// Sets the smallest logical size `io.display_size` is set to.
+    ///
+    /// Tiny or empty display sizes make imgui produce degenerate layouts, so the display size
+    /// is clamped to this minimum. When the window is minimized (resized to 0x0), the display
+    /// size isn't updated at all and keeps the last size. Defaults to 1x1.
+    pub fn set_min_display_size(&mut self, width: f32, height: f32) {
+        self.min_display_size = [width, height];
+        self.last_logical_size = None;
+    }
+    /// Sets the longest frame duration given to imgui by [`WinitPlatform::update_delta_time`].
+    ///
+    /// Longer frames, e.g. after the application was paused in a debugger, are shortened to
+    /// this duration so that imgui's animations don't jump. Defaults to 100ms.
+    pub fn set_max_delta(&mut self, max_delta: Duration) {
+        self.max_delta = max_delta;
+    }
+    /// Updates `io.delta_time` with the time elapsed since the previous call, clamped to the
+    /// maximum set with [`WinitPlatform::set_max_delta`].
+    ///
+    /// Call this once per frame, before [`WinitPlatform::prepare_frame`]. The first call
+    /// leaves `io.delta_time` unchanged.
+    pub fn update_delta_time(&mut self, io: &mut Io) {
+        self.update_delta_time_at(io, Instant::now());
+    }
+    fn update_delta_time_at(&mut self, io: &mut Io, now: Instant) {
+        if let Some(last_frame) = self.last_frame {
+            // A zero duration is turned into a tiny positive delta by imgui-rs
+            let delta = now
+                .saturating_duration_since(last_frame)
+                .min(self.max_delta);
+            io.update_delta_time(delta);
+        }
+        self.last_frame = Some(now);
+    }
+    /// Returns when the next frame should start to run at `target_hz` frames per second, e.g.
+    /// for `ControlFlow::WaitUntil` to cap the frame rate while the application is idle.
+    ///
+    /// The deadline is computed from the last call to [`WinitPlatform::update_delta_time`].
+    /// Returns now if no frame was started yet, if the deadline has already passed, or if
+    /// `target_hz` isn't positive or is so small that the deadline can't be represented.
+    pub fn next_frame_deadline(&self, target_hz: f32) -> Instant {
+        self.next_frame_deadline_at(target_hz, Instant::now())
+    }
+    fn next_frame_deadline_at(&self, target_hz: f32, now: Instant) -> Instant {
+        match self.last_frame {
+            Some(last_frame) if target_hz > 0.0 => Duration::try_from_secs_f32(1.0 / target_hz)
+                .ok()
+                .and_then(|period| last_frame.checked_add(period))
+                .map_or(now, |deadline| deadline.max(now)),
+            _ => now,
+        }
+    }
+    /// Records that a frame was drawn, to measure the actual frame rate.
+    ///
+    /// Call this when handling `Event::RedrawRequested`. winit coalesces redraw requests, so
+    /// with `ControlFlow::Poll` fewer frames may be drawn than requested: comparing
+    /// [`WinitPlatform::frames_since`] to the expected number of frames tells whether frames
+    /// were skipped.
+    pub fn mark_redraw(&mut self) {
+        self.mark_redraw_at(Instant::now());
+    }
+    fn mark_redraw_at(&mut self, now: Instant) {
+        self.redraw_count += 1;
+        self.redraw_times.push_back(now);
+        while let Some(&oldest) = self.redraw_times.front() {
+            if now.saturating_duration_since(oldest) <= REDRAW_HISTORY {
+                break;
+            }
+            self.redraw_times.pop_front();
+        }
+    }
+    /// Returns the number of frames recorded with [`WinitPlatform::mark_redraw`].
+    pub fn frame_count(&self) -> u64 {
+        self.redraw_count
+    }
+    /// Returns the number of frames recorded with [`WinitPlatform::mark_redraw`] after
+    /// *instant*.
+    ///
+    /// Only the frames of the last second are remembered, so older instants give the number
+    /// of frames drawn during the last second.
+    pub fn frames_since(&self, instant: Instant) -> usize {
+        self.redraw_times
+            .iter()
+            .filter(|&&time| time > instant)
+            .count()
+    }
+    /// Returns the frame rate measured over the last second from the frames recorded with
+    /// [`WinitPlatform::mark_redraw`], or 0 if fewer than two frames were drawn.
+    pub fn last_fps(&self) -> f32 {
+        match (self.redraw_times.front(), self.redraw_times.back()) {
+            (Some(&first), Some(&last)) if last > first => {
+                (self.redraw_times.len() - 1) as f32 / (last - first).as_secs_f32()
+            }
+            _ => 0.0,
+        }
+    }
+    /// Returns the mouse buttons that are currently held down over the window.
+    ///
+    /// This is tracked from the `MouseInput` events forwarded to imgui, independently of imgui's
+    /// own state, and cleared when the window loses focus.
+    pub fn pressed_mouse_buttons(&self) -> MouseButtons {
+        self.pressed_mouse_buttons
+    }
+    /// Returns true if the mouse button is currently held down over the window, see
+    /// [`WinitPlatform::pressed_mouse_buttons`].
+    pub fn is_mouse_button_down(&self, button: imgui::MouseButton) -> bool {
+        self.pressed_mouse_buttons.contains(button)
+    }
+    /// Returns the last position of the window, as reported by `WindowEvent::Moved`.
+    ///
+    /// This is the position of the top left corner of the window on the desktop, in physical
+    /// pixels, and can be used to convert imgui coordinates to screen coordinates or to notice
+    /// the window moving to another monitor. Returns `None` until the window was moved, which
+    /// is never reported on some platforms (e.g. Wayland).
+    pub fn window_position(&self) -> Option<PhysicalPosition<i32>> {
+        self.window_position
+    }
+    /// Returns true if imgui wants the keyboard (e.g. a text input is focused) while the window
+    /// has the focus, as reported by `WindowEvent::Focused`.
+    ///
+    /// This is advisory: nothing is grabbed by the platform. Applications can use it to grab the
+    /// keyboard themselves (e.g. with a platform specific API, or by disabling their own global
+    /// hotkeys) while the user is typing into imgui, and release it once this returns false.
+    pub fn wants_keyboard_grab(&self, io: &Io) -> bool {
+        self.focused && io.want_capture_keyboard
+    }
+    /// Returns true if the key set with [`WinitPlatform::set_mouse_passthrough_key`] is held
+    fn mouse_passthrough(&self) -> bool {
+        match self.mouse_passthrough_key {
+            Some(key) => self.pressed_keys.contains(&key),
+            None => false,
+        }
+    }
+    /// Returns true if the mouse event shouldn't be forwarded to imgui, see
+    /// [`WinitPlatform::set_mouse_passthrough_key`]
+    fn is_passed_through(&self, event: &WindowEvent) -> bool {
+        if !self.mouse_passthrough() {
+            return false;
+        }
+        match *event {
+            WindowEvent::MouseInput {
+                state: ElementState::Released,
+                button,
+                ..
+            } => match to_imgui_mouse_button(button) {
+                Some(button) => !self.pressed_mouse_buttons.contains(button),
+                None => true,
+            },
+            WindowEvent::MouseInput { .. }
+            | WindowEvent::CursorMoved { .. }
+            | WindowEvent::MouseWheel { .. } => true,
+            _ => false,
+        }
+    }
+    /// Returns true if input events should be forwarded to imgui, see
+    /// [`WinitPlatform::set_require_focus`]
+    fn accepts_input(&self) -> bool {
+        self.focused || !self.require_focus
+    }
+    /// Returns true if the application is suspended.
+    ///
+    /// This is set when winit sends `Event::Suspended` (e.g. when an Android app goes to the
+    /// background), and cleared on the following `Event::Resumed`. While suspended, the DPI
+    /// factor is reset to 1, since the window may come back on another display.
+    pub fn is_suspended(&self) -> bool {
+        self.suspended.is_some()
+    }
+    /// Returns the current DPI factor.
+    ///
+    /// The value might not be the same as the winit DPI factor (depends on the used DPI mode).
+    /// With per-axis factors (see [`WinitPlatform::set_hidpi_factor_xy`]), this is the
+    /// horizontal factor.
+    pub fn hidpi_factor(&self) -> f64 {
+        self.hidpi_factor[0]
+    }
+    /// Returns the current horizontal and vertical DPI factors.
+    ///
+    /// They are the same, unless set with [`WinitPlatform::set_hidpi_factor_xy`].
+    pub fn hidpi_factor_xy(&self) -> [f64; 2] {
+        self.hidpi_factor
+    }
+    /// Locks the DPI factor to different horizontal and vertical values, e.g. for rotated
+    /// displays or projectors with non-square pixels.
+    ///
+    /// winit only reports a single factor, so the DPI mode becomes [`HiDpiMode::Locked`]: the
+    /// factors are kept until the DPI mode is changed. `io.display_framebuffer_scale` and the
+    /// positions and sizes from winit are scaled on each axis, and `io.display_size` is
+    /// recomputed for the window. [`WinitPlatform::save_state`] only records the horizontal
+    /// factor.
+    pub fn set_hidpi_factor_xy(&mut self, io: &mut Io, window: &Window, hidpi_factor: [f64; 2]) {
+        self.set_hidpi_factor_xy_with(io, window, hidpi_factor);
+    }
+    fn set_hidpi_factor_xy_with<W: PlatformWindow + ?Sized>(
+        &mut self,
+        io: &mut Io,
+        window: &W,
+        hidpi_factor: [f64; 2],
+    ) {
+        self.hidpi_mode = ActiveHiDpiMode::Locked;
+        self.set_hidpi_factor(io, hidpi_factor);
+        self.refresh_display_size_with(io, window);
+    }
+    /// Recomputes `io.display_size` from the window's current inner size.
+    ///
+    /// The display size is normally kept up to date by [`WinitPlatform::handle_event`], but
+    /// after resizing the window programmatically (e.g. with `Window::set_inner_size`), winit
+    /// might not deliver the `Resized` event before the next frame. Calling this function
+    /// right after the resize avoids rendering a frame with a stale display size.
+    pub fn refresh_display_size(&mut self, io: &mut Io, window: &Window) {
+        self.refresh_display_size_with(io, window);
+    }
+    fn refresh_display_size_with<W: PlatformWindow + ?Sized>(&mut self, io: &mut Io, window: &W) {
+        self.set_physical_display_size(io, window.scale_factor(), window.inner_size());
+    }
+    /// Sets `io.display_size` as if the window had the given logical size.
+    ///
+    /// This is meant for rendering to a texture of a fixed size, e.g. for thumbnails or tests,
+    /// and doesn't require a window. Like for a real window, the safe area insets and the
+    /// minimum display size are applied. The next `Resized` event overrides the size.
+    pub fn set_display_size(&mut self, io: &mut Io, size: [f32; 2]) {
+        self.set_logical_display_size(io, LogicalSize::new(size[0] as f64, size[1] as f64));
+    }
+    /// Sets the DPI factor used by the platform and `io.display_framebuffer_scale`, regardless
+    /// of the window and the DPI mode.
+    ///
+    /// Like [`WinitPlatform::set_display_size`], this is meant for rendering without a window.
+    /// The current mouse position is scaled accordingly. The next `ScaleFactorChanged` event
+    /// overrides the factor, unless the DPI mode is [`HiDpiMode::Locked`].
+    pub fn set_scale_factor(&mut self, io: &mut Io, scale_factor: f64) {
+        self.set_hidpi_factor(io, [scale_factor, scale_factor]);
+    }
+    fn set_hidpi_factor(&mut self, io: &mut Io, hidpi_factor: [f64; 2]) {
+        // Mouse position needs to be changed while we still have both the old and the new values.
+        // The cursor doesn't move in physical pixels, so its position from the origin of the
+        // window (before the safe area and mouse offset) scales with old / new.
+        if io.mouse_pos[0].is_finite() && io.mouse_pos[1].is_finite() {
+            let offset = [
+                (self.safe_area.left + self.mouse_offset[0] as f64) as f32,
+                (self.safe_area.top + self.mouse_offset[1] as f64) as f32,
+            ];
+            let scale = [
+                (self.hidpi_factor[0] / hidpi_factor[0]) as f32,
+                (self.hidpi_factor[1] / hidpi_factor[1]) as f32,
+            ];
+            io.mouse_pos = [
+                (io.mouse_pos[0] + offset[0]) * scale[0] - offset[0],
+                (io.mouse_pos[1] + offset[1]) * scale[1] - offset[1],
+            ];
+        }
+        self.hidpi_factor = hidpi_factor;
+        io.display_framebuffer_scale = hidpi_factor.map(|f| f as f32);
+    }
+    /// Converts logical coordinates from winit to the logical coordinates of imgui, according
+    /// to the DPI mode
+    fn scale_from_winit(&self, window_scale_factor: f64, value: [f64; 2]) -> [f64; 2] {
+        match self.hidpi_mode {
+            ActiveHiDpiMode::Default => value,
+            _ => [
+                value[0] * window_scale_factor / self.hidpi_factor[0],
+                value[1] * window_scale_factor / self.hidpi_factor[1],
+            ],
+        }
+    }
+    fn set_physical_display_size(
+        &mut self,
+        io: &mut Io,
+        window_scale_factor: f64,
+        physical_size: PhysicalSize<u32>,
+    ) {
+        let logical_size = physical_size.to_logical(window_scale_factor);
+        let logical_size = self.scale_size(window_scale_factor, logical_size);
+        // Some window managers repeat the same size while resizing
+        if self.last_logical_size != Some(logical_size) {
+            self.set_logical_display_size(io, logical_size);
+        }
+    }
+    fn set_logical_display_size(&mut self, io: &mut Io, logical_size: LogicalSize<f64>) {
+        // Minimized windows are reported with a size of 0x0, keep the last size instead
+        if logical_size.width <= 0.0 || logical_size.height <= 0.0 {
+            return;
+        }
+        self.last_logical_size = Some(logical_size);
+        #[cfg(test)]
+        {
+            self.display_size_updates += 1;
+        }
+        let insets = self.safe_area;
+        let width = (logical_size.width - insets.left - insets.right) as f32;
+        let height = (logical_size.height - insets.top - insets.bottom) as f32;
+        io.display_size = [
+            width.max(self.min_display_size[0]),
+            height.max(self.min_display_size[1]),
+        ];
+    }
+    /// Returns the number of times `io.display_size` was set by the platform, to check that
+    /// identical resizes are skipped.
+    #[cfg(test)]
+    fn display_size_update_count(&self) -> u64 {
+        self.display_size_updates
+    }
+    /// Scales a logical size coming from winit using the current DPI mode.
+    ///
+    /// This utility function is useful if you are using a DPI mode other than default, and want
+    /// your application to use the same logical coordinates as imgui-rs.
+    pub fn scale_size_from_winit(
+        &self,
+        window: &Window,
+        logical_size: LogicalSize<f64>,
+    ) -> LogicalSize<f64> {
+        self.scale_size(window.scale_factor(), logical_size)
+    }
+    fn scale_size(
+        &self,
+        window_scale_factor: f64,
+        logical_size: LogicalSize<f64>,
+    ) -> LogicalSize<f64> {
+        let [width, height] = self.scale_from_winit(
+            window_scale_factor,
+            [logical_size.width, logical_size.height],
+        );
+        LogicalSize::new(width, height)
+    }
+    /// Scales a logical position coming from winit using the current DPI mode.
+    ///
+    /// This utility function is useful if you are using a DPI mode other than default, and want
+    /// your application to use the same logical coordinates as imgui-rs.
+    pub fn scale_pos_from_winit(
+        &self,
+        window: &Window,
+        logical_pos: LogicalPosition<f64>,
+    ) -> LogicalPosition<f64> {
+        self.scale_pos(window.scale_factor(), logical_pos)
+    }
+    fn scale_pos(
+        &self,
+        window_scale_factor: f64,
+        logical_pos: LogicalPosition<f64>,
+    ) -> LogicalPosition<f64> {
+        let [x, y] = self.scale_from_winit(window_scale_factor, [logical_pos.x, logical_pos.y]);
+        LogicalPosition::new(
+            x - self.safe_area.left - self.mouse_offset[0] as f64,
+            y - self.safe_area.top - self.mouse_offset[1] as f64,
+        )
+    }
+    /// Scales a logical position for winit using the current DPI mode.
+    ///
+    /// This utility function is useful if you are using a DPI mode other than default, and want
+    /// your application to use the same logical coordinates as imgui-rs.
+    pub fn scale_pos_for_winit(
+        &self,
+        window: &Window,
+        logical_pos: LogicalPosition<f64>,
+    ) -> LogicalPosition<f64> {
+        self.scale_pos_for_winit_with(window.scale_factor(), logical_pos)
+    }
+    fn scale_pos_for_winit_with(
+        &self,
+        window_scale_factor: f64,
+        logical_pos: LogicalPosition<f64>,
+    ) -> LogicalPosition<f64> {
+        let logical_pos = LogicalPosition::new(
+            logical_pos.x + self.safe_area.left + self.mouse_offset[0] as f64,
+            logical_pos.y + self.safe_area.top + self.mouse_offset[1] as f64,
+        );
+        match self.hidpi_mode {
+            ActiveHiDpiMode::Default => logical_pos,
+            _ => LogicalPosition::new(
+                logical_pos.x * self.hidpi_factor[0] / window_scale_factor,
+                logical_pos.y * self.hidpi_factor[1] / window_scale_factor,
+            ),
+        }
+    }
+    /// Handles a winit event.
+    ///
+    /// This function performs the following actions (depends on the event):
+    ///
+    /// * window size / dpi factor changes are applied
+    /// * the window position is tracked (see [`WinitPlatform::window_position`])
+    /// * keyboard state is updated
+    /// * mouse state is updated
+    /// * in tablet mode, axis motion updates the mouse position (see
+    ///   [`WinitPlatform::set_tablet_mode`])
+    /// * on suspend, the cursor state is forgotten; on resume, the window is attached again
+    ///   with the same DPI mode (see [`WinitPlatform::is_suspended`])
+    /// * when the event loop is destroyed, the OS cursor is restored (see
+    ///   [`WinitPlatform::shutdown`])
+    pub fn handle_event<T>(&mut self, io: &mut Io, window: &Window, event: &Event<T>) {
+        self.handle_event_with(io, window, event);
+    }
+    /// Handles a winit event like [`WinitPlatform::handle_event`], if `filter` returns true for
+    /// it.
+    ///
+    /// This lets an application keep the events imgui must not see (e.g. some of its own
+    /// `Event::UserEvent`s, or input grabbed by the game) in a single predicate, instead of
+    /// checking them before each call. Events rejected by the filter are ignored entirely, as if
+    /// they were never received.
+    ///
+    /// Returns true if the event was handled.
+    ///
+    /// ```no_run
+    /// # use imgui_winit_support::WinitPlatform;
+    /// # use winit::event::{Event, WindowEvent};
+    /// # use winit::event_loop::EventLoop;
+    /// # let event_loop = EventLoop::new();
+    /// # let window = winit::window::Window::new(&event_loop).unwrap();
+    /// # let mut imgui = imgui::Context::create();
+    /// # let mut platform = WinitPlatform::init(&mut imgui);
+    /// event_loop.run(move |event, _, _| {
+    ///     // Keeps the mouse for the application
+    ///     platform.handle_event_filtered(imgui.io_mut(), &window, &event, |event| {
+    ///         !matches!(
+    ///             event,
+    ///             Event::WindowEvent {
+    ///                 event: WindowEvent::MouseInput { .. },
+    ///                 ..
+    ///             }
+    ///         )
+    ///     });
+    /// })
+    /// ```
+    pub fn handle_event_filtered<T, F>(
+        &mut self,
+        io: &mut Io,
+        window: &Window,
+        event: &Event<T>,
+        filter: F,
+    ) -> bool
+    where
+        F: FnMut(&Event<T>) -> bool,
+    {
+        self.handle_event_filtered_with(io, window, event, filter)
+    }
+    /// Handles a winit event like [`WinitPlatform::handle_event`], and suggests how the event loop
+    /// should react to it.
+    ///
+    /// The returned hint is advisory, see [`EventHint`]. It can be translated directly into a
+    /// `ControlFlow`:
+    ///
+    /// ```no_run
+    /// # use imgui_winit_support::{EventHint, WinitPlatform};
+    /// # use winit::event_loop::{ControlFlow, EventLoop};
+    /// # let event_loop = EventLoop::new();
+    /// # let window = winit::window::Window::new(&event_loop).unwrap();
+    /// # let mut imgui = imgui::Context::create();
+    /// # let mut platform = WinitPlatform::init(&mut imgui);
+    /// event_loop.run(move |event, _, control_flow| {
+    ///     *control_flow = ControlFlow::Wait;
+    ///     match platform.handle_event_with_result(imgui.io_mut(), &window, &event) {
+    ///         EventHint::Redraw => window.request_redraw(),
+    ///         EventHint::Exit => *control_flow = ControlFlow::Exit,
+    ///         EventHint::Nothing => (),
+    ///     }
+    /// })
+    /// ```
+    pub fn handle_event_with_result<T>(
+        &mut self,
+        io: &mut Io,
+        window: &Window,
+        event: &Event<T>,
+    ) -> EventHint {
+        self.handle_event_with(io, window, event);
+        EventHint::from_event(window.id(), event)
+    }
+    fn handle_event_filtered_with<T, W, F>(
+        &mut self,
+        io: &mut Io,
+        window: &W,
+        event: &Event<T>,
+        mut filter: F,
+    ) -> bool
+    where
+        W: PlatformWindow + ?Sized,
+        F: FnMut(&Event<T>) -> bool,
+    {
+        if !filter(event) {
+            trace!("ignored event, rejected by the filter");
+            return false;
+        }
+        self.handle_event_with(io, window, event);
+        true
+    }
+    fn handle_event_with<T, W: PlatformWindow + ?Sized>(
+        &mut self,
+        io: &mut Io,
+        window: &W,
+        event: &Event<T>,
+    ) {
+        self.flush_key_releases(io, Instant::now());
+        if !matches!(
+            event,
+            Event::WindowEvent {
+                event: WindowEvent::ReceivedCharacter(_),
+                ..
+            }
+        ) {
+            // The key didn't produce a character, so it keeps its usual mapping
+            self.flush_logical_key(io);
+        }
+        match *event {
+            Event::WindowEvent {
+                window_id,
+                ref event,
+            } if window_id == window.id() => {
+                self.handle_window_event(io, window, event);
+            }
+            // Track key release events outside our window. If we don't do this,
+            // we might never see the release event if some other window gets focus.
+            // Releases of keys that imgui never saw pressed are ignored, as they would only
+            // confuse navigation.
+            Event::DeviceEvent {
+                event:
+                    DeviceEvent::Key(KeyboardInput {
+                        state: ElementState::Released,
+                        virtual_keycode: Some(key),
+                        ..
+                    }),
+                ..
+            } if self.pressed_keys.contains(&key) => {
+                self.handle_key_event(io, key, false);
+            }
+            Event::DeviceEvent {
+                event: DeviceEvent::Motion { axis, value },
+                ..
+            } if self.accepts_input() => {
+                self.handle_tablet_axis(io, window, axis, value);
+            }
+            // On Android, the window surface is destroyed when the app is suspended, and the
+            // window may come back with a different size and DPI factor.
+            Event::Suspended if self.suspended.is_none() => {
+                // Saved before the DPI factor is reset, as a locked mode keeps its factor
+                self.suspended = Some(self.save_state().hidpi_mode);
+                self.hidpi_factor = [1.0, 1.0];
+                self.last_logical_size = None;
+                self.cursor_cache = None;
+                self.applied_cursor = AppliedCursor::default();
+            }
+            Event::Resumed => {
+                if let Some(hidpi_mode) = self.suspended.take() {
+                    self.attach(io, window, hidpi_mode);
+                }
+            }
+            Event::LoopDestroyed => self.restore_cursor(window),
+            _ => (),
+        }
+    }
+    fn handle_tablet_axis<W: PlatformWindow + ?Sized>(
+        &mut self,
+        io: &mut Io,
+        window: &W,
+        axis: AxisId,
+        value: f64,
+    ) {
+        if !self.tablet.enabled || self.mouse_passthrough() {
+            return;
+        }
+        match self.tablet.axes.iter().position(|&a| a == axis) {
+            Some(index) => self.tablet.pos[index] = Some(value),
+            None => return,
+        }
+        // Wait until both coordinates are known
+        if let [Some(mut x), Some(mut y)] = self.tablet.pos {
+            if let Some([[x_min, x_max], [y_min, y_max]]) = self.tablet.ranges {
+                let size = window.inner_size();
+                x = (x - x_min) / (x_max - x_min) * f64::from(size.width);
+                y = (y - y_min) / (y_max - y_min) * f64::from(size.height);
+            }
+            let position = PhysicalPosition::new(x, y).to_logical(window.scale_factor());
+            let position = self.scale_pos(window.scale_factor(), position);
+            io.add_mouse_pos_event([position.x as f32, position.y as f32]);
+        }
+    }
+    fn handle_key_event(&mut self, io: &mut Io, key: VirtualKeyCode, pressed: bool) {
+        trace!("key {:?} {}", key, press_name(pressed));
+        if self.key_debounce.is_some() {
+            let pending = self
+                .pending_key_releases
+                .iter()
+                .position(|&(pending_key, _)| pending_key == key);
+            if !pressed {
+                // Hold the release back until we know it isn't followed by a press
+                if pending.is_none() {
+                    self.pending_key_releases.push((key, Instant::now()));
+                }
+                return;
+            } else if let Some(index) = pending {
+                // Expired releases were already flushed, so this is a bounce: drop both
+                self.pending_key_releases.remove(index);
+                return;
+            }
+        }
+        self.forward_key_event(io, key, pressed);
+    }
+    fn forward_key_event(&mut self, io: &mut Io, key: VirtualKeyCode, pressed: bool) {
+        if pressed {
+            if !self.pressed_keys.contains(&key) {
+                self.pressed_keys.push(key);
+            }
+        } else {
+            self.pressed_keys.retain(|&pressed_key| pressed_key != key);
+        }
+
+        // We map both left and right ctrl to `ModCtrl`, etc.
+        // imgui is told both "left control is pressed" and
+        // "consider the control key is pressed". Allows
+        // applications to use either general "ctrl" or a
+        // specific key. Same applies to other modifiers.
+        // https://github.com/ocornut/imgui/issues/5047
+        handle_key_modifier(io, key, pressed);
+
+        // Add main key event. Releases go to the imgui key the press was forwarded as.
+        let logical_key = self.logical_keys.iter().position(|&(k, _)| k == key);
+        let imgui_key = match logical_key {
+            Some(index) if !pressed => Some(self.logical_keys.remove(index).1),
+            Some(index) => Some(self.logical_keys[index].1),
+            None => to_imgui_key(key),
+        };
+        match imgui_key {
+            Some(key) => io.add_key_event(key, pressed),
+            None => {
+                if let Some(handler) = &mut self.unmapped_key_handler {
+                    (handler.0)(key, pressed);
+                }
+            }
+        }
+    }
+    /// Forwards the key press held back for its character with its usual mapping.
+    fn flush_logical_key(&mut self, io: &mut Io) {
+        if let Some(key) = self.pending_logical_key.take() {
+            self.handle_key_event(io, key, true);
+        }
+    }
+    /// Forwards the key press held back for its character as the imgui key of the character.
+    fn handle_logical_key(&mut self, io: &mut Io, ch: char) {
+        if let Some(key) = self.pending_logical_key.take() {
+            self.logical_keys.retain(|&(k, _)| k != key);
+            if let Some(logical_key) = char_to_imgui_key(ch) {
+                self.logical_keys.push((key, logical_key));
+            }
+            self.handle_key_event(io, key, true);
+        }
+    }
+    /// Forwards the held back key releases which are older than the debounce duration.
+    fn flush_key_releases(&mut self, io: &mut Io, now: Instant) {
+        if self.pending_key_releases.is_empty() {
+            return;
+        }
+        let debounce = self.key_debounce.unwrap_or_default();
+        let mut i = 0;
+        while i < self.pending_key_releases.len() {
+            let (key, time) = self.pending_key_releases[i];
+            if now.saturating_duration_since(time) >= debounce {
+                self.pending_key_releases.remove(i);
+                self.forward_key_event(io, key, false);
+            } else {
+                i += 1;
+            }
+        }
+    }
+    fn handle_window_event<W: PlatformWindow + ?Sized>(
+        &mut self,
+        io: &mut Io,
+        window: &W,
+        event: &WindowEvent,
+    ) {
+        if !self.accepts_input() && is_input_event(event) || self.is_passed_through(event) {
+            trace!("ignored window event, imgui doesn't accept it: {:?}", event);
+            return;
+        }
+        match *event {
+            WindowEvent::Resized(physical_size) => {
+                trace!("window resized to {:?}", physical_size);
+                self.set_physical_display_size(io, window.scale_factor(), physical_size);
+            }
+            WindowEvent::Moved(position) => {
+                trace!("window moved to {:?}", position);
+                self.window_position = Some(position);
+            }
+            // The window may be replaced by a new one (e.g. when recreating it), on which
+            // nothing was applied yet
+            WindowEvent::Destroyed => {
+                trace!("window destroyed");
+                self.cursor_cache = None;
+                self.applied_cursor = AppliedCursor::default();
+                self.last_logical_size = None;
+                self.window_position = None;
+            }
+            WindowEvent::ScaleFactorChanged { scale_factor, .. } => {
+                let hidpi_factor = match self.hidpi_mode {
+                    ActiveHiDpiMode::Default => scale_factor,
+                    ActiveHiDpiMode::Rounded => scale_factor.round(),
+                    ActiveHiDpiMode::Snapped(step) => snap_hidpi_factor(scale_factor, step),
+                    _ => return,
+                };
+                let changed = [hidpi_factor, hidpi_factor] != self.hidpi_factor;
+                trace!(
+                    "scale factor changed to {}, using DPI factor {}",
+                    scale_factor,
+                    hidpi_factor
+                );
+                self.set_hidpi_factor(io, [hidpi_factor, hidpi_factor]);
+                if changed {
+                    if let Some(handler) = &mut self.scale_factor_handler {
+                        (handler.0)(hidpi_factor);
+                    }
+                }
                 // Window size might change too if we are using DPI rounding
                 let logical_size = window.inner_size().to_logical(scale_factor);
-                let logical_size = self.scale_size_from_winit(window, logical_size);
-                io.display_size = [logical_size.width as f32, logical_size.height as f32];
+                let logical_size = self.scale_size(window.scale_factor(), logical_size);
+                self.set_logical_display_size(io, logical_size);
             }
             WindowEvent::ModifiersChanged(modifiers) => {
                 // We need to track modifiers separately because some system like macOS, will
                 // not reliably send modifier states during certain events like ScreenCapture.
                 // Gotta let the people show off their pretty imgui widgets!
-                io.add_key_event(Key::ModShift, modifiers.shift());
-                io.add_key_event(Key::ModCtrl, modifiers.ctrl());
-                io.add_key_event(Key::ModAlt, modifiers.alt());
-                io.add_key_event(Key::ModSuper, modifiers.logo());
+                trace!("modifiers changed to {:?}", modifiers);
+                let (shift, ctrl, alt, logo) = imgui_modifiers_from_winit(modifiers);
+                io.add_key_event(Key::ModShift, shift);
+                io.add_key_event(Key::ModCtrl, ctrl);
+                self.ctrl_held = ctrl;
+                io.add_key_event(Key::ModAlt, alt);
+                io.add_key_event(Key::ModSuper, logo);
             }
             WindowEvent::KeyboardInput {
                 input:
@@ -474,32 +2167,36 @@ impl WinitPlatform {
                 ..
             } => {
                 let pressed = state == ElementState::Pressed;
-
-                // We map both left and right ctrl to `ModCtrl`, etc.
-                // imgui is told both "left control is pressed" and
-                // "consider the control key is pressed". Allows
-                // applications to use either general "ctrl" or a
-                // specific key. Same applies to other modifiers.
-                // https://github.com/ocornut/imgui/issues/5047
-                handle_key_modifier(io, key, pressed);
-
-                // Add main key event
-                if let Some(key) = to_imgui_key(key) {
-                    io.add_key_event(key, pressed);
+                if self.text_only_mode
+                    && !is_modifier_key(key)
+                    && (pressed || !self.pressed_keys.contains(&key))
+                {
+                    trace!(
+                        "key {:?} {} ignored in text only mode",
+                        key,
+                        press_name(pressed)
+                    );
+                } else if pressed && self.use_logical_keys && may_produce_character(key) {
+                    trace!("key {:?} pressed, waiting for its character", key);
+                    self.pending_logical_key = Some(key);
+                } else {
+                    self.handle_key_event(io, key, pressed);
                 }
             }
             WindowEvent::ReceivedCharacter(ch) => {
-                // Exclude the backspace key ('\u{7f}'). Otherwise we will insert this char and then
-                // delete it.
-                if ch != '\u{7f}' {
-                    io.add_input_character(ch)
-                }
+                trace!("received character {:?}", ch);
+                self.handle_logical_key(io, ch);
+                handle_received_character(io, ch);
             }
             WindowEvent::CursorMoved { position, .. } => {
                 let position = position.to_logical(window.scale_factor());
-                let position = self.scale_pos_from_winit(window, position);
+                let position = self.scale_pos(window.scale_factor(), position);
+                trace!("mouse moved to {:?}", position);
                 io.add_mouse_pos_event([position.x as f32, position.y as f32]);
             }
+            WindowEvent::AxisMotion { axis, value, .. } => {
+                self.handle_tablet_axis(io, window, axis, value);
+            }
             WindowEvent::MouseWheel {
                 delta,
                 phase: TouchPhase::Moved,
@@ -508,33 +2205,44 @@ impl WinitPlatform {
                 let (h, v) = match delta {
                     MouseScrollDelta::LineDelta(h, v) => (h, v),
                     MouseScrollDelta::PixelDelta(pos) => {
-                        let pos = pos.to_logical::<f64>(self.hidpi_factor);
-                        let h = match pos.x.partial_cmp(&0.0) {
-                            Some(Ordering::Greater) => 1.0,
-                            Some(Ordering::Less) => -1.0,
-                            _ => 0.0,
-                        };
-                        let v = match pos.y.partial_cmp(&0.0) {
-                            Some(Ordering::Greater) => 1.0,
-                            Some(Ordering::Less) => -1.0,
-                            _ => 0.0,
-                        };
-                        (h, v)
+                        let pos = [pos.x / self.hidpi_factor[0], pos.y / self.hidpi_factor[1]];
+                        match self.pixel_delta_to_lines(pos) {
+                            Some(lines) => lines,
+                            None => return,
+                        }
                     }
                 };
-                io.add_mouse_wheel_event([h, v]);
+                let h = if self.invert_scroll[0] { -h } else { h };
+                let v = if self.invert_scroll[1] { -v } else { v };
+                trace!("mouse wheel scrolled by {:?}", [h, v]);
+                io.add_mouse_wheel_event([h * self.wheel_scale[0], v * self.wheel_scale[1]]);
             }
             WindowEvent::MouseInput { state, button, .. } => {
-                if let Some(mb) = to_imgui_mouse_button(button) {
-                    let pressed = state == ElementState::Pressed;
-                    io.add_mouse_button_event(mb, pressed);
-                }
-            }
+                let pressed = state == ElementState::Pressed;
+                match (to_imgui_mouse_button(button), button) {
+                    (Some(mb), _) => {
+                        trace!("mouse button {:?} {}", mb, press_name(pressed));
+                        self.pressed_mouse_buttons.set(mb, pressed);
+                        io.add_mouse_button_event(mb, pressed);
+                    }
+                    (None, MouseButton::Other(button)) => {
+                        trace!("extra mouse button {} {}", button, press_name(pressed));
+                        if let Some(handler) = &mut self.extra_mouse_button_handler {
+                            (handler.0)(button, pressed);
+                        }
+                    }
+                    _ => (),
+                }
+            }
             WindowEvent::Focused(newly_focused) => {
+                trace!("window focused: {}", newly_focused);
+                self.focused = newly_focused;
                 if !newly_focused {
                     // Set focus-lost to avoid stuck keys (like 'alt'
                     // when alt-tabbing)
                     io.app_focus_lost = true;
+                    // The releases happen elsewhere
+                    self.pressed_mouse_buttons = MouseButtons::default();
                 }
             }
             _ => (),
@@ -545,15 +2253,29 @@ impl WinitPlatform {
     /// Call this before calling the imgui-rs context `frame` function.
     /// This function performs the following actions:
     ///
-    /// * mouse cursor is repositioned (if requested by imgui-rs)
-    pub fn prepare_frame(&self, io: &mut Io, window: &Window) -> Result<(), ExternalError> {
-        if io.want_set_mouse_pos {
-            let logical_pos = self.scale_pos_for_winit(
-                window,
+    /// * key releases held back by debouncing are forwarded once their duration has elapsed
+    ///   (see [`WinitPlatform::set_key_debounce`])
+    /// * mouse cursor is repositioned (if requested by imgui-rs, and not on Wayland)
+    pub fn prepare_frame(&mut self, io: &mut Io, window: &Window) -> Result<(), ExternalError> {
+        self.prepare_frame_with(io, window)
+    }
+    fn prepare_frame_with<W: PlatformWindow + ?Sized>(
+        &mut self,
+        io: &mut Io,
+        window: &W,
+    ) -> Result<(), ExternalError> {
+        self.flush_key_releases(io, Instant::now());
+        if io.want_set_mouse_pos && self.windowing_backend != WindowingBackend::Wayland {
+            let logical_pos = self.scale_pos_for_winit_with(
+                window.scale_factor(),
                 LogicalPosition::new(f64::from(io.mouse_pos[0]), f64::from(io.mouse_pos[1])),
             );
+            trace!("warping the mouse cursor to {:?}", logical_pos);
             window.set_cursor_position(logical_pos)
         } else {
+            if io.want_set_mouse_pos {
+                trace!("not warping the mouse cursor, which Wayland doesn't allow");
+            }
             Ok(())
         }
     }
@@ -563,21 +2285,1860 @@ impl WinitPlatform {
     /// Call this before calling the imgui-rs UI `render_with`/`render` function.
     /// This function performs the following actions:
     ///
-    /// * mouse cursor is changed and/or hidden (if requested by imgui-rs)
+    /// * mouse cursor is changed and/or hidden (if requested by imgui-rs), as computed by
+    ///   [`WinitPlatform::compute_cursor`], unless the cursor is grabbed (see
+    ///   [`WinitPlatform::set_cursor_grabbed`])
     pub fn prepare_render(&mut self, ui: &Ui, window: &Window) {
-        let io = ui.io();
-        if !io
-            .config_flags
-            .contains(ConfigFlags::NO_MOUSE_CURSOR_CHANGE)
-        {
-            let cursor = CursorSettings {
-                cursor: ui.mouse_cursor(),
-                draw_cursor: io.mouse_draw_cursor,
-            };
-            if self.cursor_cache != Some(cursor) {
-                cursor.apply(window);
-                self.cursor_cache = Some(cursor);
+        self.prepare_render_with(ui, window);
+    }
+    fn prepare_render_with<W: PlatformWindow + ?Sized>(&mut self, ui: &Ui, window: &W) {
+        if self.cursor_grabbed {
+            return;
+        }
+        if let Some(cursor) = CursorSettings::from_ui(ui) {
+            self.apply_cursor(cursor, window);
+        }
+    }
+    /// Computes the OS cursor changes requested by imgui for this frame, without applying them.
+    ///
+    /// Returns `None` if cursor changes are disabled with `ConfigFlags::NO_MOUSE_CURSOR_CHANGE`.
+    ///
+    /// This is useful when the UI isn't displayed on a winit window (e.g. when it is streamed
+    /// to a remote client), so the command can be transmitted instead. Unlike
+    /// [`WinitPlatform::prepare_render`], this doesn't skip commands that were already applied.
+    pub fn compute_cursor(&self, ui: &Ui) -> Option<CursorCommand> {
+        CursorSettings::from_ui(ui).map(CursorSettings::to_command)
+    }
+    /// Restores the OS cursor changed by [`WinitPlatform::prepare_render`], making it visible
+    /// again with the default icon.
+    ///
+    /// This is done automatically by [`WinitPlatform::handle_event`] when the event loop is
+    /// destroyed, but can be called earlier, e.g. before the UI stops being drawn on a window
+    /// that stays open. Calling [`WinitPlatform::prepare_render`] afterwards applies imgui's
+    /// cursor again.
+    pub fn shutdown(&mut self, window: &Window) {
+        self.restore_cursor(window);
+    }
+    fn restore_cursor<W: PlatformWindow + ?Sized>(&mut self, window: &W) {
+        trace!("restoring the default cursor");
+        if self.applied_cursor.visible == Some(false) {
+            window.set_cursor_visible(true);
+        }
+        if matches!(self.applied_cursor.icon, Some(icon) if icon != MouseCursor::Default) {
+            window.set_cursor_icon(MouseCursor::Default);
+        }
+        self.cursor_cache = None;
+        self.applied_cursor = AppliedCursor::default();
+    }
+    /// Applies the cursor settings, only calling into the window for properties that changed
+    fn apply_cursor<W: PlatformWindow + ?Sized>(&mut self, cursor: CursorSettings, window: &W) {
+        if self.cursor_cache == Some(cursor) {
+            return;
+        }
+        let CursorCommand { visible, icon } = cursor.to_command();
+        let icon = icon.map(|icon| self.cursor_icon_override.unwrap_or(icon));
+        if self.applied_cursor.visible != Some(visible) {
+            trace!("setting the cursor visibility to {}", visible);
+            window.set_cursor_visible(visible);
+            self.applied_cursor.visible = Some(visible);
+        }
+        if let Some(icon) = icon {
+            if self.applied_cursor.icon != Some(icon) {
+                trace!("setting the cursor icon to {:?}", icon);
+                window.set_cursor_icon(icon);
+                self.applied_cursor.icon = Some(icon);
             }
         }
+        self.cursor_cache = Some(cursor);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::RefCell;
+    use std::rc::Rc;
+    use std::sync::{Mutex, MutexGuard};
+
+    // Only one imgui context may be active at a time
+    static TEST_MUTEX: Mutex<()> = Mutex::new(());
+
+    fn test_ctx() -> (MutexGuard<'static, ()>, Context) {
+        let guard = TEST_MUTEX.lock().unwrap_or_else(|err| err.into_inner());
+        let mut ctx = Context::create();
+        ctx.set_ini_filename(None);
+        (guard, ctx)
+    }
+
+    /// Records the calls made by the platform instead of talking to a real window
+    struct SpyWindow {
+        scale_factor: f64,
+        inner_size: PhysicalSize<u32>,
+        cursor_visible_calls: RefCell<Vec<bool>>,
+        cursor_icon_calls: RefCell<Vec<MouseCursor>>,
+        windowing_backend: WindowingBackend,
+    }
+
+    impl Default for SpyWindow {
+        fn default() -> Self {
+            SpyWindow {
+                scale_factor: 1.0,
+                inner_size: PhysicalSize::new(800, 600),
+                cursor_visible_calls: RefCell::default(),
+                cursor_icon_calls: RefCell::default(),
+                windowing_backend: WindowingBackend::X11,
+            }
+        }
+    }
+
+    impl PlatformWindow for SpyWindow {
+        fn id(&self) -> WindowId {
+            unsafe { WindowId::dummy() }
+        }
+        fn scale_factor(&self) -> f64 {
+            self.scale_factor
+        }
+        fn inner_size(&self) -> PhysicalSize<u32> {
+            self.inner_size
+        }
+        fn set_cursor_visible(&self, visible: bool) {
+            self.cursor_visible_calls.borrow_mut().push(visible);
+        }
+        fn set_cursor_icon(&self, icon: MouseCursor) {
+            self.cursor_icon_calls.borrow_mut().push(icon);
+        }
+        fn set_cursor_position(
+            &self,
+            _position: LogicalPosition<f64>,
+        ) -> Result<(), ExternalError> {
+            Ok(())
+        }
+        fn windowing_backend(&self) -> WindowingBackend {
+            self.windowing_backend
+        }
+    }
+
+    fn visible_cursor(cursor: imgui::MouseCursor) -> CursorSettings {
+        CursorSettings {
+            cursor: Some(cursor),
+            draw_cursor: false,
+        }
+    }
+
+    #[test]
+    fn test_cursor_icon_change_keeps_visibility() {
+        let (_guard, mut ctx) = test_ctx();
+        let mut platform = WinitPlatform::init(&mut ctx);
+        let window = SpyWindow::default();
+
+        platform.apply_cursor(visible_cursor(imgui::MouseCursor::Arrow), &window);
+        platform.apply_cursor(visible_cursor(imgui::MouseCursor::TextInput), &window);
+        platform.apply_cursor(visible_cursor(imgui::MouseCursor::TextInput), &window);
+
+        assert_eq!(*window.cursor_visible_calls.borrow(), [true]);
+        assert_eq!(
+            *window.cursor_icon_calls.borrow(),
+            [MouseCursor::Default, MouseCursor::Text]
+        );
+    }
+
+    #[test]
+    fn test_cursor_visibility_change_keeps_icon() {
+        let (_guard, mut ctx) = test_ctx();
+        let mut platform = WinitPlatform::init(&mut ctx);
+        let window = SpyWindow::default();
+
+        platform.apply_cursor(visible_cursor(imgui::MouseCursor::Hand), &window);
+        platform.apply_cursor(
+            CursorSettings {
+                cursor: Some(imgui::MouseCursor::Hand),
+                draw_cursor: true,
+            },
+            &window,
+        );
+        platform.apply_cursor(visible_cursor(imgui::MouseCursor::Hand), &window);
+
+        assert_eq!(*window.cursor_visible_calls.borrow(), [true, false, true]);
+        assert_eq!(*window.cursor_icon_calls.borrow(), [MouseCursor::Hand]);
+    }
+
+    #[test]
+    fn test_suspend_resume() {
+        let (_guard, mut ctx) = test_ctx();
+        let mut platform = WinitPlatform::init(&mut ctx);
+        let mut window = SpyWindow {
+            scale_factor: 2.0,
+            ..SpyWindow::default()
+        };
+        let io = ctx.io_mut();
+        platform.attach(io, &window, HiDpiMode::Default);
+        platform.apply_cursor(visible_cursor(imgui::MouseCursor::Arrow), &window);
+
+        platform.handle_event_with::<(), _>(io, &window, &Event::Suspended);
+        assert!(platform.is_suspended());
+        assert_eq!(platform.cursor_cache, None);
+        assert_eq!(platform.applied_cursor, AppliedCursor::default());
+        assert_eq!(platform.hidpi_factor(), 1.0);
+        assert_eq!(platform.last_logical_size, None);
+
+        // The window comes back on a display with a different DPI factor
+        window.scale_factor = 1.5;
+        window.inner_size = PhysicalSize::new(300, 150);
+        platform.handle_event_with::<(), _>(io, &window, &Event::Resumed);
+        assert!(!platform.is_suspended());
+        assert_eq!(platform.hidpi_factor(), 1.5);
+        assert_eq!(io.display_framebuffer_scale, [1.5, 1.5]);
+        assert_eq!(io.display_size, [200.0, 100.0]);
+    }
+
+    #[test]
+    fn test_suspend_resume_locked() {
+        let (_guard, mut ctx) = test_ctx();
+        let mut platform = WinitPlatform::init(&mut ctx);
+        let mut window = SpyWindow::default();
+        let io = ctx.io_mut();
+        platform.attach(io, &window, HiDpiMode::Locked(2.0));
+
+        platform.handle_event_with::<(), _>(io, &window, &Event::Suspended);
+        // A second suspend doesn't overwrite the saved factor
+        platform.handle_event_with::<(), _>(io, &window, &Event::Suspended);
+        assert_eq!(platform.hidpi_factor(), 1.0);
+
+        window.scale_factor = 1.5;
+        platform.handle_event_with::<(), _>(io, &window, &Event::Resumed);
+        assert!(!platform.is_suspended());
+        assert_eq!(platform.hidpi_factor(), 2.0);
+        assert_eq!(io.display_framebuffer_scale, [2.0, 2.0]);
+    }
+
+    #[test]
+    fn test_window_destroyed() {
+        let (_guard, mut ctx) = test_ctx();
+        let mut platform = WinitPlatform::init(&mut ctx);
+        let window = SpyWindow::default();
+        let io = ctx.io_mut();
+        platform.attach(io, &window, HiDpiMode::Default);
+        platform.apply_cursor(visible_cursor(imgui::MouseCursor::Hand), &window);
+        assert!(platform.cursor_cache.is_some());
+
+        platform.handle_window_event(io, &window, &WindowEvent::Destroyed);
+        assert_eq!(platform.cursor_cache, None);
+        assert_eq!(platform.applied_cursor, AppliedCursor::default());
+
+        // The cursor is applied to the window replacing the destroyed one
+        let new_window = SpyWindow::default();
+        platform.apply_cursor(visible_cursor(imgui::MouseCursor::Hand), &new_window);
+        assert_eq!(*new_window.cursor_icon_calls.borrow(), [MouseCursor::Hand]);
+    }
+
+    #[test]
+    fn test_hidpi_mode_snapped() {
+        assert_eq!(
+            HiDpiMode::Snapped(0.25).apply(1.37),
+            (ActiveHiDpiMode::Snapped(0.25), 1.25)
+        );
+        assert_eq!(HiDpiMode::Snapped(0.25).apply(1.4).1, 1.5);
+        assert_eq!(HiDpiMode::Snapped(0.25).apply(0.1).1, 0.25);
+        assert_eq!(
+            HiDpiMode::Default.apply(1.37),
+            (ActiveHiDpiMode::Default, 1.37)
+        );
+        assert_eq!(
+            HiDpiMode::Locked(1.0).apply(1.37),
+            (ActiveHiDpiMode::Locked, 1.0)
+        );
+    }
+
+    #[test]
+    fn test_reinit() {
+        let (_guard, mut ctx) = test_ctx();
+        let mut platform = WinitPlatform::init(&mut ctx);
+        let window = SpyWindow {
+            scale_factor: 2.0,
+            windowing_backend: WindowingBackend::Wayland,
+            ..SpyWindow::default()
+        };
+        platform.attach(ctx.io_mut(), &window, HiDpiMode::Default);
+        let flags = ctx.io().backend_flags;
+        assert_eq!(flags, BackendFlags::HAS_MOUSE_CURSORS);
+
+        // Recreate the context, e.g. to reload the theme
+        drop(ctx);
+        let mut ctx = Context::create();
+        ctx.set_ini_filename(None);
+        assert!(ctx.io().backend_flags.is_empty());
+
+        platform.reinit(&mut ctx);
+        assert_eq!(ctx.io().backend_flags, flags);
+        assert_eq!(ctx.platform_name(), Some(PLATFORM_NAME));
+        assert_eq!(ctx.io().display_framebuffer_scale, [2.0, 2.0]);
+        assert_eq!(platform.hidpi_factor(), 2.0);
+    }
+
+    #[test]
+    fn test_set_hidpi_mode() {
+        let (_guard, mut ctx) = test_ctx();
+        let mut platform = WinitPlatform::init(&mut ctx);
+        let mut window = SpyWindow::default();
+        let io = ctx.io_mut();
+        platform.attach(io, &window, HiDpiMode::Default);
+        io.mouse_pos = [100.0, 50.0];
+
+        platform.set_hidpi_mode_with(io, &window, HiDpiMode::Locked(2.0));
+        assert_eq!(platform.hidpi_factor(), 2.0);
+        assert_eq!(io.display_framebuffer_scale, [2.0, 2.0]);
+        assert_eq!(io.display_size, [400.0, 300.0]);
+        assert_eq!(io.mouse_pos, [50.0, 25.0]);
+
+        // The locked factor stays when the window moves to another display
+        window.scale_factor = 4.0;
+        let mut new_inner_size = window.inner_size;
+        platform.handle_window_event(
+            io,
+            &window,
+            &WindowEvent::ScaleFactorChanged {
+                scale_factor: 4.0,
+                new_inner_size: &mut new_inner_size,
+            },
+        );
+        assert_eq!(platform.hidpi_factor(), 2.0);
+
+        platform.set_hidpi_mode_with(io, &window, HiDpiMode::Default);
+        assert_eq!(platform.hidpi_factor(), 4.0);
+        assert_eq!(io.display_framebuffer_scale, [4.0, 4.0]);
+        assert_eq!(io.display_size, [200.0, 150.0]);
+        assert_eq!(io.mouse_pos, [25.0, 12.5]);
+    }
+
+    #[test]
+    fn test_scale_factor_changed_snapped() {
+        let (_guard, mut ctx) = test_ctx();
+        let mut platform = WinitPlatform::init(&mut ctx);
+        let mut window = SpyWindow::default();
+        let io = ctx.io_mut();
+        platform.attach(io, &window, HiDpiMode::Snapped(0.25));
+        assert_eq!(platform.hidpi_factor(), 1.0);
+
+        window.scale_factor = 1.37;
+        let mut new_inner_size = window.inner_size;
+        platform.handle_window_event(
+            io,
+            &window,
+            &WindowEvent::ScaleFactorChanged {
+                scale_factor: 1.37,
+                new_inner_size: &mut new_inner_size,
+            },
+        );
+        assert_eq!(platform.hidpi_factor(), 1.25);
+        assert_eq!(io.display_framebuffer_scale, [1.25, 1.25]);
+    }
+
+    #[test]
+    fn test_refresh_display_size() {
+        let (_guard, mut ctx) = test_ctx();
+        let mut platform = WinitPlatform::init(&mut ctx);
+        let mut window = SpyWindow {
+            scale_factor: 1.5,
+            ..SpyWindow::default()
+        };
+        let io = ctx.io_mut();
+        platform.attach(io, &window, HiDpiMode::Rounded);
+        assert_eq!(platform.hidpi_factor(), 2.0);
+
+        // Resized programmatically, without a `Resized` event
+        window.inner_size = PhysicalSize::new(1200, 900);
+        platform.refresh_display_size_with(io, &window);
+        assert_eq!(io.display_size, [600.0, 450.0]);
+    }
+
+    #[test]
+    fn test_tablet_mode() {
+        let (_guard, mut ctx) = test_ctx();
+        ctx.fonts().build_rgba32_texture();
+        let mut platform = WinitPlatform::init(&mut ctx);
+        let window = SpyWindow {
+            scale_factor: 2.0,
+            ..SpyWindow::default()
+        };
+        platform.attach(ctx.io_mut(), &window, HiDpiMode::Default);
+        platform.set_tablet_mode(true);
+        platform.set_tablet_axes(3, 4);
+
+        let motion = |axis, value| -> Event<'static, ()> {
+            Event::DeviceEvent {
+                device_id: unsafe { winit::event::DeviceId::dummy() },
+                event: DeviceEvent::Motion { axis, value },
+            }
+        };
+        let io = ctx.io_mut();
+        platform.handle_event_with(io, &window, &motion(3, 300.0));
+        platform.handle_event_with(io, &window, &motion(0, 10.0));
+        platform.handle_event_with(io, &window, &motion(4, 200.0));
+
+        ctx.frame();
+        assert_eq!(ctx.io().mouse_pos, [150.0, 100.0]);
+        ctx.render();
+
+        // Device units, from 0 to 32767 on each axis, mapped to the 800x600 window
+        platform.set_tablet_axis_ranges(Some([[0.0, 32767.0], [0.0, 32767.0]]));
+        let io = ctx.io_mut();
+        platform.handle_event_with(io, &window, &motion(3, 32767.0 / 4.0));
+        platform.handle_event_with(io, &window, &motion(4, 32767.0));
+        ctx.frame();
+        assert_eq!(ctx.io().mouse_pos, [100.0, 300.0]);
+        ctx.render();
+
+        // Ranges which don't start at zero
+        platform.set_tablet_axis_ranges(Some([[100.0, 300.0], [-50.0, 50.0]]));
+        let io = ctx.io_mut();
+        platform.handle_event_with(io, &window, &motion(3, 200.0));
+        platform.handle_event_with(io, &window, &motion(4, -50.0));
+        ctx.frame();
+        assert_eq!(ctx.io().mouse_pos, [200.0, 0.0]);
+    }
+
+    #[test]
+    #[should_panic(expected = "invalid tablet axis range")]
+    fn test_tablet_axis_ranges_invalid() {
+        let (_guard, mut ctx) = test_ctx();
+        let mut platform = WinitPlatform::init(&mut ctx);
+        platform.set_tablet_axis_ranges(Some([[0.0, 100.0], [10.0, 10.0]]));
+    }
+
+    #[test]
+    fn test_key_mapping() {
+        for (index, &(winit_key, key)) in KEY_MAPPING.iter().enumerate() {
+            assert_eq!(to_imgui_key(winit_key), Some(key));
+            // Each key appears once on both sides
+            assert!(KEY_MAPPING[index + 1..]
+                .iter()
+                .all(|&(other_winit_key, other_key)| other_winit_key != winit_key
+                    && other_key != key));
+        }
+        assert_eq!(to_imgui_key(VirtualKeyCode::Calculator), None);
+    }
+
+    #[test]
+    fn test_input_text() {
+        let (_guard, mut ctx) = test_ctx();
+        ctx.fonts().build_rgba32_texture();
+        let platform = WinitPlatform::init(&mut ctx);
+        let io = ctx.io_mut();
+        io.display_size = [800.0, 600.0];
+
+        platform.input_text(io, "ab\u{7f}c");
+
+        ctx.frame();
+        assert_eq!(ctx.io().input_queue_characters().collect::<String>(), "abc");
+    }
+
+    #[test]
+    fn test_handle_received_text() {
+        let (_guard, mut ctx) = test_ctx();
+        ctx.fonts().build_rgba32_texture();
+        let io = ctx.io_mut();
+        io.display_size = [800.0, 600.0];
+
+        // A long paste, with multi-byte characters and backspaces to filter out
+        let text: String = "aé\u{7f}€".chars().cycle().take(1000).collect();
+        handle_received_text(io, &text);
+
+        ctx.frame();
+        let expected: String = text.chars().filter(|&ch| ch != '\u{7f}').collect();
+        assert_eq!(expected.chars().count(), 750);
+        assert_eq!(
+            ctx.io().input_queue_characters().collect::<String>(),
+            expected
+        );
+    }
+
+    #[test]
+    fn test_imgui_modifiers_from_winit() {
+        assert_eq!(
+            imgui_modifiers_from_winit(ModifiersState::empty()),
+            (false, false, false, false)
+        );
+        assert_eq!(
+            imgui_modifiers_from_winit(ModifiersState::SHIFT | ModifiersState::ALT),
+            (true, false, true, false)
+        );
+        assert_eq!(
+            imgui_modifiers_from_winit(ModifiersState::CTRL | ModifiersState::LOGO),
+            (false, true, false, true)
+        );
+
+        let (_guard, mut ctx) = test_ctx();
+        ctx.fonts().build_rgba32_texture();
+        let mut platform = WinitPlatform::init(&mut ctx);
+        let window = SpyWindow::default();
+        let io = ctx.io_mut();
+        io.display_size = [800.0, 600.0];
+        platform.handle_window_event(
+            io,
+            &window,
+            &WindowEvent::ModifiersChanged(ModifiersState::SHIFT | ModifiersState::CTRL),
+        );
+        let ui = ctx.frame();
+        assert!(ui.io().key_shift && ui.io().key_ctrl);
+        assert!(!ui.io().key_alt && !ui.io().key_super);
+    }
+
+    #[test]
+    fn test_safe_area_insets() {
+        let (_guard, mut ctx) = test_ctx();
+        ctx.fonts().build_rgba32_texture();
+        let mut platform = WinitPlatform::init(&mut ctx);
+        let window = SpyWindow {
+            scale_factor: 2.0,
+            ..SpyWindow::default()
+        };
+        platform.set_safe_area_insets(30.0, 10.0, 5.0, 15.0);
+        platform.attach(ctx.io_mut(), &window, HiDpiMode::Default);
+        assert_eq!(ctx.io().display_size, [380.0, 260.0]);
+
+        #[allow(deprecated)]
+        let cursor_moved = WindowEvent::CursorMoved {
+            device_id: unsafe { winit::event::DeviceId::dummy() },
+            position: PhysicalPosition::new(100.0, 100.0),
+            modifiers: Default::default(),
+        };
+        platform.handle_window_event(ctx.io_mut(), &window, &cursor_moved);
+        ctx.frame();
+        assert_eq!(ctx.io().mouse_pos, [45.0, 20.0]);
+    }
+
+    #[test]
+    fn test_hidpi_factor_xy() {
+        let (_guard, mut ctx) = test_ctx();
+        ctx.fonts().build_rgba32_texture();
+        let mut platform = WinitPlatform::init(&mut ctx);
+        let window = SpyWindow::default();
+        platform.attach(ctx.io_mut(), &window, HiDpiMode::Default);
+
+        platform.set_hidpi_factor_xy_with(ctx.io_mut(), &window, [1.0, 2.0]);
+        assert_eq!(platform.hidpi_factor_xy(), [1.0, 2.0]);
+        assert_eq!(platform.hidpi_factor(), 1.0);
+        assert_eq!(ctx.io().display_framebuffer_scale, [1.0, 2.0]);
+        assert_eq!(ctx.io().display_size, [800.0, 300.0]);
+
+        #[allow(deprecated)]
+        let cursor_moved = WindowEvent::CursorMoved {
+            device_id: unsafe { winit::event::DeviceId::dummy() },
+            position: PhysicalPosition::new(100.0, 100.0),
+            modifiers: Default::default(),
+        };
+        platform.handle_window_event(ctx.io_mut(), &window, &cursor_moved);
+        ctx.frame();
+        assert_eq!(ctx.io().mouse_pos, [100.0, 50.0]);
+        ctx.render();
+
+        // The mouse position stays under the cursor, at (100, 100) physical pixels
+        platform.set_hidpi_factor_xy_with(ctx.io_mut(), &window, [2.0, 1.0]);
+        assert_eq!(ctx.io().mouse_pos, [50.0, 100.0]);
+        assert_eq!(ctx.io().display_size, [400.0, 600.0]);
+
+        // The factors are locked, winit only reports a single one
+        let mut new_inner_size = window.inner_size;
+        platform.handle_window_event(
+            ctx.io_mut(),
+            &window,
+            &WindowEvent::ScaleFactorChanged {
+                scale_factor: 1.5,
+                new_inner_size: &mut new_inner_size,
+            },
+        );
+        assert_eq!(platform.hidpi_factor_xy(), [2.0, 1.0]);
+        assert_eq!(platform.save_state().hidpi_mode, HiDpiMode::Locked(2.0));
+    }
+
+    #[test]
+    fn test_mouse_offset() {
+        let (_guard, mut ctx) = test_ctx();
+        ctx.fonts().build_rgba32_texture();
+        let mut platform = WinitPlatform::init(&mut ctx);
+        let window = SpyWindow::default();
+        platform.set_mouse_offset([40.0, 25.0]);
+        platform.attach(ctx.io_mut(), &window, HiDpiMode::Default);
+        assert_eq!(ctx.io().display_size, [800.0, 600.0]);
+
+        #[allow(deprecated)]
+        let cursor_moved = WindowEvent::CursorMoved {
+            device_id: unsafe { winit::event::DeviceId::dummy() },
+            position: PhysicalPosition::new(100.0, 100.0),
+            modifiers: Default::default(),
+        };
+        platform.handle_window_event(ctx.io_mut(), &window, &cursor_moved);
+        ctx.frame();
+        assert_eq!(ctx.io().mouse_pos, [60.0, 75.0]);
+    }
+
+    fn key_input(key: VirtualKeyCode, state: ElementState) -> Event<'static, ()> {
+        #[allow(deprecated)]
+        Event::WindowEvent {
+            window_id: unsafe { WindowId::dummy() },
+            event: WindowEvent::KeyboardInput {
+                device_id: unsafe { winit::event::DeviceId::dummy() },
+                input: KeyboardInput {
+                    scancode: 0,
+                    state,
+                    virtual_keycode: Some(key),
+                    modifiers: Default::default(),
+                },
+                is_synthetic: false,
+            },
+        }
+    }
+
+    #[test]
+    fn test_key_debounce() {
+        let (_guard, mut ctx) = test_ctx();
+        ctx.fonts().build_rgba32_texture();
+        let mut platform = WinitPlatform::init(&mut ctx);
+        let window = SpyWindow::default();
+        platform.attach(ctx.io_mut(), &window, HiDpiMode::Default);
+        platform.set_key_debounce(Some(Duration::from_millis(50)));
+
+        let io = ctx.io_mut();
+        platform.handle_event_with(
+            io,
+            &window,
+            &key_input(VirtualKeyCode::A, ElementState::Pressed),
+        );
+        // auto-repeat reported as a release immediately followed by a press
+        platform.handle_event_with(
+            io,
+            &window,
+            &key_input(VirtualKeyCode::A, ElementState::Released),
+        );
+        platform.handle_event_with(
+            io,
+            &window,
+            &key_input(VirtualKeyCode::A, ElementState::Pressed),
+        );
+        assert!(ctx.frame().is_key_down(Key::A));
+        ctx.render();
+
+        // a real release is forwarded once the debounce duration has elapsed
+        let io = ctx.io_mut();
+        platform.handle_event_with(
+            io,
+            &window,
+            &key_input(VirtualKeyCode::A, ElementState::Released),
+        );
+        assert!(ctx.frame().is_key_down(Key::A));
+        ctx.render();
+        std::thread::sleep(Duration::from_millis(60));
+        platform.handle_event_with::<(), _>(ctx.io_mut(), &window, &Event::MainEventsCleared);
+        assert!(!ctx.frame().is_key_down(Key::A));
+        ctx.render();
+
+        // a lone release, with no event after the debounce duration, is forwarded when the
+        // next frame is prepared
+        let io = ctx.io_mut();
+        platform.handle_event_with(
+            io,
+            &window,
+            &key_input(VirtualKeyCode::B, ElementState::Pressed),
+        );
+        platform.handle_event_with(
+            io,
+            &window,
+            &key_input(VirtualKeyCode::B, ElementState::Released),
+        );
+        platform.prepare_frame_with(ctx.io_mut(), &window).unwrap();
+        assert!(ctx.frame().is_key_down(Key::B));
+        ctx.render();
+        std::thread::sleep(Duration::from_millis(60));
+        platform.prepare_frame_with(ctx.io_mut(), &window).unwrap();
+        assert!(!ctx.frame().is_key_down(Key::B));
+    }
+
+    #[test]
+    fn test_windowing_backend() {
+        use raw_window_handle::{
+            AppKitWindowHandle, WaylandWindowHandle, WebWindowHandle, Win32WindowHandle,
+            XcbWindowHandle, XlibWindowHandle,
+        };
+        let cases = [
+            (
+                RawWindowHandle::Xlib(XlibWindowHandle::empty()),
+                WindowingBackend::X11,
+            ),
+            (
+                RawWindowHandle::Xcb(XcbWindowHandle::empty()),
+                WindowingBackend::X11,
+            ),
+            (
+                RawWindowHandle::Wayland(WaylandWindowHandle::empty()),
+                WindowingBackend::Wayland,
+            ),
+            (
+                RawWindowHandle::Win32(Win32WindowHandle::empty()),
+                WindowingBackend::Windows,
+            ),
+            (
+                RawWindowHandle::AppKit(AppKitWindowHandle::empty()),
+                WindowingBackend::MacOS,
+            ),
+            (
+                RawWindowHandle::Web(WebWindowHandle::empty()),
+                WindowingBackend::Other,
+            ),
+        ];
+        for (handle, backend) in cases {
+            assert_eq!(WindowingBackend::from_raw_handle(handle), backend);
+        }
+
+        // Cursor warping is only offered to imgui where it works
+        let (_guard, mut ctx) = test_ctx();
+        let mut platform = WinitPlatform::init(&mut ctx);
+        let mut window = SpyWindow {
+            windowing_backend: WindowingBackend::Wayland,
+            ..SpyWindow::default()
+        };
+        platform.attach(ctx.io_mut(), &window, HiDpiMode::Default);
+        assert!(!ctx
+            .io()
+            .backend_flags
+            .contains(BackendFlags::HAS_SET_MOUSE_POS));
+        window.windowing_backend = WindowingBackend::X11;
+        platform.attach(ctx.io_mut(), &window, HiDpiMode::Default);
+        assert!(ctx
+            .io()
+            .backend_flags
+            .contains(BackendFlags::HAS_SET_MOUSE_POS));
+    }
+
+    fn received_character(ch: char) -> Event<'static, ()> {
+        Event::WindowEvent {
+            window_id: unsafe { WindowId::dummy() },
+            event: WindowEvent::ReceivedCharacter(ch),
+        }
+    }
+
+    #[test]
+    fn test_text_only_mode() {
+        let (_guard, mut ctx) = test_ctx();
+        ctx.fonts().build_rgba32_texture();
+        let mut platform = WinitPlatform::init(&mut ctx);
+        let window = SpyWindow::default();
+        platform.attach(ctx.io_mut(), &window, HiDpiMode::Default);
+
+        // A key pressed before text only mode is enabled
+        let io = ctx.io_mut();
+        platform.handle_event_with(
+            io,
+            &window,
+            &key_input(VirtualKeyCode::Down, ElementState::Pressed),
+        );
+        platform.set_text_only_mode(true);
+        assert!(platform.text_only_mode());
+        platform.handle_event_with(
+            io,
+            &window,
+            &key_input(VirtualKeyCode::A, ElementState::Pressed),
+        );
+        platform.handle_event_with(io, &window, &received_character('a'));
+        platform.handle_event_with(
+            io,
+            &window,
+            &key_input(VirtualKeyCode::Left, ElementState::Pressed),
+        );
+        platform.handle_event_with(
+            io,
+            &window,
+            &key_input(VirtualKeyCode::LShift, ElementState::Pressed),
+        );
+        platform.handle_event_with(io, &window, &received_character('B'));
+        let ui = ctx.frame();
+        assert_eq!(ui.io().input_queue_characters().collect::<String>(), "aB");
+        assert!(ui.is_key_down(Key::DownArrow));
+        assert!(!ui.is_key_down(Key::LeftArrow));
+        assert!(!ui.is_key_down(Key::A));
+        assert!(ui.is_key_down(Key::LeftShift));
+        assert!(ui.io().key_shift);
+        ctx.render();
+
+        // The key pressed before is still released
+        let io = ctx.io_mut();
+        platform.handle_event_with(
+            io,
+            &window,
+            &key_input(VirtualKeyCode::Down, ElementState::Released),
+        );
+        assert!(!ctx.frame().is_key_down(Key::DownArrow));
+        ctx.render();
+
+        platform.set_text_only_mode(false);
+        let io = ctx.io_mut();
+        platform.handle_event_with(
+            io,
+            &window,
+            &key_input(VirtualKeyCode::Left, ElementState::Pressed),
+        );
+        assert!(ctx.frame().is_key_down(Key::LeftArrow));
+    }
+
+    #[test]
+    fn test_logical_keys() {
+        let (_guard, mut ctx) = test_ctx();
+        ctx.fonts().build_rgba32_texture();
+        let mut platform = WinitPlatform::init(&mut ctx);
+        let window = SpyWindow::default();
+        platform.attach(ctx.io_mut(), &window, HiDpiMode::Default);
+        platform.set_use_logical_keys(true);
+
+        // The key labeled Z on a German layout is where Y is on a US layout
+        let io = ctx.io_mut();
+        platform.handle_event_with(
+            io,
+            &window,
+            &key_input(VirtualKeyCode::Y, ElementState::Pressed),
+        );
+        platform.handle_event_with(io, &window, &received_character('z'));
+        let ui = ctx.frame();
+        assert!(ui.is_key_down(Key::Z));
+        assert!(!ui.is_key_down(Key::Y));
+        ctx.render();
+
+        // The release goes to the same imgui key
+        let io = ctx.io_mut();
+        platform.handle_event_with(
+            io,
+            &window,
+            &key_input(VirtualKeyCode::Y, ElementState::Released),
+        );
+        assert!(!ctx.frame().is_key_down(Key::Z));
+        ctx.render();
+
+        // A key that imgui has no key for, but whose character it has
+        let io = ctx.io_mut();
+        platform.handle_event_with(
+            io,
+            &window,
+            &key_input(VirtualKeyCode::Plus, ElementState::Pressed),
+        );
+        platform.handle_event_with(io, &window, &received_character('+'));
+        assert!(ctx.frame().is_key_down(Key::Equal));
+        ctx.render();
+        let io = ctx.io_mut();
+        platform.handle_event_with(
+            io,
+            &window,
+            &key_input(VirtualKeyCode::Plus, ElementState::Released),
+        );
+        ctx.frame();
+        ctx.render();
+
+        // Without a character, the key keeps its usual mapping
+        let io = ctx.io_mut();
+        platform.handle_event_with(
+            io,
+            &window,
+            &key_input(VirtualKeyCode::Y, ElementState::Pressed),
+        );
+        platform.handle_event_with::<(), _>(io, &window, &Event::MainEventsCleared);
+        assert!(ctx.frame().is_key_down(Key::Y));
+        ctx.render();
+        let io = ctx.io_mut();
+        platform.handle_event_with(
+            io,
+            &window,
+            &key_input(VirtualKeyCode::Y, ElementState::Released),
+        );
+        ctx.frame();
+        ctx.render();
+
+        // Disabled, the physical mapping is used
+        platform.set_use_logical_keys(false);
+        let io = ctx.io_mut();
+        platform.handle_event_with(
+            io,
+            &window,
+            &key_input(VirtualKeyCode::Y, ElementState::Pressed),
+        );
+        platform.handle_event_with(io, &window, &received_character('z'));
+        let ui = ctx.frame();
+        assert!(ui.is_key_down(Key::Y));
+        assert!(!ui.is_key_down(Key::Z));
+    }
+
+    #[test]
+    fn test_handle_event_filtered() {
+        let (_guard, mut ctx) = test_ctx();
+        ctx.fonts().build_rgba32_texture();
+        let mut platform = WinitPlatform::init(&mut ctx);
+        let window = SpyWindow::default();
+        let mut rejected = 0;
+        let mut no_mouse = |event: &Event<()>| {
+            let is_mouse = matches!(
+                event,
+                Event::WindowEvent {
+                    event: WindowEvent::CursorMoved { .. }
+                        | WindowEvent::MouseInput { .. }
+                        | WindowEvent::MouseWheel { .. },
+                    ..
+                }
+            );
+            rejected += is_mouse as usize;
+            !is_mouse
+        };
+        #[allow(deprecated)]
+        let cursor_moved = Event::WindowEvent {
+            window_id: window.id(),
+            event: WindowEvent::CursorMoved {
+                device_id: unsafe { winit::event::DeviceId::dummy() },
+                position: PhysicalPosition::new(10.0, 10.0),
+                modifiers: Default::default(),
+            },
+        };
+        let mouse_pressed = Event::WindowEvent {
+            window_id: window.id(),
+            event: mouse_input(MouseButton::Left, ElementState::Pressed),
+        };
+
+        let io = ctx.io_mut();
+        io.display_size = [800.0, 600.0];
+        assert!(!platform.handle_event_filtered_with(io, &window, &cursor_moved, &mut no_mouse));
+        assert!(!platform.handle_event_filtered_with(io, &window, &mouse_pressed, &mut no_mouse));
+        assert!(platform.handle_event_filtered_with(
+            io,
+            &window,
+            &key_input(VirtualKeyCode::A, ElementState::Pressed),
+            &mut no_mouse,
+        ));
+        assert_eq!(rejected, 2);
+        let ui = ctx.frame();
+        assert_eq!(ui.io().mouse_pos, [-f32::MAX, -f32::MAX]);
+        assert!(!ui.is_mouse_down(imgui::MouseButton::Left));
+        assert!(ui.is_key_down(imgui::Key::A));
+        ctx.render();
+
+        // Without a filter, the same events reach imgui
+        let io = ctx.io_mut();
+        platform.handle_event_with(io, &window, &cursor_moved);
+        platform.handle_event_with(io, &window, &mouse_pressed);
+        let ui = ctx.frame();
+        assert_eq!(ui.io().mouse_pos, [10.0, 10.0]);
+        assert!(ui.is_mouse_down(imgui::MouseButton::Left));
+    }
+
+    fn mouse_input(button: MouseButton, state: ElementState) -> WindowEvent<'static> {
+        #[allow(deprecated)]
+        WindowEvent::MouseInput {
+            device_id: unsafe { winit::event::DeviceId::dummy() },
+            state,
+            button,
+            modifiers: Default::default(),
+        }
+    }
+
+    #[test]
+    fn test_extra_mouse_button_handler() {
+        let (_guard, mut ctx) = test_ctx();
+        let mut platform = WinitPlatform::init(&mut ctx);
+        let window = SpyWindow::default();
+        let events = Rc::new(RefCell::new(Vec::new()));
+        let handler_events = Rc::clone(&events);
+        platform.set_extra_mouse_button_handler(Some(Box::new(move |button, pressed| {
+            handler_events.borrow_mut().push((button, pressed))
+        })));
+
+        let io = ctx.io_mut();
+        for event in [
+            mouse_input(MouseButton::Other(7), ElementState::Pressed),
+            mouse_input(MouseButton::Other(7), ElementState::Released),
+            mouse_input(MouseButton::Other(3), ElementState::Pressed),
+            mouse_input(MouseButton::Left, ElementState::Pressed),
+        ] {
+            platform.handle_window_event(io, &window, &event);
+        }
+
+        // Buttons imgui knows about are not passed to the handler
+        assert_eq!(*events.borrow(), [(7, true), (7, false)]);
+    }
+
+    #[test]
+    fn test_scale_factor_handler() {
+        let (_guard, mut ctx) = test_ctx();
+        let mut platform = WinitPlatform::init(&mut ctx);
+        platform.hidpi_mode = ActiveHiDpiMode::Rounded;
+        let window = SpyWindow::default();
+        let factors = Rc::new(RefCell::new(Vec::new()));
+        let handler_factors = Rc::clone(&factors);
+        platform.set_scale_factor_handler(Some(Box::new(move |factor| {
+            handler_factors.borrow_mut().push(factor)
+        })));
+
+        let io = ctx.io_mut();
+        for scale_factor in [2.2, 1.8, 1.0] {
+            let mut new_inner_size = window.inner_size;
+            platform.handle_window_event(
+                io,
+                &window,
+                &WindowEvent::ScaleFactorChanged {
+                    scale_factor,
+                    new_inner_size: &mut new_inner_size,
+                },
+            );
+        }
+
+        // 1.8 rounds to the current factor
+        assert_eq!(*factors.borrow(), [2.0, 1.0]);
+    }
+
+    #[test]
+    fn test_unmapped_key_handler() {
+        let (_guard, mut ctx) = test_ctx();
+        let mut platform = WinitPlatform::init(&mut ctx);
+        let window = SpyWindow::default();
+        let events = Rc::new(RefCell::new(Vec::new()));
+        let handler_events = Rc::clone(&events);
+        platform.set_unmapped_key_handler(Some(Box::new(move |key, pressed| {
+            handler_events.borrow_mut().push((key, pressed))
+        })));
+
+        let io = ctx.io_mut();
+        for event in [
+            key_input(VirtualKeyCode::MyComputer, ElementState::Pressed),
+            key_input(VirtualKeyCode::A, ElementState::Pressed),
+            key_input(VirtualKeyCode::MyComputer, ElementState::Released),
+            key_input(VirtualKeyCode::A, ElementState::Released),
+        ] {
+            platform.handle_event_with(io, &window, &event);
+        }
+
+        // Keys imgui knows about are not passed to the handler
+        assert_eq!(
+            *events.borrow(),
+            [
+                (VirtualKeyCode::MyComputer, true),
+                (VirtualKeyCode::MyComputer, false)
+            ]
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn test_platform_state_round_trip() {
+        let (_guard, mut ctx) = test_ctx();
+        let mut platform = WinitPlatform::init(&mut ctx);
+        platform.hidpi_mode = ActiveHiDpiMode::Locked;
+        platform.hidpi_factor = [1.5, 1.5];
+        platform.cursor_cache = Some(CursorSettings {
+            cursor: Some(imgui::MouseCursor::TextInput),
+            draw_cursor: false,
+        });
+
+        let state = platform.save_state();
+        assert_eq!(state.hidpi_mode, HiDpiMode::Locked(1.5));
+
+        let json = serde_json::to_string(&state).unwrap();
+        let restored: PlatformState = serde_json::from_str(&json).unwrap();
+        assert_eq!(restored, state);
+    }
+
+    #[test]
+    fn test_event_hint() {
+        let (_guard, mut ctx) = test_ctx();
+        let mut platform = WinitPlatform::init(&mut ctx);
+        let window = SpyWindow::default();
+        let window_id = window.id();
+        let io = ctx.io_mut();
+
+        let close: Event<'static, ()> = Event::WindowEvent {
+            window_id,
+            event: WindowEvent::CloseRequested,
+        };
+        platform.handle_event_with(io, &window, &close);
+        assert_eq!(EventHint::from_event(window_id, &close), EventHint::Exit);
+
+        let redraw: Event<'static, ()> = Event::RedrawRequested(window_id);
+        platform.handle_event_with(io, &window, &redraw);
+        assert_eq!(EventHint::from_event(window_id, &redraw), EventHint::Redraw);
+
+        let key = key_input(VirtualKeyCode::A, ElementState::Pressed);
+        assert_eq!(EventHint::from_event(window_id, &key), EventHint::Redraw);
+        let cleared: Event<'static, ()> = Event::MainEventsCleared;
+        assert_eq!(
+            EventHint::from_event(window_id, &cleared),
+            EventHint::Nothing
+        );
+    }
+
+    fn device_key_release(key: VirtualKeyCode) -> Event<'static, ()> {
+        #[allow(deprecated)]
+        Event::DeviceEvent {
+            device_id: unsafe { winit::event::DeviceId::dummy() },
+            event: DeviceEvent::Key(KeyboardInput {
+                scancode: 0,
+                state: ElementState::Released,
+                virtual_keycode: Some(key),
+                modifiers: Default::default(),
+            }),
+        }
+    }
+
+    #[test]
+    fn test_device_release_of_untracked_key() {
+        let (_guard, mut ctx) = test_ctx();
+        ctx.fonts().build_rgba32_texture();
+        let mut platform = WinitPlatform::init(&mut ctx);
+        let window = SpyWindow::default();
+        let io = ctx.io_mut();
+        io.display_size = [800.0, 600.0];
+
+        // The key is down in imgui, but the platform never saw it pressed
+        io.add_key_event(Key::B, true);
+        platform.handle_event_with(io, &window, &device_key_release(VirtualKeyCode::B));
+        assert!(ctx.frame().is_key_down(Key::B));
+        ctx.render();
+
+        // A device release for a key pressed in our window is forwarded
+        let io = ctx.io_mut();
+        platform.handle_event_with(
+            io,
+            &window,
+            &key_input(VirtualKeyCode::A, ElementState::Pressed),
+        );
+        assert!(ctx.frame().is_key_down(Key::A));
+        ctx.render();
+        let io = ctx.io_mut();
+        platform.handle_event_with(io, &window, &device_key_release(VirtualKeyCode::A));
+        assert!(!ctx.frame().is_key_down(Key::A));
+    }
+
+    #[test]
+    fn test_compute_cursor() {
+        let (_guard, mut ctx) = test_ctx();
+        ctx.fonts().build_rgba32_texture();
+        let platform = WinitPlatform::init(&mut ctx);
+        ctx.io_mut().display_size = [800.0, 600.0];
+
+        let ui = ctx.frame();
+        ui.set_mouse_cursor(Some(imgui::MouseCursor::TextInput));
+        assert_eq!(
+            platform.compute_cursor(ui),
+            Some(CursorCommand {
+                visible: true,
+                icon: Some(MouseCursor::Text),
+            })
+        );
+
+        ui.set_mouse_cursor(None);
+        assert_eq!(
+            platform.compute_cursor(ui),
+            Some(CursorCommand {
+                visible: false,
+                icon: None,
+            })
+        );
+    }
+
+    #[test]
+    fn test_wants_keyboard_grab() {
+        let (_guard, mut ctx) = test_ctx();
+        ctx.fonts().build_rgba32_texture();
+        let mut platform = WinitPlatform::init(&mut ctx);
+        let window = SpyWindow::default();
+        ctx.io_mut().display_size = [800.0, 600.0];
+        assert!(!platform.wants_keyboard_grab(ctx.io()));
+
+        // The text input gets the focus on the frame after it was requested
+        let mut text = String::new();
+        for _ in 0..3 {
+            let ui = ctx.frame();
+            ui.window("Keyboard grab").build(|| {
+                ui.set_keyboard_focus_here();
+                ui.input_text("Text", &mut text).build();
+            });
+            ctx.render();
+        }
+        assert!(platform.wants_keyboard_grab(ctx.io()));
+
+        platform.handle_window_event(ctx.io_mut(), &window, &WindowEvent::Focused(false));
+        assert!(!platform.wants_keyboard_grab(ctx.io()));
+        platform.handle_window_event(ctx.io_mut(), &window, &WindowEvent::Focused(true));
+        assert!(platform.wants_keyboard_grab(ctx.io()));
+    }
+
+    #[test]
+    fn test_require_focus() {
+        let (_guard, mut ctx) = test_ctx();
+        ctx.fonts().build_rgba32_texture();
+        let mut platform = WinitPlatform::init(&mut ctx);
+        platform.set_require_focus(true);
+        let window = SpyWindow::default();
+        let io = ctx.io_mut();
+        io.display_size = [800.0, 600.0];
+
+        platform.handle_window_event(io, &window, &WindowEvent::Focused(false));
+        platform.handle_window_event(
+            io,
+            &window,
+            &mouse_input(MouseButton::Left, ElementState::Pressed),
+        );
+        platform.handle_window_event(
+            io,
+            &window,
+            &WindowEvent::Resized(PhysicalSize::new(400, 300)),
+        );
+        assert!(!ctx.frame().is_mouse_down(imgui::MouseButton::Left));
+        ctx.render();
+        assert_eq!(ctx.io().display_size, [400.0, 300.0]);
+
+        let io = ctx.io_mut();
+        platform.handle_window_event(io, &window, &WindowEvent::Focused(true));
+        platform.handle_window_event(
+            io,
+            &window,
+            &mouse_input(MouseButton::Left, ElementState::Pressed),
+        );
+        assert!(ctx.frame().is_mouse_down(imgui::MouseButton::Left));
+    }
+
+    #[test]
+    fn test_backend_name() {
+        let name = WinitPlatform::backend_name();
+        assert!(name.starts_with("imgui-winit-support "));
+        assert!(name.ends_with(env!("CARGO_PKG_VERSION")));
+
+        let (_guard, mut ctx) = test_ctx();
+        WinitPlatform::init(&mut ctx);
+        assert_eq!(ctx.platform_name(), Some(PLATFORM_NAME));
+    }
+
+    #[test]
+    fn test_invert_scroll() {
+        let (_guard, mut ctx) = test_ctx();
+        ctx.fonts().build_rgba32_texture();
+        let mut platform = WinitPlatform::init(&mut ctx);
+        let window = SpyWindow::default();
+        ctx.io_mut().display_size = [800.0, 600.0];
+
+        #[allow(deprecated)]
+        let wheel = WindowEvent::MouseWheel {
+            device_id: unsafe { winit::event::DeviceId::dummy() },
+            delta: MouseScrollDelta::LineDelta(1.0, 2.0),
+            phase: TouchPhase::Moved,
+            modifiers: Default::default(),
+        };
+        for invert in [[false, false], [true, false], [false, true], [true, true]] {
+            platform.set_invert_scroll(invert[0], invert[1]);
+            platform.handle_window_event(ctx.io_mut(), &window, &wheel);
+            ctx.frame();
+            let io = ctx.io();
+            let expected_h = if invert[0] { -1.0 } else { 1.0 };
+            let expected_v = if invert[1] { -2.0 } else { 2.0 };
+            assert_eq!([io.mouse_wheel_h, io.mouse_wheel], [expected_h, expected_v]);
+            ctx.render();
+        }
+    }
+
+    #[test]
+    fn test_wheel_scale() {
+        let (_guard, mut ctx) = test_ctx();
+        ctx.fonts().build_rgba32_texture();
+        let mut platform = WinitPlatform::init(&mut ctx);
+        let window = SpyWindow::default();
+        ctx.io_mut().display_size = [800.0, 600.0];
+        platform.set_wheel_scale([2.0, 2.0]);
+        platform.set_scroll_mode(ScrollMode::SignOnly);
+
+        #[allow(deprecated)]
+        let wheel = |delta| WindowEvent::MouseWheel {
+            device_id: unsafe { winit::event::DeviceId::dummy() },
+            delta,
+            phase: TouchPhase::Moved,
+            modifiers: Default::default(),
+        };
+        for delta in [
+            MouseScrollDelta::LineDelta(-1.0, 1.0),
+            MouseScrollDelta::PixelDelta(PhysicalPosition::new(-20.0, 35.0)),
+        ] {
+            platform.handle_window_event(ctx.io_mut(), &window, &wheel(delta));
+            ctx.frame();
+            let io = ctx.io();
+            assert_eq!([io.mouse_wheel_h, io.mouse_wheel], [-2.0, 2.0]);
+            ctx.render();
+        }
+    }
+
+    #[test]
+    fn test_scroll_mode() {
+        let (_guard, mut ctx) = test_ctx();
+        ctx.fonts().build_rgba32_texture();
+        let mut platform = WinitPlatform::init(&mut ctx);
+        let window = SpyWindow::default();
+        ctx.io_mut().display_size = [800.0, 600.0];
+        assert_eq!(
+            platform.scroll_mode(),
+            ScrollMode::Lines {
+                pixels_per_line: 50.0
+            }
+        );
+
+        #[allow(deprecated)]
+        let wheel = WindowEvent::MouseWheel {
+            device_id: unsafe { winit::event::DeviceId::dummy() },
+            delta: MouseScrollDelta::PixelDelta(PhysicalPosition::new(-10.0, 30.0)),
+            phase: TouchPhase::Moved,
+            modifiers: Default::default(),
+        };
+        // Wheel deltas over three events, with each mode
+        let mut scroll = |platform: &mut WinitPlatform| {
+            (0..3)
+                .map(|_| {
+                    platform.handle_window_event(ctx.io_mut(), &window, &wheel);
+                    ctx.frame();
+                    let io = ctx.io();
+                    let delta = [io.mouse_wheel_h, io.mouse_wheel];
+                    ctx.render();
+                    delta
+                })
+                .collect::<Vec<_>>()
+        };
+
+        platform.set_scroll_mode(ScrollMode::SignOnly);
+        assert_eq!(scroll(&mut platform), [[-1.0, 1.0]; 3]);
+
+        platform.set_scroll_mode(ScrollMode::Lines {
+            pixels_per_line: 20.0,
+        });
+        assert_eq!(scroll(&mut platform), [[-0.5, 1.5]; 3]);
+
+        platform.set_scroll_mode(ScrollMode::Accumulate { threshold: 20.0 });
+        assert_eq!(scroll(&mut platform), [[0.0, 1.0], [-1.0, 2.0], [0.0, 1.0]]);
+    }
+
+    #[test]
+    fn test_pinch_scales_fonts() {
+        let (_guard, mut ctx) = test_ctx();
+        let mut platform = WinitPlatform::init(&mut ctx);
+        let io = ctx.io_mut();
+
+        // Left as is by default
+        platform.handle_magnify(io, 0.5);
+        assert_eq!(io.font_global_scale, 1.0);
+
+        platform.set_pinch_scales_fonts(true);
+        platform.set_pinch_font_scale_range(0.5, 2.0);
+        platform.handle_magnify(io, 0.5);
+        assert_eq!(io.font_global_scale, 1.5);
+        platform.handle_magnify(io, 0.5);
+        assert_eq!(io.font_global_scale, 2.0);
+        platform.handle_magnify(io, -0.5);
+        assert_eq!(io.font_global_scale, 1.0);
+        for _ in 0..4 {
+            platform.handle_magnify(io, -0.25);
+        }
+        assert_eq!(io.font_global_scale, 0.5);
+
+        // Invalid deltas would make the scale NaN
+        platform.handle_magnify(io, f64::NAN);
+        platform.handle_magnify(io, f64::INFINITY);
+        assert_eq!(io.font_global_scale, 0.5);
+        assert_eq!(platform.take_magnify_delta(), 0.0);
+    }
+
+    #[test]
+    #[should_panic(expected = "invalid font scale range")]
+    fn test_pinch_font_scale_range_invalid() {
+        let (_guard, mut ctx) = test_ctx();
+        let mut platform = WinitPlatform::init(&mut ctx);
+        platform.set_pinch_font_scale_range(2.0, 1.0);
+    }
+
+    #[test]
+    fn test_magnify() {
+        let (_guard, mut ctx) = test_ctx();
+        ctx.fonts().build_rgba32_texture();
+        let mut platform = WinitPlatform::init(&mut ctx);
+        ctx.io_mut().display_size = [800.0, 600.0];
+
+        platform.handle_magnify(ctx.io_mut(), 0.25);
+        platform.handle_magnify(ctx.io_mut(), -0.125);
+        assert_eq!(platform.take_magnify_delta(), 0.125);
+        assert_eq!(platform.take_magnify_delta(), 0.0);
+        // Not fed to imgui by default
+        ctx.frame();
+        assert_eq!(ctx.io().mouse_wheel, 0.0);
+        ctx.render();
+
+        // Only fed as Ctrl+wheel when imgui allows scaling the fonts
+        platform.set_magnify_as_wheel(true);
+        platform.handle_magnify(ctx.io_mut(), 0.25);
+        ctx.frame();
+        assert_eq!(ctx.io().mouse_wheel, 0.0);
+        ctx.render();
+
+        ctx.io_mut().font_allow_user_scaling = true;
+        platform.handle_magnify(ctx.io_mut(), 0.25);
+        assert_eq!(platform.take_magnify_delta(), 0.5);
+        ctx.frame();
+        assert_eq!(ctx.io().mouse_wheel, 2.5);
+        assert!(ctx.io().key_ctrl);
+        ctx.render();
+        // Ctrl is released again on the next frame
+        ctx.frame();
+        assert_eq!(ctx.io().mouse_wheel, 0.0);
+        assert!(!ctx.io().key_ctrl);
+    }
+
+    #[test]
+    fn test_rotate() {
+        let (_guard, mut ctx) = test_ctx();
+        let mut platform = WinitPlatform::init(&mut ctx);
+        let window = SpyWindow::default();
+
+        platform.handle_rotate(15.0);
+        platform.handle_rotate(-5.0);
+        assert_eq!(platform.take_rotate_delta(), 10.0);
+        assert_eq!(platform.take_rotate_delta(), 0.0);
+
+        // Like other input, ignored without focus when it is required
+        platform.set_require_focus(true);
+        platform.handle_window_event(ctx.io_mut(), &window, &WindowEvent::Focused(false));
+        platform.handle_rotate(15.0);
+        assert_eq!(platform.take_rotate_delta(), 0.0);
+    }
+
+    #[test]
+    fn test_shutdown_restores_cursor() {
+        let (_guard, mut ctx) = test_ctx();
+        let mut platform = WinitPlatform::init(&mut ctx);
+        let window = SpyWindow::default();
+
+        // Nothing was changed yet, so there is nothing to restore
+        platform.restore_cursor(&window);
+        assert!(window.cursor_visible_calls.borrow().is_empty());
+        assert!(window.cursor_icon_calls.borrow().is_empty());
+
+        platform.apply_cursor(visible_cursor(imgui::MouseCursor::Hand), &window);
+        platform.apply_cursor(
+            CursorSettings {
+                cursor: Some(imgui::MouseCursor::Hand),
+                draw_cursor: true,
+            },
+            &window,
+        );
+        platform.handle_event_with(ctx.io_mut(), &window, &Event::<()>::LoopDestroyed);
+
+        assert_eq!(*window.cursor_visible_calls.borrow(), [true, false, true]);
+        assert_eq!(
+            *window.cursor_icon_calls.borrow(),
+            [MouseCursor::Hand, MouseCursor::Default]
+        );
+
+        // The cursor is applied again from scratch afterwards
+        platform.apply_cursor(visible_cursor(imgui::MouseCursor::Hand), &window);
+        assert_eq!(
+            *window.cursor_visible_calls.borrow(),
+            [true, false, true, true]
+        );
+        assert_eq!(
+            *window.cursor_icon_calls.borrow(),
+            [MouseCursor::Hand, MouseCursor::Default, MouseCursor::Hand]
+        );
+    }
+
+    #[test]
+    fn test_display_size_clamping() {
+        let (_guard, mut ctx) = test_ctx();
+        let mut platform = WinitPlatform::init(&mut ctx);
+        let window = SpyWindow::default();
+        let io = ctx.io_mut();
+        platform.attach(io, &window, HiDpiMode::Default);
+        assert_eq!(io.display_size, [800.0, 600.0]);
+
+        // Minimized
+        platform.handle_window_event(io, &window, &WindowEvent::Resized(PhysicalSize::new(0, 0)));
+        assert_eq!(io.display_size, [800.0, 600.0]);
+        platform.handle_window_event(io, &window, &WindowEvent::Resized(PhysicalSize::new(0, 30)));
+        assert_eq!(io.display_size, [800.0, 600.0]);
+
+        platform.set_min_display_size(100.0, 50.0);
+        platform.handle_window_event(
+            io,
+            &window,
+            &WindowEvent::Resized(PhysicalSize::new(40, 30)),
+        );
+        assert_eq!(io.display_size, [100.0, 50.0]);
+    }
+
+    #[test]
+    fn test_identical_resizes_are_skipped() {
+        let (_guard, mut ctx) = test_ctx();
+        let mut platform = WinitPlatform::init(&mut ctx);
+        let window = SpyWindow::default();
+        let io = ctx.io_mut();
+        platform.attach(io, &window, HiDpiMode::Default);
+        let resized = WindowEvent::Resized(PhysicalSize::new(400, 300));
+
+        let updates = platform.display_size_update_count();
+        platform.handle_window_event(io, &window, &resized);
+        assert_eq!(io.display_size, [400.0, 300.0]);
+        assert_eq!(platform.display_size_update_count(), updates + 1);
+        platform.handle_window_event(io, &window, &resized);
+        assert_eq!(platform.display_size_update_count(), updates + 1);
+
+        // The same size is applied again once the minimum size changed
+        platform.set_min_display_size(500.0, 100.0);
+        platform.handle_window_event(io, &window, &resized);
+        assert_eq!(io.display_size, [500.0, 300.0]);
+        assert_eq!(platform.display_size_update_count(), updates + 2);
+    }
+
+    #[test]
+    fn test_attach_window_checked() {
+        let (_guard, mut ctx) = test_ctx();
+        let mut platform = WinitPlatform::init(&mut ctx);
+        let io = ctx.io_mut();
+
+        let window = SpyWindow {
+            scale_factor: 0.0,
+            ..SpyWindow::default()
+        };
+        assert_eq!(
+            platform.attach_checked(io, &window, HiDpiMode::Default),
+            Err(AttachWindowError::InvalidScaleFactor(0.0))
+        );
+        let window = SpyWindow {
+            inner_size: PhysicalSize::new(0, 0),
+            ..SpyWindow::default()
+        };
+        assert_eq!(
+            platform.attach_checked(io, &window, HiDpiMode::Default),
+            Err(AttachWindowError::ZeroSize(PhysicalSize::new(0, 0)))
+        );
+        assert_eq!(platform.hidpi_factor(), 1.0);
+        assert_eq!(io.display_size, [-1.0, -1.0]);
+
+        let window = SpyWindow {
+            scale_factor: 2.0,
+            ..SpyWindow::default()
+        };
+        assert_eq!(
+            platform.attach_checked(io, &window, HiDpiMode::Default),
+            Ok(())
+        );
+        assert_eq!(platform.hidpi_factor(), 2.0);
+        assert_eq!(io.display_size, [400.0, 300.0]);
+    }
+
+    #[test]
+    fn test_update_delta_time() {
+        let (_guard, mut ctx) = test_ctx();
+        let mut platform = WinitPlatform::init(&mut ctx);
+        let io = ctx.io_mut();
+        let initial_delta = io.delta_time;
+        let start = Instant::now();
+
+        platform.update_delta_time_at(io, start);
+        assert_eq!(io.delta_time, initial_delta);
+
+        let now = start + Duration::from_millis(20);
+        platform.update_delta_time_at(io, now);
+        assert_eq!(io.delta_time, 0.02);
+
+        // After a long pause
+        let now = now + Duration::from_secs(5);
+        platform.update_delta_time_at(io, now);
+        assert_eq!(io.delta_time, 0.1);
+
+        platform.set_max_delta(Duration::from_secs(1));
+        platform.update_delta_time_at(io, now + Duration::from_secs(5));
+        assert_eq!(io.delta_time, 1.0);
+
+        // Frames can't take no time at all
+        platform.update_delta_time_at(io, now);
+        assert!(io.delta_time > 0.0);
+    }
+
+    #[test]
+    fn test_next_frame_deadline() {
+        let (_guard, mut ctx) = test_ctx();
+        let mut platform = WinitPlatform::init(&mut ctx);
+        let start = Instant::now();
+        assert_eq!(platform.next_frame_deadline_at(60.0, start), start);
+
+        platform.update_delta_time_at(ctx.io_mut(), start);
+        let deadline = platform.next_frame_deadline_at(60.0, start);
+        let wait = deadline - start;
+        assert!(wait > Duration::from_micros(16_600) && wait < Duration::from_micros(16_700));
+        // Later in the frame, the deadline stays the same
+        let now = start + Duration::from_millis(10);
+        assert_eq!(platform.next_frame_deadline_at(60.0, now), deadline);
+
+        // The frame took too long
+        let now = start + Duration::from_millis(20);
+        assert_eq!(platform.next_frame_deadline_at(60.0, now), now);
+        assert_eq!(platform.next_frame_deadline_at(0.0, start), start);
+        assert_eq!(platform.next_frame_deadline_at(-60.0, start), start);
+        assert_eq!(platform.next_frame_deadline_at(f32::NAN, start), start);
+        // The period would overflow `Duration` or `Instant`
+        assert_eq!(platform.next_frame_deadline_at(1e-40, start), start);
+        assert_eq!(platform.next_frame_deadline_at(1e-30, start), start);
+        // No wait at an infinite rate
+        assert_eq!(platform.next_frame_deadline_at(f32::INFINITY, now), now);
+    }
+
+    #[test]
+    fn test_mark_redraw() {
+        let (_guard, mut ctx) = test_ctx();
+        let mut platform = WinitPlatform::init(&mut ctx);
+        let start = Instant::now();
+        assert_eq!(platform.last_fps(), 0.0);
+
+        for frame in 0..=10 {
+            platform.mark_redraw_at(start + Duration::from_millis(frame * 20));
+        }
+        assert_eq!(platform.frame_count(), 11);
+        assert!((platform.last_fps() - 50.0).abs() < 0.01);
+        assert_eq!(platform.frames_since(start), 10);
+        assert_eq!(platform.frames_since(start + Duration::from_millis(150)), 3);
+
+        // Frames older than a second are forgotten
+        let later = start + Duration::from_secs(3);
+        for frame in 0..=4 {
+            platform.mark_redraw_at(later + Duration::from_millis(frame * 100));
+        }
+        assert_eq!(platform.frame_count(), 16);
+        assert!((platform.last_fps() - 10.0).abs() < 0.01);
+        assert_eq!(platform.frames_since(start), 5);
+    }
+
+    #[test]
+    fn test_pressed_mouse_buttons() {
+        let (_guard, mut ctx) = test_ctx();
+        let mut platform = WinitPlatform::init(&mut ctx);
+        let window = SpyWindow::default();
+        let io = ctx.io_mut();
+        assert!(platform.pressed_mouse_buttons().is_empty());
+
+        for event in [
+            mouse_input(MouseButton::Left, ElementState::Pressed),
+            mouse_input(MouseButton::Right, ElementState::Pressed),
+            mouse_input(MouseButton::Other(9), ElementState::Pressed),
+        ] {
+            platform.handle_window_event(io, &window, &event);
+        }
+        assert!(platform.is_mouse_button_down(imgui::MouseButton::Left));
+        assert!(platform.is_mouse_button_down(imgui::MouseButton::Right));
+        assert!(!platform.is_mouse_button_down(imgui::MouseButton::Middle));
+        assert_eq!(
+            platform.pressed_mouse_buttons().iter().collect::<Vec<_>>(),
+            [imgui::MouseButton::Left, imgui::MouseButton::Right]
+        );
+
+        platform.handle_window_event(
+            io,
+            &window,
+            &mouse_input(MouseButton::Left, ElementState::Released),
+        );
+        assert!(!platform.is_mouse_button_down(imgui::MouseButton::Left));
+        assert!(platform.is_mouse_button_down(imgui::MouseButton::Right));
+
+        platform.handle_window_event(io, &window, &WindowEvent::Focused(false));
+        assert!(platform.pressed_mouse_buttons().is_empty());
+    }
+
+    #[test]
+    fn test_window_position() {
+        let (_guard, mut ctx) = test_ctx();
+        let mut platform = WinitPlatform::init(&mut ctx);
+        let window = SpyWindow::default();
+        assert_eq!(platform.window_position(), None);
+
+        for position in [
+            PhysicalPosition::new(120, 80),
+            PhysicalPosition::new(-1800, 40),
+        ] {
+            platform.handle_window_event(ctx.io_mut(), &window, &WindowEvent::Moved(position));
+            assert_eq!(platform.window_position(), Some(position));
+        }
+    }
+
+    #[test]
+    fn test_synthetic_display_size() {
+        let (_guard, mut ctx) = test_ctx();
+        let mut platform = WinitPlatform::init(&mut ctx);
+        platform.set_mouse_offset([10.0, 0.0]);
+        let io = ctx.io_mut();
+        io.mouse_pos = [10.0, 20.0];
+
+        // The cursor stays at (20, 20) physical pixels from the origin of the window
+        platform.set_display_size(io, [320.0, 240.0]);
+        platform.set_scale_factor(io, 2.0);
+        assert_eq!(io.display_size, [320.0, 240.0]);
+        assert_eq!(io.display_framebuffer_scale, [2.0, 2.0]);
+        assert_eq!(io.mouse_pos, [0.0, 10.0]);
+        assert_eq!(platform.hidpi_factor(), 2.0);
+
+        platform.set_scale_factor(io, 4.0);
+        assert_eq!(io.display_framebuffer_scale, [4.0, 4.0]);
+        assert_eq!(io.mouse_pos, [-5.0, 5.0]);
+        assert_eq!(platform.hidpi_factor(), 4.0);
+    }
+
+    #[test]
+    fn test_cursor_grabbed() {
+        let (_guard, mut ctx) = test_ctx();
+        ctx.fonts().build_rgba32_texture();
+        let mut platform = WinitPlatform::init(&mut ctx);
+        platform.set_display_size(ctx.io_mut(), [800.0, 600.0]);
+        let window = SpyWindow::default();
+        let calls = |window: &SpyWindow| {
+            (
+                window.cursor_visible_calls.borrow().len(),
+                window.cursor_icon_calls.borrow().len(),
+            )
+        };
+
+        platform.prepare_render_with(ctx.frame(), &window);
+        ctx.render();
+        let applied = calls(&window);
+        assert_ne!(applied, (0, 0));
+
+        platform.set_cursor_grabbed(true);
+        ctx.io_mut().mouse_draw_cursor = true;
+        platform.prepare_render_with(ctx.frame(), &window);
+        ctx.render();
+        assert_eq!(calls(&window), applied);
+
+        // imgui's cursor is applied again, even the properties that didn't change
+        ctx.io_mut().mouse_draw_cursor = false;
+        platform.set_cursor_grabbed(false);
+        platform.prepare_render_with(ctx.frame(), &window);
+        ctx.render();
+        assert_eq!(calls(&window), (applied.0 + 1, applied.1 + 1));
+    }
+
+    #[test]
+    fn test_cursor_icon_override() {
+        let (_guard, mut ctx) = test_ctx();
+        let mut platform = WinitPlatform::init(&mut ctx);
+        let window = SpyWindow::default();
+
+        platform.set_cursor_icon_override(Some(MouseCursor::Crosshair));
+        platform.apply_cursor(visible_cursor(imgui::MouseCursor::Arrow), &window);
+        platform.apply_cursor(visible_cursor(imgui::MouseCursor::TextInput), &window);
+        // The visibility requested by imgui is kept
+        platform.apply_cursor(
+            CursorSettings {
+                cursor: None,
+                draw_cursor: false,
+            },
+            &window,
+        );
+        platform.apply_cursor(visible_cursor(imgui::MouseCursor::Hand), &window);
+        assert_eq!(*window.cursor_visible_calls.borrow(), [true, false, true]);
+        assert_eq!(*window.cursor_icon_calls.borrow(), [MouseCursor::Crosshair]);
+
+        // imgui's icon is applied again once the override is removed
+        platform.set_cursor_icon_override(None);
+        assert_eq!(platform.cursor_icon_override(), None);
+        platform.apply_cursor(visible_cursor(imgui::MouseCursor::Hand), &window);
+        assert_eq!(
+            *window.cursor_icon_calls.borrow(),
+            [MouseCursor::Crosshair, MouseCursor::Hand]
+        );
+    }
+
+    #[test]
+    fn test_mouse_passthrough_key() {
+        let (_guard, mut ctx) = test_ctx();
+        ctx.fonts().build_rgba32_texture();
+        let mut platform = WinitPlatform::init(&mut ctx);
+        platform.set_mouse_passthrough_key(Some(VirtualKeyCode::Space));
+        let window = SpyWindow::default();
+        #[allow(deprecated)]
+        let cursor_moved = |x, y| WindowEvent::CursorMoved {
+            device_id: unsafe { winit::event::DeviceId::dummy() },
+            position: PhysicalPosition::new(x, y),
+            modifiers: Default::default(),
+        };
+
+        // A button pressed before holding the key
+        let io = ctx.io_mut();
+        io.display_size = [800.0, 600.0];
+        platform.handle_window_event(io, &window, &cursor_moved(10.0, 10.0));
+        platform.handle_window_event(
+            io,
+            &window,
+            &mouse_input(MouseButton::Right, ElementState::Pressed),
+        );
+        platform.handle_event_with(
+            io,
+            &window,
+            &key_input(VirtualKeyCode::Space, ElementState::Pressed),
+        );
+        ctx.frame();
+        ctx.render();
+
+        let io = ctx.io_mut();
+        platform.handle_window_event(io, &window, &cursor_moved(50.0, 50.0));
+        platform.handle_window_event(
+            io,
+            &window,
+            &mouse_input(MouseButton::Left, ElementState::Pressed),
+        );
+        platform.handle_window_event(
+            io,
+            &window,
+            &mouse_input(MouseButton::Right, ElementState::Released),
+        );
+        let ui = ctx.frame();
+        assert_eq!(ui.io().mouse_pos, [10.0, 10.0]);
+        assert!(!ui.is_mouse_down(imgui::MouseButton::Left));
+        assert!(!ui.is_mouse_down(imgui::MouseButton::Right));
+        assert!(ui.is_key_down(imgui::Key::Space));
+        ctx.render();
+
+        let io = ctx.io_mut();
+        platform.handle_event_with(
+            io,
+            &window,
+            &key_input(VirtualKeyCode::Space, ElementState::Released),
+        );
+        ctx.frame();
+        ctx.render();
+        platform.handle_window_event(ctx.io_mut(), &window, &cursor_moved(50.0, 50.0));
+        assert_eq!(ctx.frame().io().mouse_pos, [50.0, 50.0]);
+    }
+
+    #[cfg(feature = "log")]
+    #[test]
+    fn test_logging() {
+        /// Keeps the messages logged by every test, since the logger is global
+        struct TestLogger(Mutex<Vec<String>>);
+        impl log::Log for TestLogger {
+            fn enabled(&self, _: &log::Metadata<'_>) -> bool {
+                true
+            }
+            fn log(&self, record: &log::Record<'_>) {
+                self.0.lock().unwrap().push(record.args().to_string());
+            }
+            fn flush(&self) {}
+        }
+        static LOGGER: TestLogger = TestLogger(Mutex::new(Vec::new()));
+        log::set_logger(&LOGGER).unwrap();
+        log::set_max_level(log::LevelFilter::Trace);
+
+        let (_guard, mut ctx) = test_ctx();
+        let mut platform = WinitPlatform::init(&mut ctx);
+        let window = SpyWindow::default();
+        platform.handle_window_event(
+            ctx.io_mut(),
+            &window,
+            &mouse_input(MouseButton::Middle, ElementState::Pressed),
+        );
+
+        assert!(LOGGER
+            .0
+            .lock()
+            .unwrap()
+            .iter()
+            .any(|message| message == "mouse button Middle pressed"));
     }
 }
@@ -0,0 +1,81 @@
+//! Measures the cost of `DrawData::snapshot` on a heavy frame of about 50k vertices.
+//!
+//! Run with `cargo bench -p imgui --bench draw_data_snapshot`.
+
+use std::time::{Duration, Instant};
+
+use imgui::{Condition, Context, DrawData, Ui};
+
+const ITERATIONS: u32 = 200;
+const MIN_VERTICES: i32 = 50_000;
+
+/// Rows of each window, which keeps every draw list below the 64k vertices allowed by 16-bit
+/// indices
+const ROWS: usize = 50;
+
+fn build_ui(ui: &Ui, windows: usize) {
+    for window in 0..windows {
+        ui.window(format!("Bench {}", window))
+            .position([window as f32 * 10.0, 0.0], Condition::Always)
+            .size([600.0, 700.0], Condition::Always)
+            .build(|| {
+                let draw_list = ui.get_window_draw_list();
+                for row in 0..ROWS {
+                    ui.text(format!("Row {} with some text to draw", row));
+                    let y = row as f32 * 12.0;
+                    draw_list
+                        .add_rect([10.0, y], [200.0, y + 10.0], [1.0, 0.5, 0.0, 1.0])
+                        .filled(true)
+                        .rounding(3.0)
+                        .build();
+                    draw_list
+                        .add_circle([300.0, y], 5.0, [0.0, 0.5, 1.0, 1.0])
+                        .build();
+                }
+            });
+    }
+}
+
+fn run(draw_data: &DrawData) -> Duration {
+    let start = Instant::now();
+    for _ in 0..ITERATIONS {
+        std::hint::black_box(draw_data.snapshot());
+    }
+    start.elapsed() / ITERATIONS
+}
+
+fn main() {
+    let mut ctx = Context::create();
+    ctx.set_ini_filename(None);
+    let io = ctx.io_mut();
+    io.display_size = [1024.0, 768.0];
+    io.delta_time = 1.0 / 60.0;
+    ctx.fonts().build_rgba32_texture();
+
+    // Add windows until the frame is heavy enough. New windows are hidden during their first
+    // frame, so each count is rendered twice.
+    let mut windows = 1;
+    loop {
+        build_ui(ctx.frame(), windows);
+        ctx.render();
+        build_ui(ctx.frame(), windows);
+        if ctx.render().total_vtx_count >= MIN_VERTICES {
+            break;
+        }
+        windows += 1;
+    }
+    build_ui(ctx.frame(), windows);
+    let draw_data = ctx.render();
+
+    // warm up
+    run(draw_data);
+
+    let duration = run(draw_data);
+    println!(
+        "DrawData::snapshot, {} vertices and {} indices in {} draw lists:",
+        draw_data.total_vtx_count,
+        draw_data.total_idx_count,
+        draw_data.draw_lists_count()
+    );
+    println!("  {:?} per snapshot", duration);
+}
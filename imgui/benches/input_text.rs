@@ -0,0 +1,57 @@
+//! Measures the per-frame cost of `input_text_multiline` on a 1 MB string that is not being
+//! edited.
+//!
+//! Run with `cargo bench -p imgui --bench input_text`. The previous implementation scanned the
+//! buffer for the null terminator after every call to find the new length. The "old" numbers
+//! approximate it by running the current implementation followed by the same scan, so they
+//! only estimate the cost of the scan, not of the previous code as a whole.
+
+use std::time::{Duration, Instant};
+
+use imgui::Context;
+
+const FRAMES: u32 = 200;
+
+fn run(ctx: &mut Context, text: &mut String, old_write_back: bool) -> Duration {
+    let start = Instant::now();
+    for _ in 0..FRAMES {
+        {
+            let ui = ctx.frame();
+            let _window = ui.window("Bench").begin();
+            ui.input_text_multiline("##text", text, [400.0, 300.0])
+                .build();
+            if old_write_back {
+                // The scan the previous implementation did after calling imgui, on every frame.
+                // The buffer is already truncated here, so this scans the same bytes.
+                let len = text
+                    .as_bytes()
+                    .iter()
+                    .position(|x| *x == b'\0')
+                    .unwrap_or(text.len());
+                std::hint::black_box(len);
+            }
+        }
+        ctx.render();
+    }
+    start.elapsed() / FRAMES
+}
+
+fn main() {
+    let mut ctx = Context::create();
+    ctx.set_ini_filename(None);
+    let io = ctx.io_mut();
+    io.display_size = [1024.0, 768.0];
+    io.delta_time = 1.0 / 60.0;
+    ctx.fonts().build_rgba32_texture();
+
+    let mut text = "lorem ipsum dolor sit amet\n".repeat((1 << 20) / 27);
+
+    // warm up
+    run(&mut ctx, &mut text, false);
+
+    let old = run(&mut ctx, &mut text, true);
+    let new = run(&mut ctx, &mut text, false);
+    println!("input_text_multiline, 1 MB string, not edited:");
+    println!("  with the old null scan: {:?} per frame", old);
+    println!("  current write-back:     {:?} per frame", new);
+}
@@ -131,6 +131,79 @@ impl ImColor32 {
     const B_SHIFT: u32 = 16;
     /// The number of bits to shift the byte of the alpha channel. Always 24.
     const A_SHIFT: u32 = 24;
+
+    /// Construct a fully opaque color from hue, saturation and value in the range
+    /// `0.0 ..= 1.0`, like the color editors do.
+    ///
+    /// The hue wraps around, so `1.0` is red again.
+    ///
+    /// # Example
+    /// ```
+    /// # use imgui::ImColor32;
+    /// assert_eq!(ImColor32::from_hsv(1.0 / 3.0, 1.0, 1.0), ImColor32::from_rgb(0, 0xff, 0));
+    /// ```
+    pub fn from_hsv(h: f32, s: f32, v: f32) -> Self {
+        let [r, g, b] = color_convert_hsv_to_rgb(h, s, v);
+        Self::from_rgb_f32s(r, g, b)
+    }
+
+    /// Return the hue, saturation and value of the color, each in `0.0 ..= 1.0`.
+    ///
+    /// Grays (including black and white) have a hue and saturation of 0.0.
+    pub fn to_hsv(self) -> [f32; 3] {
+        let [r, g, b, _] = self.to_rgba_f32s();
+        color_convert_rgb_to_hsv(r, g, b)
+    }
+
+    /// Construct a fully opaque color from hue, saturation and lightness in the range
+    /// `0.0 ..= 1.0`.
+    ///
+    /// Unlike the value of [`Self::from_hsv`], a lightness of 1.0 is always white, and 0.5
+    /// gives the most saturated colors.
+    pub fn from_hsl(h: f32, s: f32, l: f32) -> Self {
+        let (s, l) = (saturate(s), saturate(l));
+        let v = l + s * l.min(1.0 - l);
+        let s = if v > 0.0 { 2.0 * (1.0 - l / v) } else { 0.0 };
+        Self::from_hsv(h, s, v)
+    }
+
+    /// Return the hue, saturation and lightness of the color, each in `0.0 ..= 1.0`.
+    pub fn to_hsl(self) -> [f32; 3] {
+        let [h, s, v] = self.to_hsv();
+        let l = v * (1.0 - s / 2.0);
+        let s = if l > 0.0 && l < 1.0 {
+            (v - l) / l.min(1.0 - l)
+        } else {
+            0.0
+        };
+        [h, s, l]
+    }
+
+    /// Return the color with its alpha replaced, from a value in the range `0.0 ..= 1.0`.
+    #[inline]
+    pub fn with_alpha(self, a: f32) -> Self {
+        let [r, g, b, _] = self.to_rgba();
+        Self::from_rgba(r, g, b, f32_to_u8_sat(a))
+    }
+
+    /// Return the color mixed with white: an amount of 0.0 leaves it unchanged, and 1.0 makes
+    /// it white. The alpha is kept.
+    pub fn lighten(self, amount: f32) -> Self {
+        self.mix_rgb(1.0, amount)
+    }
+
+    /// Return the color mixed with black: an amount of 0.0 leaves it unchanged, and 1.0 makes
+    /// it black. The alpha is kept.
+    pub fn darken(self, amount: f32) -> Self {
+        self.mix_rgb(0.0, amount)
+    }
+
+    fn mix_rgb(self, target: f32, amount: f32) -> Self {
+        let amount = saturate(amount);
+        let [r, g, b, a] = self.to_rgba_f32s();
+        let mix = |c: f32| c + (target - c) * amount;
+        Self::from_rgba_f32s(mix(r), mix(g), mix(b), a)
+    }
 }
 
 impl Default for ImColor32 {
@@ -270,6 +343,105 @@ impl From<ImColor32> for (f32, f32, f32, f32) {
     }
 }
 
+/// Converts a color from floats to the packed `0xAABBGGRR` format, rounding like imgui does
+/// for the colors of its widgets.
+#[doc(alias = "ColorConvertFloat4ToU32")]
+pub fn color_convert_float4_to_u32(rgba: [f32; 4]) -> u32 {
+    ImColor32::from(rgba).to_bits()
+}
+
+/// Converts a color from the packed `0xAABBGGRR` format to floats, like imgui does.
+#[doc(alias = "ColorConvertU32ToFloat4")]
+pub fn color_convert_u32_to_float4(color: u32) -> [f32; 4] {
+    ImColor32::from_bits(color).to_rgba_f32s()
+}
+
+/// Converts a color from RGB to HSV, with all components in `0.0 ..= 1.0`.
+///
+/// This is a port of imgui's own conversion, so it gives the same results as the color
+/// editors.
+#[doc(alias = "ColorConvertRGBtoHSV")]
+pub fn color_convert_rgb_to_hsv(mut r: f32, mut g: f32, mut b: f32) -> [f32; 3] {
+    let mut k = 0.0;
+    if g < b {
+        std::mem::swap(&mut g, &mut b);
+        k = -1.0;
+    }
+    if r < g {
+        std::mem::swap(&mut r, &mut g);
+        k = -2.0 / 6.0 - k;
+    }
+    let chroma = r - g.min(b);
+    let h = (k + (g - b) / (6.0 * chroma + 1e-20)).abs();
+    let s = chroma / (r + 1e-20);
+    [h, s, r]
+}
+
+/// Converts a color from HSV to RGB, with all components in `0.0 ..= 1.0`. The hue wraps
+/// around.
+///
+/// This is a port of imgui's own conversion, so it gives the same results as the color
+/// editors.
+#[doc(alias = "ColorConvertHSVtoRGB")]
+pub fn color_convert_hsv_to_rgb(h: f32, s: f32, v: f32) -> [f32; 3] {
+    if s == 0.0 {
+        // Gray
+        return [v, v, v];
+    }
+    let h = (h % 1.0) / (60.0 / 360.0);
+    let i = h as i32;
+    let f = h - i as f32;
+    let p = v * (1.0 - s);
+    let q = v * (1.0 - s * f);
+    let t = v * (1.0 - s * (1.0 - f));
+    match i {
+        0 => [v, t, p],
+        1 => [q, v, p],
+        2 => [p, v, t],
+        3 => [p, q, v],
+        4 => [t, p, v],
+        _ => [v, p, q],
+    }
+}
+
+/// Generators of visually distinct colors, e.g. for the categories of a graph or the
+/// severities of a log.
+///
+/// The colors are saturated enough to tell apart, and bright enough to read on the
+/// default dark style.
+#[derive(Copy, Clone, Debug)]
+pub struct Palette;
+
+impl Palette {
+    const SATURATION: f32 = 0.65;
+    const VALUE: f32 = 0.9;
+
+    /// Returns `n` colors with hues spread by the golden ratio.
+    ///
+    /// The first colors are the same whatever `n` is, so this suits sets which grow over
+    /// time, e.g. with `Palette::golden_ratio(index + 1)[index]`. Consecutive colors are
+    /// always far apart, but some colors get close to each other as `n` grows.
+    pub fn golden_ratio(n: usize) -> Vec<ImColor32> {
+        const GOLDEN_RATIO_CONJUGATE: f32 = 0.618_034;
+        (0..n)
+            .map(|i| {
+                let h = (i as f32 * GOLDEN_RATIO_CONJUGATE).fract();
+                ImColor32::from_hsv(h, Self::SATURATION, Self::VALUE)
+            })
+            .collect()
+    }
+
+    /// Returns `n` colors with evenly spaced hues, starting from red.
+    ///
+    /// This gives the most distinct colors for a set of known size, but every color changes
+    /// with `n`.
+    pub fn categorical(n: usize) -> Vec<ImColor32> {
+        (0..n)
+            .map(|i| ImColor32::from_hsv(i as f32 / n as f32, Self::SATURATION, Self::VALUE))
+            .collect()
+    }
+}
+
 // These utilities might be worth making `pub` as free functions in
 // `crate::color` so user code can ensure their numeric handling is
 // consistent...
@@ -377,3 +549,136 @@ fn test_saturate_all_u8s() {
         assert_eq!(u, v);
     }
 }
+
+#[test]
+fn test_hsv_conversion() {
+    // (r, g, b) and (h, s, v) pairs
+    let references = [
+        ([1.0, 0.0, 0.0], [0.0, 1.0, 1.0]),
+        ([0.0, 1.0, 0.0], [1.0 / 3.0, 1.0, 1.0]),
+        ([0.0, 0.0, 1.0], [2.0 / 3.0, 1.0, 1.0]),
+        ([1.0, 1.0, 0.0], [1.0 / 6.0, 1.0, 1.0]),
+        ([0.5, 0.25, 0.25], [0.0, 0.5, 0.5]),
+        // Grays have no hue nor saturation
+        ([0.0, 0.0, 0.0], [0.0, 0.0, 0.0]),
+        ([0.5, 0.5, 0.5], [0.0, 0.0, 0.5]),
+        ([1.0, 1.0, 1.0], [0.0, 0.0, 1.0]),
+    ];
+    for ([r, g, b], [h, s, v]) in references {
+        let hsv = color_convert_rgb_to_hsv(r, g, b);
+        assert!(
+            hsv.iter().zip([h, s, v]).all(|(a, b)| (a - b).abs() < 1e-6),
+            "{:?} -> {:?}",
+            [r, g, b],
+            hsv
+        );
+        let rgb = color_convert_hsv_to_rgb(h, s, v);
+        assert!(
+            rgb.iter().zip([r, g, b]).all(|(a, b)| (a - b).abs() < 1e-6),
+            "{:?} -> {:?}",
+            [h, s, v],
+            rgb
+        );
+    }
+    // The hue wraps around
+    assert_eq!(color_convert_hsv_to_rgb(1.0, 1.0, 1.0), [1.0, 0.0, 0.0]);
+    assert_eq!(
+        color_convert_hsv_to_rgb(1.25, 0.5, 1.0),
+        color_convert_hsv_to_rgb(0.25, 0.5, 1.0)
+    );
+    assert_eq!(
+        ImColor32::from_hsv(5.0 / 6.0, 1.0, 1.0),
+        ImColor32::from_rgb(0xff, 0, 0xff)
+    );
+
+    // Same results as imgui, over a grid of colors
+    let steps = [0.0, 0.1, 0.25, 0.5, 0.7, 0.9, 1.0];
+    for r in steps {
+        for g in steps {
+            for b in steps {
+                let mut hsv = [0.0; 3];
+                unsafe {
+                    crate::sys::igColorConvertRGBtoHSV(
+                        r,
+                        g,
+                        b,
+                        &mut hsv[0],
+                        &mut hsv[1],
+                        &mut hsv[2],
+                    );
+                }
+                assert_eq!(color_convert_rgb_to_hsv(r, g, b), hsv);
+
+                let (h, s, v) = (r * 1.5 - 0.25, g, b);
+                let mut rgb = [0.0; 3];
+                unsafe {
+                    crate::sys::igColorConvertHSVtoRGB(
+                        h,
+                        s,
+                        v,
+                        &mut rgb[0],
+                        &mut rgb[1],
+                        &mut rgb[2],
+                    );
+                }
+                assert_eq!(color_convert_hsv_to_rgb(h, s, v), rgb);
+            }
+        }
+    }
+
+    for bits in [0, 0x80ff_4020, 0xffff_ffff, 0x1234_5678] {
+        let floats = color_convert_u32_to_float4(bits);
+        let mut sys_floats = crate::sys::ImVec4::zero();
+        unsafe { crate::sys::igColorConvertU32ToFloat4(&mut sys_floats, bits) };
+        assert_eq!(floats, <[f32; 4]>::from(sys_floats));
+        assert_eq!(color_convert_float4_to_u32(floats), bits);
+        assert_eq!(color_convert_float4_to_u32(floats), unsafe {
+            crate::sys::igColorConvertFloat4ToU32(sys_floats)
+        });
+    }
+}
+
+#[test]
+fn test_color_helpers() {
+    let color = ImColor32::from_rgba(0x40, 0x80, 0xc0, 0xff);
+    let [h, s, v] = color.to_hsv();
+    assert_eq!(ImColor32::from_hsv(h, s, v), color);
+    let [h, s, l] = color.to_hsl();
+    assert!((l - 0.5).abs() < 0.01);
+    assert_eq!(ImColor32::from_hsl(h, s, l), color);
+    assert_eq!(ImColor32::from_hsl(0.3, 1.0, 1.0), ImColor32::WHITE);
+    assert_eq!(
+        ImColor32::from_hsl(0.0, 1.0, 0.5),
+        ImColor32::from_rgb(0xff, 0, 0)
+    );
+    assert_eq!(ImColor32::WHITE.to_hsl(), [0.0, 0.0, 1.0]);
+
+    assert_eq!(color.with_alpha(0.5).to_rgba(), [0x40, 0x80, 0xc0, 0x80]);
+    assert_eq!(color.lighten(0.0), color);
+    assert_eq!(color.lighten(1.0), ImColor32::WHITE);
+    assert_eq!(color.darken(1.0), ImColor32::BLACK);
+    assert_eq!(
+        color.with_alpha(0.5).darken(0.5).to_rgba(),
+        [0x20, 0x40, 0x60, 0x80]
+    );
+}
+
+#[test]
+fn test_palette() {
+    let categorical = Palette::categorical(6);
+    assert_eq!(categorical.len(), 6);
+    assert_eq!(categorical[0].to_rgba(), [230, 80, 80, 255]);
+    let hues: Vec<f32> = categorical.iter().map(|c| c.to_hsv()[0]).collect();
+    for (i, h) in hues.iter().enumerate() {
+        assert!((h - i as f32 / 6.0).abs() < 0.01, "{:?}", hues);
+    }
+
+    // Growing the set keeps the existing colors
+    let golden = Palette::golden_ratio(10);
+    assert_eq!(Palette::golden_ratio(3), golden[..3]);
+    for pair in golden.windows(2) {
+        let distance = (pair[0].to_hsv()[0] - pair[1].to_hsv()[0]).abs();
+        assert!(distance.min(1.0 - distance) > 0.3);
+    }
+    assert!(Palette::categorical(0).is_empty());
+}
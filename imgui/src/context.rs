@@ -8,6 +8,7 @@ use std::ptr;
 use crate::clipboard::{ClipboardBackend, ClipboardContext};
 use crate::fonts::atlas::{FontAtlas, FontId, SharedFontAtlas};
 use crate::io::Io;
+use crate::shell::OpenInShell;
 use crate::style::Style;
 use crate::{sys, DrawData};
 use crate::{MouseCursor, Ui};
@@ -213,6 +214,14 @@ impl Context {
         let data = unsafe { CStr::from_ptr(sys::igSaveIniSettingsToMemory(ptr::null_mut())) };
         buf.push_str(&data.to_string_lossy());
     }
+    /// Sets the callback used by [`Ui::text_link_open_url`] to open URLs and files.
+    ///
+    /// With the `open-url` feature, new contexts open them with the usual platform command,
+    /// using the [`open`](https://docs.rs/open) crate. Otherwise, no callback is set by default
+    /// and links do nothing when clicked.
+    pub fn set_open_in_shell_callback<F: FnMut(&str) + 'static>(&mut self, callback: F) {
+        self.ui.open_in_shell.set(callback);
+    }
     /// Sets the clipboard backend used for clipboard operations
     pub fn set_clipboard_backend<T: ClipboardBackend>(&mut self, backend: T) {
         let clipboard_ctx: Box<UnsafeCell<_>> = Box::new(ClipboardContext::new(backend).into());
@@ -256,6 +265,8 @@ impl Context {
             )),
             ui: Ui {
                 buffer: UnsafeCell::new(crate::string::UiBuffer::new(1024)),
+                open_in_shell: OpenInShell::platform_default(),
+                draw_callbacks: Default::default(),
             },
         }
     }
@@ -276,6 +287,13 @@ impl Drop for Context {
             if !sys::igGetCurrentContext().is_null() && sys::igGetFrameCount() > 0 {
                 sys::igEndFrame();
             }
+            // the atlas is destroyed with the context, unless it's shared
+            if self.shared_font_atlas.is_none() {
+                let prev_ctx = sys::igGetCurrentContext();
+                sys::igSetCurrentContext(self.raw);
+                (*(*sys::igGetIO()).Fonts.cast::<FontAtlas>()).release_glyph_ranges();
+                sys::igSetCurrentContext(prev_ctx);
+            }
             sys::igDestroyContext(self.raw);
         }
     }
@@ -321,6 +339,7 @@ impl SuspendedContext {
     /// If there is already an active context, nothing happens and `Err` is returned, containing
     /// the original suspended context.
     #[doc(alias = "SetCurrentContext")]
+    #[allow(clippy::result_large_err)] // the suspended context is handed back as is on failure
     pub fn activate(self) -> Result<Context, SuspendedContext> {
         let _guard = CTX_MUTEX.lock();
         if no_current_context() {
@@ -353,6 +372,8 @@ impl SuspendedContext {
             )),
             ui: Ui {
                 buffer: UnsafeCell::new(crate::string::UiBuffer::new(1024)),
+                open_in_shell: OpenInShell::platform_default(),
+                draw_callbacks: Default::default(),
             },
         };
         if ctx.is_current_context() {
@@ -550,6 +571,8 @@ impl Context {
         unsafe {
             sys::igNewFrame();
         }
+        // The draw data of the previous frame, which referenced them, is gone
+        self.ui.draw_callbacks.clear();
 
         &mut self.ui
     }
@@ -1,15 +1,226 @@
 use std::ptr::null;
 
-use crate::Ui;
+use bitflags::bitflags;
+
+use crate::math::MintVec2;
+use crate::sys;
+use crate::{Id, Ui, ViewportFlags};
+
+bitflags! {
+    /// Flags for dock nodes and dockspaces
+    #[repr(transparent)]
+    pub struct DockNodeFlags: u32 {
+        /// Don't display the dockspace node but keep it alive. Windows docked into it are
+        /// undocked.
+        const KEEP_ALIVE_ONLY = sys::ImGuiDockNodeFlags_KeepAliveOnly;
+        /// Disable docking inside the central node, which is always kept empty
+        const NO_DOCKING_IN_CENTRAL_NODE = sys::ImGuiDockNodeFlags_NoDockingInCentralNode;
+        /// Let the background of the empty central node show through
+        const PASSTHRU_CENTRAL_NODE = sys::ImGuiDockNodeFlags_PassthruCentralNode;
+        /// Disable splitting the node into smaller nodes
+        const NO_SPLIT = sys::ImGuiDockNodeFlags_NoSplit;
+        /// Disable resizing the node with the splitters
+        const NO_RESIZE = sys::ImGuiDockNodeFlags_NoResize;
+        /// Hide the tab bar when the node holds a single window
+        const AUTO_HIDE_TAB_BAR = sys::ImGuiDockNodeFlags_AutoHideTabBar;
+    }
+}
+
+/// Identifies a dock node, such as a dockspace.
+///
+/// The id of a dockspace is the [`Id`] it was created with.
+#[repr(transparent)]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub struct DockId(pub(crate) u32);
+
+impl DockId {
+    /// Returns the raw `ImGuiID` value, e.g. to pass it to `imgui::sys` functions.
+    #[inline]
+    pub const fn raw(self) -> u32 {
+        self.0
+    }
+}
+
+impl From<Id> for DockId {
+    fn from(id: Id) -> Self {
+        DockId(id.0)
+    }
+}
+
+/// Restricts where a window can dock, and how its viewport behaves.
+///
+/// Windows can only be dragged into dockspaces and next to windows of the same class. A
+/// window without a class (class id 0) can still be dragged into a classed dockspace, unless
+/// the class of the dockspace disables it with [`WindowClass::docking_allow_unclassed`].
+///
+/// Set with [`Window::window_class`](crate::Window::window_class), or when creating a
+/// dockspace with [`Ui::dockspace`].
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct WindowClass {
+    class_id: Id,
+    docking_allow_unclassed: bool,
+    dock_node_flags_override: DockNodeFlags,
+    viewport_flags_override_set: ViewportFlags,
+    viewport_flags_override_clear: ViewportFlags,
+}
+
+impl Default for WindowClass {
+    fn default() -> Self {
+        WindowClass {
+            class_id: Id(0),
+            docking_allow_unclassed: true,
+            dock_node_flags_override: DockNodeFlags::empty(),
+            viewport_flags_override_set: ViewportFlags::empty(),
+            viewport_flags_override_clear: ViewportFlags::empty(),
+        }
+    }
+}
+
+impl WindowClass {
+    /// Creates a window class with the given id, e.g. from [`Ui::get_id`].
+    pub fn new(class_id: Id) -> Self {
+        WindowClass::default().class_id(class_id)
+    }
+    /// Sets the class id. Windows only dock with windows and dockspaces of the same class.
+    pub fn class_id(mut self, class_id: Id) -> Self {
+        self.class_id = class_id;
+        self
+    }
+    /// Sets whether windows without a class can dock with this class. Enabled by default.
+    pub fn docking_allow_unclassed(mut self, allow: bool) -> Self {
+        self.docking_allow_unclassed = allow;
+        self
+    }
+    /// Sets flags added to the dock node hosting the window.
+    pub fn dock_node_flags_override(mut self, flags: DockNodeFlags) -> Self {
+        self.dock_node_flags_override = flags;
+        self
+    }
+    /// Sets flags added to the viewport of the window, when it is outside the main viewport.
+    pub fn viewport_flags_override_set(mut self, flags: ViewportFlags) -> Self {
+        self.viewport_flags_override_set = flags;
+        self
+    }
+    /// Sets flags removed from the viewport of the window, when it is outside the main
+    /// viewport.
+    pub fn viewport_flags_override_clear(mut self, flags: ViewportFlags) -> Self {
+        self.viewport_flags_override_clear = flags;
+        self
+    }
+
+    pub(crate) fn raw(&self) -> sys::ImGuiWindowClass {
+        sys::ImGuiWindowClass {
+            ClassId: self.class_id.0,
+            // No parent viewport
+            ParentViewportId: u32::MAX,
+            ViewportFlagsOverrideSet: self.viewport_flags_override_set.bits() as i32,
+            ViewportFlagsOverrideClear: self.viewport_flags_override_clear.bits() as i32,
+            TabItemFlagsOverrideSet: 0,
+            DockNodeFlagsOverrideSet: self.dock_node_flags_override.bits() as i32,
+            DockingAlwaysTabBar: false,
+            DockingAllowUnclassed: self.docking_allow_unclassed,
+        }
+    }
+}
 
 impl Ui {
-    pub fn dockspace_over_main_viewport(&self) {
-        unsafe {
+    /// Creates a dockspace covering the main viewport, which windows can be docked into.
+    ///
+    /// Returns the id of the dockspace, e.g. to dock windows into it with
+    /// [`Window::dock_id`](crate::Window::dock_id).
+    #[doc(alias = "DockSpaceOverViewport")]
+    pub fn dockspace_over_main_viewport(&self) -> DockId {
+        let id = unsafe {
             sys::igDockSpaceOverViewport(
                 sys::igGetMainViewport(),
                 sys::ImGuiDockNodeFlags_PassthruCentralNode as i32,
                 null(),
-            );
-        }
+            )
+        };
+        DockId(id)
+    }
+
+    /// Creates a dockspace in the current window, which windows can be docked into.
+    ///
+    /// A size of 0.0 fills the available space on that axis. With a window class, only
+    /// windows of that class (and windows without a class, if allowed) can dock into the
+    /// dockspace.
+    ///
+    /// A dockspace must be submitted every frame, before the windows docked into it, for
+    /// them to stay docked.
+    #[doc(alias = "DockSpace")]
+    pub fn dockspace(
+        &self,
+        id: Id,
+        size: impl Into<MintVec2>,
+        flags: DockNodeFlags,
+        window_class: Option<&WindowClass>,
+    ) -> DockId {
+        let window_class = window_class.map(WindowClass::raw);
+        let id = unsafe {
+            sys::igDockSpace(
+                id.0,
+                size.into().into(),
+                flags.bits() as i32,
+                window_class
+                    .as_ref()
+                    .map_or(null(), |class| class as *const _),
+            )
+        };
+        DockId(id)
     }
 }
+
+#[test]
+fn test_window_class_docking() {
+    let (_guard, mut ctx) = crate::test::test_ctx_initialized();
+    ctx.io_mut().config_flags |= crate::ConfigFlags::DOCKING_ENABLE;
+
+    let mut results = Vec::new();
+    for _ in 0..3 {
+        let ui = ctx.frame();
+        let documents = WindowClass::new(ui.get_id("Documents")).docking_allow_unclassed(false);
+        let dockspace = ui
+            .window("Editor")
+            .size([400.0, 300.0], crate::Condition::Always)
+            .build(|| {
+                let id = ui.get_id("Dockspace");
+                let dockspace =
+                    ui.dockspace(id, [0.0, 0.0], DockNodeFlags::empty(), Some(&documents));
+                assert_eq!(dockspace, DockId::from(id));
+                dockspace
+            })
+            .unwrap();
+        let document = ui
+            .window("Document")
+            .window_class(&documents)
+            .dock_id(dockspace, crate::Condition::Always)
+            .build(|| (ui.is_window_docked(), ui.window_dock_id()));
+        let tool = ui
+            .window("Tool")
+            .build(|| (ui.is_window_docked(), ui.window_dock_id()));
+        results.push((dockspace, document, tool));
+        ctx.render();
+    }
+
+    let (dockspace, document, tool) = results[2];
+    assert_eq!(document, Some((true, Some(dockspace))));
+    assert_eq!(tool, Some((false, None)));
+
+    let raw = WindowClass::new(Id(7))
+        .docking_allow_unclassed(false)
+        .dock_node_flags_override(DockNodeFlags::NO_SPLIT)
+        .viewport_flags_override_set(ViewportFlags::NO_DECORATION)
+        .raw();
+    assert_eq!(raw.ClassId, 7);
+    assert!(!raw.DockingAllowUnclassed);
+    assert_eq!(
+        raw.DockNodeFlagsOverrideSet,
+        sys::ImGuiDockNodeFlags_NoSplit as i32
+    );
+    assert_eq!(
+        raw.ViewportFlagsOverrideSet,
+        sys::ImGuiViewportFlags_NoDecoration as i32
+    );
+    assert_eq!(raw.ViewportFlagsOverrideClear, 0);
+}
@@ -24,6 +24,10 @@
 //! 3.  An unsafe implementation is provided which allows for any data to be unsafely copied. Note that once
 //!     you use this method, the safe implementations in #1 and #2 can create memory unsafety problems; notably,
 //!     they both assume that a payload has certain header information within it.
+//! 4.  With the `serde` feature, any `Serialize` type can be sent with
+//!     `DragDropSource::begin_payload_serde`. The data is serialized into the payload
+//!     buffer, and deserialized again by `DragDropTarget::accept_payload_serde`, so
+//!     non-Copy types such as `String` or `Vec` can be transferred safely.
 //!
 //! For examples of each payload type, see [DragDropSource].
 use std::{any, ffi, marker::PhantomData};
@@ -292,6 +296,77 @@ impl<'ui, T: AsRef<str>> DragDropSource<'ui, T> {
     }
 }
 
+#[cfg(feature = "serde")]
+impl<'ui, T: AsRef<str>> DragDropSource<'ui, T> {
+    /// Creates the source of a drag with a serialized payload, and returns a handle on the tooltip.
+    ///
+    /// This works like [begin_payload](Self::begin_payload), but the payload can be any
+    /// `Serialize` type. It is serialized into the buffer held by ImGui only while a drag is
+    /// actually happening, and can be received with
+    /// [accept_payload_serde](DragDropTarget::accept_payload_serde).
+    ///
+    /// ```no_run
+    /// # use imgui::*;
+    /// #[derive(serde::Serialize, serde::Deserialize)]
+    /// struct Asset {
+    ///     path: String,
+    ///     tags: Vec<String>,
+    /// }
+    ///
+    /// fn show_ui(ui: &Ui, asset: &Asset) {
+    ///     ui.button("Drag me!");
+    ///     if let Some(tooltip) = ui.drag_drop_source_config("ASSET").begin_payload_serde(asset) {
+    ///         ui.text(&asset.path);
+    ///         tooltip.end();
+    ///     }
+    ///
+    ///     ui.button("Target me!");
+    ///     if let Some(target) = ui.drag_drop_target() {
+    ///         if let Some(Ok(payload)) =
+    ///             target.accept_payload_serde::<Asset, _>("ASSET", DragDropFlags::empty())
+    ///         {
+    ///             println!("Received {}", payload.data.path);
+    ///         }
+    ///     }
+    /// }
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if the payload fails to serialize (for example, a map with non-string keys).
+    pub fn begin_payload_serde<P: serde::Serialize + ?Sized>(
+        self,
+        payload: &P,
+    ) -> Option<DragDropSourceToolTip<'ui>> {
+        let should_begin = unsafe { sys::igBeginDragDropSource(self.flags.bits() as i32) };
+        if !should_begin {
+            return None;
+        }
+
+        let header = TypedPayloadHeader::new::<SerdePayload>();
+        let mut buf = Vec::with_capacity(std::mem::size_of::<TypedPayloadHeader>());
+        // SAFETY: the header is plain data, and is only ever read back as a whole with
+        // `read_unaligned` in this process.
+        buf.extend_from_slice(unsafe {
+            std::slice::from_raw_parts(
+                &header as *const _ as *const u8,
+                std::mem::size_of::<TypedPayloadHeader>(),
+            )
+        });
+        serde_json::to_writer(&mut buf, payload).expect("failed to serialize drag drop payload");
+
+        unsafe {
+            sys::igSetDragDropPayload(
+                self.ui.scratch_txt(&self.name),
+                buf.as_ptr() as *const ffi::c_void,
+                buf.len(),
+                self.cond as i32,
+            );
+        }
+        Some(DragDropSourceToolTip::push())
+    }
+}
+
 /// A helper struct for RAII drap-drop support.
 pub struct DragDropSourceToolTip<'ui>(PhantomData<&'ui Ui>);
 
@@ -408,11 +483,12 @@ impl<'ui> DragDropTarget<'ui> {
         // convert the unsafe payload to our Result
         output.map(|unsafe_payload| {
             // sheering off the typeid...
-            let received =
-                unsafe { (unsafe_payload.data as *const TypedPayloadHeader).read_unaligned() };
+            let received = unsafe_payload.header();
             let expected = any::TypeId::of::<T>();
 
-            if received.type_id == expected {
+            if received.type_id == expected
+                && unsafe_payload.size == std::mem::size_of::<TypedPayload<T>>()
+            {
                 let data =
                     unsafe { (unsafe_payload.data as *const TypedPayload<T>).read_unaligned() }
                         .data;
@@ -430,6 +506,47 @@ impl<'ui> DragDropTarget<'ui> {
         })
     }
 
+    /// Accepts a payload sent with [begin_payload_serde](DragDropSource::begin_payload_serde),
+    /// deserializing it into a `T`.
+    ///
+    /// Like with [accept_payload](Self::accept_payload), passing
+    /// [ACCEPT_BEFORE_DELIVERY](DragDropFlags::ACCEPT_BEFORE_DELIVERY) makes this return the
+    /// payload while it is only hovering the target (`preview`), before it is dropped
+    /// (`delivery`). Since the payload is deserialized on every call, prefer peeking only when
+    /// the preview needs the data.
+    #[cfg(feature = "serde")]
+    pub fn accept_payload_serde<T: serde::de::DeserializeOwned, Name: AsRef<str>>(
+        &self,
+        name: Name,
+        flags: DragDropFlags,
+    ) -> Option<Result<DragDropPayloadSerde<T>, PayloadDeserializeError>> {
+        let output = unsafe { self.accept_payload_unchecked(name, flags) };
+
+        output.map(|unsafe_payload| {
+            let received = unsafe_payload.header();
+            if received.type_id != any::TypeId::of::<SerdePayload>() {
+                return Err(PayloadDeserializeError::WrongType(PayloadIsWrongType {
+                    received,
+                    expected: TypedPayloadHeader::new::<SerdePayload>(),
+                }));
+            }
+
+            let header_size = std::mem::size_of::<TypedPayloadHeader>();
+            // SAFETY: the header matched, so the payload was created by `begin_payload_serde`,
+            // and ImGui owns a copy of `size` bytes.
+            let bytes = unsafe {
+                std::slice::from_raw_parts(unsafe_payload.data as *const u8, unsafe_payload.size)
+            };
+            let data = serde_json::from_slice(&bytes[header_size..])
+                .map_err(PayloadDeserializeError::Deserialize)?;
+            Ok(DragDropPayloadSerde {
+                data,
+                preview: unsafe_payload.preview,
+                delivery: unsafe_payload.delivery,
+            })
+        })
+    }
+
     /// Accepts a drag and drop payload  which contains a raw pointer to [c_void](std::ffi::c_void)
     /// and a size in bytes. Users should generally avoid using this function
     /// if one of the safer variants is acceptable.
@@ -539,6 +656,41 @@ pub struct DragDropPayload {
     pub delivery: bool,
 }
 
+impl DragDropPayload {
+    /// Reads the header of a typed payload, without trusting the payload to have one.
+    fn header(&self) -> TypedPayloadHeader {
+        if self.data.is_null() || self.size < std::mem::size_of::<TypedPayloadHeader>() {
+            TypedPayloadHeader::new::<RawPayload>()
+        } else {
+            unsafe { (self.data as *const TypedPayloadHeader).read_unaligned() }
+        }
+    }
+}
+
+/// A DragDropPayload with status information and deserialized data in it.
+#[cfg(feature = "serde")]
+#[derive(Debug, Clone)]
+#[non_exhaustive]
+pub struct DragDropPayloadSerde<T> {
+    /// The deserialized data.
+    pub data: T,
+
+    /// Set when [`accept_payload_serde`](DragDropTarget::accept_payload_serde) was called
+    /// and mouse has been hovering the target item.
+    pub preview: bool,
+
+    /// Set when [`accept_payload_serde`](DragDropTarget::accept_payload_serde) was
+    /// called and mouse button is released over the target item.
+    pub delivery: bool,
+}
+
+/// Marks payloads which are too small to have been sent with a typed header.
+struct RawPayload;
+
+/// Marks payloads sent with [begin_payload_serde](DragDropSource::begin_payload_serde).
+#[cfg(feature = "serde")]
+struct SerdePayload;
+
 /// A typed payload.
 #[derive(Debug, Clone, Copy)]
 #[repr(C)]
@@ -611,3 +763,167 @@ impl std::fmt::Display for PayloadIsWrongType {
 }
 
 impl std::error::Error for PayloadIsWrongType {}
+
+/// Indicates that a payload could not be received by
+/// [accept_payload_serde](DragDropTarget::accept_payload_serde).
+#[cfg(feature = "serde")]
+#[derive(Debug)]
+pub enum PayloadDeserializeError {
+    /// The payload was not sent with [begin_payload_serde](DragDropSource::begin_payload_serde).
+    WrongType(PayloadIsWrongType),
+    /// The payload could not be deserialized into the requested type.
+    Deserialize(serde_json::Error),
+}
+
+#[cfg(feature = "serde")]
+impl std::fmt::Display for PayloadDeserializeError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PayloadDeserializeError::WrongType(e) => e.fmt(f),
+            PayloadDeserializeError::Deserialize(e) => {
+                write!(f, "Failed to deserialize payload: {}", e)
+            }
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl std::error::Error for PayloadDeserializeError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            PayloadDeserializeError::WrongType(e) => Some(e),
+            PayloadDeserializeError::Deserialize(e) => Some(e),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Context;
+
+    /// Runs a frame with an external drag source and a target under the mouse, so the payload
+    /// can be peeked at within the same frame.
+    fn drag_drop_frame(
+        ctx: &mut Context,
+        source: impl FnOnce(DragDropSource<'_, &str>),
+        target: impl FnOnce(&DragDropTarget<'_>),
+    ) {
+        let ui = ctx.frame();
+        let _window = ui
+            .window("Example")
+            .position([0.0, 0.0], Condition::Always)
+            .size([300.0, 300.0], Condition::Always)
+            .begin();
+        source(
+            ui.drag_drop_source_config("PAYLOAD")
+                .flags(DragDropFlags::SOURCE_EXTERN),
+        );
+        ui.button_with_size("Target", [200.0, 200.0]);
+        if let Some(drop_target) = ui.drag_drop_target() {
+            target(&drop_target);
+        }
+    }
+
+    fn test_ctx() -> (parking_lot::ReentrantMutexGuard<'static, ()>, Context) {
+        let (guard, mut ctx) = crate::test::test_ctx_initialized();
+        ctx.io_mut().mouse_pos = [100.0, 100.0];
+        // let imgui know where the window is before hovering it
+        drag_drop_frame(&mut ctx, |_| {}, |_| {});
+        ctx.render();
+        (guard, ctx)
+    }
+
+    #[test]
+    fn test_pod_payload_round_trip() {
+        #[derive(Debug, Clone, Copy, PartialEq)]
+        struct Item {
+            id: u32,
+            weight: f32,
+        }
+
+        let (_guard, mut ctx) = test_ctx();
+        let mut received = None;
+        drag_drop_frame(
+            &mut ctx,
+            |source| {
+                source.begin_payload(Item { id: 7, weight: 0.5 });
+            },
+            |target| {
+                assert!(target
+                    .accept_payload::<Item, _>("PAYLOAD", DragDropFlags::empty())
+                    .is_none());
+                assert!(target
+                    .accept_payload::<u32, _>("PAYLOAD", DragDropFlags::ACCEPT_BEFORE_DELIVERY)
+                    .unwrap()
+                    .is_err());
+                received = target
+                    .accept_payload::<Item, _>("PAYLOAD", DragDropFlags::ACCEPT_BEFORE_DELIVERY);
+            },
+        );
+        let payload = received.unwrap().unwrap();
+        assert_eq!(payload.data, Item { id: 7, weight: 0.5 });
+        assert!(!payload.delivery);
+    }
+
+    #[test]
+    fn test_raw_payload_is_wrong_type() {
+        let (_guard, mut ctx) = test_ctx();
+        drag_drop_frame(
+            &mut ctx,
+            |source| unsafe {
+                let byte = 1u8;
+                source.begin_payload_unchecked(&byte as *const u8 as *const ffi::c_void, 1);
+            },
+            |target| {
+                let payload = target
+                    .accept_payload::<u64, _>("PAYLOAD", DragDropFlags::ACCEPT_BEFORE_DELIVERY);
+                assert!(payload.unwrap().is_err());
+            },
+        );
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serde_payload_round_trip() {
+        #[derive(Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+        struct Asset {
+            path: String,
+            tags: Vec<String>,
+        }
+
+        let asset = Asset {
+            path: "textures/grass.png".into(),
+            tags: vec!["terrain".into(), "green".into()],
+        };
+        let (_guard, mut ctx) = test_ctx();
+        let mut received = None;
+        drag_drop_frame(
+            &mut ctx,
+            |source| {
+                source.begin_payload_serde(&asset);
+            },
+            |target| {
+                assert!(matches!(
+                    target
+                        .accept_payload::<u32, _>("PAYLOAD", DragDropFlags::ACCEPT_BEFORE_DELIVERY),
+                    Some(Err(_))
+                ));
+                assert!(matches!(
+                    target.accept_payload_serde::<u32, _>(
+                        "PAYLOAD",
+                        DragDropFlags::ACCEPT_BEFORE_DELIVERY
+                    ),
+                    Some(Err(PayloadDeserializeError::Deserialize(_)))
+                ));
+                received = target.accept_payload_serde::<Asset, _>(
+                    "PAYLOAD",
+                    DragDropFlags::ACCEPT_BEFORE_DELIVERY,
+                );
+            },
+        );
+        let payload = received.unwrap().unwrap();
+        assert_eq!(payload.data, asset);
+        assert!(!payload.delivery);
+    }
+}
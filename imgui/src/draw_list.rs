@@ -21,6 +21,7 @@ use sys::{ImDrawCmd, ImDrawList};
 use super::Ui;
 use crate::render::renderer::TextureId;
 
+use std::cell::{Cell, RefCell};
 use std::marker::PhantomData;
 
 bitflags!(
@@ -41,6 +42,18 @@ bitflags!(
     }
 );
 
+impl DrawFlags {
+    /// Dear ImGui rounds all corners when no corner is selected, which is surprising after
+    /// disabling every corner one by one, so make that case explicit.
+    fn with_explicit_corners(self) -> DrawFlags {
+        if self.intersects(DrawFlags::ROUND_CORNERS_ALL | DrawFlags::ROUND_CORNERS_NONE) {
+            self
+        } else {
+            self | DrawFlags::ROUND_CORNERS_NONE
+        }
+    }
+}
+
 bitflags!(
     /// Draw list flags
     #[repr(C)]
@@ -73,7 +86,7 @@ enum DrawListType {
 pub struct DrawListMut<'ui> {
     draw_list_type: DrawListType,
     draw_list: *mut ImDrawList,
-    _phantom: PhantomData<&'ui Ui>,
+    ui: &'ui Ui,
 }
 
 // Lock for each variant of draw list. See https://github.com/imgui-rs/imgui-rs/issues/488
@@ -122,18 +135,18 @@ impl<'ui> DrawListMut<'ui> {
     }
 
     #[doc(alias = "GetWindowDrawList")]
-    pub(crate) fn window(_: &Ui) -> Self {
+    pub(crate) fn window(ui: &'ui Ui) -> Self {
         Self::lock_draw_list(DrawListType::Window);
 
         Self {
             draw_list: unsafe { sys::igGetWindowDrawList() },
             draw_list_type: DrawListType::Window,
-            _phantom: PhantomData,
+            ui,
         }
     }
 
     #[doc(alias = "GetBackgroundDrawList")]
-    pub(crate) fn background(_: &Ui) -> Self {
+    pub(crate) fn background(ui: &'ui Ui) -> Self {
         Self::lock_draw_list(DrawListType::Background);
         Self {
             draw_list: unsafe {
@@ -147,12 +160,12 @@ impl<'ui> DrawListMut<'ui> {
                 }
             },
             draw_list_type: DrawListType::Background,
-            _phantom: PhantomData,
+            ui,
         }
     }
 
     #[doc(alias = "GetForegroundDrawList")]
-    pub(crate) fn foreground(_: &Ui) -> Self {
+    pub(crate) fn foreground(ui: &'ui Ui) -> Self {
         Self::lock_draw_list(DrawListType::Foreground);
         Self {
             draw_list: unsafe {
@@ -166,7 +179,7 @@ impl<'ui> DrawListMut<'ui> {
                 }
             },
             draw_list_type: DrawListType::Foreground,
-            _phantom: PhantomData,
+            ui,
         }
     }
 
@@ -175,6 +188,9 @@ impl<'ui> DrawListMut<'ui> {
     /// are then drawn in the increasing order of their channel number, and not
     /// in the order they were called.
     ///
+    /// The channels are merged even if the closure panics. A draw list can only be split once
+    /// at a time: to split it again within the closure, use a separate [`DrawListSplitter`].
+    ///
     /// # Example
     ///
     /// ```rust,no_run
@@ -191,24 +207,44 @@ impl<'ui> DrawListMut<'ui> {
     /// ```
     #[doc(alias = "ChannelsSplit")]
     pub fn channels_split<F: FnOnce(&ChannelsSplit<'_>)>(&self, channels_count: u32, f: F) {
-        unsafe { sys::ImDrawList_ChannelsSplit(self.draw_list, channels_count as i32) };
-        f(&ChannelsSplit {
-            draw_list: self,
-            channels_count,
-        });
-        unsafe { sys::ImDrawList_ChannelsMerge(self.draw_list) };
+        let splitter = unsafe { &mut (*self.draw_list)._Splitter as *mut _ };
+        f(&ChannelsSplit::new(self, splitter, channels_count));
     }
 }
 
-/// Represent the drawing interface within a call to [`channels_split`].
+/// Represent the drawing interface within a call to [`channels_split`] or
+/// [`DrawListSplitter::split`].
+///
+/// The channels are merged when this is dropped.
 ///
 /// [`channels_split`]: DrawListMut::channels_split
 pub struct ChannelsSplit<'ui> {
     draw_list: &'ui DrawListMut<'ui>,
+    splitter: *mut sys::ImDrawListSplitter,
     channels_count: u32,
 }
 
 impl<'ui> ChannelsSplit<'ui> {
+    fn new(
+        draw_list: &'ui DrawListMut<'ui>,
+        splitter: *mut sys::ImDrawListSplitter,
+        channels_count: u32,
+    ) -> Self {
+        assert!(channels_count > 0, "Cannot split into 0 channels.");
+        debug_assert!(
+            unsafe { (*splitter)._Count <= 1 },
+            "Nested channel splitting is not supported! Use a separate DrawListSplitter instead."
+        );
+        unsafe {
+            sys::ImDrawListSplitter_Split(splitter, draw_list.draw_list, channels_count as i32)
+        };
+        ChannelsSplit {
+            draw_list,
+            splitter,
+            channels_count,
+        }
+    }
+
     /// Change current channel.
     ///
     /// Panic if channel_index overflows the number of channels.
@@ -221,11 +257,84 @@ impl<'ui> ChannelsSplit<'ui> {
             self.channels_count
         );
         unsafe {
-            sys::ImDrawList_ChannelsSetCurrent(self.draw_list.draw_list, channel_index as i32)
+            sys::ImDrawListSplitter_SetCurrentChannel(
+                self.splitter,
+                self.draw_list.draw_list,
+                channel_index as i32,
+            )
         };
     }
 }
 
+impl Drop for ChannelsSplit<'_> {
+    fn drop(&mut self) {
+        unsafe { sys::ImDrawListSplitter_Merge(self.splitter, self.draw_list.draw_list) };
+    }
+}
+
+/// A channel splitter that can be kept across frames.
+///
+/// [`DrawListMut::channels_split`] uses a splitter owned by the draw list, so it can't be
+/// nested. Owning the splitter allows splitting a draw list that is already split, and reusing
+/// the memory of the channels from one frame to the next.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// # use imgui::*;
+/// fn custom_drawing(ui: &Ui, splitter: &mut DrawListSplitter) {
+///     let draw_list = ui.get_window_draw_list();
+///     splitter.split(&draw_list, 2, |channels| {
+///         channels.set_current(1);
+///         // ... Draw the nodes
+///         channels.set_current(0);
+///         // ... Draw the links behind the nodes
+///     });
+/// }
+/// ```
+#[doc(alias = "ImDrawListSplitter")]
+pub struct DrawListSplitter {
+    raw: *mut sys::ImDrawListSplitter,
+}
+
+impl DrawListSplitter {
+    /// Creates a new splitter.
+    pub fn new() -> Self {
+        DrawListSplitter {
+            raw: unsafe { sys::ImDrawListSplitter_ImDrawListSplitter() },
+        }
+    }
+
+    /// Splits *draw_list* into *channels_count* drawing channels, like
+    /// [`DrawListMut::channels_split`].
+    pub fn split<F: FnOnce(&ChannelsSplit<'_>)>(
+        &mut self,
+        draw_list: &DrawListMut<'_>,
+        channels_count: u32,
+        f: F,
+    ) {
+        f(&ChannelsSplit::new(draw_list, self.raw, channels_count));
+    }
+
+    /// Frees the memory of the channels.
+    #[doc(alias = "ClearFreeMemory")]
+    pub fn clear_free_memory(&mut self) {
+        unsafe { sys::ImDrawListSplitter_ClearFreeMemory(self.raw) };
+    }
+}
+
+impl Default for DrawListSplitter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Drop for DrawListSplitter {
+    fn drop(&mut self) {
+        unsafe { sys::ImDrawListSplitter_destroy(self.raw) };
+    }
+}
+
 /// Drawing functions
 impl<'ui> DrawListMut<'ui> {
     /// Returns a line from point `p1` to `p2` with color `c`.
@@ -370,6 +479,71 @@ impl<'ui> DrawListMut<'ui> {
         BezierCurve::new(self, pos0, cp0, cp1, pos1, color)
     }
 
+    /// Returns a quadratic Bezier curve stretching from `pos0` to `pos1`, whose
+    /// curvature is defined by `cp`.
+    #[doc(alias = "AddBezierQuadratic")]
+    pub fn add_bezier_quadratic(
+        &'ui self,
+        pos0: impl Into<MintVec2>,
+        cp: impl Into<MintVec2>,
+        pos1: impl Into<MintVec2>,
+        color: impl Into<ImColor32>,
+    ) -> BezierQuadratic<'ui> {
+        BezierQuadratic::new(self, pos0, cp, pos1, color)
+    }
+
+    /// Returns a regular polygon with `num_segments` sides, inscribed in the circle with the
+    /// given `center` and `radius`.
+    #[doc(alias = "AddNgon", alias = "AddNgonFilled")]
+    pub fn add_ngon<C>(
+        &'ui self,
+        center: impl Into<MintVec2>,
+        radius: f32,
+        color: C,
+        num_segments: u32,
+    ) -> Ngon<'ui>
+    where
+        C: Into<ImColor32>,
+    {
+        Ngon::new(self, center, radius, color, num_segments)
+    }
+
+    /// Returns an ellipse with the given `center`, horizontal and vertical `radius` and `color`.
+    #[doc(alias = "AddEllipse", alias = "AddEllipseFilled")]
+    pub fn add_ellipse<C>(
+        &'ui self,
+        center: impl Into<MintVec2>,
+        radius: impl Into<MintVec2>,
+        color: C,
+    ) -> Ellipse<'ui>
+    where
+        C: Into<ImColor32>,
+    {
+        Ellipse::new(self, center, radius, color)
+    }
+
+    /// Starts building a path, made of segments that are then stroked or filled at once.
+    ///
+    /// This borrows the draw list mutably, as the other drawing functions build their shapes
+    /// with the same path.
+    ///
+    /// ```no_run
+    /// # use imgui::*;
+    /// # use imgui::draw_list::DrawFlags;
+    /// # let mut ctx = Context::create();
+    /// # let ui = ctx.frame();
+    /// let mut draw_list = ui.get_window_draw_list();
+    /// draw_list
+    ///     .path()
+    ///     .line_to([10.0, 10.0])
+    ///     .bezier_cubic_curve_to([50.0, 0.0], [50.0, 100.0], [90.0, 90.0])
+    ///     .stroke([1.0, 1.0, 1.0], DrawFlags::empty(), 2.0);
+    /// ```
+    #[doc(alias = "PathClear")]
+    pub fn path(&mut self) -> PathBuilder<'_> {
+        PathBuilder::new(self)
+    }
+
     /// Push a clipping rectangle on the stack, run `f` and pop it.
     ///
     /// Clip all drawings done within the closure `f` in the given
@@ -445,6 +619,10 @@ impl<'ui> DrawListMut<'ui> {
     /// Draw the specified image to a quad with the specified
     /// coordinates. Similar to [`DrawListMut::add_image`] but this
     /// method is able to draw non-rectangle images.
+    ///
+    /// The points are the corners of the quad, in clockwise order starting from the one
+    /// showing the top-left corner of the image. See [`ImageQuad::uv`] to map other parts
+    /// of the texture to the corners.
     pub fn add_image_quad(
         &'ui self,
         texture_id: TextureId,
@@ -456,7 +634,10 @@ impl<'ui> DrawListMut<'ui> {
         ImageQuad::new(self, texture_id, p1, p2, p3, p4)
     }
 
-    /// Draw the speciied image, with rounded corners
+    /// Draw the specified image, with rounded corners.
+    ///
+    /// All corners are rounded by default, see [`ImageRounded::round_top_left`] and the
+    /// similar methods to select which ones are.
     pub fn add_image_rounded(
         &'ui self,
         texture_id: TextureId,
@@ -469,11 +650,29 @@ impl<'ui> DrawListMut<'ui> {
 
     /// Draw the specified callback.
     ///
-    /// Note: if this DrawList is never rendered the callback will leak because DearImGui
-    /// does not provide a method to clean registered callbacks.
+    /// The callback is run by the renderer when it reaches this point of the draw list, which
+    /// allows interleaving custom rendering (e.g. a 3D viewport) with imgui's draw order. The
+    /// renderer's state isn't restored afterwards: if the callback changes it, follow with
+    /// [`DrawListMut::add_reset_render_state`].
+    ///
+    /// The callback is kept until the next frame starts, and dropped without being run if the
+    /// draw data wasn't rendered by then.
+    #[doc(alias = "AddCallback")]
     pub fn add_callback<F: FnOnce() + 'static>(&'ui self, callback: F) -> Callback<'ui, F> {
         Callback::new(self, callback)
     }
+
+    /// Asks the renderer to set up its render state again, e.g. after a callback added with
+    /// [`DrawListMut::add_callback`] changed it.
+    ///
+    /// Renderers see this as [`DrawCmd::ResetRenderState`](crate::DrawCmd::ResetRenderState).
+    #[doc(alias = "ImDrawCallback_ResetRenderState")]
+    pub fn add_reset_render_state(&self) {
+        // The sentinel value used by Dear ImGui, which is never called
+        let reset: unsafe extern "C" fn(*const ImDrawList, *const ImDrawCmd) =
+            unsafe { std::mem::transmute(-1isize) };
+        unsafe { sys::ImDrawList_AddCallback(self.draw_list, Some(reset), std::ptr::null_mut()) };
+    }
 }
 
 /// Represents a line about to be drawn
@@ -677,7 +876,7 @@ impl<'ui> Rect<'ui> {
                     self.p2.into(),
                     self.color.into(),
                     self.rounding,
-                    self.flags.bits() as i32,
+                    self.flags.with_explicit_corners().bits() as i32,
                 );
             }
         } else {
@@ -688,7 +887,7 @@ impl<'ui> Rect<'ui> {
                     self.p2.into(),
                     self.color.into(),
                     self.rounding,
-                    self.flags.bits() as i32,
+                    self.flags.with_explicit_corners().bits() as i32,
                     self.thickness,
                 );
             }
@@ -918,6 +1117,385 @@ impl<'ui> BezierCurve<'ui> {
     }
 }
 
+/// Represents a quadratic Bezier curve about to be drawn
+#[must_use = "should call .build() to draw the object"]
+pub struct BezierQuadratic<'ui> {
+    pos0: [f32; 2],
+    cp: [f32; 2],
+    pos1: [f32; 2],
+    color: ImColor32,
+    thickness: f32,
+    /// If num_segments is not set, the bezier curve is auto-tessalated.
+    num_segments: Option<u32>,
+    draw_list: &'ui DrawListMut<'ui>,
+}
+
+impl<'ui> BezierQuadratic<'ui> {
+    /// Typically constructed by [`DrawListMut::add_bezier_quadratic`]
+    pub fn new<C>(
+        draw_list: &'ui DrawListMut<'_>,
+        pos0: impl Into<MintVec2>,
+        cp: impl Into<MintVec2>,
+        pos1: impl Into<MintVec2>,
+        c: C,
+    ) -> Self
+    where
+        C: Into<ImColor32>,
+    {
+        Self {
+            pos0: pos0.into().into(),
+            cp: cp.into().into(),
+            pos1: pos1.into().into(),
+            color: c.into(),
+            thickness: 1.0,
+            num_segments: None,
+            draw_list,
+        }
+    }
+
+    /// Set curve's thickness (default to 1.0 pixel)
+    pub fn thickness(mut self, thickness: f32) -> Self {
+        self.thickness = thickness;
+        self
+    }
+
+    /// Set number of segments used to draw the Bezier curve. If not set, the
+    /// bezier curve is auto-tessalated.
+    pub fn num_segments(mut self, num_segments: u32) -> Self {
+        self.num_segments = Some(num_segments);
+        self
+    }
+
+    /// Draw the curve on the window.
+    pub fn build(self) {
+        unsafe {
+            sys::ImDrawList_AddBezierQuadratic(
+                self.draw_list.draw_list,
+                self.pos0.into(),
+                self.cp.into(),
+                self.pos1.into(),
+                self.color.into(),
+                self.thickness,
+                self.num_segments.unwrap_or(0) as i32,
+            )
+        }
+    }
+}
+
+/// Represents a regular polygon about to be drawn
+#[must_use = "should call .build() to draw the object"]
+pub struct Ngon<'ui> {
+    center: [f32; 2],
+    radius: f32,
+    color: ImColor32,
+    num_segments: u32,
+    thickness: f32,
+    filled: bool,
+    draw_list: &'ui DrawListMut<'ui>,
+}
+
+impl<'ui> Ngon<'ui> {
+    /// Typically constructed by [`DrawListMut::add_ngon`]
+    pub fn new<C>(
+        draw_list: &'ui DrawListMut<'_>,
+        center: impl Into<MintVec2>,
+        radius: f32,
+        color: C,
+        num_segments: u32,
+    ) -> Self
+    where
+        C: Into<ImColor32>,
+    {
+        Self {
+            center: center.into().into(),
+            radius,
+            color: color.into(),
+            num_segments,
+            thickness: 1.0,
+            filled: false,
+            draw_list,
+        }
+    }
+
+    /// Set polygon's thickness (default to 1.0 pixel)
+    pub fn thickness(mut self, thickness: f32) -> Self {
+        self.thickness = thickness;
+        self
+    }
+
+    /// Set to `true` to make a filled polygon (default to `false`).
+    pub fn filled(mut self, filled: bool) -> Self {
+        self.filled = filled;
+        self
+    }
+
+    /// Draw the polygon on the window.
+    pub fn build(self) {
+        if self.filled {
+            unsafe {
+                sys::ImDrawList_AddNgonFilled(
+                    self.draw_list.draw_list,
+                    self.center.into(),
+                    self.radius,
+                    self.color.into(),
+                    self.num_segments as i32,
+                )
+            }
+        } else {
+            unsafe {
+                sys::ImDrawList_AddNgon(
+                    self.draw_list.draw_list,
+                    self.center.into(),
+                    self.radius,
+                    self.color.into(),
+                    self.num_segments as i32,
+                    self.thickness,
+                )
+            }
+        }
+    }
+}
+
+/// Represents an ellipse about to be drawn
+#[must_use = "should call .build() to draw the object"]
+pub struct Ellipse<'ui> {
+    center: [f32; 2],
+    radius: [f32; 2],
+    color: ImColor32,
+    rotation: f32,
+    num_segments: u32,
+    thickness: f32,
+    filled: bool,
+    draw_list: &'ui DrawListMut<'ui>,
+}
+
+impl<'ui> Ellipse<'ui> {
+    /// Typically constructed by [`DrawListMut::add_ellipse`]
+    pub fn new<C>(
+        draw_list: &'ui DrawListMut<'_>,
+        center: impl Into<MintVec2>,
+        radius: impl Into<MintVec2>,
+        color: C,
+    ) -> Self
+    where
+        C: Into<ImColor32>,
+    {
+        Self {
+            center: center.into().into(),
+            radius: radius.into().into(),
+            color: color.into(),
+            rotation: 0.0,
+            num_segments: 0,
+            thickness: 1.0,
+            filled: false,
+            draw_list,
+        }
+    }
+
+    /// Set the rotation of the ellipse in radians, clockwise (default to 0.0)
+    pub fn rotation(mut self, rotation: f32) -> Self {
+        self.rotation = rotation;
+        self
+    }
+
+    /// Set number of segment used to draw the ellipse, default to 0.
+    /// Add more segments if you want a smoother ellipse.
+    pub fn num_segments(mut self, num_segments: u32) -> Self {
+        self.num_segments = num_segments;
+        self
+    }
+
+    /// Set ellipse's thickness (default to 1.0 pixel)
+    pub fn thickness(mut self, thickness: f32) -> Self {
+        self.thickness = thickness;
+        self
+    }
+
+    /// Set to `true` to make a filled ellipse (default to `false`).
+    pub fn filled(mut self, filled: bool) -> Self {
+        self.filled = filled;
+        self
+    }
+
+    /// Draw the ellipse on the window.
+    pub fn build(self) {
+        // The bundled Dear ImGui has no ellipses yet, so the ellipse is built as a path, with
+        // the same automatic tessellation as circles.
+        let [radius_x, radius_y] = self.radius;
+        let num_segments = match self.num_segments {
+            0 => {
+                let max_error = unsafe { (*sys::igGetStyle()).CircleTessellationMaxError };
+                auto_segment_count(radius_x.max(radius_y), max_error)
+            }
+            n => n,
+        };
+        let (sin_rotation, cos_rotation) = self.rotation.sin_cos();
+        let draw_list = self.draw_list.draw_list;
+        unsafe {
+            sys::ImDrawList_PathClear(draw_list);
+            for i in 0..num_segments {
+                let angle = i as f32 * std::f32::consts::TAU / num_segments as f32;
+                let x = radius_x * angle.cos();
+                let y = radius_y * angle.sin();
+                let point = [
+                    self.center[0] + x * cos_rotation - y * sin_rotation,
+                    self.center[1] + x * sin_rotation + y * cos_rotation,
+                ];
+                sys::ImDrawList_PathLineTo(draw_list, point.into());
+            }
+            if self.filled {
+                sys::ImDrawList_PathFillConvex(draw_list, self.color.into());
+            } else {
+                sys::ImDrawList_PathStroke(
+                    draw_list,
+                    self.color.into(),
+                    DrawFlags::CLOSED.bits() as i32,
+                    self.thickness,
+                );
+            }
+        }
+    }
+}
+
+/// Number of segments Dear ImGui uses for a circle of the given radius
+/// (`IM_DRAWLIST_CIRCLE_AUTO_SEGMENT_CALC`)
+fn auto_segment_count(radius: f32, max_error: f32) -> u32 {
+    if radius <= 0.0 {
+        return 4;
+    }
+    let count = (std::f32::consts::PI / (1.0 - max_error.min(radius) / radius).acos()).ceil();
+    let count = count as u32;
+    // Rounded up to an even number
+    (count + count % 2).clamp(4, 512)
+}
+
+/// Builds a path in a draw list, made of segments that are then stroked or filled at once.
+///
+/// Typically constructed by [`DrawListMut::path`]. Segments are added to the end of the path,
+/// starting from the last point, and are tessellated automatically. If neither
+/// [`stroke`](Self::stroke) nor [`fill`](Self::fill) is called, the path is discarded.
+#[must_use = "should call .stroke() or .fill() to draw the path"]
+pub struct PathBuilder<'a> {
+    draw_list: *mut ImDrawList,
+    _phantom: PhantomData<&'a mut ()>,
+}
+
+impl<'a> PathBuilder<'a> {
+    fn new(draw_list: &'a mut DrawListMut<'_>) -> Self {
+        unsafe { sys::ImDrawList_PathClear(draw_list.draw_list) };
+        PathBuilder {
+            draw_list: draw_list.draw_list,
+            _phantom: PhantomData,
+        }
+    }
+
+    /// Adds a straight line to `pos`. The first point of a path is added this way.
+    #[doc(alias = "PathLineTo")]
+    pub fn line_to(self, pos: impl Into<MintVec2>) -> Self {
+        unsafe { sys::ImDrawList_PathLineTo(self.draw_list, pos.into().into()) };
+        self
+    }
+
+    /// Adds an arc of the circle with the given `center` and `radius`, from angle `a_min` to
+    /// `a_max` (in radians, clockwise from the positive x axis).
+    ///
+    /// The path continues with a straight line to the start of the arc, if needed.
+    #[doc(alias = "PathArcTo")]
+    pub fn arc_to(self, center: impl Into<MintVec2>, radius: f32, a_min: f32, a_max: f32) -> Self {
+        unsafe {
+            sys::ImDrawList_PathArcTo(
+                self.draw_list,
+                center.into().into(),
+                radius,
+                a_min,
+                a_max,
+                0,
+            )
+        };
+        self
+    }
+
+    /// Adds a cubic Bezier curve to `pos`, whose curvature is defined by `cp0` and `cp1`.
+    #[doc(alias = "PathBezierCubicCurveTo")]
+    pub fn bezier_cubic_curve_to(
+        self,
+        cp0: impl Into<MintVec2>,
+        cp1: impl Into<MintVec2>,
+        pos: impl Into<MintVec2>,
+    ) -> Self {
+        unsafe {
+            sys::ImDrawList_PathBezierCubicCurveTo(
+                self.draw_list,
+                cp0.into().into(),
+                cp1.into().into(),
+                pos.into().into(),
+                0,
+            )
+        };
+        self
+    }
+
+    /// Adds a quadratic Bezier curve to `pos`, whose curvature is defined by `cp`.
+    #[doc(alias = "PathBezierQuadraticCurveTo")]
+    pub fn bezier_quadratic_curve_to(
+        self,
+        cp: impl Into<MintVec2>,
+        pos: impl Into<MintVec2>,
+    ) -> Self {
+        unsafe {
+            sys::ImDrawList_PathBezierQuadraticCurveTo(
+                self.draw_list,
+                cp.into().into(),
+                pos.into().into(),
+                0,
+            )
+        };
+        self
+    }
+
+    /// Adds a rectangle, with corners rounded by `rounding`.
+    #[doc(alias = "PathRect")]
+    pub fn rect(self, min: impl Into<MintVec2>, max: impl Into<MintVec2>, rounding: f32) -> Self {
+        unsafe {
+            sys::ImDrawList_PathRect(
+                self.draw_list,
+                min.into().into(),
+                max.into().into(),
+                rounding,
+                0,
+            )
+        };
+        self
+    }
+
+    /// Draws the outline of the path. Use [`DrawFlags::CLOSED`] to connect its last point
+    /// to the first one.
+    #[doc(alias = "PathStroke")]
+    pub fn stroke(self, color: impl Into<ImColor32>, flags: DrawFlags, thickness: f32) {
+        unsafe {
+            sys::ImDrawList_PathStroke(
+                self.draw_list,
+                color.into().into(),
+                flags.bits() as i32,
+                thickness,
+            )
+        };
+    }
+
+    /// Fills the path, which must be a convex shape.
+    #[doc(alias = "PathFillConvex")]
+    pub fn fill(self, color: impl Into<ImColor32>) {
+        unsafe { sys::ImDrawList_PathFillConvex(self.draw_list, color.into().into()) };
+    }
+}
+
+impl Drop for PathBuilder<'_> {
+    fn drop(&mut self) {
+        // Already done by stroke and fill
+        unsafe { sys::ImDrawList_PathClear(self.draw_list) };
+    }
+}
+
 /// Image draw list primitive, not to be confused with the widget
 /// [`imgui::Image`](crate::Image).
 #[must_use = "should call .build() to draw the object"]
@@ -1187,7 +1765,7 @@ impl<'ui> ImageRounded<'ui> {
                 self.uv_max.into(),
                 self.col.into(),
                 self.rounding,
-                self.draw_flags.bits() as i32,
+                self.draw_flags.with_explicit_corners().bits() as i32,
             );
         }
     }
@@ -1209,28 +1787,338 @@ impl<'ui, F: FnOnce() + 'static> Callback<'ui, F> {
     }
     /// Adds the callback to the draw-list so it will be run when the window is drawn
     pub fn build(self) {
-        use std::os::raw::c_void;
-        // F is Sized, so *mut F must be a thin pointer.
-        let callback: *mut F = Box::into_raw(Box::new(self.callback));
-
+        let callback = self
+            .draw_list
+            .ui
+            .draw_callbacks
+            .push(Box::new(self.callback));
         unsafe {
             sys::ImDrawList_AddCallback(
                 self.draw_list.draw_list,
-                Some(Self::run_callback),
-                callback as *mut c_void,
+                Some(run_draw_callback),
+                callback as *mut std::os::raw::c_void,
             );
         }
     }
-    unsafe extern "C" fn run_callback(_parent_list: *const ImDrawList, cmd: *const ImDrawCmd) {
-        // We are modifying through a C const pointer, but that should be harmless.
-        let cmd = &mut *(cmd as *mut ImDrawCmd);
-        // Consume the pointer and leave a NULL behind to avoid a double-free or
-        // calling twice an FnOnce. It should not happen, but better safe than sorry.
-        let callback = std::mem::replace(&mut cmd.UserCallbackData, std::ptr::null_mut());
-        if callback.is_null() {
-            return;
-        }
-        let callback = Box::from_raw(callback as *mut F);
+}
+
+type DrawCallback = Cell<Option<Box<dyn FnOnce()>>>;
+
+/// The callbacks added during a frame, which are referenced by the draw data until the next
+/// frame starts.
+#[derive(Default)]
+// Boxed so that the pointers given to Dear ImGui stay valid when the vector grows
+#[allow(clippy::vec_box)]
+pub(crate) struct DrawCallbacks(RefCell<Vec<Box<DrawCallback>>>);
+
+impl std::fmt::Debug for DrawCallbacks {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("DrawCallbacks")
+            .field(&self.0.borrow().len())
+            .finish()
+    }
+}
+
+impl DrawCallbacks {
+    /// Returns a pointer to the stored callback, which stays valid until `clear` is called.
+    fn push(&self, callback: Box<dyn FnOnce()>) -> *const DrawCallback {
+        let callback = Box::new(Cell::new(Some(callback)));
+        let ptr = &*callback as *const DrawCallback;
+        self.0.borrow_mut().push(callback);
+        ptr
+    }
+
+    pub(crate) fn clear(&mut self) {
+        self.0.get_mut().clear();
+    }
+}
+
+unsafe extern "C" fn run_draw_callback(_parent_list: *const ImDrawList, cmd: *const ImDrawCmd) {
+    let callback = (*cmd).UserCallbackData as *const DrawCallback;
+    // Renderers may run the same draw data more than once, but an FnOnce can only be called once
+    if let Some(callback) = (*callback).take() {
         callback();
     }
 }
+
+#[test]
+fn test_path_and_shape_vertex_counts() {
+    let (_guard, mut ctx) = crate::test::test_ctx_initialized();
+    // Without anti-aliasing, a convex fill has one vertex per point, and a stroke 4 vertices
+    // per segment
+    ctx.style_mut().anti_aliased_lines = false;
+    ctx.style_mut().anti_aliased_fill = false;
+
+    let mut vertex_count = |draw: &dyn Fn(&mut DrawListMut<'_>)| {
+        let ui = ctx.frame();
+        draw(&mut ui.get_background_draw_list());
+        ctx.render().total_vtx_count
+    };
+    let base = vertex_count(&|_| ());
+    let mut count = |draw: &dyn Fn(&mut DrawListMut<'_>)| vertex_count(draw) - base;
+
+    assert_eq!(
+        count(&|draw_list| draw_list
+            .path()
+            .line_to([0.0, 0.0])
+            .line_to([10.0, 0.0])
+            .line_to([10.0, 10.0])
+            .fill([1.0, 1.0, 1.0])),
+        3
+    );
+    assert_eq!(
+        count(&|draw_list| draw_list
+            .path()
+            .line_to([0.0, 0.0])
+            .line_to([10.0, 0.0])
+            .line_to([10.0, 10.0])
+            .stroke([1.0, 1.0, 1.0], DrawFlags::empty(), 1.0)),
+        2 * 4
+    );
+    assert_eq!(
+        count(&|draw_list| draw_list
+            .path()
+            .line_to([0.0, 0.0])
+            .line_to([10.0, 0.0])
+            .line_to([10.0, 10.0])
+            .stroke([1.0, 1.0, 1.0], DrawFlags::CLOSED, 1.0)),
+        3 * 4
+    );
+    // A path that isn't drawn is discarded
+    assert_eq!(
+        count(&|draw_list| {
+            let _ = draw_list.path().line_to([0.0, 0.0]).line_to([10.0, 0.0]);
+            draw_list
+                .path()
+                .line_to([0.0, 0.0])
+                .line_to([10.0, 0.0])
+                .line_to([10.0, 10.0])
+                .fill([1.0, 1.0, 1.0]);
+        }),
+        3
+    );
+
+    assert_eq!(
+        count(&|draw_list| draw_list
+            .add_ngon([50.0, 50.0], 20.0, [1.0, 1.0, 1.0], 6)
+            .filled(true)
+            .build()),
+        6
+    );
+    assert_eq!(
+        count(&|draw_list| draw_list
+            .add_ngon([50.0, 50.0], 20.0, [1.0, 1.0, 1.0], 6)
+            .build()),
+        6 * 4
+    );
+    assert_eq!(
+        count(&|draw_list| draw_list
+            .add_bezier_quadratic([0.0, 0.0], [50.0, 100.0], [100.0, 0.0], [1.0, 1.0, 1.0])
+            .num_segments(10)
+            .build()),
+        10 * 4
+    );
+    assert_eq!(
+        count(&|draw_list| draw_list
+            .add_ellipse([50.0, 50.0], [40.0, 20.0], [1.0, 1.0, 1.0])
+            .num_segments(16)
+            .filled(true)
+            .build()),
+        16
+    );
+    assert_eq!(
+        count(&|draw_list| draw_list
+            .add_ellipse([50.0, 50.0], [40.0, 20.0], [1.0, 1.0, 1.0])
+            .rotation(1.0)
+            .num_segments(16)
+            .build()),
+        16 * 4
+    );
+}
+
+#[test]
+fn test_auto_segment_count() {
+    // Same as circles in Dear ImGui, with the default maximum error of 0.3
+    assert_eq!(auto_segment_count(0.0, 0.3), 4);
+    assert_eq!(auto_segment_count(5.0, 0.3), 10);
+    assert_eq!(auto_segment_count(50.0, 0.3), 30);
+    assert_eq!(auto_segment_count(1.0e6, 0.3), 512);
+}
+
+#[test]
+fn test_explicit_corners() {
+    assert_eq!(
+        DrawFlags::empty().with_explicit_corners(),
+        DrawFlags::ROUND_CORNERS_NONE
+    );
+    assert_eq!(
+        DrawFlags::CLOSED.with_explicit_corners(),
+        DrawFlags::CLOSED | DrawFlags::ROUND_CORNERS_NONE
+    );
+    assert_eq!(
+        DrawFlags::ROUND_CORNERS_TOP_LEFT.with_explicit_corners(),
+        DrawFlags::ROUND_CORNERS_TOP_LEFT
+    );
+    assert_eq!(
+        DrawFlags::ROUND_CORNERS_ALL.with_explicit_corners(),
+        DrawFlags::ROUND_CORNERS_ALL
+    );
+}
+
+#[test]
+fn test_image_rounded_corners() {
+    let (_guard, mut ctx) = crate::test::test_ctx_initialized();
+    ctx.style_mut().anti_aliased_fill = false;
+
+    let mut vertex_count = |draw: &dyn Fn(&DrawListMut<'_>)| {
+        let ui = ctx.frame();
+        draw(&ui.get_background_draw_list());
+        ctx.render().total_vtx_count
+    };
+    let base = vertex_count(&|_| ());
+    let mut count = |draw: &dyn Fn(&DrawListMut<'_>)| vertex_count(draw) - base;
+    let texture_id = TextureId::new(1);
+
+    let rounded = count(&|draw_list| {
+        draw_list
+            .add_image_rounded(texture_id, [0.0, 0.0], [100.0, 100.0], 10.0)
+            .build()
+    });
+    let one_corner = count(&|draw_list| {
+        draw_list
+            .add_image_rounded(texture_id, [0.0, 0.0], [100.0, 100.0], 10.0)
+            .round_all(false)
+            .round_top_left(true)
+            .build()
+    });
+    let no_corner = count(&|draw_list| {
+        draw_list
+            .add_image_rounded(texture_id, [0.0, 0.0], [100.0, 100.0], 10.0)
+            .round_top_left(false)
+            .round_top_right(false)
+            .round_bot_left(false)
+            .round_bot_right(false)
+            .build()
+    });
+    let quad = count(&|draw_list| {
+        draw_list
+            .add_image_quad(
+                texture_id,
+                [0.0, 0.0],
+                [100.0, 10.0],
+                [100.0, 90.0],
+                [0.0, 100.0],
+            )
+            .build()
+    });
+
+    assert!(rounded > one_corner);
+    assert!(one_corner > no_corner);
+    assert_eq!(no_corner, 4);
+    assert_eq!(quad, 4);
+}
+
+#[test]
+fn test_channels_split_draw_order() {
+    let (_guard, mut ctx) = crate::test::test_ctx_initialized();
+    const RED: [u8; 4] = [255, 0, 0, 255];
+    const WHITE: [u8; 4] = [255, 255, 255, 255];
+    let mut splitter = DrawListSplitter::new();
+
+    // Draws red on channel 1 then white on channel 0, and returns the colors of the
+    // background draw list in submission and in drawing order
+    let mut colors = |splitter: Option<&mut DrawListSplitter>| {
+        let ui = ctx.frame();
+        let draw_list = ui.get_background_draw_list();
+        let draw = |channels: &ChannelsSplit<'_>| {
+            channels.set_current(1);
+            draw_list
+                .add_rect([0.0, 0.0], [10.0, 10.0], [1.0, 0.0, 0.0])
+                .filled(true)
+                .build();
+            channels.set_current(0);
+            draw_list
+                .add_rect([0.0, 0.0], [10.0, 10.0], [1.0, 1.0, 1.0])
+                .filled(true)
+                .build();
+        };
+        match splitter {
+            Some(splitter) => splitter.split(&draw_list, 2, draw),
+            None => draw_list.channels_split(2, draw),
+        }
+        drop(draw_list);
+        let list = ctx
+            .render()
+            .draw_lists()
+            .find(|list| !list.vtx_buffer().is_empty())
+            .unwrap();
+        let vtx = list.vtx_buffer();
+        let idx = list.idx_buffer();
+        (
+            [vtx[0].col, vtx[vtx.len() - 1].col],
+            [
+                vtx[idx[0] as usize].col,
+                vtx[idx[idx.len() - 1] as usize].col,
+            ],
+        )
+    };
+
+    // The splitter is reused from one frame to the next
+    for _ in 0..2 {
+        assert_eq!(colors(None), ([RED, WHITE], [WHITE, RED]));
+        assert_eq!(colors(Some(&mut splitter)), ([RED, WHITE], [WHITE, RED]));
+    }
+}
+
+#[test]
+fn test_draw_callbacks() {
+    use crate::internal::RawWrapper;
+    use crate::DrawCmd;
+    use std::rc::Rc;
+
+    let (_guard, mut ctx) = crate::test::test_ctx_initialized();
+    let calls = Rc::new(Cell::new(0));
+    {
+        let ui = ctx.frame();
+        let draw_list = ui.get_background_draw_list();
+        let calls = Rc::clone(&calls);
+        draw_list
+            .add_callback(move || calls.set(calls.get() + 1))
+            .build();
+        draw_list.add_reset_render_state();
+    }
+
+    // Run the commands twice, like a renderer drawing the same frame again
+    for _ in 0..2 {
+        let draw_data = ctx.render();
+        let mut commands = Vec::new();
+        for draw_list in draw_data.draw_lists() {
+            for command in draw_list.commands() {
+                match command {
+                    DrawCmd::RawCallback { callback, raw_cmd } => {
+                        unsafe { callback(draw_list.raw(), raw_cmd) };
+                        commands.push("callback");
+                    }
+                    DrawCmd::ResetRenderState => commands.push("reset"),
+                    DrawCmd::Elements { .. } => (),
+                }
+            }
+        }
+        assert_eq!(commands, ["callback", "reset"]);
+        assert_eq!(calls.get(), 1);
+    }
+
+    // Callbacks that were never run are dropped when the next frame starts
+    {
+        let ui = ctx.frame();
+        let calls = Rc::clone(&calls);
+        ui.get_background_draw_list()
+            .add_callback(move || calls.set(calls.get() + 1))
+            .build();
+    }
+    ctx.render();
+    assert_eq!(Rc::strong_count(&calls), 2);
+    ctx.frame();
+    assert_eq!(Rc::strong_count(&calls), 1);
+    assert_eq!(calls.get(), 1);
+}
@@ -0,0 +1,281 @@
+use std::ffi::CStr;
+
+use crate::sys;
+use crate::Ui;
+
+/// The state of the imgui stacks, to recover from missing `end`/`pop` calls, see
+/// [`Ui::error_recovery_store_state`].
+///
+/// Dear ImGui only gained error recovery in 1.91.5, so this is an emulation on top of the
+/// internal API, which the bindings only include with the `docking` feature. It recovers from
+/// unbalanced calls (windows, child windows, popups, tables, tab bars, tree nodes, groups, ids,
+/// disabled blocks, style colors and variables, fonts, item flags and focus scopes), not from
+/// other errors that trigger an assertion in Dear ImGui.
+#[derive(Debug)]
+pub struct ErrorRecoveryState {
+    window_stack_size: i32,
+    table: *mut sys::ImGuiTable,
+    tab_bar: *mut sys::ImGuiTabBar,
+    tree_depth: i32,
+    stack_sizes: sys::ImGuiStackSizes,
+}
+
+/// Stack sizes to recover the current window to
+struct WindowTarget {
+    tree_depth: i32,
+    stack_sizes: sys::ImGuiStackSizes,
+    /// Size of the focus scope stack, which doesn't include the scope of the window in
+    /// `stack_sizes` of windows begun after the state was stored
+    focus_scope_stack_size: i32,
+}
+
+impl Ui {
+    /// Stores the state of the imgui stacks, to restore it later with
+    /// [`Ui::try_to_recover_state`].
+    ///
+    /// This is meant to contain code that may leave windows or stacks open, like the UI of
+    /// plugins, see [`Ui::with_error_recovery`].
+    #[doc(alias = "ErrorRecoveryStoreState")]
+    pub fn error_recovery_store_state(&self) -> ErrorRecoveryState {
+        unsafe {
+            let g = sys::igGetCurrentContext();
+            let mut stack_sizes = sys::ImGuiStackSizes::default();
+            sys::ImGuiStackSizes_SetToCurrentState(&mut stack_sizes);
+            ErrorRecoveryState {
+                window_stack_size: (*g).CurrentWindowStack.Size,
+                table: (*g).CurrentTable,
+                tab_bar: (*g).CurrentTabBar,
+                tree_depth: (*(*g).CurrentWindow).DC.TreeDepth,
+                stack_sizes,
+            }
+        }
+    }
+
+    /// Ends the windows and pops the stacks left open since `state` was stored.
+    ///
+    /// Returns a description of each missing call, e.g. `Recovered from missing End() for
+    /// 'Plugin'`. With the `log` feature, these are also logged as warnings.
+    #[doc(alias = "ErrorRecoveryTryToRecoverState")]
+    // The loops change the context through imgui calls
+    #[allow(clippy::while_immutable_condition)]
+    pub fn try_to_recover_state(&self, state: &ErrorRecoveryState) -> Vec<String> {
+        let mut errors = Vec::new();
+        unsafe {
+            let g = sys::igGetCurrentContext();
+            while (*g).CurrentWindowStack.Size > state.window_stack_size {
+                let stack = &(*g).CurrentWindowStack;
+                let stack_sizes = (*stack.Data.add(stack.Size as usize - 1)).StackSizesOnBegin;
+                let target = WindowTarget {
+                    tree_depth: 0,
+                    stack_sizes,
+                    focus_scope_stack_size: i32::from(stack_sizes.SizeOfFocusScopeStack) + 1,
+                };
+                recover_window(state, &target, &mut errors);
+
+                let window = (*g).CurrentWindow;
+                let name = window_name(window);
+                if (*window).Flags & sys::ImGuiWindowFlags_ChildWindow as i32 != 0 {
+                    errors.push(format!("Recovered from missing EndChild() for '{}'", name));
+                    sys::igEndChild();
+                } else {
+                    errors.push(format!("Recovered from missing End() for '{}'", name));
+                    sys::igEnd();
+                }
+            }
+            let target = WindowTarget {
+                tree_depth: state.tree_depth,
+                stack_sizes: state.stack_sizes,
+                focus_scope_stack_size: i32::from(state.stack_sizes.SizeOfFocusScopeStack),
+            };
+            recover_window(state, &target, &mut errors);
+        }
+        #[cfg(feature = "log")]
+        for error in &errors {
+            log::warn!("{}", error);
+        }
+        errors
+    }
+
+    /// Calls `f`, then ends the windows and pops the stacks it left open.
+    ///
+    /// The state is also recovered if `f` panics, so that the rest of the frame can still be
+    /// built when the panic is caught with [`std::panic::catch_unwind`] around this call.
+    /// To get the description of the recovered errors, use [`Ui::error_recovery_scope`].
+    ///
+    /// ```no_run
+    /// # use imgui::*;
+    /// # let mut ctx = Context::create();
+    /// # let ui = ctx.frame();
+    /// # let plugin_draw = |ui: &Ui| {};
+    /// ui.with_error_recovery(|| plugin_draw(ui));
+    /// ```
+    pub fn with_error_recovery<R>(&self, f: impl FnOnce() -> R) -> R {
+        let _scope = self.error_recovery_scope();
+        f()
+    }
+
+    /// Stores the state of the imgui stacks, and returns a scope which recovers it when ended
+    /// or dropped.
+    pub fn error_recovery_scope(&self) -> ErrorRecoveryScope<'_> {
+        ErrorRecoveryScope {
+            ui: self,
+            state: Some(self.error_recovery_store_state()),
+        }
+    }
+}
+
+/// Recovers the imgui stacks when ended or dropped, see [`Ui::error_recovery_scope`].
+#[must_use = "the state is recovered as soon as the scope is dropped"]
+pub struct ErrorRecoveryScope<'ui> {
+    ui: &'ui Ui,
+    state: Option<ErrorRecoveryState>,
+}
+
+impl<'ui> ErrorRecoveryScope<'ui> {
+    /// Recovers the state, returning the description of the recovered errors.
+    pub fn end(mut self) -> Vec<String> {
+        let state = self.state.take().unwrap();
+        self.ui.try_to_recover_state(&state)
+    }
+}
+
+impl Drop for ErrorRecoveryScope<'_> {
+    fn drop(&mut self) {
+        if let Some(state) = self.state.take() {
+            self.ui.try_to_recover_state(&state);
+        }
+    }
+}
+
+unsafe fn window_name(window: *const sys::ImGuiWindow) -> String {
+    CStr::from_ptr((*window).Name)
+        .to_string_lossy()
+        .into_owned()
+}
+
+/// Pops the stacks of the current window down to the target, like `ErrorCheckEndWindowRecover`
+/// but without going past the stored state
+#[allow(clippy::while_immutable_condition)]
+unsafe fn recover_window(
+    state: &ErrorRecoveryState,
+    target: &WindowTarget,
+    errors: &mut Vec<String>,
+) {
+    let g = sys::igGetCurrentContext();
+    let window = (*g).CurrentWindow;
+    let name = window_name(window);
+    let sizes = &target.stack_sizes;
+    let mut missing = |call: &str| {
+        errors.push(format!("Recovered from missing {}() in '{}'", call, name));
+    };
+
+    loop {
+        let table = (*g).CurrentTable;
+        if table == state.table
+            || table.is_null()
+            || (*table).OuterWindow != window && (*table).InnerWindow != window
+        {
+            break;
+        }
+        missing("EndTable");
+        sys::igEndTable();
+    }
+    while (*g).CurrentTabBar != state.tab_bar && !(*g).CurrentTabBar.is_null() {
+        missing("EndTabBar");
+        sys::igEndTabBar();
+    }
+    while (*window).DC.TreeDepth > target.tree_depth {
+        missing("TreePop");
+        sys::igTreePop();
+    }
+    while (*g).GroupStack.Size > i32::from(sizes.SizeOfGroupStack) {
+        missing("EndGroup");
+        sys::igEndGroup();
+    }
+    while (*window).IDStack.Size > i32::from(sizes.SizeOfIDStack) {
+        missing("PopID");
+        sys::igPopID();
+    }
+    while (*g).DisabledStackSize > sizes.SizeOfDisabledStack {
+        missing("EndDisabled");
+        sys::igEndDisabled();
+    }
+    while (*g).ColorStack.Size > i32::from(sizes.SizeOfColorStack) {
+        missing("PopStyleColor");
+        sys::igPopStyleColor(1);
+    }
+    while (*g).ItemFlagsStack.Size > i32::from(sizes.SizeOfItemFlagsStack) {
+        missing("PopItemFlag");
+        sys::igPopItemFlag();
+    }
+    while (*g).StyleVarStack.Size > i32::from(sizes.SizeOfStyleVarStack) {
+        missing("PopStyleVar");
+        sys::igPopStyleVar(1);
+    }
+    while (*g).FontStack.Size > i32::from(sizes.SizeOfFontStack) {
+        missing("PopFont");
+        sys::igPopFont();
+    }
+    while (*g).FocusScopeStack.Size > target.focus_scope_stack_size {
+        missing("PopFocusScope");
+        sys::igPopFocusScope();
+    }
+}
+
+#[test]
+fn test_error_recovery() {
+    use std::mem::forget;
+
+    let (_guard, mut ctx) = crate::test::test_ctx_initialized();
+    for _ in 0..2 {
+        let ui = ctx.frame();
+        ui.window("Host").build(|| {
+            let _id = ui.push_id("host");
+            let color = ui.push_style_color(crate::StyleColor::Text, [1.0, 0.0, 0.0, 1.0]);
+            let id_before = ui.get_id("item");
+
+            let scope = ui.error_recovery_scope();
+            // A misbehaving plugin, which leaves everything open
+            forget(ui.push_id("plugin"));
+            forget(ui.push_style_var(crate::StyleVar::Alpha(0.5)));
+            forget(
+                ui.tree_node_config("Node")
+                    .opened(true, crate::Condition::Always)
+                    .push(),
+            );
+            forget(ui.begin_group());
+            forget(ui.window("Plugin").begin());
+            forget(ui.push_style_color(crate::StyleColor::Button, [0.0; 4]));
+            forget(ui.child_window("Child").begin());
+            let errors = scope.end();
+
+            assert!(errors[0].starts_with("Recovered from missing EndChild() for 'Plugin/"));
+            assert_eq!(
+                errors[1..],
+                [
+                    "Recovered from missing PopStyleColor() in 'Plugin'",
+                    "Recovered from missing End() for 'Plugin'",
+                    "Recovered from missing TreePop() in 'Host'",
+                    "Recovered from missing EndGroup() in 'Host'",
+                    "Recovered from missing PopID() in 'Host'",
+                    "Recovered from missing PopStyleVar() in 'Host'",
+                ]
+            );
+            // The host's own stacks are kept
+            assert_eq!(ui.get_id("item"), id_before);
+            color.pop();
+        });
+
+        // Recovering when nothing was left open does nothing
+        let scope = ui.error_recovery_scope();
+        ui.window("Well behaved").build(|| ui.text("Hello"));
+        assert!(scope.end().is_empty());
+
+        let result = ui.with_error_recovery(|| {
+            forget(ui.window("Forgotten").begin());
+            42
+        });
+        assert_eq!(result, 42);
+        ctx.render();
+    }
+}
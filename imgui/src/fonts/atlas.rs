@@ -24,6 +24,9 @@ bitflags! {
     }
 }
 
+/// Glyph ranges built at runtime, stored behind the user data pointer of the atlas that uses them
+type GlyphRangesStorage = Vec<Rc<[sys::ImWchar]>>;
+
 /// A font identifier
 #[derive(Copy, Clone, Debug, Eq, PartialEq)]
 pub struct FontId(pub(crate) *const Font);
@@ -78,6 +81,43 @@ impl FontAtlas {
         }
         font_id
     }
+    /// Merges the glyphs of an icon font (e.g. FontAwesome or Material Icons) into the last
+    /// added font, so that icons can be used in any text drawn with it.
+    ///
+    /// *glyph_ranges* are the codepoints of the icons, usually from the private use area
+    /// (e.g. `U+E000-U+F8FF`). Returns the font the icons were merged into.
+    ///
+    /// Panics
+    /// ======
+    ///
+    /// This function will panic if no font was added to the atlas yet.
+    pub fn add_merged_icon_font(
+        &mut self,
+        ttf: &[u8],
+        size_pixels: f32,
+        glyph_ranges: FontGlyphRanges,
+        config: IconFontConfig,
+    ) -> FontId {
+        assert!(
+            self.config_data.Size > 0,
+            "An icon font must be merged into a font added before"
+        );
+        let font_config = FontConfig {
+            glyph_ranges,
+            glyph_min_advance_x: config.glyph_min_advance_x.unwrap_or(size_pixels),
+            pixel_snap_h: config.pixel_snap_h,
+            glyph_offset: config.glyph_offset,
+            rasterizer_multiply: config.rasterizer_multiply,
+            oversample_h: 1,
+            ..FontConfig::default()
+        };
+        let source = FontSource::TtfData {
+            data: ttf,
+            size_pixels,
+            config: Some(font_config),
+        };
+        self.add_font_internal(&source, true)
+    }
     fn add_font_internal(&mut self, font_source: &FontSource<'_>, merge_mode: bool) -> FontId {
         let mut raw_config = sys_font_config_default();
         raw_config.MergeMode = merge_mode;
@@ -85,6 +125,7 @@ impl FontAtlas {
             FontSource::DefaultFontData { config } => unsafe {
                 if let Some(config) = config {
                     config.apply_to_raw_config(&mut raw_config, self.raw_mut());
+                    self.retain_glyph_ranges(&config.glyph_ranges);
                 }
                 sys::ImFontAtlas_AddFontDefault(self.raw_mut(), &raw_config)
             },
@@ -97,6 +138,7 @@ impl FontAtlas {
                     unsafe {
                         config.apply_to_raw_config(&mut raw_config, self.raw_mut());
                     }
+                    self.retain_glyph_ranges(&config.glyph_ranges);
                 }
                 // We can't guarantee `data` is alive when the font atlas is built, so
                 // make a copy and move ownership of the data to the atlas
@@ -115,6 +157,25 @@ impl FontAtlas {
         };
         FontId(raw_font as *const _)
     }
+    /// Keeps ranges built by a `GlyphRangesBuilder` alive, since the atlas reads them again
+    /// every time it is built
+    fn retain_glyph_ranges(&mut self, glyph_ranges: &FontGlyphRanges) {
+        if let Some(ranges) = glyph_ranges.owned_storage() {
+            if self.user_data.is_null() {
+                let storage = Box::<GlyphRangesStorage>::default();
+                self.user_data = Box::into_raw(storage) as *mut c_void;
+            }
+            let storage = unsafe { &mut *(self.user_data as *mut GlyphRangesStorage) };
+            storage.push(Rc::clone(ranges));
+        }
+    }
+    /// Drops the ranges kept alive by `retain_glyph_ranges`, once no font config refers to them
+    pub(crate) fn release_glyph_ranges(&mut self) {
+        if !self.user_data.is_null() {
+            drop(unsafe { Box::from_raw(self.user_data as *mut GlyphRangesStorage) });
+            self.user_data = ptr::null_mut();
+        }
+    }
     pub fn fonts(&self) -> Vec<FontId> {
         let mut result = Vec::new();
         unsafe {
@@ -207,12 +268,137 @@ impl FontAtlas {
             }
         }
     }
+    /// Reserves a rectangle of the texture for custom pixels, e.g. images to draw with
+    /// [`Image`](crate::Image).
+    ///
+    /// The rectangle is packed when the atlas is built. Then, see [`FontAtlas::custom_rect`]
+    /// for its position, and [`FontAtlas::write_custom_rect_pixels`] to fill it.
+    #[doc(alias = "AddCustomRectRegular")]
+    pub fn add_custom_rect_regular(&mut self, width: u32, height: u32) -> CustomRectId {
+        let index = unsafe {
+            sys::ImFontAtlas_AddCustomRectRegular(self.raw_mut(), width as i32, height as i32)
+        };
+        CustomRectId(index)
+    }
+    /// Reserves a rectangle of the texture for custom pixels, which are drawn as the glyph of
+    /// *codepoint* in *font*.
+    ///
+    /// This can be used to embed icons in text. *advance_x* is the horizontal space taken by
+    /// the glyph, and *offset* the position of the rectangle relative to the pen position. Like
+    /// [`FontAtlas::add_custom_rect_regular`], the rectangle has to be filled once the atlas is
+    /// built.
+    #[doc(alias = "AddCustomRectFontGlyph")]
+    pub fn add_custom_rect_font_glyph(
+        &mut self,
+        font: FontId,
+        codepoint: char,
+        width: u32,
+        height: u32,
+        advance_x: f32,
+        offset: [f32; 2],
+    ) -> CustomRectId {
+        let index = unsafe {
+            sys::ImFontAtlas_AddCustomRectFontGlyph(
+                self.raw_mut(),
+                font.0 as *mut sys::ImFont,
+                codepoint as sys::ImWchar,
+                width as i32,
+                height as i32,
+                advance_x,
+                offset.into(),
+            )
+        };
+        CustomRectId(index)
+    }
+    /// Returns the position of a custom rectangle in the texture.
+    ///
+    /// Returns `None` if the atlas hasn't been built since the rectangle was added, or if the
+    /// atlas was cleared.
+    #[doc(alias = "GetCustomRectByIndex", alias = "CalcCustomRectUV")]
+    pub fn custom_rect(&self, id: CustomRectId) -> Option<CustomRect> {
+        if id.0 < 0 || id.0 >= self.custom_rects.Size {
+            return None;
+        }
+        let atlas = self as *const FontAtlas as *mut sys::ImFontAtlas;
+        unsafe {
+            let rect = sys::ImFontAtlas_GetCustomRectByIndex(atlas, id.0);
+            if !sys::ImFontAtlasCustomRect_IsPacked(rect) {
+                return None;
+            }
+            let mut uv_min = sys::ImVec2::zero();
+            let mut uv_max = sys::ImVec2::zero();
+            sys::ImFontAtlas_CalcCustomRectUV(atlas, rect, &mut uv_min, &mut uv_max);
+            let rect = &*rect;
+            Some(CustomRect {
+                min: [rect.X as u32, rect.Y as u32],
+                max: [
+                    rect.X as u32 + rect.Width as u32,
+                    rect.Y as u32 + rect.Height as u32,
+                ],
+                uv_min: uv_min.into(),
+                uv_max: uv_max.into(),
+            })
+        }
+    }
+    /// Copies RGBA pixels, in rows of 4 bytes per pixel, to a custom rectangle of the built
+    /// texture.
+    ///
+    /// This works with both texture formats: with [`FontAtlas::build_alpha8_texture`], only the
+    /// alpha channel is kept. The texture has to be uploaded again afterwards, e.g. by
+    /// reloading the font texture of the renderer.
+    ///
+    /// Panics
+    /// ======
+    ///
+    /// This function will panic if the texture isn't built, if the rectangle isn't packed (see
+    /// [`FontAtlas::custom_rect`]), or if the size of *pixels* doesn't match the rectangle.
+    pub fn write_custom_rect_pixels(&mut self, id: CustomRectId, pixels: &[u8]) {
+        let rect = self
+            .custom_rect(id)
+            .expect("The custom rectangle must be packed in a built atlas");
+        assert!(
+            !self.tex_pixels_alpha8.is_null() || !self.tex_pixels_rgba32.is_null(),
+            "The font atlas texture must be built"
+        );
+        let width = (rect.max[0] - rect.min[0]) as usize;
+        let height = (rect.max[1] - rect.min[1]) as usize;
+        assert_eq!(
+            pixels.len(),
+            width * height * 4,
+            "The pixels must cover the whole custom rectangle ({}x{})",
+            width,
+            height
+        );
+
+        let tex_width = self.tex_width as usize;
+        let tex_len = tex_width * self.tex_height as usize;
+        for (y, row) in pixels.chunks_exact(width * 4).enumerate() {
+            let start = (rect.min[1] as usize + y) * tex_width + rect.min[0] as usize;
+            unsafe {
+                if !self.tex_pixels_rgba32.is_null() {
+                    let tex =
+                        slice::from_raw_parts_mut(self.tex_pixels_rgba32 as *mut u8, tex_len * 4);
+                    tex[start * 4..(start + width) * 4].copy_from_slice(row);
+                }
+                if !self.tex_pixels_alpha8.is_null() {
+                    let tex = slice::from_raw_parts_mut(self.tex_pixels_alpha8, tex_len);
+                    for (alpha, pixel) in tex[start..start + width]
+                        .iter_mut()
+                        .zip(row.chunks_exact(4))
+                    {
+                        *alpha = pixel[3];
+                    }
+                }
+            }
+        }
+    }
     /// Clears the font atlas completely (both input and output data)
     #[doc(alias = "Clear")]
     pub fn clear(&mut self) {
         unsafe {
             sys::ImFontAtlas_Clear(self.raw_mut());
         }
+        self.release_glyph_ranges();
     }
     /// Clears output font data (glyph storage, UV coordinates)
     #[doc(alias = "ClearFonts")]
@@ -236,6 +422,96 @@ impl FontAtlas {
         unsafe {
             sys::ImFontAtlas_ClearInputData(self.raw_mut());
         }
+        self.release_glyph_ranges();
+    }
+    /// Selects the font rasterizer.
+    ///
+    /// FreeType is used by default when the `freetype` feature is enabled. This only applies
+    /// when the atlas is built: to rebuild an atlas that was already built, call
+    /// [`FontAtlas::clear_tex_data`] and build the texture again.
+    #[cfg(all(feature = "freetype", not(feature = "wasm")))]
+    pub fn set_font_builder(&mut self, builder: FontBuilder) {
+        self.font_builder_io = unsafe {
+            match builder {
+                FontBuilder::StbTrueType => sys::ImGuiRs_GetBuilderForStbTruetype(),
+                FontBuilder::FreeType => sys::ImGuiFreeType_GetBuilderForFreeType(),
+            }
+        };
+    }
+    /// Sets the FreeType options of all the fonts in the atlas.
+    ///
+    /// They are combined with the options of each font (see
+    /// [`FontConfig::font_builder_flags`]). Like [`FontAtlas::set_font_builder`], this only
+    /// applies when the atlas is built.
+    #[cfg(feature = "freetype")]
+    pub fn set_font_builder_flags(&mut self, flags: FreeTypeBuilderFlags) {
+        self.font_builder_flags = flags.bits() as i32;
+    }
+    /// Returns the FreeType options of all the fonts in the atlas.
+    #[cfg(feature = "freetype")]
+    pub fn font_builder_flags(&self) -> FreeTypeBuilderFlags {
+        FreeTypeBuilderFlags::from_bits_truncate(self.font_builder_flags as u32)
+    }
+}
+
+/// A custom rectangle identifier, see [`FontAtlas::add_custom_rect_regular`]
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct CustomRectId(i32);
+
+/// The position of a custom rectangle in the font atlas texture
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct CustomRect {
+    /// Upper-left corner, in pixels
+    pub min: [u32; 2],
+    /// Lower-right corner (exclusive), in pixels
+    pub max: [u32; 2],
+    /// Texture coordinates of the upper-left corner
+    pub uv_min: [f32; 2],
+    /// Texture coordinates of the lower-right corner
+    pub uv_max: [f32; 2],
+}
+
+/// A font rasterizer, see [`FontAtlas::set_font_builder`]
+#[cfg(all(feature = "freetype", not(feature = "wasm")))]
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum FontBuilder {
+    /// The stb_truetype rasterizer, which is the only one available without the `freetype`
+    /// feature
+    StbTrueType,
+    /// The FreeType rasterizer, which gives better results at small sizes
+    FreeType,
+}
+
+#[cfg(feature = "freetype")]
+bitflags! {
+    /// Options for the FreeType font rasterizer.
+    ///
+    /// See [`FontAtlas::set_font_builder_flags`] and [`FontConfig::font_builder_flags`].
+    #[repr(transparent)]
+    pub struct FreeTypeBuilderFlags: u32 {
+        /// Disable hinting. This generally generates 'blurrier' bitmap glyphs when the glyph
+        /// are rendered in any of the anti-aliased modes.
+        const NO_HINTING = sys::ImGuiFreeTypeBuilderFlags_NoHinting;
+        /// Disable auto-hinter.
+        const NO_AUTO_HINT = sys::ImGuiFreeTypeBuilderFlags_NoAutoHint;
+        /// Indicates that the auto-hinter is preferred over the font's native hinter.
+        const FORCE_AUTO_HINT = sys::ImGuiFreeTypeBuilderFlags_ForceAutoHint;
+        /// A lighter hinting algorithm for gray-level modes. Many generated glyphs are fuzzier
+        /// but better resemble their original shape.
+        const LIGHT_HINTING = sys::ImGuiFreeTypeBuilderFlags_LightHinting;
+        /// Strong hinting algorithm that should only be used for monochrome output.
+        const MONO_HINTING = sys::ImGuiFreeTypeBuilderFlags_MonoHinting;
+        /// Styling: Should we artificially embolden the font?
+        const BOLD = sys::ImGuiFreeTypeBuilderFlags_Bold;
+        /// Styling: Should we slant the font, emulating italic style?
+        const OBLIQUE = sys::ImGuiFreeTypeBuilderFlags_Oblique;
+        /// Disable anti-aliasing. Combine this with `MONO_HINTING` for best results!
+        const MONOCHROME = sys::ImGuiFreeTypeBuilderFlags_Monochrome;
+        /// Enable FreeType color-layered glyphs, e.g. for color emoji. Build the texture with
+        /// [`FontAtlas::build_rgba32_texture`] to keep the colors.
+        const LOAD_COLOR = sys::ImGuiFreeTypeBuilderFlags_LoadColor;
+        /// Enable FreeType bitmap glyphs
+        const BITMAP = sys::ImGuiFreeTypeBuilderFlags_Bitmap;
     }
 }
 
@@ -293,6 +569,32 @@ pub enum FontSource<'a> {
     },
 }
 
+/// Configuration settings for an icon font, see [`FontAtlas::add_merged_icon_font`]
+#[derive(Clone, Debug, PartialEq)]
+pub struct IconFontConfig {
+    /// Minimum horizontal advance of the icons, which makes them monospaced and aligned in
+    /// lists. Defaults to the font size when `None`.
+    pub glyph_min_advance_x: Option<f32>,
+    /// Align every icon to pixel boundary, keeping their lines sharp. Defaults to true.
+    pub pixel_snap_h: bool,
+    /// Offset of the icons, e.g. a few pixels down to center them vertically with text, since
+    /// icon fonts don't have the same baseline as text fonts. Defaults to no offset.
+    pub glyph_offset: [f32; 2],
+    /// Brighten (>1.0) or darken (<1.0) the icons. Defaults to 1.0.
+    pub rasterizer_multiply: f32,
+}
+
+impl Default for IconFontConfig {
+    fn default() -> Self {
+        IconFontConfig {
+            glyph_min_advance_x: None,
+            pixel_snap_h: true,
+            glyph_offset: [0.0, 0.0],
+            rasterizer_multiply: 1.0,
+        }
+    }
+}
+
 /// Configuration settings for a font
 #[derive(Clone, Debug)]
 pub struct FontConfig {
@@ -314,7 +616,10 @@ pub struct FontConfig {
     pub glyph_min_advance_x: f32,
     /// Maximum advance_x for glyphs
     pub glyph_max_advance_x: f32,
-    /// Settings for a custom font rasterizer if used
+    /// Settings for a custom font rasterizer if used.
+    ///
+    /// With the `freetype` feature, these are the bits of `FreeTypeBuilderFlags`, e.g.
+    /// `FreeTypeBuilderFlags::BOLD.bits()`.
     pub font_builder_flags: u32,
     /// Brighten (>1.0) or darken (<1.0) font output
     pub rasterizer_multiply: f32,
@@ -472,7 +777,10 @@ impl Drop for SharedFontAtlas {
     fn drop(&mut self) {
         // if we're about to drop the last one...
         if Rc::strong_count(&self.0) == 1 {
-            unsafe { sys::ImFontAtlas_destroy(*self.0) };
+            unsafe {
+                FontAtlas::from_raw_mut(&mut **self.0).release_glyph_ranges();
+                sys::ImFontAtlas_destroy(*self.0);
+            }
         }
     }
 }
@@ -525,3 +833,102 @@ impl Drop for SharedFontAtlas {
 //         }
 //     }
 // }
+
+#[cfg(all(feature = "freetype", not(feature = "wasm")))]
+#[test]
+fn test_font_builders() {
+    let (_guard, mut ctx) = crate::test::test_ctx();
+    let fonts = ctx.fonts();
+    fonts.add_font(&[FontSource::TtfData {
+        data: include_bytes!("../../../resources/Roboto-Regular.ttf"),
+        size_pixels: 13.0,
+        config: None,
+    }]);
+    let pixels = |fonts: &mut FontAtlas| {
+        fonts.clear_tex_data();
+        fonts.build_alpha8_texture().data.to_vec()
+    };
+
+    let freetype = pixels(fonts);
+    fonts.set_font_builder_flags(FreeTypeBuilderFlags::BOLD);
+    assert_eq!(fonts.font_builder_flags(), FreeTypeBuilderFlags::BOLD);
+    assert_ne!(pixels(fonts), freetype);
+    fonts.set_font_builder_flags(FreeTypeBuilderFlags::empty());
+    assert_eq!(pixels(fonts), freetype);
+
+    fonts.set_font_builder(FontBuilder::StbTrueType);
+    assert_ne!(pixels(fonts), freetype);
+    fonts.set_font_builder(FontBuilder::FreeType);
+    assert_eq!(pixels(fonts), freetype);
+}
+
+#[test]
+fn test_custom_rects() {
+    let (_guard, mut ctx) = crate::test::test_ctx();
+    let fonts = ctx.fonts();
+    let font = fonts.add_font(&[FontSource::DefaultFontData { config: None }]);
+    let regular = fonts.add_custom_rect_regular(3, 2);
+    let glyph = fonts.add_custom_rect_font_glyph(font, '\u{e000}', 2, 2, 3.0, [0.0, 0.0]);
+    assert_eq!(fonts.custom_rect(regular), None);
+
+    let pixels: Vec<u8> = (0..3 * 2 * 4).map(|i| i as u8).collect();
+    for rgba32 in [false, true] {
+        fonts.clear_tex_data();
+        if rgba32 {
+            fonts.build_rgba32_texture();
+        } else {
+            fonts.build_alpha8_texture();
+        }
+        let rect = fonts.custom_rect(regular).unwrap();
+        assert_eq!(
+            [rect.max[0] - rect.min[0], rect.max[1] - rect.min[1]],
+            [3, 2]
+        );
+        assert!(fonts.custom_rect(glyph).is_some());
+        fonts.write_custom_rect_pixels(regular, &pixels);
+
+        let texture = if rgba32 {
+            fonts.build_rgba32_texture()
+        } else {
+            fonts.build_alpha8_texture()
+        };
+        let bytes_per_pixel = if rgba32 { 4 } else { 1 };
+        for y in 0..2 {
+            let start = ((rect.min[1] + y) * texture.width + rect.min[0]) as usize;
+            let row = &texture.data[start * bytes_per_pixel..(start + 3) * bytes_per_pixel];
+            let expected = &pixels[y as usize * 12..(y as usize + 1) * 12];
+            if rgba32 {
+                assert_eq!(row, expected);
+            } else {
+                let alpha: Vec<u8> = expected.iter().skip(3).step_by(4).copied().collect();
+                assert_eq!(row, &alpha[..]);
+            }
+        }
+    }
+
+    fonts.clear();
+    assert_eq!(fonts.custom_rect(regular), None);
+}
+
+#[test]
+fn test_add_merged_icon_font() {
+    let (_guard, mut ctx) = crate::test::test_ctx();
+    let fonts = ctx.fonts();
+    let font = fonts.add_font(&[FontSource::DefaultFontData { config: None }]);
+    let merged = fonts.add_merged_icon_font(
+        include_bytes!("../../../resources/mplus-1p-regular.ttf"),
+        13.0,
+        FontGlyphRanges::from_slice(&[0x2605, 0x2605, 0]),
+        IconFontConfig::default(),
+    );
+    assert_eq!(merged, font);
+    assert_eq!(fonts.fonts(), [font]);
+    fonts.build_alpha8_texture();
+
+    let font = fonts.get_font(font).unwrap() as *const Font as *mut sys::ImFont;
+    unsafe {
+        let star = sys::ImFont_FindGlyphNoFallback(font, 0x2605);
+        assert!(!star.is_null());
+        assert!((*star).AdvanceX >= 13.0);
+    }
+}
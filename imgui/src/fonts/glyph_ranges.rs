@@ -1,3 +1,6 @@
+use std::ptr;
+use std::rc::Rc;
+
 use crate::sys;
 
 #[derive(Clone, Eq, PartialEq, Debug)]
@@ -6,11 +9,13 @@ enum FontGlyphRangeData {
     ChineseFull,
     Cyrillic,
     Default,
+    Greek,
     Japanese,
     Korean,
     Thai,
     Vietnamese,
     Custom(*const sys::ImWchar),
+    Owned(Rc<[sys::ImWchar]>),
 }
 
 /// A set of Unicode codepoints
@@ -29,6 +34,10 @@ impl FontGlyphRanges {
     pub fn cyrillic() -> FontGlyphRanges {
         FontGlyphRanges(FontGlyphRangeData::Cyrillic)
     }
+    /// A set of glyph ranges appropriate for use with Greek text.
+    pub fn greek() -> FontGlyphRanges {
+        FontGlyphRanges(FontGlyphRangeData::Greek)
+    }
     /// A set of glyph ranges appropriate for use with Japanese text.
     pub fn japanese() -> FontGlyphRanges {
         FontGlyphRanges(FontGlyphRangeData::Japanese)
@@ -142,6 +151,15 @@ impl FontGlyphRanges {
         FontGlyphRanges(FontGlyphRangeData::Custom(ptr))
     }
 
+    /// Returns the storage of ranges built by a [`GlyphRangesBuilder`], which has to be kept
+    /// alive by the atlas using them.
+    pub(crate) fn owned_storage(&self) -> Option<&Rc<[sys::ImWchar]>> {
+        match &self.0 {
+            FontGlyphRangeData::Owned(ranges) => Some(ranges),
+            _ => None,
+        }
+    }
+
     pub(crate) unsafe fn to_ptr(&self, atlas: *mut sys::ImFontAtlas) -> *const sys::ImWchar {
         match self.0 {
             FontGlyphRangeData::ChineseFull => sys::ImFontAtlas_GetGlyphRangesChineseFull(atlas),
@@ -150,11 +168,13 @@ impl FontGlyphRanges {
             }
             FontGlyphRangeData::Cyrillic => sys::ImFontAtlas_GetGlyphRangesCyrillic(atlas),
             FontGlyphRangeData::Default => sys::ImFontAtlas_GetGlyphRangesDefault(atlas),
+            FontGlyphRangeData::Greek => sys::ImFontAtlas_GetGlyphRangesGreek(atlas),
             FontGlyphRangeData::Japanese => sys::ImFontAtlas_GetGlyphRangesJapanese(atlas),
             FontGlyphRangeData::Korean => sys::ImFontAtlas_GetGlyphRangesKorean(atlas),
             FontGlyphRangeData::Thai => sys::ImFontAtlas_GetGlyphRangesThai(atlas),
             FontGlyphRangeData::Vietnamese => sys::ImFontAtlas_GetGlyphRangesVietnamese(atlas),
             FontGlyphRangeData::Custom(ptr) => ptr,
+            FontGlyphRangeData::Owned(ref ranges) => ranges.as_ptr(),
         }
     }
 }
@@ -165,3 +185,149 @@ impl Default for FontGlyphRanges {
         FontGlyphRanges(FontGlyphRangeData::Default)
     }
 }
+
+/// Builds a set of glyph ranges from text, characters and other ranges.
+///
+/// The built [`FontGlyphRanges`] own their storage, so they can be used after the builder is
+/// dropped. A font atlas keeps the ranges of its fonts alive until its input data is cleared.
+///
+/// # Example
+///
+/// ```no_run
+/// # use imgui::*;
+/// # let mut ctx = Context::create();
+/// # let font_data: &[u8] = &[];
+/// let glyph_ranges = GlyphRangesBuilder::new()
+///     .add_ranges(FontGlyphRanges::default())
+///     .add_text("Größe, Ελληνικά")
+///     .build();
+/// ctx.fonts().add_font(&[FontSource::TtfData {
+///     data: font_data,
+///     size_pixels: 13.0,
+///     config: Some(FontConfig {
+///         glyph_ranges,
+///         ..FontConfig::default()
+///     }),
+/// }]);
+/// ```
+#[doc(alias = "ImFontGlyphRangesBuilder")]
+pub struct GlyphRangesBuilder {
+    raw: *mut sys::ImFontGlyphRangesBuilder,
+}
+
+impl GlyphRangesBuilder {
+    /// Creates a new builder, with no glyphs.
+    pub fn new() -> Self {
+        GlyphRangesBuilder {
+            raw: unsafe { sys::ImFontGlyphRangesBuilder_ImFontGlyphRangesBuilder() },
+        }
+    }
+
+    /// Adds a single character.
+    #[doc(alias = "AddChar")]
+    pub fn add_char(&mut self, c: char) -> &mut Self {
+        unsafe { sys::ImFontGlyphRangesBuilder_AddChar(self.raw, c as sys::ImWchar) };
+        self
+    }
+
+    /// Adds all the characters of *text*.
+    #[doc(alias = "AddText")]
+    pub fn add_text(&mut self, text: &str) -> &mut Self {
+        let range = text.as_bytes().as_ptr_range();
+        unsafe {
+            sys::ImFontGlyphRangesBuilder_AddText(self.raw, range.start as _, range.end as _)
+        };
+        self
+    }
+
+    /// Adds all the characters of *ranges*.
+    #[doc(alias = "AddRanges")]
+    pub fn add_ranges(&mut self, ranges: FontGlyphRanges) -> &mut Self {
+        unsafe {
+            if ranges.owned_storage().is_some() || matches!(ranges.0, FontGlyphRangeData::Custom(_))
+            {
+                sys::ImFontGlyphRangesBuilder_AddRanges(self.raw, ranges.to_ptr(ptr::null_mut()));
+            } else {
+                // The predefined ranges are static, but are returned by methods of the atlas
+                let atlas = sys::ImFontAtlas_ImFontAtlas();
+                sys::ImFontGlyphRangesBuilder_AddRanges(self.raw, ranges.to_ptr(atlas));
+                sys::ImFontAtlas_destroy(atlas);
+            }
+        }
+        self
+    }
+
+    /// Builds the glyph ranges of all the characters added so far.
+    #[doc(alias = "BuildRanges")]
+    pub fn build(&self) -> FontGlyphRanges {
+        let mut out = sys::ImVector_ImWchar {
+            Size: 0,
+            Capacity: 0,
+            Data: ptr::null_mut(),
+        };
+        let ranges = unsafe {
+            sys::ImFontGlyphRangesBuilder_BuildRanges(self.raw, &mut out);
+            let ranges = Rc::from(std::slice::from_raw_parts(out.Data, out.Size as usize));
+            sys::igMemFree(out.Data as *mut _);
+            ranges
+        };
+        FontGlyphRanges(FontGlyphRangeData::Owned(ranges))
+    }
+}
+
+impl Default for GlyphRangesBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Drop for GlyphRangesBuilder {
+    fn drop(&mut self) {
+        unsafe { sys::ImFontGlyphRangesBuilder_destroy(self.raw) };
+    }
+}
+
+#[test]
+fn test_glyph_ranges_builder() {
+    use crate::{FontConfig, FontSource};
+
+    let (_guard, mut ctx) = crate::test::test_ctx();
+    let glyph_ranges = {
+        let mut builder = GlyphRangesBuilder::new();
+        builder.add_text("héllo").add_char('λ');
+        builder.build()
+    };
+    let contains = |ranges: &FontGlyphRanges, c: char| {
+        let ranges = ranges.owned_storage().unwrap();
+        ranges
+            .chunks(2)
+            .any(|range| range.len() == 2 && (range[0]..=range[1]).contains(&(c as u32)))
+    };
+    assert!(contains(&glyph_ranges, 'é'));
+    assert!(contains(&glyph_ranges, 'λ'));
+    assert!(!contains(&glyph_ranges, 'a'));
+    assert_eq!(glyph_ranges.owned_storage().unwrap().last(), Some(&0));
+
+    let with_default = GlyphRangesBuilder::new()
+        .add_ranges(FontGlyphRanges::default())
+        .add_ranges(glyph_ranges.clone())
+        .build();
+    assert!(contains(&with_default, 'a'));
+    assert!(contains(&with_default, 'λ'));
+
+    let fonts = ctx.fonts();
+    let font = fonts.add_font(&[FontSource::TtfData {
+        data: include_bytes!("../../../resources/Roboto-Regular.ttf"),
+        size_pixels: 13.0,
+        config: Some(FontConfig {
+            glyph_ranges,
+            ..FontConfig::default()
+        }),
+    }]);
+    fonts.build_alpha8_texture();
+    let font = fonts.get_font(font).unwrap() as *const crate::Font as *mut sys::ImFont;
+    unsafe {
+        assert!(!sys::ImFont_FindGlyphNoFallback(font, 'é' as sys::ImWchar).is_null());
+        assert!(sys::ImFont_FindGlyphNoFallback(font, 'a' as sys::ImWchar).is_null());
+    }
+}
@@ -0,0 +1,247 @@
+use std::ptr;
+
+use crate::fonts::atlas::{FontConfig, FontId, FontSource};
+use crate::Context;
+
+/// Size of the default font (ProggyClean.ttf), when not specified in its config
+const DEFAULT_FONT_SIZE: f32 = 13.0;
+
+/// A font registered in a [`FontManager`]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub struct ManagedFontId(usize);
+
+/// An owned copy of a [`FontSource`]
+#[derive(Clone, Debug)]
+enum ManagedFontSource {
+    DefaultFontData {
+        config: Option<FontConfig>,
+    },
+    TtfData {
+        data: Vec<u8>,
+        size_pixels: f32,
+        config: Option<FontConfig>,
+    },
+}
+
+impl ManagedFontSource {
+    fn new(source: &FontSource<'_>) -> Self {
+        match source {
+            FontSource::DefaultFontData { config } => ManagedFontSource::DefaultFontData {
+                config: config.clone(),
+            },
+            FontSource::TtfData {
+                data,
+                size_pixels,
+                config,
+            } => ManagedFontSource::TtfData {
+                data: data.to_vec(),
+                size_pixels: *size_pixels,
+                config: config.clone(),
+            },
+        }
+    }
+
+    /// Returns the source with its sizes, in logical pixels, converted to physical pixels
+    fn scaled(&self, scale: f32) -> FontSource<'_> {
+        match self {
+            ManagedFontSource::DefaultFontData { config } => {
+                let mut config = config.clone().unwrap_or_default();
+                if config.size_pixels <= 0.0 {
+                    config.size_pixels = DEFAULT_FONT_SIZE;
+                }
+                FontSource::DefaultFontData {
+                    config: Some(scale_config(config, scale)),
+                }
+            }
+            ManagedFontSource::TtfData {
+                data,
+                size_pixels,
+                config,
+            } => FontSource::TtfData {
+                data,
+                size_pixels: size_pixels * scale,
+                config: config.clone().map(|config| scale_config(config, scale)),
+            },
+        }
+    }
+}
+
+fn scale_config(mut config: FontConfig, scale: f32) -> FontConfig {
+    config.size_pixels *= scale;
+    config.glyph_extra_spacing = config.glyph_extra_spacing.map(|v| v * scale);
+    config.glyph_offset = config.glyph_offset.map(|v| v * scale);
+    config.glyph_min_advance_x *= scale;
+    if config.glyph_max_advance_x != f32::MAX {
+        config.glyph_max_advance_x *= scale;
+    }
+    config
+}
+
+/// Rasterizes fonts at the DPI factor of the display, so that text stays sharp on high DPI
+/// displays.
+///
+/// Fonts are registered once with their size in logical pixels, and
+/// [`FontManager::rebuild`] adds them to the font atlas at the size in physical pixels, then
+/// sets `Io::font_global_scale` so that they are displayed at their logical size. The first
+/// registered font is used as the default font.
+///
+/// The fonts of every DPI factor seen so far are kept in the atlas, so that moving a window
+/// back and forth between displays doesn't rasterize the fonts again. The manager owns the
+/// atlas: fonts added to it directly are removed when it is rebuilt.
+///
+/// # Example
+///
+/// ```no_run
+/// # use imgui::*;
+/// # fn reload_font_texture(ctx: &mut Context) {}
+/// let mut ctx = Context::create();
+/// let mut font_manager = FontManager::new();
+/// let roboto = font_manager.add_font(&[FontSource::TtfData {
+///     data: include_bytes!("../../../resources/Roboto-Regular.ttf"),
+///     size_pixels: 13.0,
+///     config: None,
+/// }]);
+///
+/// // Before the first frame, and whenever the DPI factor changes
+/// let hidpi_factor = 2.0;
+/// if font_manager.rebuild(&mut ctx, hidpi_factor) {
+///     reload_font_texture(&mut ctx);
+/// }
+///
+/// let ui = ctx.frame();
+/// let _font = ui.push_font(font_manager.font(roboto).unwrap());
+/// ```
+#[derive(Debug, Default)]
+pub struct FontManager {
+    fonts: Vec<Vec<ManagedFontSource>>,
+    /// The atlas fonts of each DPI factor, in the order of `fonts`
+    variants: Vec<(f64, Vec<FontId>)>,
+    scale_factor: Option<f64>,
+}
+
+impl FontManager {
+    /// Creates a font manager, with no fonts.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Registers a font, merging glyphs from several sources like [`FontAtlas::add_font`].
+    ///
+    /// Sizes are given in logical pixels. The font is added to the atlas on the next call to
+    /// [`FontManager::rebuild`].
+    ///
+    /// [`FontAtlas::add_font`]: crate::FontAtlas::add_font
+    pub fn add_font(&mut self, font_sources: &[FontSource<'_>]) -> ManagedFontId {
+        assert!(!font_sources.is_empty(), "A font needs at least one source");
+        self.fonts
+            .push(font_sources.iter().map(ManagedFontSource::new).collect());
+        self.variants.clear();
+        ManagedFontId(self.fonts.len() - 1)
+    }
+
+    /// Returns the atlas font of a registered font, at the current DPI factor.
+    ///
+    /// Returns `None` if the font was registered after the last rebuild.
+    pub fn font(&self, id: ManagedFontId) -> Option<FontId> {
+        let scale_factor = self.scale_factor?;
+        let (_, fonts) = self.variants.iter().find(|(s, _)| *s == scale_factor)?;
+        fonts.get(id.0).copied()
+    }
+
+    /// Returns the DPI factor of the last rebuild.
+    pub fn scale_factor(&self) -> Option<f64> {
+        self.scale_factor
+    }
+
+    /// Switches the fonts to the given DPI factor, e.g. `WinitPlatform::hidpi_factor`.
+    ///
+    /// This must be called between frames. Returns true if the font atlas was rebuilt, in
+    /// which case the renderer has to reload the font texture, e.g. with
+    /// `reload_font_texture`. Switching to a DPI factor that was already used doesn't rebuild
+    /// the atlas.
+    pub fn rebuild(&mut self, ctx: &mut Context, scale_factor: f64) -> bool {
+        let rebuilt = !self.variants.iter().any(|(s, _)| *s == scale_factor);
+        if rebuilt {
+            let mut scale_factors: Vec<f64> = self.variants.iter().map(|(s, _)| *s).collect();
+            scale_factors.push(scale_factor);
+
+            let atlas = ctx.fonts();
+            atlas.clear();
+            self.variants = scale_factors
+                .into_iter()
+                .map(|s| {
+                    let fonts = self
+                        .fonts
+                        .iter()
+                        .map(|sources| {
+                            let sources: Vec<FontSource<'_>> = sources
+                                .iter()
+                                .map(|source| source.scaled(s as f32))
+                                .collect();
+                            atlas.add_font(&sources)
+                        })
+                        .collect();
+                    (s, fonts)
+                })
+                .collect();
+        }
+        self.scale_factor = Some(scale_factor);
+
+        let default_font = self.fonts.first().and_then(|_| self.font(ManagedFontId(0)));
+        let io = ctx.io_mut();
+        io.font_global_scale = (1.0 / scale_factor) as f32;
+        io.font_default = match default_font {
+            Some(font) => font.0 as *mut _,
+            None => ptr::null_mut(),
+        };
+        rebuilt
+    }
+}
+
+#[test]
+fn test_font_manager() {
+    let (_guard, mut ctx) = crate::test::test_ctx();
+    let mut manager = FontManager::new();
+    let default = manager.add_font(&[FontSource::DefaultFontData { config: None }]);
+    let roboto = manager.add_font(&[FontSource::TtfData {
+        data: include_bytes!("../../../resources/Roboto-Regular.ttf"),
+        size_pixels: 16.0,
+        config: None,
+    }]);
+    assert_eq!(manager.font(default), None);
+
+    // Font sizes are only known once the atlas is built by the renderer
+    let font_size = |ctx: &mut Context, manager: &FontManager, id| {
+        let font = manager.font(id).unwrap();
+        ctx.fonts().build_rgba32_texture();
+        ctx.fonts().get_font(font).unwrap().font_size
+    };
+
+    assert!(manager.rebuild(&mut ctx, 1.0));
+    assert_eq!(font_size(&mut ctx, &manager, default), 13.0);
+    assert_eq!(font_size(&mut ctx, &manager, roboto), 16.0);
+    assert_eq!(ctx.io().font_global_scale, 1.0);
+    assert_eq!(
+        ctx.io().font_default,
+        manager.font(default).unwrap().0 as *mut _
+    );
+
+    assert!(manager.rebuild(&mut ctx, 2.0));
+    assert_eq!(font_size(&mut ctx, &manager, default), 26.0);
+    assert_eq!(font_size(&mut ctx, &manager, roboto), 32.0);
+    assert_eq!(ctx.io().font_global_scale, 0.5);
+    assert_eq!(ctx.fonts().fonts().len(), 4);
+
+    // Going back to a known DPI factor reuses its fonts
+    assert!(!manager.rebuild(&mut ctx, 1.0));
+    assert_eq!(font_size(&mut ctx, &manager, roboto), 16.0);
+    assert_eq!(ctx.io().font_global_scale, 1.0);
+    assert!(!manager.rebuild(&mut ctx, 1.0));
+
+    // Registering a font rebuilds the atlas
+    let third = manager.add_font(&[FontSource::DefaultFontData { config: None }]);
+    assert_eq!(manager.font(third), None);
+    assert!(manager.rebuild(&mut ctx, 1.0));
+    assert!(manager.font(third).is_some());
+    assert_eq!(ctx.fonts().fonts().len(), 3);
+}
@@ -6,6 +6,7 @@ pub mod atlas;
 pub mod font;
 pub mod glyph;
 pub mod glyph_ranges;
+pub mod manager;
 
 /// # Fonts
 impl Ui {
@@ -33,4 +34,52 @@ impl Ui {
     pub fn set_window_font_scale(&self, scale: f32) {
         unsafe { sys::igSetWindowFontScale(scale) }
     }
+    /// Returns the font scale of the current window, as set by
+    /// [`Ui::set_window_font_scale`]
+    pub fn window_font_scale(&self) -> f32 {
+        // imgui has no getter, but the font size is updated as soon as the scale is set. Child
+        // windows inherit the scale of their parent, which this leaves out.
+        let scaled = self.current_font_size();
+        unsafe { sys::igSetWindowFontScale(1.0) };
+        let scale = scaled / self.current_font_size();
+        unsafe { sys::igSetWindowFontScale(scale) };
+        scale
+    }
+    /// Runs a closure with the font of the current window scaled, then restores the previous
+    /// scale.
+    ///
+    /// This enlarges text, and the widgets sized after it, without loading another font size,
+    /// so the text may look blurry. Sizes from the style, such as paddings, are not scaled.
+    pub fn with_font_scale<R>(&self, scale: f32, f: impl FnOnce() -> R) -> R {
+        let previous = self.window_font_scale();
+        self.set_window_font_scale(scale);
+        let result = f();
+        self.set_window_font_scale(previous);
+        result
+    }
+}
+
+#[test]
+fn test_with_font_scale() {
+    let (_guard, mut ctx) = crate::test::test_ctx_initialized();
+    let ui = ctx.frame();
+    ui.window("Scaled").build(|| {
+        let size = ui.current_font_size();
+        assert_eq!(ui.window_font_scale(), 1.0);
+        ui.with_font_scale(2.0, || {
+            assert_eq!(ui.current_font_size(), size * 2.0);
+            ui.with_font_scale(0.5, || assert_eq!(ui.window_font_scale(), 0.5));
+            assert_eq!(ui.window_font_scale(), 2.0);
+        });
+        assert_eq!(ui.current_font_size(), size);
+
+        ui.set_window_font_scale(1.5);
+        ui.child_window("Child").build(|| {
+            // The parent's scale is applied to the text, but isn't the child's own
+            assert_eq!(ui.current_font_size(), size * 1.5);
+            assert_eq!(ui.window_font_scale(), 1.0);
+        });
+        ui.with_font_scale(3.0, || ());
+        assert_eq!(ui.window_font_scale(), 1.5);
+    });
 }
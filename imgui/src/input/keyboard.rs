@@ -1,4 +1,9 @@
+#[cfg(feature = "docking")]
+use bitflags::bitflags;
+
 use crate::sys;
+#[cfg(feature = "docking")]
+use crate::Id;
 use crate::Ui;
 
 /// A key identifier
@@ -302,6 +307,114 @@ impl Key {
     pub const COUNT: usize = sys::ImGuiKey_NamedKey_COUNT as usize;
 }
 
+/// The owner of a key, see [`Ui::set_key_owner`].
+///
+/// Key ownership is part of the internal imgui API, which the bindings only include with the
+/// `docking` feature.
+#[cfg(feature = "docking")]
+#[derive(Copy, Clone, Debug, Hash, Eq, PartialEq)]
+pub enum KeyOwner {
+    /// Any owner. Testing it only fails if the key is locked by an owner.
+    Any,
+    /// No owner. Setting it releases the key, testing it fails if the key has an owner.
+    None,
+    /// An item or window, e.g. from [`Ui::item_id`] or [`Ui::get_id`]
+    Id(Id),
+}
+
+#[cfg(feature = "docking")]
+impl KeyOwner {
+    fn raw(self) -> sys::ImGuiID {
+        match self {
+            // ImGuiKeyOwner_Any and ImGuiKeyOwner_None are defines, without bindings
+            KeyOwner::Any => 0,
+            KeyOwner::None => u32::MAX,
+            KeyOwner::Id(id) => id.0,
+        }
+    }
+
+    fn from_raw(raw: sys::ImGuiID) -> Self {
+        match raw {
+            0 => KeyOwner::Any,
+            u32::MAX => KeyOwner::None,
+            id => KeyOwner::Id(Id(id)),
+        }
+    }
+}
+
+#[cfg(feature = "docking")]
+impl From<Id> for KeyOwner {
+    fn from(id: Id) -> Self {
+        KeyOwner::Id(id)
+    }
+}
+
+#[cfg(feature = "docking")]
+bitflags! {
+    /// Flags for [`Ui::set_key_owner`]
+    #[repr(transparent)]
+    pub struct KeyOwnerFlags: u32 {
+        /// Only the owner sees the key as pressed or down for the rest of the frame, even
+        /// when testing for [`KeyOwner::Any`], e.g. with [`Ui::is_key_pressed`]
+        const LOCK_THIS_FRAME = sys::ImGuiInputFlags_LockThisFrame;
+        /// Like `LOCK_THIS_FRAME`, but the lock lasts until the key is released
+        const LOCK_UNTIL_RELEASE = sys::ImGuiInputFlags_LockUntilRelease;
+    }
+}
+
+/// A key combined with modifiers, e.g. Ctrl+S, used as a keyboard shortcut.
+///
+/// ```
+/// # use imgui::{Key, KeyChord};
+/// const SAVE_AS: KeyChord = KeyChord::new(Key::S).ctrl().shift();
+/// ```
+#[derive(Copy, Clone, Debug, Hash, Eq, PartialEq)]
+pub struct KeyChord {
+    /// The key that triggers the chord
+    pub key: Key,
+    /// Whether Control must be held
+    pub ctrl: bool,
+    /// Whether Shift must be held
+    pub shift: bool,
+    /// Whether Alt must be held
+    pub alt: bool,
+    /// Whether Cmd/Super/Windows must be held
+    pub super_: bool,
+}
+
+impl KeyChord {
+    /// Creates a chord for a key pressed without any modifier.
+    pub const fn new(key: Key) -> Self {
+        KeyChord {
+            key,
+            ctrl: false,
+            shift: false,
+            alt: false,
+            super_: false,
+        }
+    }
+    /// Requires Control to be held.
+    pub const fn ctrl(mut self) -> Self {
+        self.ctrl = true;
+        self
+    }
+    /// Requires Shift to be held.
+    pub const fn shift(mut self) -> Self {
+        self.shift = true;
+        self
+    }
+    /// Requires Alt to be held.
+    pub const fn alt(mut self) -> Self {
+        self.alt = true;
+        self
+    }
+    /// Requires Cmd/Super/Windows to be held.
+    pub const fn super_(mut self) -> Self {
+        self.super_ = true;
+        self
+    }
+}
+
 /// Target widget selection for keyboard focus
 #[derive(Copy, Clone, Debug, Hash, Eq, PartialEq)]
 pub enum FocusedWidget {
@@ -473,4 +586,162 @@ impl Ui {
             sys::igSetKeyboardFocusHere(target_widget.as_offset());
         }
     }
+
+    /// Returns the name of a key, e.g. "S" or "Enter".
+    #[doc(alias = "GetKeyName")]
+    pub fn key_name(&self, key: Key) -> &str {
+        unsafe {
+            let name = sys::igGetKeyName(key as sys::ImGuiKey);
+            std::ffi::CStr::from_ptr(name).to_str().unwrap_or("")
+        }
+    }
+
+    /// Returns the human-readable name of a key chord, e.g. "Ctrl+Shift+S".
+    #[doc(alias = "GetKeyChordName")]
+    pub fn key_chord_name(&self, chord: KeyChord) -> String {
+        let mut name = String::new();
+        for (held, modifier) in [
+            (chord.ctrl, "Ctrl+"),
+            (chord.shift, "Shift+"),
+            (chord.alt, "Alt+"),
+            (chord.super_, "Super+"),
+        ] {
+            if held {
+                name.push_str(modifier);
+            }
+        }
+        name.push_str(self.key_name(chord.key));
+        name
+    }
+
+    /// Returns true if the chord's key was pressed this frame while exactly its modifiers are
+    /// held. Key repeats are ignored.
+    ///
+    /// While a text input is active, chords without Ctrl, Alt or Super are ignored, so typing
+    /// isn't mistaken for shortcuts. With the `docking` feature, a key locked by another owner
+    /// (see `Ui::set_key_owner`) isn't seen as pressed either.
+    pub fn is_key_chord_pressed(&self, chord: KeyChord) -> bool {
+        let io = self.io();
+        if io.want_text_input && !(chord.ctrl || chord.alt || chord.super_) {
+            return false;
+        }
+        io.key_ctrl == chord.ctrl
+            && io.key_shift == chord.shift
+            && io.key_alt == chord.alt
+            && io.key_super == chord.super_
+            && self.is_key_pressed_no_repeat(chord.key)
+    }
+
+    /// Sets the owner of a key, to arbitrate between widgets which react to the same key.
+    ///
+    /// The owner is kept until it is changed, or until the key is released. Without a lock,
+    /// only [`Ui::test_key_owner`] is affected. With [`KeyOwnerFlags::LOCK_THIS_FRAME`] or
+    /// [`KeyOwnerFlags::LOCK_UNTIL_RELEASE`], [`Ui::is_key_pressed`] and the other key
+    /// queries also return false for the key, until the lock ends. The lock only affects
+    /// code that runs after this call, so the owner must be submitted first.
+    ///
+    /// The usual pattern is for a widget to claim a key while it is active or focused:
+    ///
+    /// ```no_run
+    /// # use imgui::*;
+    /// # let mut ctx = Context::create();
+    /// # let ui = ctx.frame();
+    /// # let mut playing = false;
+    /// ui.button("Record");
+    /// if ui.is_item_focused() {
+    ///     // Space activates the focused button, not the timeline
+    ///     ui.set_key_owner(Key::Space, ui.item_id(), KeyOwnerFlags::LOCK_THIS_FRAME);
+    /// }
+    /// // Only true when no widget locked Space
+    /// if ui.is_key_pressed(Key::Space) {
+    ///     playing = !playing;
+    /// }
+    /// ```
+    #[cfg(feature = "docking")]
+    #[doc(alias = "SetKeyOwner")]
+    pub fn set_key_owner(&self, key: Key, owner: impl Into<KeyOwner>, flags: KeyOwnerFlags) {
+        unsafe { sys::igSetKeyOwner(key as u32, owner.into().raw(), flags.bits() as i32) }
+    }
+
+    /// Sets the last item as the owner of a key, if it is hovered or active.
+    ///
+    /// See [`Ui::set_key_owner`].
+    #[cfg(feature = "docking")]
+    #[doc(alias = "SetItemKeyOwner")]
+    pub fn set_item_key_owner(&self, key: Key, flags: KeyOwnerFlags) {
+        unsafe { sys::igSetItemKeyOwner(key as u32, flags.bits() as i32) }
+    }
+
+    /// Returns the owner of a key, or [`KeyOwner::None`] if it has none.
+    #[cfg(feature = "docking")]
+    #[doc(alias = "GetKeyOwner")]
+    pub fn key_owner(&self, key: Key) -> KeyOwner {
+        KeyOwner::from_raw(unsafe { sys::igGetKeyOwner(key as u32) })
+    }
+
+    /// Returns true if the given owner can read the key: it owns the key, or the key has no
+    /// owner. [`KeyOwner::Any`] can read every key which isn't locked.
+    #[cfg(feature = "docking")]
+    #[doc(alias = "TestKeyOwner")]
+    pub fn test_key_owner(&self, key: Key, owner: impl Into<KeyOwner>) -> bool {
+        unsafe { sys::igTestKeyOwner(key as u32, owner.into().raw()) }
+    }
+}
+
+#[test]
+fn test_key_chord_pressed() {
+    let (_guard, mut ctx) = crate::test::test_ctx_initialized();
+    let save = KeyChord::new(Key::S).ctrl();
+    assert_eq!(ctx.frame().key_chord_name(save), "Ctrl+S");
+    ctx.render();
+
+    ctx.io_mut().add_key_event(Key::S, true);
+    assert!(!ctx.frame().is_key_chord_pressed(save));
+    ctx.render();
+
+    // Events for the same key are spread over several frames, so release it separately
+    ctx.io_mut().add_key_event(Key::S, false);
+    ctx.frame();
+    ctx.render();
+
+    ctx.io_mut().add_key_event(Key::ModCtrl, true);
+    ctx.io_mut().add_key_event(Key::S, true);
+    let ui = ctx.frame();
+    assert!(ui.is_key_chord_pressed(save));
+    assert!(!ui.is_key_chord_pressed(KeyChord::new(Key::S).ctrl().shift()));
+}
+
+#[cfg(feature = "docking")]
+#[test]
+fn test_key_owner() {
+    let (_guard, mut ctx) = crate::test::test_ctx_initialized();
+    ctx.io_mut().add_key_event(Key::Space, true);
+    let ui = ctx.frame();
+    let owner = ui.get_id("owner");
+    let other = ui.get_id("other");
+    assert_eq!(ui.key_owner(Key::Space), KeyOwner::None);
+    assert!(ui.test_key_owner(Key::Space, other));
+
+    // Without a lock, only the owner tests are affected
+    ui.set_key_owner(Key::Space, owner, KeyOwnerFlags::empty());
+    assert_eq!(ui.key_owner(Key::Space), KeyOwner::Id(owner));
+    assert!(ui.test_key_owner(Key::Space, owner));
+    assert!(!ui.test_key_owner(Key::Space, other));
+    assert!(!ui.test_key_owner(Key::Space, KeyOwner::None));
+    assert!(ui.test_key_owner(Key::Space, KeyOwner::Any));
+    assert!(ui.is_key_pressed(Key::Space));
+
+    ui.set_key_owner(Key::Space, owner, KeyOwnerFlags::LOCK_THIS_FRAME);
+    assert!(!ui.test_key_owner(Key::Space, KeyOwner::Any));
+    assert!(!ui.is_key_pressed(Key::Space));
+    assert!(!ui.is_key_chord_pressed(KeyChord::new(Key::Space)));
+    ctx.render();
+
+    // The lock ends with the frame, the owner stays while the key is down
+    let ui = ctx.frame();
+    assert_eq!(ui.key_owner(Key::Space), KeyOwner::Id(owner));
+    assert!(ui.is_key_down(Key::Space));
+    ui.set_key_owner(Key::Space, KeyOwner::None, KeyOwnerFlags::empty());
+    assert_eq!(ui.key_owner(Key::Space), KeyOwner::None);
+    assert!(ui.test_key_owner(Key::Space, other));
 }
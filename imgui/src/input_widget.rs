@@ -59,6 +59,10 @@ bitflags!(
 
 macro_rules! impl_text_flags {
     ($InputType:ident) => {
+        /// Replaces all the flags of the widget.
+        ///
+        /// `InputTextFlags::CALLBACK_RESIZE` is always added back when building the widget,
+        /// since imgui-rs relies on it to grow the `String` in place.
         #[inline]
         pub fn flags(mut self, flags: InputTextFlags) -> Self {
             self.flags = flags;
@@ -284,16 +288,15 @@ where
     /// 3. Truncations by ImGui appear to be done primarily by insertions of `\0` to the truncation point.
     /// We will handle this for you and edit the string "properly" too, but this might show up in callbacks.
     pub fn build(self) -> bool {
+        let len = self.buf.len();
         // needs to be null-terminated! this is a hack!
         self.buf.push('\0');
 
         let (ptr, capacity) = (self.buf.as_mut_ptr(), self.buf.capacity());
+        let flags = self.flags | InputTextFlags::CALLBACK_RESIZE;
 
-        let mut data = UserData {
-            container: self.buf,
-            cback_handler: self.callback_handler,
-        };
-        let data = &mut data as *mut _ as *mut c_void;
+        let mut data = UserData::new(self.buf, self.callback_handler);
+        let data_ptr = &mut data as *mut _ as *mut c_void;
 
         let o = unsafe {
             if let Some(hint) = self.hint {
@@ -303,9 +306,9 @@ where
                     hint,
                     ptr as *mut sys::cty::c_char,
                     capacity,
-                    self.flags.bits() as i32,
+                    flags.bits() as i32,
                     Some(callback::<T>),
-                    data,
+                    data_ptr,
                 )
             } else {
                 let label = self.ui.scratch_txt(self.label);
@@ -314,40 +317,25 @@ where
                     label,
                     ptr as *mut sys::cty::c_char,
                     capacity,
-                    self.flags.bits() as i32,
+                    flags.bits() as i32,
                     Some(callback::<T>),
-                    data,
+                    data_ptr,
                 )
             }
         };
 
-        let cap = self.buf.capacity();
-
-        // SAFETY: this slice is simply a view into the underlying buffer
-        // of a String. We MAY be holding onto a view of uninitialized memory,
-        // however, since we're holding this as a u8 slice, I think it should be
-        // alright...
-        // additionally, we can go over the bytes directly, rather than char indices,
-        // because NUL will never appear in any UTF8 outside the NUL character (ie, within
-        // a char).
-        let buf = unsafe { std::slice::from_raw_parts(self.buf.as_ptr(), cap) };
-        if let Some(len) = buf.iter().position(|x| *x == b'\0') {
-            // `len` is the position of the first `\0` byte in the String
-            unsafe {
-                self.buf.as_mut_vec().set_len(len);
-            }
-        } else {
-            // There is no null terminator, the best we can do is to not
-            // update the string length.
-        }
+        data.finish(len);
 
         o
     }
 }
 
 #[must_use]
-pub struct InputTextMultiline<'ui, 'p, L, T = PassthroughCallback> {
+pub struct InputTextMultiline<'ui, 'p, L, T = PassthroughCallback, H = &'static str> {
     label: L,
+    // the hint can only be displayed with `igInputTextEx`, which is only in the docking bindings
+    #[cfg_attr(not(feature = "docking"), allow(dead_code))]
+    hint: Option<H>,
     buf: &'p mut String,
     flags: InputTextFlags,
     size: [f32; 2],
@@ -373,6 +361,7 @@ impl<'ui, 'p, L: AsRef<str>> InputTextMultiline<'ui, 'p, L, PassthroughCallback>
     pub fn new(ui: &'ui Ui, label: L, buf: &'p mut String, size: impl Into<MintVec2>) -> Self {
         InputTextMultiline {
             label,
+            hint: None,
             buf,
             flags: InputTextFlags::CALLBACK_RESIZE,
             size: size.into().into(),
@@ -382,7 +371,30 @@ impl<'ui, 'p, L: AsRef<str>> InputTextMultiline<'ui, 'p, L, PassthroughCallback>
     }
 }
 
-impl<'ui, 'p, T: InputTextCallbackHandler, L: AsRef<str>> InputTextMultiline<'ui, 'p, L, T> {
+impl<'ui, 'p, T, L, H> InputTextMultiline<'ui, 'p, L, T, H>
+where
+    T: InputTextCallbackHandler,
+    L: AsRef<str>,
+    H: AsRef<str>,
+{
+    /// Sets the hint displayed in the input text background while the text is empty.
+    ///
+    /// This is only available with the `docking` feature, because upstream Dear ImGui only
+    /// supports hints on multiline inputs through its internal API.
+    #[cfg(feature = "docking")]
+    #[inline]
+    pub fn hint<H2: AsRef<str>>(self, hint: H2) -> InputTextMultiline<'ui, 'p, L, T, H2> {
+        InputTextMultiline {
+            label: self.label,
+            hint: Some(hint),
+            buf: self.buf,
+            flags: self.flags,
+            size: self.size,
+            callback_handler: self.callback_handler,
+            ui: self.ui,
+        }
+    }
+
     impl_text_flags!(InputText);
 
     // I am commenting this ability out for now -- because we need to push `\0` for imgui,
@@ -406,7 +418,7 @@ impl<'ui, 'p, T: InputTextCallbackHandler, L: AsRef<str>> InputTextMultiline<'ui
         mut self,
         callbacks: InputTextMultilineCallback,
         callback_handler: T2,
-    ) -> InputTextMultiline<'ui, 'p, L, T2> {
+    ) -> InputTextMultiline<'ui, 'p, L, T2, H> {
         if callbacks.contains(InputTextMultilineCallback::COMPLETION) {
             self.flags.insert(InputTextFlags::CALLBACK_COMPLETION);
         }
@@ -422,6 +434,7 @@ impl<'ui, 'p, T: InputTextCallbackHandler, L: AsRef<str>> InputTextMultiline<'ui
 
         InputTextMultiline {
             label: self.label,
+            hint: self.hint,
             buf: self.buf,
             flags: self.flags,
             size: self.size,
@@ -445,47 +458,56 @@ impl<'ui, 'p, T: InputTextCallbackHandler, L: AsRef<str>> InputTextMultiline<'ui
     /// 3. Truncations by ImGui appear to be done primarily by insertions of `\0` to the truncation point.
     /// We will handle this for you and edit the string "properly" too, but this might show up in callbacks.
     pub fn build(self) -> bool {
+        let len = self.buf.len();
         // needs to be null-terminated! this is a hack!
         self.buf.push('\0');
         let (ptr, capacity) = (self.buf.as_mut_ptr(), self.buf.capacity());
+        let flags = self.flags | InputTextFlags::CALLBACK_RESIZE;
 
-        let mut data = UserData {
-            container: self.buf,
-            cback_handler: self.callback_handler,
-        };
-        let data = &mut data as *mut _ as *mut c_void;
+        let mut data = UserData::new(self.buf, self.callback_handler);
+        let data_ptr = &mut data as *mut _ as *mut c_void;
 
         let o = unsafe {
-            sys::igInputTextMultiline(
-                self.ui.scratch_txt(self.label),
-                ptr as *mut sys::cty::c_char,
-                capacity,
-                self.size.into(),
-                self.flags.bits() as i32,
-                Some(callback::<T>),
-                data,
-            )
+            cfg_if::cfg_if! {
+                if #[cfg(feature = "docking")] {
+                    if let Some(hint) = self.hint {
+                        let (label, hint) = self.ui.scratch_txt_two(self.label, hint);
+                        sys::igInputTextEx(
+                            label,
+                            hint,
+                            ptr as *mut sys::cty::c_char,
+                            capacity as i32,
+                            self.size.into(),
+                            (flags.bits() | sys::ImGuiInputTextFlags_Multiline) as i32,
+                            Some(callback::<T>),
+                            data_ptr,
+                        )
+                    } else {
+                        sys::igInputTextMultiline(
+                            self.ui.scratch_txt(self.label),
+                            ptr as *mut sys::cty::c_char,
+                            capacity,
+                            self.size.into(),
+                            flags.bits() as i32,
+                            Some(callback::<T>),
+                            data_ptr,
+                        )
+                    }
+                } else {
+                    sys::igInputTextMultiline(
+                        self.ui.scratch_txt(self.label),
+                        ptr as *mut sys::cty::c_char,
+                        capacity,
+                        self.size.into(),
+                        flags.bits() as i32,
+                        Some(callback::<T>),
+                        data_ptr,
+                    )
+                }
+            }
         };
 
-        let cap = self.buf.capacity();
-
-        // SAFETY: this slice is simply a view into the underlying buffer
-        // of a String. We MAY be holding onto a view of uninitialized memory,
-        // however, since we're holding this as a u8 slice, I think it should be
-        // alright...
-        // additionally, we can go over the bytes directly, rather than char indices,
-        // because NUL will never appear in any UTF8 outside the NUL character (ie, within
-        // a char).
-        let buf = unsafe { std::slice::from_raw_parts(self.buf.as_ptr(), cap) };
-        if let Some(len) = buf.iter().position(|x| *x == b'\0') {
-            // `len` is the position of the first `\0` byte in the String
-            unsafe {
-                self.buf.as_mut_vec().set_len(len);
-            }
-        } else {
-            // There is no null terminator, the best we can do is to not
-            // update the string length.
-        }
+        data.finish(len);
 
         o
     }
@@ -970,6 +992,30 @@ pub trait InputTextCallbackHandler {
     fn on_always(&mut self, _: TextCallbackData) {}
 }
 
+/// Handlers can also be passed by mutable reference, so that state which needs
+/// to outlive the frame (such as a command history) can be kept by the caller.
+impl<T: InputTextCallbackHandler + ?Sized> InputTextCallbackHandler for &mut T {
+    fn char_filter(&mut self, c: char) -> Option<char> {
+        T::char_filter(self, c)
+    }
+
+    fn on_completion(&mut self, data: TextCallbackData) {
+        T::on_completion(self, data)
+    }
+
+    fn on_edit(&mut self, data: TextCallbackData) {
+        T::on_edit(self, data)
+    }
+
+    fn on_history(&mut self, dir: HistoryDirection, data: TextCallbackData) {
+        T::on_history(self, dir, data)
+    }
+
+    fn on_always(&mut self, data: TextCallbackData) {
+        T::on_always(self, data)
+    }
+}
+
 /// The arrow key a user pressed to trigger the `on_history` callback.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub enum HistoryDirection {
@@ -1199,6 +1245,33 @@ impl TextCallbackData {
 struct UserData<T> {
     container: *mut String,
     cback_handler: T,
+    /// The length of the text written back by imgui, if it wrote to the buffer.
+    new_len: Option<usize>,
+}
+
+impl<T> UserData<T> {
+    fn new(container: &mut String, cback_handler: T) -> Self {
+        UserData {
+            container,
+            cback_handler,
+            new_len: None,
+        }
+    }
+
+    /// Removes the null terminator pushed for imgui, and applies the length of the new text
+    /// if imgui edited the string.
+    ///
+    /// When `CALLBACK_RESIZE` is set, imgui always calls the resize callback right before
+    /// copying text back into the buffer, so there is no need to scan the (possibly very large)
+    /// buffer for the null terminator on frames where nothing changed.
+    fn finish(self, old_len: usize) {
+        let len = self.new_len.unwrap_or(old_len);
+        // SAFETY: either imgui did not touch the buffer, and the first `old_len` bytes are the
+        // original string, or it copied `len` bytes of valid UTF-8 into it.
+        unsafe {
+            (*self.container).as_mut_vec().set_len(len);
+        }
+    }
 }
 
 /// This is our default callback.
@@ -1250,8 +1323,13 @@ extern "C" fn callback<T: InputTextCallbackHandler>(
                     buffer.reserve(additional_bytes);
 
                     (*data).Buf = buffer.as_mut_ptr() as *mut _;
-                    (*data).BufDirty = true;
+                    (*data).BufSize = buffer.capacity().min(i32::MAX as usize) as i32;
                 }
+
+                // imgui copies the new text into the buffer right after this callback, and
+                // clamps it to the buffer size.
+                let new_len = ((*data).BufTextLen).min((*data).BufSize - 1);
+                callback_data.user_data.new_len = Some(new_len as usize);
             }
         }
         InputTextFlags::CALLBACK_CHAR_FILTER => {
@@ -1295,3 +1373,40 @@ extern "C" fn callback<T: InputTextCallbackHandler>(
 /// actually run, since you will not have pass imgui any flags).
 pub struct PassthroughCallback;
 impl InputTextCallbackHandler for PassthroughCallback {}
+
+#[test]
+fn test_input_text_multiline_edit_in_place() {
+    let (_guard, mut ctx) = crate::test::test_ctx_initialized();
+    let mut text = "a".repeat(1 << 20);
+    text.reserve(16);
+    let ptr = text.as_ptr();
+
+    let frame = |ctx: &mut crate::Context, text: &mut String, focus: bool| {
+        let edited = {
+            let ui = ctx.frame();
+            let _window = ui.window("Example").begin();
+            if focus {
+                ui.set_keyboard_focus_here();
+            }
+            ui.input_text_multiline("##text", text, [0.0, 0.0]).build()
+        };
+        ctx.render();
+        edited
+    };
+
+    // Not edited: the string is left as is, without reallocating
+    assert!(!frame(&mut ctx, &mut text, true));
+    assert_eq!(text.len(), 1 << 20);
+    assert_eq!(text.as_ptr(), ptr);
+    // The focus request activates the widget on the next frame
+    assert!(!frame(&mut ctx, &mut text, false));
+    assert_eq!(text.len(), 1 << 20);
+
+    // Typing (at the start of the text, where the cursor is) grows the string through the
+    // resize callback
+    ctx.io_mut().add_input_character('b');
+    assert!(frame(&mut ctx, &mut text, false));
+    assert_eq!(text.len(), (1 << 20) + 1);
+    assert!(text.starts_with('b'));
+    assert_eq!(text.matches('a').count(), 1 << 20);
+}
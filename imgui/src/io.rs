@@ -404,6 +404,19 @@ impl Io {
             sys::ImGuiIO_AddInputCharactersUTF8(self.raw_mut(), buf.as_ptr() as *const _);
         }
     }
+    /// Queue new character input for all characters of a string, e.g. pasted text.
+    ///
+    /// This converts the string once, instead of once per character. Null characters are
+    /// skipped, like imgui does for single characters.
+    #[doc(alias = "AddInputCharactersUTF8")]
+    pub fn add_input_characters(&mut self, text: &str) {
+        let mut buf = Vec::with_capacity(text.len() + 1);
+        buf.extend(text.bytes().filter(|&b| b != 0));
+        buf.push(0);
+        unsafe {
+            sys::ImGuiIO_AddInputCharactersUTF8(self.raw_mut(), buf.as_ptr() as *const _);
+        }
+    }
     /// Clear character input buffer
     #[doc(alias = "ClearCharacters")]
     pub fn clear_input_characters(&mut self) {
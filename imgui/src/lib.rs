@@ -4,7 +4,10 @@
 
 pub extern crate imgui_sys as sys;
 
+use std::borrow::Cow;
 use std::cell;
+use std::fmt;
+use std::hash::{Hash, Hasher};
 use std::os::raw::c_char;
 
 pub use self::clipboard::*;
@@ -13,11 +16,14 @@ pub use self::context::*;
 #[cfg(feature = "docking")]
 pub use self::dock_space::*;
 pub use self::drag_drop::{DragDropFlags, DragDropSource, DragDropTarget};
-pub use self::draw_list::{ChannelsSplit, DrawListMut};
+pub use self::draw_list::{ChannelsSplit, DrawListMut, DrawListSplitter};
+#[cfg(feature = "docking")]
+pub use self::error_recovery::*;
 pub use self::fonts::atlas::*;
 pub use self::fonts::font::*;
 pub use self::fonts::glyph::*;
 pub use self::fonts::glyph_ranges::*;
+pub use self::fonts::manager::*;
 pub use self::input::keyboard::*;
 pub use self::input::mouse::*;
 pub use self::input_widget::*;
@@ -70,6 +76,8 @@ mod context;
 mod dock_space;
 pub mod drag_drop;
 pub mod draw_list;
+#[cfg(feature = "docking")]
+mod error_recovery;
 mod fonts;
 mod input;
 mod input_widget;
@@ -84,6 +92,7 @@ mod plothistogram;
 mod plotlines;
 mod popups;
 mod render;
+mod shell;
 mod stacks;
 mod style;
 #[cfg(feature = "tables-api")]
@@ -91,6 +100,7 @@ mod tables;
 #[cfg(test)]
 mod test;
 pub mod text_filter;
+pub mod themes;
 mod utils;
 mod widget;
 mod window;
@@ -131,6 +141,10 @@ impl Context {
 pub struct Ui {
     /// our scratch sheet
     buffer: cell::UnsafeCell<string::UiBuffer>,
+    /// used by `text_link_open_url`, set with `Context::set_open_in_shell_callback`
+    open_in_shell: shell::OpenInShell,
+    /// callbacks added with `DrawListMut::add_callback`, kept alive until the next frame
+    draw_callbacks: draw_list::DrawCallbacks,
 }
 
 impl Ui {
@@ -262,6 +276,49 @@ impl Ui {
             sys::igShowMetricsWindow(opened);
         }
     }
+    /// Renders the ID stack tool window.
+    ///
+    /// Displays how the id of the hovered item was built, to debug id conflicts.
+    #[doc(alias = "ShowStackToolWindow", alias = "ShowIDStackToolWindow")]
+    pub fn show_id_stack_tool_window(&self, opened: &mut bool) {
+        unsafe {
+            sys::igShowStackToolWindow(opened);
+        }
+    }
+    /// Renders the debug log window.
+    ///
+    /// Displays the events logged by Dear ImGui (focus, popups, navigation, ...), which can be
+    /// filtered by category.
+    #[doc(alias = "ShowDebugLogWindow")]
+    pub fn show_debug_log_window(&self, opened: &mut bool) {
+        unsafe {
+            sys::igShowDebugLogWindow(opened);
+        }
+    }
+    /// Starts the item picker: the next item clicked breaks into the debugger, to find the
+    /// code that submitted it.
+    ///
+    /// Only available with the `docking` feature, whose bindings include the internal API.
+    ///
+    /// # Warning
+    ///
+    /// Picking an item calls `IM_DEBUG_BREAK()`. Without a debugger attached, this will most
+    /// likely crash the process (e.g. with `SIGTRAP`).
+    #[cfg(feature = "docking")]
+    #[doc(alias = "DebugStartItemPicker")]
+    pub fn debug_start_item_picker(&self) {
+        unsafe {
+            sys::igDebugStartItemPicker();
+        }
+    }
+    /// Renders the UTF-8 encoding of each character of the text, to debug text and font
+    /// issues.
+    #[doc(alias = "DebugTextEncoding")]
+    pub fn debug_text_encoding(&self, text: impl AsRef<str>) {
+        unsafe {
+            sys::igDebugTextEncoding(self.scratch_txt(text));
+        }
+    }
     /// Renders a style editor block (not a window) for the given `Style` structure
     #[doc(alias = "ShowStyleEditor")]
     pub fn show_style_editor(&self, style: &mut Style) {
@@ -293,10 +350,16 @@ impl Ui {
 /// implementations.  Now, however, it is made from the `Ui` object
 /// directly, with a few deprecated helper methods here.
 #[repr(transparent)]
-#[derive(Copy, Clone, Debug, Eq, PartialEq, Default, Hash)]
+#[derive(Copy, Clone, Eq, PartialEq, Default, Hash)]
 pub struct Id(pub(crate) u32);
 
 impl Id {
+    /// Returns the raw `ImGuiID` value, e.g. to pass it to `imgui::sys` functions.
+    #[inline]
+    pub const fn raw(self) -> u32 {
+        self.0
+    }
+
     #[deprecated(since = "0.8.0", note = "Use ui.new_id_int(...)")]
     #[allow(non_snake_case)]
     pub fn Int(input: i32, ui: &Ui) -> Self {
@@ -316,7 +379,120 @@ impl Id {
     }
 }
 
+impl fmt::Debug for Id {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Id({:#010x})", self.0)
+    }
+}
+
+/// Formats the id in hexadecimal, like the ID stack tool of imgui
+impl fmt::Display for Id {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:#010x}", self.0)
+    }
+}
+
+/// Something an [`Id`] can be computed from, with [`Ui::get_id`].
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum IdSource<'a> {
+    /// A string, with the same `##` and `###` handling as labels
+    Str(&'a str),
+    /// An integer
+    Int(i32),
+    /// A pointer, of which only the address is used
+    Ptr(*const std::os::raw::c_void),
+}
+
+impl IdSource<'_> {
+    /// Hashes a value, so that e.g. composite keys can be used as ids without formatting
+    /// them to strings.
+    ///
+    /// The hash is computed with a fixed algorithm rather than `std`'s randomized one, so a
+    /// value gives the same id in every run of a program. It may still change with a new
+    /// version of imgui-rs, or of the crate defining the `Hash` impl, and it depends on the
+    /// endianness of the platform. This only matters for ids that are saved, which are the
+    /// ones of windows and tables in the .ini settings: their entries are lost when the ids
+    /// change.
+    pub fn hash<T: Hash + ?Sized>(value: &T) -> IdSource<'static> {
+        IdSource::Int(stable_hash(value))
+    }
+}
+
+/// Hashes a value to an integer id, see [`IdSource::hash`]
+pub(crate) fn stable_hash<T: Hash + ?Sized>(value: &T) -> i32 {
+    let mut hasher = StableHasher::default();
+    value.hash(&mut hasher);
+    let hash = hasher.finish();
+    (hash ^ (hash >> 32)) as i32
+}
+
+impl<'a> From<&'a str> for IdSource<'a> {
+    fn from(s: &'a str) -> Self {
+        IdSource::Str(s)
+    }
+}
+
+impl<'a> From<&'a String> for IdSource<'a> {
+    fn from(s: &'a String) -> Self {
+        IdSource::Str(s)
+    }
+}
+
+impl From<i32> for IdSource<'_> {
+    fn from(i: i32) -> Self {
+        IdSource::Int(i)
+    }
+}
+
+/// Uses the value as a pointer, like [`Ui::new_id`]
+impl From<usize> for IdSource<'_> {
+    fn from(i: usize) -> Self {
+        IdSource::Ptr(i as *const _)
+    }
+}
+
+impl<T> From<*const T> for IdSource<'_> {
+    fn from(p: *const T) -> Self {
+        IdSource::Ptr(p as *const _)
+    }
+}
+
+/// 64-bit FNV-1a, which unlike `DefaultHasher` gives the same results in every run
+struct StableHasher(u64);
+
+impl Default for StableHasher {
+    fn default() -> Self {
+        StableHasher(0xcbf2_9ce4_8422_2325)
+    }
+}
+
+impl Hasher for StableHasher {
+    fn finish(&self) -> u64 {
+        self.0
+    }
+
+    fn write(&mut self, bytes: &[u8]) {
+        for &byte in bytes {
+            self.0 = (self.0 ^ u64::from(byte)).wrapping_mul(0x0000_0100_0000_01b3);
+        }
+    }
+}
+
 impl Ui {
+    /// Computes the id of a string, integer, pointer or hashed value in the current ID stack,
+    /// like widgets do for their labels.
+    ///
+    /// The id can be used with the APIs taking an [`Id`], e.g. to open a popup or to dock a
+    /// window.
+    #[doc(alias = "GetID")]
+    pub fn get_id<'a>(&self, source: impl Into<IdSource<'a>>) -> Id {
+        match source.into() {
+            IdSource::Str(s) => self.new_id_str(s),
+            IdSource::Int(i) => self.new_id_int(i),
+            IdSource::Ptr(p) => Id(unsafe { sys::igGetID_Ptr(p) }),
+        }
+    }
+
     /// Create new [`Id`] from a `usize`. See [`Id`] for details.
     pub fn new_id(&self, input: usize) -> Id {
         let p = input as *const std::os::raw::c_void;
@@ -632,6 +808,47 @@ impl Ui {
     pub fn tooltip_text<T: AsRef<str>>(&self, text: T) {
         self.tooltip(|| self.text(text));
     }
+
+    /// Constructs a tooltip for the last item, if it is hovered with
+    /// [`ItemHoveredFlags::FOR_TOOLTIP`].
+    ///
+    /// Unlike checking [`is_item_hovered`](Self::is_item_hovered), this waits for a short
+    /// delay before showing the tooltip.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use imgui::*;
+    /// fn user_interface(ui: &Ui) {
+    ///     ui.text_disabled("(?)");
+    ///     ui.item_tooltip(|| {
+    ///         ui.text("Some help");
+    ///     });
+    /// }
+    /// ```
+    #[doc(alias = "BeginItemTooltip")]
+    pub fn item_tooltip<F: FnOnce()>(&self, f: F) {
+        if let Some(_tooltip) = self.begin_item_tooltip() {
+            f();
+        }
+    }
+    /// Constructs a tooltip for the last item, if it is hovered with
+    /// [`ItemHoveredFlags::FOR_TOOLTIP`].
+    ///
+    /// Returns a `TooltipToken` that must be ended by calling `.end()`
+    #[doc(alias = "BeginItemTooltip")]
+    pub fn begin_item_tooltip(&self) -> Option<TooltipToken<'_>> {
+        if self.is_item_hovered_with_flags(ItemHoveredFlags::FOR_TOOLTIP) {
+            Some(self.begin_tooltip())
+        } else {
+            None
+        }
+    }
+    /// Shortcut to call [`Self::item_tooltip`] with simple text content.
+    #[doc(alias = "SetItemTooltip")]
+    pub fn item_tooltip_text<T: AsRef<str>>(&self, text: T) {
+        self.item_tooltip(|| self.text(text));
+    }
 }
 
 create_token!(
@@ -746,6 +963,37 @@ impl Ui {
         }
     }
 
+    /// Builds a list box for choosing from `len` items, whose labels are produced on demand.
+    ///
+    /// Unlike [`Ui::list_box`], no slice of items is needed: `label_fn` is only called for the
+    /// items which are visible, so long lists don't need to be formatted every frame. Use
+    /// [`ListBox::build_with`] to also set the size of the list box.
+    ///
+    /// ```no_run
+    /// # use imgui::*;
+    /// # use std::borrow::Cow;
+    /// # let mut ctx = Context::create();
+    /// # { let ui = ctx.frame();
+    /// let mut current = 0;
+    /// ui.list_box_with("Entities", &mut current, 100_000, |i| {
+    ///     Cow::Owned(format!("Entity #{}", i))
+    /// });
+    /// # };
+    /// ```
+    #[doc(alias = "ListBox")]
+    pub fn list_box_with<'a, L>(
+        &self,
+        label: impl AsRef<str>,
+        current_item: &mut usize,
+        len: usize,
+        label_fn: L,
+    ) -> bool
+    where
+        L: Fn(usize) -> Cow<'a, str>,
+    {
+        ListBox::new(label).build_with(self, current_item, len, label_fn)
+    }
+
     // written out for the future times...
     // #[doc(alias = "ListBox")]
     // pub fn list_box_const<'p, StringType: AsRef<str> + ?Sized, const N: usize>(
@@ -936,6 +1184,7 @@ pub enum Condition {
 /// A cardinal direction
 #[repr(i32)]
 #[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Direction {
     None = sys::ImGuiDir_None,
     Left = sys::ImGuiDir_Left,
@@ -943,3 +1192,24 @@ pub enum Direction {
     Up = sys::ImGuiDir_Up,
     Down = sys::ImGuiDir_Down,
 }
+
+#[test]
+fn test_debug_windows() {
+    let (_guard, mut ctx) = crate::test::test_ctx_initialized();
+    for frame in 0..2 {
+        let ui = ctx.frame();
+        let mut opened = true;
+        ui.show_id_stack_tool_window(&mut opened);
+        ui.show_debug_log_window(&mut opened);
+        ui.window("Encoding").build(|| {
+            let start = ui.cursor_pos();
+            ui.debug_text_encoding("aé€");
+            // New windows are hidden on their first frame
+            if frame == 1 {
+                assert!(ui.cursor_pos()[1] > start[1]);
+            }
+        });
+        assert!(opened);
+        ctx.render();
+    }
+}
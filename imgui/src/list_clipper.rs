@@ -1,4 +1,5 @@
 use std::marker::PhantomData;
+use std::ops::Range;
 
 use crate::sys;
 use crate::Ui;
@@ -99,6 +100,53 @@ impl<'ui> ListClipperToken<'ui> {
         }
     }
 
+    /// Forces the given item to be displayed, even if it is not visible.
+    ///
+    /// This is typically used to keep the focused or selected item submitted (and measured),
+    /// so that keyboard navigation and scrolling to it keep working. Must be called before the
+    /// first call to `step`.
+    #[doc(alias = "IncludeItemByIndex", alias = "ForceDisplayRangeByIndices")]
+    pub fn include_item_by_index(&mut self, item_index: i32) {
+        self.include_items_by_index(item_index..item_index + 1);
+    }
+
+    /// Forces the given range of items to be displayed, even if they are not visible.
+    ///
+    /// See [`ListClipperToken::include_item_by_index`]. Due to alignment, an extra item may be
+    /// included on either end of the range.
+    #[doc(alias = "IncludeItemsByIndex", alias = "ForceDisplayRangeByIndices")]
+    pub fn include_items_by_index(&mut self, items: Range<i32>) {
+        unsafe {
+            sys::ImGuiListClipper_ForceDisplayRangeByIndices(
+                self.list_clipper,
+                items.start,
+                items.end,
+            );
+        }
+    }
+
+    /// Moves the cursor to where the given item would be, assuming all items have the same height.
+    ///
+    /// This is useful for coarse clipping of items with variable heights: submit the items
+    /// of a range, then seek to the next range. The item height must be known, i.e. either set
+    /// with [`ListClipper::items_height`] or measured by the first call to `step`. This does not
+    /// account for frozen table rows.
+    #[doc(alias = "SeekCursorForItem")]
+    pub fn seek_cursor_for_item(&mut self, item_index: i32) {
+        unsafe {
+            let clipper = &*self.list_clipper;
+            if clipper.ItemsHeight <= 0.0 {
+                return;
+            }
+            let pos_y = (f64::from(clipper.StartPosY)
+                + f64::from(item_index) * f64::from(clipper.ItemsHeight))
+                as f32;
+            let mut pos = sys::ImVec2::zero();
+            sys::igGetCursorScreenPos(&mut pos);
+            sys::igSetCursorScreenPos(sys::ImVec2::new(pos.x, pos_y));
+        }
+    }
+
     /// This is automatically called back the final call to
     /// `step`. You can call it sooner but typically not needed.
     pub fn end(&mut self) {
@@ -122,6 +170,17 @@ impl<'ui> ListClipperToken<'ui> {
     pub fn iter(self) -> ListClipperIterator<'ui> {
         ListClipperIterator::new(self)
     }
+
+    /// Get an iterator which outputs the ranges of visible indexes, one per call to `step`.
+    ///
+    /// The clipper is ended when the iterator is exhausted or dropped, so it is fine to
+    /// `break` out of the loop early.
+    pub fn ranges(self) -> ListClipperRanges<'ui> {
+        ListClipperRanges {
+            list_clipper: self,
+            exhausted: false,
+        }
+    }
 }
 
 impl<'ui> Drop for ListClipperToken<'ui> {
@@ -132,6 +191,37 @@ impl<'ui> Drop for ListClipperToken<'ui> {
     }
 }
 
+/// Iterator over the ranges of visible items, see [`ListClipperToken::ranges`].
+pub struct ListClipperRanges<'ui> {
+    list_clipper: ListClipperToken<'ui>,
+    exhausted: bool,
+}
+
+impl ListClipperRanges<'_> {
+    /// See [`ListClipperToken::seek_cursor_for_item`].
+    pub fn seek_cursor_for_item(&mut self, item_index: i32) {
+        self.list_clipper.seek_cursor_for_item(item_index);
+    }
+}
+
+impl Iterator for ListClipperRanges<'_> {
+    type Item = Range<i32>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while !self.exhausted {
+            if !self.list_clipper.step() {
+                self.exhausted = true;
+                break;
+            }
+            let range = self.list_clipper.display_start()..self.list_clipper.display_end();
+            if !range.is_empty() {
+                return Some(range);
+            }
+        }
+        None
+    }
+}
+
 pub struct ListClipperIterator<'ui> {
     list_clipper: ListClipperToken<'ui>,
     exhausted: bool,
@@ -268,3 +358,79 @@ fn iterator_usage() {
     // Should be consistent with size in `cpp_style_usage`
     assert_eq!(ticks, 44);
 }
+
+#[test]
+fn ranges_include_item() {
+    let (_guard, mut ctx) = crate::test::test_ctx_initialized();
+
+    for _ in 0..2 {
+        let ranges = {
+            let ui = ctx.frame();
+            let _window = ui
+                .window("Example")
+                .position([0.0, 0.0], crate::Condition::Always)
+                .size([100.0, 800.0], crate::Condition::Always)
+                .begin();
+
+            let mut clipper = ListClipper::new(1_000_000).begin(ui);
+            clipper.include_item_by_index(999_999);
+            let ranges: Vec<_> = clipper
+                .ranges()
+                .inspect(|range| {
+                    for _ in range.clone() {
+                        ui.text("...");
+                    }
+                })
+                .collect();
+            ranges
+        };
+        ctx.render();
+
+        assert!(ranges.iter().any(|range| range.contains(&999_999)));
+        let count: i32 = ranges.iter().map(|range| range.len() as i32).sum();
+        assert!(count < 100, "{} items submitted", count);
+    }
+}
+
+#[test]
+fn ranges_early_exit() {
+    let (_guard, mut ctx) = crate::test::test_ctx_initialized();
+
+    for _ in 0..2 {
+        let (items, start_y, end_y, item_height) = {
+            let ui = ctx.frame();
+            let _window = ui
+                .window("Example")
+                .position([0.0, 0.0], crate::Condition::Always)
+                .size([100.0, 800.0], crate::Condition::Always)
+                .begin();
+            let start_y = ui.cursor_pos()[1];
+            let mut items = Vec::new();
+            'items: for range in ListClipper::new(1000).begin(ui).ranges() {
+                for item in range {
+                    ui.text("...");
+                    items.push(item);
+                    if item == 5 {
+                        // the clipper is still ended when dropped
+                        break 'items;
+                    }
+                }
+            }
+            let end_y = ui.cursor_pos()[1];
+            ui.text("after the clipper");
+            (items, start_y, end_y, ui.text_line_height_with_spacing())
+        };
+        ctx.render();
+
+        assert_eq!(items, (0..=5).collect::<Vec<_>>());
+        // Ending the clipper moves the cursor after the last item, as if all
+        // of them were submitted
+        let expected_end_y = start_y + 1000.0 * item_height;
+        assert!(
+            (end_y - expected_end_y).abs() < 1.0,
+            "cursor at {}, expected {}",
+            end_y,
+            expected_end_y
+        );
+    }
+}
@@ -1,13 +1,15 @@
-use std::os::raw::c_float;
+use std::os::raw::{c_float, c_void};
 use std::{f32, mem};
 
 use super::Ui;
+use crate::plotlines::values_getter_trampoline;
 
 #[must_use]
 pub struct PlotHistogram<'ui, 'p, Label, Overlay = &'static str> {
     label: Label,
     values: &'p [f32],
     values_offset: usize,
+    stride: usize,
     overlay_text: Option<Overlay>,
     scale_min: f32,
     scale_max: f32,
@@ -21,6 +23,7 @@ impl<'ui, 'p, Label: AsRef<str>> PlotHistogram<'ui, 'p, Label> {
             label,
             values,
             values_offset: 0usize,
+            stride: 1,
             overlay_text: None,
             scale_min: f32::MAX,
             scale_max: f32::MAX,
@@ -31,11 +34,28 @@ impl<'ui, 'p, Label: AsRef<str>> PlotHistogram<'ui, 'p, Label> {
 }
 
 impl<'ui, 'p, Label: AsRef<str>, Overlay: AsRef<str>> PlotHistogram<'ui, 'p, Label, Overlay> {
+    /// Sets the index of the first value to plot, wrapping around the end of the values.
+    ///
+    /// This is useful for plotting a ring buffer: pass the position of the oldest value.
     pub fn values_offset(mut self, values_offset: usize) -> Self {
         self.values_offset = values_offset;
         self
     }
 
+    /// Only plots every `stride`-th value of the slice, e.g. `2` to plot one channel of
+    /// interleaved stereo samples. Defaults to `1`.
+    ///
+    /// Has no effect with [`build_with_getter`](Self::build_with_getter).
+    ///
+    /// # Panics
+    ///
+    /// Panics if `stride` is 0.
+    pub fn stride(mut self, stride: usize) -> Self {
+        assert!(stride > 0, "stride must be at least 1");
+        self.stride = stride;
+        self
+    }
+
     pub fn overlay_text<NewOverlay: AsRef<str>>(
         self,
         overlay_text: NewOverlay,
@@ -44,6 +64,7 @@ impl<'ui, 'p, Label: AsRef<str>, Overlay: AsRef<str>> PlotHistogram<'ui, 'p, Lab
             label: self.label,
             values: self.values,
             values_offset: self.values_offset,
+            stride: self.stride,
             overlay_text: Some(overlay_text),
             scale_min: self.scale_min,
             scale_max: self.scale_max,
@@ -74,13 +95,47 @@ impl<'ui, 'p, Label: AsRef<str>, Overlay: AsRef<str>> PlotHistogram<'ui, 'p, Lab
             sys::igPlotHistogram_FloatPtr(
                 label,
                 self.values.as_ptr() as *const c_float,
-                self.values.len() as i32,
+                // one value per chunk of `stride` values
+                self.values.chunks(self.stride).len() as i32,
+                self.values_offset as i32,
+                overlay_text,
+                self.scale_min,
+                self.scale_max,
+                self.graph_size.into(),
+                (self.stride * mem::size_of::<f32>()) as i32,
+            );
+        }
+    }
+
+    /// Builds the plot, reading `len` values from `getter` instead of the slice given when
+    /// creating this builder (which can be empty).
+    ///
+    /// This avoids copying data which isn't laid out as a contiguous `&[f32]`:
+    ///
+    /// ```no_run
+    /// # use imgui::*;
+    /// # use std::collections::VecDeque;
+    /// fn show_ui(ui: &Ui, frame_times: &VecDeque<f32>) {
+    ///     ui.plot_histogram("Frame times", &[])
+    ///         .scale_min(0.0)
+    ///         .graph_size([0.0, 80.0])
+    ///         .build_with_getter(frame_times.len(), |i| frame_times[i]);
+    /// }
+    /// ```
+    pub fn build_with_getter<F: Fn(usize) -> f32>(self, len: usize, getter: F) {
+        unsafe {
+            let (label, overlay_text) = self.ui.scratch_txt_with_opt(self.label, self.overlay_text);
+
+            sys::igPlotHistogram_FnFloatPtr(
+                label,
+                Some(values_getter_trampoline::<F>),
+                &getter as *const F as *mut c_void,
+                len as i32,
                 self.values_offset as i32,
                 overlay_text,
                 self.scale_min,
                 self.scale_max,
                 self.graph_size.into(),
-                mem::size_of::<f32>() as i32,
             );
         }
     }
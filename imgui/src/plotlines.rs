@@ -1,4 +1,4 @@
-use std::os::raw::c_float;
+use std::os::raw::{c_float, c_int, c_void};
 use std::{f32, mem};
 
 use super::Ui;
@@ -8,6 +8,7 @@ pub struct PlotLines<'ui, 'p, Label, Overlay = &'static str> {
     label: Label,
     values: &'p [f32],
     values_offset: usize,
+    stride: usize,
     overlay_text: Option<Overlay>,
     scale_min: f32,
     scale_max: f32,
@@ -21,6 +22,7 @@ impl<'ui, 'p, Label: AsRef<str>> PlotLines<'ui, 'p, Label> {
             label,
             values,
             values_offset: 0usize,
+            stride: 1,
             overlay_text: None,
             scale_min: f32::MAX,
             scale_max: f32::MAX,
@@ -31,11 +33,28 @@ impl<'ui, 'p, Label: AsRef<str>> PlotLines<'ui, 'p, Label> {
 }
 
 impl<'ui, 'p, Label: AsRef<str>, Overlay: AsRef<str>> PlotLines<'ui, 'p, Label, Overlay> {
+    /// Sets the index of the first value to plot, wrapping around the end of the values.
+    ///
+    /// This is useful for plotting a ring buffer: pass the position of the oldest value.
     pub fn values_offset(mut self, values_offset: usize) -> Self {
         self.values_offset = values_offset;
         self
     }
 
+    /// Only plots every `stride`-th value of the slice, e.g. `2` to plot one channel of
+    /// interleaved stereo samples. Defaults to `1`.
+    ///
+    /// Has no effect with [`build_with_getter`](Self::build_with_getter).
+    ///
+    /// # Panics
+    ///
+    /// Panics if `stride` is 0.
+    pub fn stride(mut self, stride: usize) -> Self {
+        assert!(stride > 0, "stride must be at least 1");
+        self.stride = stride;
+        self
+    }
+
     pub fn overlay_text<Overlay2: AsRef<str>>(
         self,
         overlay_text: Overlay2,
@@ -44,6 +63,7 @@ impl<'ui, 'p, Label: AsRef<str>, Overlay: AsRef<str>> PlotLines<'ui, 'p, Label,
             label: self.label,
             values: self.values,
             values_offset: self.values_offset,
+            stride: self.stride,
             overlay_text: Some(overlay_text),
             scale_min: self.scale_min,
             scale_max: self.scale_max,
@@ -74,14 +94,74 @@ impl<'ui, 'p, Label: AsRef<str>, Overlay: AsRef<str>> PlotLines<'ui, 'p, Label,
             sys::igPlotLines_FloatPtr(
                 label,
                 self.values.as_ptr() as *const c_float,
-                self.values.len() as i32,
+                // one value per chunk of `stride` values
+                self.values.chunks(self.stride).len() as i32,
                 self.values_offset as i32,
                 overlay,
                 self.scale_min,
                 self.scale_max,
                 self.graph_size.into(),
-                mem::size_of::<f32>() as i32,
+                (self.stride * mem::size_of::<f32>()) as i32,
             );
         }
     }
+
+    /// Builds the plot, reading `len` values from `getter` instead of the slice given when
+    /// creating this builder (which can be empty).
+    ///
+    /// This avoids copying data which isn't laid out as a contiguous `&[f32]`:
+    ///
+    /// ```no_run
+    /// # use imgui::*;
+    /// # use std::collections::VecDeque;
+    /// fn show_ui(ui: &Ui, frame_times: &VecDeque<f32>) {
+    ///     ui.plot_lines("Frame times", &[])
+    ///         .scale_min(0.0)
+    ///         .graph_size([0.0, 80.0])
+    ///         .build_with_getter(frame_times.len(), |i| frame_times[i]);
+    /// }
+    /// ```
+    pub fn build_with_getter<F: Fn(usize) -> f32>(self, len: usize, getter: F) {
+        unsafe {
+            let (label, overlay) = self.ui.scratch_txt_with_opt(self.label, self.overlay_text);
+
+            sys::igPlotLines_FnFloatPtr(
+                label,
+                Some(values_getter_trampoline::<F>),
+                &getter as *const F as *mut c_void,
+                len as i32,
+                self.values_offset as i32,
+                overlay,
+                self.scale_min,
+                self.scale_max,
+                self.graph_size.into(),
+            );
+        }
+    }
+}
+
+/// Calls the values getter passed as `data` to `build_with_getter`.
+pub(crate) unsafe extern "C" fn values_getter_trampoline<F: Fn(usize) -> f32>(
+    data: *mut c_void,
+    idx: c_int,
+) -> f32 {
+    let getter = &*(data as *const F);
+    getter(idx as usize)
+}
+
+#[test]
+fn test_plot_lines_with_getter() {
+    let (_guard, mut ctx) = crate::test::test_ctx_initialized();
+    let ui = ctx.frame();
+    let _window = ui.window("Example").begin();
+
+    let ring = std::collections::VecDeque::from(vec![1.0, 2.0, 3.0, 4.0]);
+    let max_index = std::cell::Cell::new(0);
+    ui.plot_lines("ring", &[])
+        .values_offset(2)
+        .build_with_getter(ring.len(), |i| {
+            max_index.set(max_index.get().max(i));
+            ring[i]
+        });
+    assert_eq!(max_index.get(), ring.len() - 1);
 }
@@ -1,9 +1,29 @@
+use bitflags::bitflags;
 use std::ptr;
 
 use crate::sys;
 use crate::window::WindowFlags;
 use crate::Ui;
 
+bitflags! {
+    /// Flags for opening popups and checking whether they are open
+    #[repr(transparent)]
+    pub struct PopupFlags: u32 {
+        /// Don't open if there's already a popup at the same level of the popup stack
+        const NO_OPEN_OVER_EXISTING_POPUP = sys::ImGuiPopupFlags_NoOpenOverExistingPopup;
+        /// For window context popups, don't open when clicking over an item, only over empty
+        /// space
+        const NO_OPEN_OVER_ITEMS = sys::ImGuiPopupFlags_NoOpenOverItems;
+        /// For [`Ui::is_popup_open_with_flags`], ignore the id and check for any popup
+        const ANY_POPUP_ID = sys::ImGuiPopupFlags_AnyPopupId;
+        /// For [`Ui::is_popup_open_with_flags`], search at any level of the popup stack, not
+        /// only the current one. Must be combined with [`ANY_POPUP_ID`](Self::ANY_POPUP_ID)
+        const ANY_POPUP_LEVEL = sys::ImGuiPopupFlags_AnyPopupLevel;
+        /// For [`Ui::is_popup_open_with_flags`], check whether any popup is open at all
+        const ANY_POPUP = sys::ImGuiPopupFlags_AnyPopup;
+    }
+}
+
 /// Create a modal pop-up.
 ///
 /// # Example
@@ -21,6 +41,10 @@ use crate::Ui;
 ///     }
 /// };
 /// ```
+///
+/// Modals can be nested by opening and building another modal from within the closure (or
+/// token scope) of the first one. Closing the inner modal with [`Ui::close_current_popup`] then
+/// leaves the outer one open.
 #[must_use]
 pub struct PopupModal<'ui, 'p, Label> {
     ui: &'ui Ui,
@@ -41,6 +65,9 @@ impl<'ui, 'p, Label: AsRef<str>> PopupModal<'ui, 'p, Label> {
     }
     /// Pass a mutable boolean which will be updated to refer to the current
     /// "open" state of the modal.
+    ///
+    /// This adds a close button to the title bar, which closes the modal and sets the boolean
+    /// to false. The modal still has to be opened with [`Ui::open_popup`].
     pub fn opened(mut self, opened: &'p mut bool) -> Self {
         self.opened = Some(opened);
         self
@@ -162,9 +189,54 @@ impl Ui {
     /// - [`modal_popup_config`](Self::modal_popup_config)
     ///
     /// The confusing aspect to popups is that ImGui holds control over the popup itself.
+    ///
+    /// Popup ids are relative to the ID stack, so this must be called with the same ID stack
+    /// as the function building the popup: the popup won't appear if, for example, it is
+    /// opened inside a [`push_id`](Self::push_id) scope and built outside of it.
     #[doc(alias = "OpenPopup")]
     pub fn open_popup(&self, str_id: impl AsRef<str>) {
-        unsafe { sys::igOpenPopup_Str(self.scratch_txt(str_id), 0) };
+        self.open_popup_with_flags(str_id, PopupFlags::empty());
+    }
+
+    /// Instructs ImGui that a popup is open, like [`open_popup`](Self::open_popup).
+    #[doc(alias = "OpenPopup")]
+    pub fn open_popup_with_flags(&self, str_id: impl AsRef<str>, flags: PopupFlags) {
+        unsafe { sys::igOpenPopup_Str(self.scratch_txt(str_id), flags.bits() as i32) };
+    }
+
+    /// Returns true if the popup is open at the current level of the popup stack.
+    #[doc(alias = "IsPopupOpen")]
+    pub fn is_popup_open(&self, str_id: impl AsRef<str>) -> bool {
+        self.is_popup_open_with_flags(str_id, PopupFlags::empty())
+    }
+
+    /// Returns true if the popup is open, using flags such as [`PopupFlags::ANY_POPUP_LEVEL`]
+    /// to widen the search.
+    ///
+    /// With [`PopupFlags::ANY_POPUP_ID`], the id is ignored.
+    ///
+    /// # Panics
+    ///
+    /// Panics if [`PopupFlags::ANY_POPUP_LEVEL`] is used without [`PopupFlags::ANY_POPUP_ID`],
+    /// since Dear ImGui can't look up a popup by name at other levels.
+    #[doc(alias = "IsPopupOpen")]
+    pub fn is_popup_open_with_flags(&self, str_id: impl AsRef<str>, flags: PopupFlags) -> bool {
+        assert!(
+            !flags.contains(PopupFlags::ANY_POPUP_LEVEL)
+                || flags.contains(PopupFlags::ANY_POPUP_ID),
+            "PopupFlags::ANY_POPUP_LEVEL can only be used together with PopupFlags::ANY_POPUP_ID"
+        );
+        let str_id = self.scratch_txt(str_id);
+        unsafe {
+            cfg_if::cfg_if! {
+                if #[cfg(feature = "docking")] {
+                    // Has extra overload in docking branch
+                    sys::igIsPopupOpen_Str(str_id, flags.bits() as i32)
+                } else {
+                    sys::igIsPopupOpen(str_id, flags.bits() as i32)
+                }
+            }
+        }
     }
 
     /// Construct a popup that can have any kind of content.
@@ -257,3 +329,48 @@ create_token!(
     /// to drop on its own.
     drop { sys::igEndPopup() }
 );
+
+#[test]
+fn test_nested_modal_popups() {
+    let (_guard, mut ctx) = crate::test::test_ctx_initialized();
+
+    // Opens the popups requested at each level, and returns which modals were built
+    let mut frame = |open_outer: bool, open_inner: bool, close_inner: bool| {
+        let result = {
+            let ui = ctx.frame();
+            let _window = ui.window("Example").begin();
+            if open_outer {
+                ui.open_popup("Settings");
+            }
+            let mut inner = false;
+            let outer = ui
+                .modal_popup_config("Settings")
+                .build(|| {
+                    if open_inner {
+                        ui.open_popup("Confirm");
+                        // The outer modal is already open at this level
+                        ui.open_popup_with_flags("Other", PopupFlags::NO_OPEN_OVER_EXISTING_POPUP);
+                        assert!(ui.is_popup_open("Confirm"));
+                        assert!(!ui.is_popup_open("Other"));
+                    }
+                    ui.modal_popup_config("Confirm").build(|| {
+                        inner = true;
+                        if close_inner {
+                            ui.close_current_popup();
+                        }
+                    });
+                })
+                .is_some();
+            let any_open = ui.is_popup_open_with_flags("", PopupFlags::ANY_POPUP);
+            (outer, inner, any_open)
+        };
+        ctx.render();
+        result
+    };
+
+    assert_eq!(frame(false, false, false), (false, false, false));
+    assert_eq!(frame(true, false, false), (true, false, true));
+    assert_eq!(frame(false, true, false), (true, true, true));
+    assert_eq!(frame(false, false, true), (true, true, true));
+    assert_eq!(frame(false, false, false), (true, false, true));
+}
@@ -1,4 +1,3 @@
-use std::mem::size_of;
 use std::slice;
 
 use crate::internal::{RawCast, RawWrapper};
@@ -57,9 +56,9 @@ impl DrawData {
     }
     #[inline]
     pub(crate) unsafe fn cmd_lists(&self) -> &[*const DrawList] {
-        slice::from_raw_parts(
+        vector_slice(
             self.cmd_lists as *const *const DrawList,
-            self.cmd_lists_count as usize,
+            self.cmd_lists_count,
         )
     }
     /// Converts all buffers from indexed to non-indexed, in case you cannot render indexed
@@ -147,28 +146,15 @@ impl RawWrapper for DrawList {
 impl DrawList {
     #[inline]
     pub(crate) unsafe fn cmd_buffer(&self) -> &[sys::ImDrawCmd] {
-        slice::from_raw_parts(
-            self.0.CmdBuffer.Data as *const sys::ImDrawCmd,
-            self.0.CmdBuffer.Size as usize,
-        )
+        vector_slice(self.0.CmdBuffer.Data, self.0.CmdBuffer.Size)
     }
     #[inline]
     pub fn idx_buffer(&self) -> &[DrawIdx] {
-        unsafe {
-            slice::from_raw_parts(
-                self.0.IdxBuffer.Data as *const DrawIdx,
-                self.0.IdxBuffer.Size as usize,
-            )
-        }
+        unsafe { vector_slice(self.0.IdxBuffer.Data, self.0.IdxBuffer.Size) }
     }
     #[inline]
     pub fn vtx_buffer(&self) -> &[DrawVert] {
-        unsafe {
-            slice::from_raw_parts(
-                self.0.VtxBuffer.Data as *const DrawVert,
-                self.0.VtxBuffer.Size as usize,
-            )
-        }
+        unsafe { vector_slice(self.0.VtxBuffer.Data.cast(), self.0.VtxBuffer.Size) }
     }
 
     /// # Safety
@@ -182,7 +168,7 @@ impl DrawList {
             core::mem::size_of::<DrawVert>(),
         );
         assert!(core::mem::align_of::<VTy>() <= core::mem::align_of::<DrawVert>());
-        slice::from_raw_parts(self.0.VtxBuffer.Data.cast(), self.0.VtxBuffer.Size as usize)
+        vector_slice(self.0.VtxBuffer.Data.cast(), self.0.VtxBuffer.Size)
     }
 
     #[inline]
@@ -195,6 +181,16 @@ impl DrawList {
     }
 }
 
+/// Returns the items of an `ImVector`, whose data is null until something is pushed
+#[inline]
+unsafe fn vector_slice<'a, T>(data: *const T, size: i32) -> &'a [T] {
+    if size == 0 {
+        &[]
+    } else {
+        slice::from_raw_parts(data, size as usize)
+    }
+}
+
 pub struct DrawCmdIterator<'a> {
     iter: std::slice::Iter<'a, sys::ImDrawCmd>,
 }
@@ -212,7 +208,7 @@ impl<'a> Iterator for DrawCmdIterator<'a> {
                 idx_offset: cmd.IdxOffset as usize,
             };
             match cmd.UserCallback {
-                Some(raw_callback) if raw_callback as usize == -1isize as usize => {
+                Some(raw_callback) if is_reset_render_state(raw_callback) => {
                     DrawCmd::ResetRenderState
                 }
                 Some(raw_callback) => DrawCmd::RawCallback {
@@ -228,6 +224,14 @@ impl<'a> Iterator for DrawCmdIterator<'a> {
     }
 }
 
+/// Whether a callback is the sentinel value used by Dear ImGui to reset the render state
+#[inline]
+fn is_reset_render_state(
+    callback: unsafe extern "C" fn(*const sys::ImDrawList, *const sys::ImDrawCmd),
+) -> bool {
+    callback as usize == -1isize as usize
+}
+
 /// A vertex index
 pub type DrawIdx = sys::ImDrawIdx;
 
@@ -247,7 +251,12 @@ pub enum DrawCmd {
         count: usize,
         cmd_params: DrawCmdParams,
     },
+    /// The renderer should set up its render state again, see
+    /// [`DrawListMut::add_reset_render_state`](crate::DrawListMut::add_reset_render_state)
     ResetRenderState,
+    /// A callback, see [`DrawListMut::add_callback`](crate::DrawListMut::add_callback).
+    ///
+    /// Renderers should run it with `callback(draw_list.raw(), raw_cmd)`.
     RawCallback {
         callback: unsafe extern "C" fn(*const sys::ImDrawList, cmd: *const sys::ImDrawCmd),
         raw_cmd: *const sys::ImDrawCmd,
@@ -289,92 +298,248 @@ fn test_drawvert_memory_layout() {
     assert_field_offset!(col, col);
 }
 
-/// A container for a heap-allocated deep copy of a `DrawData` struct.
+/// An owned deep copy of a [`DrawData`], usually taken with [`DrawData::snapshot`].
+///
+/// `DrawData` borrows memory of the context which is invalidated by the next frame, while a
+/// snapshot can be kept around or sent to a render thread. Renderers accept both through
+/// [`DrawDataSource`].
 ///
-/// Can be used to retain draw data for rendering on a different thread.
-/// The underlying copy is released when this struct is dropped.
+/// Callbacks added with [`DrawListMut::add_callback`](crate::DrawListMut::add_callback) belong
+/// to the frame that added them, so they are left out of the copy. Requests to reset the render
+/// state are kept.
+#[derive(Clone, Debug, Default)]
 pub struct OwnedDrawData {
-    draw_data: *mut sys::ImDrawData,
+    /// For convenience, sum of all draw list index buffer sizes.
+    pub total_idx_count: i32,
+    /// For convenience, sum of all draw list vertex buffer sizes.
+    pub total_vtx_count: i32,
+    /// Upper-left position of the viewport to render.
+    pub display_pos: [f32; 2],
+    /// Size of the viewport to render.
+    pub display_size: [f32; 2],
+    /// Amount of pixels for each unit of display_size.
+    pub framebuffer_scale: [f32; 2],
+    draw_lists: Vec<OwnedDrawList>,
 }
 
 impl OwnedDrawData {
-    /// If this struct contains a `DrawData` object, then this function returns a reference to it.
-    ///
-    /// Otherwise, this struct is empty and so this function returns `None`.
+    /// Returns an iterator over the copied draw lists.
     #[inline]
-    pub fn draw_data(&self) -> Option<&DrawData> {
-        if !self.draw_data.is_null() {
-            Some(unsafe { DrawData::from_raw(&*self.draw_data) })
-        } else {
-            None
-        }
+    pub fn draw_lists(&self) -> std::slice::Iter<'_, OwnedDrawList> {
+        self.draw_lists.iter()
+    }
+    /// Returns the number of copied draw lists.
+    #[inline]
+    pub fn draw_lists_count(&self) -> usize {
+        self.draw_lists.len()
     }
+}
 
-    #[cfg(feature = "docking")]
-    unsafe fn copy_docking_properties(source: &sys::ImDrawData, dest: *mut sys::ImDrawData) {
-        (*dest).OwnerViewport = source.OwnerViewport;
+impl DrawData {
+    /// Copies the draw data, so that it can be rendered after the next frame started or on
+    /// another thread. See [`OwnedDrawData`].
+    pub fn snapshot(&self) -> OwnedDrawData {
+        OwnedDrawData::from(self)
+    }
+}
+
+impl From<&DrawData> for OwnedDrawData {
+    fn from(draw_data: &DrawData) -> Self {
+        OwnedDrawData {
+            total_idx_count: draw_data.total_idx_count,
+            total_vtx_count: draw_data.total_vtx_count,
+            display_pos: draw_data.display_pos,
+            display_size: draw_data.display_size,
+            framebuffer_scale: draw_data.framebuffer_scale,
+            draw_lists: draw_data.draw_lists().map(OwnedDrawList::from).collect(),
+        }
     }
-    #[cfg(not(feature = "docking"))]
-    unsafe fn copy_docking_properties(_source: &sys::ImDrawData, _dest: *mut sys::ImDrawData) {}
 }
 
-impl Default for OwnedDrawData {
-    /// The default `OwnedDrawData` struct is empty.
+/// An owned copy of a [`DrawList`], part of an [`OwnedDrawData`]
+#[derive(Clone, Debug, Default)]
+pub struct OwnedDrawList {
+    vtx_buffer: Vec<DrawVert>,
+    idx_buffer: Vec<DrawIdx>,
+    cmd_buffer: Vec<sys::ImDrawCmd>,
+}
+
+// SAFETY: the only pointers in the copied commands are texture ids, which are opaque values
+// for the renderer, and the reset render state sentinel. User callbacks and their data, which
+// may not be thread-safe, are not copied.
+unsafe impl Send for OwnedDrawList {}
+unsafe impl Sync for OwnedDrawList {}
+
+impl OwnedDrawList {
+    #[inline]
+    pub fn idx_buffer(&self) -> &[DrawIdx] {
+        &self.idx_buffer
+    }
+    #[inline]
+    pub fn vtx_buffer(&self) -> &[DrawVert] {
+        &self.vtx_buffer
+    }
+
+    /// # Safety
+    /// This is equivalent to `transmute(self.vtx_buffer())` with a little more
+    /// checking, and thus inherits the safety considerations of `transmute`ing
+    /// slices.
+    pub unsafe fn transmute_vtx_buffer<VTy: Copy>(&self) -> &[VTy] {
+        assert_eq!(
+            core::mem::size_of::<VTy>(),
+            core::mem::size_of::<DrawVert>(),
+        );
+        assert!(core::mem::align_of::<VTy>() <= core::mem::align_of::<DrawVert>());
+        slice::from_raw_parts(self.vtx_buffer.as_ptr().cast(), self.vtx_buffer.len())
+    }
+
+    /// Returns the draw commands, which never include [`DrawCmd::RawCallback`].
     #[inline]
-    fn default() -> Self {
-        Self {
-            draw_data: std::ptr::null_mut(),
+    pub fn commands(&self) -> DrawCmdIterator<'_> {
+        DrawCmdIterator {
+            iter: self.cmd_buffer.iter(),
         }
     }
 }
 
-impl From<&DrawData> for OwnedDrawData {
-    /// Construct `OwnedDrawData` from `DrawData` by creating a heap-allocated deep copy of the given `DrawData`
-    fn from(value: &DrawData) -> Self {
-        OwnedDrawData {
-            draw_data: unsafe {
-                let other_ptr = value.raw();
-                let result = sys::ImDrawData_ImDrawData();
-                (*result).Valid = other_ptr.Valid;
-                (*result).TotalIdxCount = other_ptr.TotalIdxCount;
-                (*result).TotalVtxCount = other_ptr.TotalVtxCount;
-                (*result).DisplayPos = other_ptr.DisplayPos;
-                (*result).DisplaySize = other_ptr.DisplaySize;
-                (*result).FramebufferScale = other_ptr.FramebufferScale;
-                (*result).CmdListsCount = other_ptr.CmdListsCount;
-                (*result).CmdLists = sys::igMemAlloc(
-                    size_of::<*mut sys::ImDrawList>() * other_ptr.CmdListsCount as usize,
-                ) as *mut *mut sys::ImDrawList;
-                OwnedDrawData::copy_docking_properties(other_ptr, result);
-
-                let mut current_draw_list = (*result).CmdLists;
-                for i in 0..other_ptr.CmdListsCount as usize {
-                    *current_draw_list = sys::ImDrawList_CloneOutput(*other_ptr.CmdLists.add(i));
-                    current_draw_list = current_draw_list.add(1);
-                }
-                result
-            },
+impl From<&DrawList> for OwnedDrawList {
+    fn from(draw_list: &DrawList) -> Self {
+        let cmd_buffer = unsafe { draw_list.cmd_buffer() }
+            .iter()
+            .filter(|cmd| match cmd.UserCallback {
+                Some(callback) => is_reset_render_state(callback),
+                None => true,
+            })
+            .map(|cmd| sys::ImDrawCmd {
+                UserCallbackData: std::ptr::null_mut(),
+                ..*cmd
+            })
+            .collect();
+        OwnedDrawList {
+            vtx_buffer: draw_list.vtx_buffer().to_vec(),
+            idx_buffer: draw_list.idx_buffer().to_vec(),
+            cmd_buffer,
         }
     }
 }
 
-impl Drop for OwnedDrawData {
-    /// Releases any heap-allocated memory consumed by this `OwnedDrawData` object
-    fn drop(&mut self) {
-        unsafe {
-            if !self.draw_data.is_null() {
-                if !(*self.draw_data).CmdLists.is_null() {
-                    for i in 0..(*self.draw_data).CmdListsCount as usize {
-                        let ptr = *(*self.draw_data).CmdLists.add(i);
-                        if !ptr.is_null() {
-                            sys::ImDrawList_destroy(ptr);
-                        }
-                    }
-                    sys::igMemFree((*self.draw_data).CmdLists as *mut std::ffi::c_void);
-                }
-                sys::ImDrawData_destroy(self.draw_data);
-                self.draw_data = std::ptr::null_mut();
-            }
+/// Draw data that renderers can draw: the [`DrawData`] of the last frame, or an
+/// [`OwnedDrawData`] snapshot.
+pub trait DrawDataSource {
+    /// Upper-left position of the viewport to render.
+    fn display_pos(&self) -> [f32; 2];
+    /// Size of the viewport to render.
+    fn display_size(&self) -> [f32; 2];
+    /// Amount of pixels for each unit of display_size.
+    fn framebuffer_scale(&self) -> [f32; 2];
+    /// Returns an iterator over the draw lists to render.
+    fn draw_list_refs(&self) -> DrawListRefIterator<'_>;
+}
+
+impl DrawDataSource for DrawData {
+    #[inline]
+    fn display_pos(&self) -> [f32; 2] {
+        self.display_pos
+    }
+    #[inline]
+    fn display_size(&self) -> [f32; 2] {
+        self.display_size
+    }
+    #[inline]
+    fn framebuffer_scale(&self) -> [f32; 2] {
+        self.framebuffer_scale
+    }
+    #[inline]
+    fn draw_list_refs(&self) -> DrawListRefIterator<'_> {
+        DrawListRefIterator(DrawListRefs::Borrowed(self.draw_lists()))
+    }
+}
+
+impl DrawDataSource for OwnedDrawData {
+    #[inline]
+    fn display_pos(&self) -> [f32; 2] {
+        self.display_pos
+    }
+    #[inline]
+    fn display_size(&self) -> [f32; 2] {
+        self.display_size
+    }
+    #[inline]
+    fn framebuffer_scale(&self) -> [f32; 2] {
+        self.framebuffer_scale
+    }
+    #[inline]
+    fn draw_list_refs(&self) -> DrawListRefIterator<'_> {
+        DrawListRefIterator(DrawListRefs::Owned(self.draw_lists()))
+    }
+}
+
+/// A draw list of a [`DrawDataSource`]
+#[derive(Copy, Clone)]
+pub enum DrawListRef<'a> {
+    Borrowed(&'a DrawList),
+    Owned(&'a OwnedDrawList),
+}
+
+impl<'a> DrawListRef<'a> {
+    #[inline]
+    pub fn idx_buffer(&self) -> &'a [DrawIdx] {
+        match *self {
+            DrawListRef::Borrowed(draw_list) => draw_list.idx_buffer(),
+            DrawListRef::Owned(draw_list) => draw_list.idx_buffer(),
+        }
+    }
+    #[inline]
+    pub fn vtx_buffer(&self) -> &'a [DrawVert] {
+        match *self {
+            DrawListRef::Borrowed(draw_list) => draw_list.vtx_buffer(),
+            DrawListRef::Owned(draw_list) => draw_list.vtx_buffer(),
+        }
+    }
+
+    /// # Safety
+    /// See [`DrawList::transmute_vtx_buffer`].
+    pub unsafe fn transmute_vtx_buffer<VTy: Copy>(&self) -> &'a [VTy] {
+        match *self {
+            DrawListRef::Borrowed(draw_list) => draw_list.transmute_vtx_buffer(),
+            DrawListRef::Owned(draw_list) => draw_list.transmute_vtx_buffer(),
+        }
+    }
+
+    #[inline]
+    pub fn commands(&self) -> DrawCmdIterator<'a> {
+        match *self {
+            DrawListRef::Borrowed(draw_list) => draw_list.commands(),
+            DrawListRef::Owned(draw_list) => draw_list.commands(),
+        }
+    }
+
+    /// Returns the draw list of the context, which [`DrawCmd::RawCallback`] commands are run
+    /// with. Snapshots have no callbacks.
+    #[inline]
+    pub fn as_draw_list(&self) -> Option<&'a DrawList> {
+        match *self {
+            DrawListRef::Borrowed(draw_list) => Some(draw_list),
+            DrawListRef::Owned(_) => None,
+        }
+    }
+}
+
+/// Iterator over the draw lists of a [`DrawDataSource`]
+pub struct DrawListRefIterator<'a>(DrawListRefs<'a>);
+
+enum DrawListRefs<'a> {
+    Borrowed(DrawListIterator<'a>),
+    Owned(std::slice::Iter<'a, OwnedDrawList>),
+}
+
+impl<'a> Iterator for DrawListRefIterator<'a> {
+    type Item = DrawListRef<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match &mut self.0 {
+            DrawListRefs::Borrowed(iter) => iter.next().map(DrawListRef::Borrowed),
+            DrawListRefs::Owned(iter) => iter.next().map(DrawListRef::Owned),
         }
     }
 }
@@ -383,7 +548,8 @@ impl Drop for OwnedDrawData {
 #[cfg(test)]
 fn test_owneddrawdata_default() {
     let default = OwnedDrawData::default();
-    assert!(default.draw_data().is_none());
+    assert_eq!(default.draw_lists_count(), 0);
+    assert_eq!(default.total_vtx_count, 0);
 }
 
 #[test]
@@ -404,73 +570,83 @@ fn test_owneddrawdata_from_drawdata() {
         DisplaySize: sys::ImVec2 { x: 789.0, y: 012.0 },
         FramebufferScale: sys::ImVec2 { x: 3.0, y: 7.0 },
         #[cfg(feature = "docking")]
-        OwnerViewport: unsafe { std::ptr::null_mut::<sys::ImGuiViewport>().offset(123) },
+        OwnerViewport: std::ptr::null_mut(),
     };
     let draw_data = unsafe { DrawData::from_raw(&draw_data_raw) };
 
     // Clone it, and ensure the underlying properties have been cloned
     let owned_draw_data: OwnedDrawData = draw_data.into();
-    let inner_draw_data = owned_draw_data.draw_data();
-    assert!(inner_draw_data.is_some());
-    let owned_draw_data_raw = unsafe { inner_draw_data.unwrap().raw() };
-    assert_eq!(draw_data_raw.Valid, owned_draw_data_raw.Valid);
-    assert_eq!(
-        draw_data_raw.CmdListsCount,
-        owned_draw_data_raw.CmdListsCount
-    );
-    assert!(!draw_data_raw.CmdLists.is_null());
-    assert_eq!(
-        draw_data_raw.TotalIdxCount,
-        owned_draw_data_raw.TotalIdxCount
-    );
-    assert_eq!(
-        draw_data_raw.TotalVtxCount,
-        owned_draw_data_raw.TotalVtxCount
-    );
-    assert_eq!(draw_data_raw.DisplayPos, owned_draw_data_raw.DisplayPos);
-    assert_eq!(draw_data_raw.DisplaySize, owned_draw_data_raw.DisplaySize);
-    assert_eq!(
-        draw_data_raw.FramebufferScale,
-        owned_draw_data_raw.FramebufferScale
-    );
-
-    #[cfg(feature = "docking")]
-    assert_eq!(
-        draw_data_raw.OwnerViewport,
-        owned_draw_data_raw.OwnerViewport
-    );
+    assert_eq!(owned_draw_data.draw_lists_count(), 1);
+    assert_eq!(owned_draw_data.total_idx_count, 123);
+    assert_eq!(owned_draw_data.total_vtx_count, 456);
+    assert_eq!(owned_draw_data.display_pos, [123.0, 456.0]);
+    assert_eq!(owned_draw_data.display_size, [789.0, 012.0]);
+    assert_eq!(owned_draw_data.framebuffer_scale, [3.0, 7.0]);
 }
 
 #[test]
 #[cfg(test)]
-fn test_owneddrawdata_drop() {
-    let (_guard, _ctx) = crate::test::test_ctx();
-    let initial_allocation_count = unsafe { (*sys::igGetIO()).MetricsActiveAllocations };
+fn test_owneddrawdata_snapshot() {
+    fn assert_send_sync<T: Send + Sync>() {}
+    assert_send_sync::<OwnedDrawData>();
+
+    /// A comparable form of the commands
+    fn command_stream(draw_list: DrawListRef<'_>) -> Vec<Option<(usize, DrawCmdParams)>> {
+        draw_list
+            .commands()
+            .filter_map(|cmd| match cmd {
+                DrawCmd::Elements { count, cmd_params } => Some(Some((count, cmd_params))),
+                DrawCmd::ResetRenderState => Some(None),
+                DrawCmd::RawCallback { .. } => None,
+            })
+            .collect()
+    }
 
-    // Build a dummy draw data object
-    let mut draw_list = sys::ImDrawList::default();
-    let mut draw_lists_raw = [std::ptr::addr_of_mut!(draw_list)];
-    let draw_data_raw = sys::ImDrawData {
-        Valid: true,
-        CmdListsCount: 1,
-        CmdLists: draw_lists_raw.as_mut_ptr(),
-        TotalIdxCount: 0,
-        TotalVtxCount: 0,
-        DisplayPos: sys::ImVec2 { x: 0.0, y: 0.0 },
-        DisplaySize: sys::ImVec2 { x: 800.0, y: 600.0 },
-        FramebufferScale: sys::ImVec2 { x: 1.0, y: 1.0 },
-        #[cfg(feature = "docking")]
-        OwnerViewport: std::ptr::null_mut(),
+    let (_guard, mut ctx) = crate::test::test_ctx_initialized();
+    let build = |ui: &crate::Ui| {
+        ui.window("Snapshot").build(|| {
+            ui.text("Some text");
+            let draw_list = ui.get_window_draw_list();
+            draw_list.add_callback(|| {}).build();
+            draw_list.add_reset_render_state();
+            ui.button("A button");
+        });
     };
-    let draw_data = unsafe { DrawData::from_raw(&draw_data_raw) };
-
-    // Clone it, then drop it, and ensure all allocations are returned
-    {
-        let _owned_draw_data: OwnedDrawData = draw_data.into();
-        let cloned_allocation_count = unsafe { (*sys::igGetIO()).MetricsActiveAllocations };
-        assert!(cloned_allocation_count > initial_allocation_count);
-        // owned_draw_data is dropped here...
+    // New windows are hidden during their first frame
+    build(ctx.frame());
+    ctx.render();
+    build(ctx.frame());
+    let draw_data = ctx.render();
+    assert!(draw_data.draw_lists_count() > 0);
+    let snapshot = draw_data.snapshot();
+
+    assert_eq!(snapshot.display_size, draw_data.display_size);
+    assert_eq!(snapshot.total_vtx_count, draw_data.total_vtx_count);
+    assert_eq!(snapshot.draw_lists_count(), draw_data.draw_lists_count());
+    for (original, copy) in draw_data.draw_list_refs().zip(snapshot.draw_list_refs()) {
+        assert_eq!(original.vtx_buffer(), copy.vtx_buffer());
+        assert_eq!(original.idx_buffer(), copy.idx_buffer());
+        assert_eq!(command_stream(original), command_stream(copy));
+        // The callback is left out, but not the reset
+        assert!(copy
+            .commands()
+            .all(|cmd| !matches!(cmd, DrawCmd::RawCallback { .. })));
     }
-    let final_allocation_count = unsafe { (*sys::igGetIO()).MetricsActiveAllocations };
-    assert_eq!(initial_allocation_count, final_allocation_count);
+    assert!(snapshot
+        .draw_list_refs()
+        .flat_map(|draw_list| draw_list.commands())
+        .any(|cmd| matches!(cmd, DrawCmd::ResetRenderState)));
+
+    // The snapshot outlives the frame, and can be moved to another thread
+    let total_vtx_count = draw_data.total_vtx_count as usize;
+    let _ui = ctx.frame();
+    let vtx_count = std::thread::spawn(move || {
+        snapshot
+            .draw_lists()
+            .map(|draw_list| draw_list.vtx_buffer().len())
+            .sum::<usize>()
+    })
+    .join()
+    .unwrap();
+    assert_eq!(vtx_count, total_vtx_count);
 }
@@ -0,0 +1,50 @@
+use std::cell::Cell;
+use std::fmt;
+
+type Handler = Box<dyn FnMut(&str)>;
+
+/// Callback used to open URLs and files, see [`Context::set_open_in_shell_callback`].
+///
+/// The handler is boxed twice so this stays pointer-sized.
+///
+/// [`Context::set_open_in_shell_callback`]: crate::Context::set_open_in_shell_callback
+pub(crate) struct OpenInShell(Cell<Option<Box<Handler>>>);
+
+impl fmt::Debug for OpenInShell {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("OpenInShell")
+    }
+}
+
+impl OpenInShell {
+    /// Returns the handler set on new contexts.
+    pub(crate) fn platform_default() -> OpenInShell {
+        #[cfg(feature = "open-url")]
+        let handler = Some(Box::new(Box::new(open_in_shell) as Handler));
+        #[cfg(not(feature = "open-url"))]
+        let handler = None;
+        OpenInShell(Cell::new(handler))
+    }
+
+    pub(crate) fn set<F: FnMut(&str) + 'static>(&mut self, handler: F) {
+        *self.0.get_mut() = Some(Box::new(Box::new(handler)));
+    }
+
+    /// Opens the path with the handler, if any.
+    pub(crate) fn open(&self, path: &str) {
+        // Taken out while running, so the cell is never borrowed twice
+        if let Some(mut handler) = self.0.take() {
+            handler(path);
+            self.0.set(Some(handler));
+        }
+    }
+}
+
+/// Opens the path with the `open` crate, which picks the usual command of the platform (and
+/// handles e.g. WSL, or desktops without `xdg-open`).
+///
+/// The command runs on a separate thread so the UI doesn't wait for it, and failures are ignored.
+#[cfg(feature = "open-url")]
+fn open_in_shell(path: &str) {
+    let _ = open::that_in_background(path);
+}
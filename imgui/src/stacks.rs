@@ -4,6 +4,7 @@ use crate::math::MintVec4;
 use crate::style::{StyleColor, StyleVar};
 use crate::sys;
 use crate::Ui;
+use std::hash::Hash;
 use std::mem;
 use std::os::raw::c_char;
 
@@ -177,6 +178,109 @@ unsafe fn push_style_var(style_var: StyleVar) {
     }
 }
 
+/// A set of style variables and colors, pushed and popped together.
+///
+/// This replaces a series of [`Ui::push_style_var`] and [`Ui::push_style_color`] calls, whose
+/// tokens are easy to pop in the wrong order, e.g. for a themed panel. Everything is popped
+/// in one go, in the reverse order, when the closure given to [`StyleStack::build`] returns
+/// (even early) or the token returned by [`StyleStack::push`] is dropped.
+///
+/// # Examples
+///
+/// ```no_run
+/// # use imgui::*;
+/// # let mut ctx = Context::create();
+/// # let ui = ctx.frame();
+/// let rounded = StyleStack::new()
+///     .var(StyleVar::FrameRounding(8.0))
+///     .var(StyleVar::FramePadding([12.0, 6.0]))
+///     .color(StyleColor::Button, [0.2, 0.5, 0.3, 1.0]);
+/// rounded.build(ui, || {
+///     ui.button("Save");
+///     ui.button("Load");
+/// });
+/// ```
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct StyleStack {
+    vars: Vec<StyleVar>,
+    colors: Vec<(StyleColor, [f32; 4])>,
+}
+
+impl StyleStack {
+    /// Creates an empty set of style changes.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a change of a style variable.
+    pub fn var(mut self, style_var: StyleVar) -> Self {
+        self.vars.push(style_var);
+        self
+    }
+
+    /// Adds a change of a style color.
+    pub fn color(mut self, style_color: StyleColor, color: impl Into<MintVec4>) -> Self {
+        self.colors.push((style_color, color.into().into()));
+        self
+    }
+
+    /// Pushes all the changes.
+    ///
+    /// Returns a `StyleStackScope` which pops them all when dropped or ended.
+    pub fn push<'ui>(&self, ui: &'ui Ui) -> StyleStackScope<'ui> {
+        for &style_var in &self.vars {
+            unsafe { push_style_var(style_var) };
+        }
+        for &(style_color, color) in &self.colors {
+            unsafe { sys::igPushStyleColor_Vec4(style_color as i32, color.into()) };
+        }
+        StyleStackScope::new(ui, self.vars.len() as i32, self.colors.len() as i32)
+    }
+
+    /// Pushes all the changes, runs the closure and pops them.
+    pub fn build<R>(&self, ui: &Ui, f: impl FnOnce() -> R) -> R {
+        let _scope = self.push(ui);
+        f()
+    }
+}
+
+/// Tracks the changes pushed by a [`StyleStack`], which are popped by calling `.end()` or by
+/// dropping.
+///
+/// Like for the other tokens, the changes must be popped in the window they were pushed in,
+/// which imgui checks when the window ends.
+#[must_use = "if unused, the style changes are popped right away"]
+pub struct StyleStackScope<'ui> {
+    vars: i32,
+    colors: i32,
+    _ui: std::marker::PhantomData<&'ui Ui>,
+}
+
+impl<'ui> StyleStackScope<'ui> {
+    fn new(_: &'ui Ui, vars: i32, colors: i32) -> Self {
+        StyleStackScope {
+            vars,
+            colors,
+            _ui: std::marker::PhantomData,
+        }
+    }
+
+    /// Pops all the changes from the style and color stacks.
+    #[inline]
+    pub fn end(self) {
+        // left empty for drop
+    }
+}
+
+impl Drop for StyleStackScope<'_> {
+    fn drop(&mut self) {
+        unsafe {
+            sys::igPopStyleColor(self.colors);
+            sys::igPopStyleVar(self.vars);
+        }
+    }
+}
+
 /// # Parameter stacks (current window)
 impl Ui {
     /// Changes the item width by pushing a change to the item width stack.
@@ -327,7 +431,7 @@ create_token!(
 ///
 /// The "item flags" stack was a concept invented in imgui-rs that doesn't have an
 /// ImGui equivalent. We're phasing these out to make imgui-rs feel simpler to use.
-#[must_use]
+#[must_use = "if unused, the token is dropped right away, which ends what it tracks"]
 pub struct ItemFlagsStackToken<'a>(
     std::marker::PhantomData<&'a Ui>,
     mem::Discriminant<ItemFlag>,
@@ -495,6 +599,29 @@ impl Ui {
         IdStackToken::new(self)
     }
 
+    /// Pushes a hash of any hashable value to the ID stack, e.g. a composite key, without
+    /// formatting it to a string.
+    ///
+    /// The same value always gives the same id, see [`IdSource::hash`] for how stable it is.
+    ///
+    /// Returns an `IdStackToken` that can be popped by calling `.end()`
+    /// or by dropping manually.
+    ///
+    /// ```no_run
+    /// # let mut imgui = imgui::Context::create();
+    /// # let ui = imgui.frame();
+    /// for (entity, component) in [(1u64, "Transform"), (1, "Mesh"), (2, "Transform")] {
+    ///     let _id = ui.push_id_hash(&(entity, component));
+    ///     ui.button("Remove");
+    /// }
+    /// ```
+    ///
+    /// [`IdSource::hash`]: crate::IdSource::hash
+    #[doc(alias = "PushId")]
+    pub fn push_id_hash<T: Hash + ?Sized>(&self, value: &T) -> IdStackToken<'_> {
+        self.push_id_int(crate::stable_hash(value))
+    }
+
     /// Pushes a `ptr` to the ID stack.
     ///
     /// Returns an `IdStackToken` that can be popped by calling `.end()`
@@ -509,3 +636,83 @@ impl Ui {
         IdStackToken::new(self)
     }
 }
+
+#[test]
+fn test_style_stack() {
+    let (_guard, mut ctx) = crate::test::test_ctx_initialized();
+    let ui = ctx.frame();
+    let rounding = ui.clone_style().frame_rounding;
+    let button = ui.style_color(StyleColor::Button);
+
+    let outer = StyleStack::new()
+        .var(StyleVar::FrameRounding(8.0))
+        .var(StyleVar::FrameRounding(6.0))
+        .color(StyleColor::Button, [1.0, 0.0, 0.0, 1.0]);
+    let inner = StyleStack::new().var(StyleVar::FrameRounding(2.0));
+    let result = outer.build(ui, || {
+        // The last change of a variable wins
+        assert_eq!(ui.clone_style().frame_rounding, 6.0);
+        inner.build(ui, || assert_eq!(ui.clone_style().frame_rounding, 2.0));
+        assert_eq!(ui.clone_style().frame_rounding, 6.0);
+        assert_eq!(ui.style_color(StyleColor::Button), [1.0, 0.0, 0.0, 1.0]);
+        42
+    });
+    assert_eq!(result, 42);
+    assert_eq!(ui.clone_style().frame_rounding, rounding);
+    assert_eq!(ui.style_color(StyleColor::Button), button);
+
+    // Returning early still pops the changes
+    let draw_values = |values: &[i32]| -> Result<(), i32> {
+        let _scope = outer.push(ui);
+        for &value in values {
+            if value < 0 {
+                return Err(value);
+            }
+            ui.text(value.to_string());
+        }
+        Ok(())
+    };
+    assert_eq!(draw_values(&[1, -4, 5]), Err(-4));
+    assert_eq!(ui.clone_style().frame_rounding, rounding);
+    assert_eq!(ui.style_color(StyleColor::Button), button);
+}
+
+#[test]
+fn test_push_id_hash() {
+    use crate::IdSource;
+
+    let (_guard, mut ctx) = crate::test::test_ctx_initialized();
+    let ui = ctx.frame();
+    let id_in = |key: &(u64, &str)| {
+        let _id = ui.push_id_hash(key);
+        ui.get_id("item")
+    };
+    assert_eq!(id_in(&(1, "Transform")), id_in(&(1, "Transform")));
+    assert_ne!(id_in(&(1, "Transform")), id_in(&(1, "Mesh")));
+    assert_ne!(id_in(&(1, "Transform")), id_in(&(2, "Transform")));
+    assert_ne!(id_in(&(1, "Transform")), ui.get_id("item"));
+
+    let key = (7u32, "Light");
+    assert_eq!(
+        ui.get_id(IdSource::hash(&key)),
+        ui.get_id(IdSource::hash(&key))
+    );
+    assert_ne!(
+        ui.get_id(IdSource::hash(&key)),
+        ui.get_id(IdSource::hash(&(8u32, "Light")))
+    );
+    // The hash doesn't depend on the run
+    assert_eq!(
+        IdSource::hash("imgui"),
+        IdSource::Int(0xdfae_92d8_u32 as i32)
+    );
+
+    assert_eq!(ui.get_id("label"), ui.new_id_str("label"));
+    assert_eq!(ui.get_id(&String::from("label")), ui.new_id_str("label"));
+    assert_eq!(ui.get_id(3), ui.new_id_int(3));
+    assert_eq!(ui.get_id(5usize), ui.new_id(5));
+
+    let id = ui.get_id("label");
+    assert_eq!(id.to_string(), format!("{:#010x}", id.raw()));
+    assert_eq!(format!("{:?}", id), format!("Id({})", id));
+}
@@ -7,8 +7,15 @@ use crate::sys;
 use crate::Direction;
 
 /// User interface style/colors
+///
+/// With the `serde` feature, styles can be saved to and loaded from theme files. Fields are
+/// named like in Rust, and colors are a map from [`StyleColor::name`] to RGBA values. Missing
+/// fields and colors are set to their default value, and unknown ones are ignored, so that
+/// theme files keep working across Dear ImGui versions.
 #[repr(C)]
 #[derive(Debug, Copy, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
 pub struct Style {
     /// Global alpha applies to everything
     pub alpha: f32,
@@ -150,13 +157,94 @@ pub struct Style {
     /// Decrease for higher quality but more geometry.
     pub circle_tesselation_max_error: f32,
     /// Style colors.
+    #[cfg_attr(feature = "serde", serde(with = "serde_colors"))]
     pub colors: [[f32; 4]; StyleColor::COUNT],
 }
 
 unsafe impl RawCast<sys::ImGuiStyle> for Style {}
 
+impl Default for Style {
+    /// The default style of Dear ImGui, with dark colors
+    fn default() -> Self {
+        unsafe {
+            let raw = sys::ImGuiStyle_ImGuiStyle();
+            let style = *Style::from_raw(&*raw);
+            sys::ImGuiStyle_destroy(raw);
+            style
+        }
+    }
+}
+
+/// (De)serializes style colors as a map from color names to values
+#[cfg(feature = "serde")]
+mod serde_colors {
+    use std::fmt;
+
+    use serde::de::{Deserializer, IgnoredAny, MapAccess, Visitor};
+    use serde::ser::{SerializeMap, Serializer};
+
+    use super::{Style, StyleColor};
+
+    type Colors = [[f32; 4]; StyleColor::COUNT];
+
+    pub fn serialize<S: Serializer>(colors: &Colors, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut map = serializer.serialize_map(Some(StyleColor::COUNT))?;
+        for color in StyleColor::VARIANTS {
+            map.serialize_entry(color.name(), &colors[color as usize])?;
+        }
+        map.end()
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Colors, D::Error> {
+        struct ColorsVisitor;
+
+        impl<'de> Visitor<'de> for ColorsVisitor {
+            type Value = Colors;
+
+            fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.write_str("a map of style color names to RGBA colors")
+            }
+
+            fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Colors, A::Error> {
+                let mut colors = Style::default().colors;
+                while let Some(name) = map.next_key::<String>()? {
+                    match StyleColor::VARIANTS
+                        .iter()
+                        .find(|color| color.name() == name)
+                    {
+                        Some(&color) => colors[color as usize] = map.next_value()?,
+                        // A color of another Dear ImGui version
+                        None => {
+                            map.next_value::<IgnoredAny>()?;
+                        }
+                    }
+                }
+                Ok(colors)
+            }
+        }
+
+        deserializer.deserialize_map(ColorsVisitor)
+    }
+}
+
 impl Style {
-    /// Scales all sizes in the style
+    /// Scales all sizes in the style: paddings, spacings, roundings, minimum sizes and the
+    /// mouse cursor scale, rounded down to whole pixels. Border sizes and alignments are left
+    /// unchanged, like colors.
+    ///
+    /// Use this with the DPI factor when fonts are enlarged for high DPI displays, so that
+    /// widgets grow along with the text. Since the sizes are rounded, scale a copy of the
+    /// unscaled style when the factor changes, rather than scaling the current style again:
+    ///
+    /// ```no_run
+    /// # let mut ctx = imgui::Context::create();
+    /// # let hidpi_factor = 2.0;
+    /// let base_style = ctx.style().clone();
+    /// // whenever the DPI factor changes
+    /// let style = ctx.style_mut();
+    /// *style = base_style;
+    /// style.scale_all_sizes(hidpi_factor);
+    /// ```
     #[doc(alias = "ScaleAllSizes")]
     pub fn scale_all_sizes(&mut self, scale_factor: f32) {
         unsafe {
@@ -212,6 +300,7 @@ impl IndexMut<StyleColor> for Style {
 /// [`crate::Ui::show_demo_window`] until you spot it
 #[repr(u32)]
 #[derive(Copy, Clone, Debug, Hash, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[non_exhaustive]
 pub enum StyleColor {
     /// Default color of text througout application
@@ -605,6 +694,124 @@ fn test_style_scaling() {
     assert_eq!(style.cell_padding, [58.0, 60.0]);
 }
 
+#[test]
+fn test_style_scaling_covers_all_fields() {
+    let (_guard, mut ctx) = crate::test::test_ctx();
+    let style = ctx.style_mut();
+    let original = *style;
+    style.scale_all_sizes(2.0);
+    let scaled = *style;
+
+    // Destructured without `..`, so that fields added by a new imgui version have to be
+    // sorted into scaled or unscaled ones here
+    let Style {
+        alpha,
+        disabled_alpha,
+        window_padding,
+        window_rounding,
+        window_border_size,
+        window_min_size,
+        window_title_align,
+        window_menu_button_position,
+        child_rounding,
+        child_border_size,
+        popup_rounding,
+        popup_border_size,
+        frame_padding,
+        frame_rounding,
+        frame_border_size,
+        item_spacing,
+        item_inner_spacing,
+        cell_padding,
+        touch_extra_padding,
+        indent_spacing,
+        columns_min_spacing,
+        scrollbar_size,
+        scrollbar_rounding,
+        grab_min_size,
+        grab_rounding,
+        log_slider_deadzone,
+        tab_rounding,
+        tab_border_size,
+        tab_min_width_for_close_button,
+        color_button_position,
+        button_text_align,
+        selectable_text_align,
+        display_window_padding,
+        display_safe_area_padding,
+        mouse_cursor_scale,
+        anti_aliased_lines,
+        anti_aliased_lines_use_tex,
+        anti_aliased_fill,
+        curve_tessellation_tol,
+        circle_tesselation_max_error,
+        colors,
+    } = original;
+
+    let double = |v: f32| (v * 2.0).floor();
+    let double2 = |v: [f32; 2]| [double(v[0]), double(v[1])];
+    assert_eq!(scaled.window_padding, double2(window_padding));
+    assert_eq!(scaled.window_rounding, double(window_rounding));
+    assert_eq!(scaled.window_min_size, double2(window_min_size));
+    assert_eq!(scaled.child_rounding, double(child_rounding));
+    assert_eq!(scaled.popup_rounding, double(popup_rounding));
+    assert_eq!(scaled.frame_padding, double2(frame_padding));
+    assert_eq!(scaled.frame_rounding, double(frame_rounding));
+    assert_eq!(scaled.item_spacing, double2(item_spacing));
+    assert_eq!(scaled.item_inner_spacing, double2(item_inner_spacing));
+    assert_eq!(scaled.cell_padding, double2(cell_padding));
+    assert_eq!(scaled.touch_extra_padding, double2(touch_extra_padding));
+    assert_eq!(scaled.indent_spacing, double(indent_spacing));
+    assert_eq!(scaled.columns_min_spacing, double(columns_min_spacing));
+    assert_eq!(scaled.scrollbar_size, double(scrollbar_size));
+    assert_eq!(scaled.scrollbar_rounding, double(scrollbar_rounding));
+    assert_eq!(scaled.grab_min_size, double(grab_min_size));
+    assert_eq!(scaled.grab_rounding, double(grab_rounding));
+    assert_eq!(scaled.log_slider_deadzone, double(log_slider_deadzone));
+    assert_eq!(scaled.tab_rounding, double(tab_rounding));
+    assert_eq!(
+        scaled.tab_min_width_for_close_button,
+        double(tab_min_width_for_close_button)
+    );
+    assert_eq!(
+        scaled.display_window_padding,
+        double2(display_window_padding)
+    );
+    assert_eq!(
+        scaled.display_safe_area_padding,
+        double2(display_safe_area_padding)
+    );
+    assert_eq!(scaled.mouse_cursor_scale, mouse_cursor_scale * 2.0);
+
+    assert_eq!(scaled.alpha, alpha);
+    assert_eq!(scaled.disabled_alpha, disabled_alpha);
+    assert_eq!(scaled.window_border_size, window_border_size);
+    assert_eq!(scaled.window_title_align, window_title_align);
+    assert_eq!(
+        scaled.window_menu_button_position,
+        window_menu_button_position
+    );
+    assert_eq!(scaled.child_border_size, child_border_size);
+    assert_eq!(scaled.popup_border_size, popup_border_size);
+    assert_eq!(scaled.frame_border_size, frame_border_size);
+    assert_eq!(scaled.tab_border_size, tab_border_size);
+    assert_eq!(scaled.color_button_position, color_button_position);
+    assert_eq!(scaled.button_text_align, button_text_align);
+    assert_eq!(scaled.selectable_text_align, selectable_text_align);
+    assert_eq!(scaled.anti_aliased_lines, anti_aliased_lines);
+    assert_eq!(
+        scaled.anti_aliased_lines_use_tex,
+        anti_aliased_lines_use_tex
+    );
+    assert_eq!(scaled.anti_aliased_fill, anti_aliased_fill);
+    assert_eq!(scaled.curve_tessellation_tol, curve_tessellation_tol);
+    assert_eq!(
+        scaled.circle_tesselation_max_error,
+        circle_tesselation_max_error
+    );
+    assert_eq!(scaled.colors, colors);
+}
+
 #[test]
 fn test_style_color_indexing() {
     let (_guard, mut ctx) = crate::test::test_ctx();
@@ -615,6 +822,59 @@ fn test_style_color_indexing() {
     assert_eq!(style.colors[StyleColor::Tab as usize], value);
 }
 
+#[test]
+#[cfg(feature = "serde")]
+fn test_style_serde_round_trip() {
+    let mut style = Style::default();
+    style.use_light_colors();
+    style.window_padding = [1.0, 2.0];
+    style.window_menu_button_position = Direction::Right;
+    style.anti_aliased_fill = false;
+    style[StyleColor::Tab] = [0.1, 0.2, 0.3, 1.0];
+
+    let json = serde_json::to_string(&style).unwrap();
+    let loaded: Style = serde_json::from_str(&json).unwrap();
+    assert_eq!(loaded.window_padding, [1.0, 2.0]);
+    assert_eq!(loaded.window_menu_button_position, Direction::Right);
+    assert!(!loaded.anti_aliased_fill);
+    assert_eq!(loaded.colors, style.colors);
+    assert_eq!(serde_json::to_string(&loaded).unwrap(), json);
+
+    // Colors are stored by name
+    let value: serde_json::Value = serde_json::from_str(&json).unwrap();
+    let colors = value["colors"].as_object().unwrap();
+    assert_eq!(colors.len(), StyleColor::COUNT);
+    assert_eq!(colors["Tab"].as_array().unwrap().len(), 4);
+}
+
+#[test]
+#[cfg(feature = "serde")]
+fn test_style_serde_missing_and_unknown_fields() {
+    // A theme file of another imgui version, missing some fields and with unknown ones
+    let json = r#"{
+        "alpha": 0.5,
+        "frame_padding": [3.0, 4.0],
+        "color_button_position": "Left",
+        "some_future_var": [1.0, 2.0],
+        "colors": {
+            "Text": [1.0, 0.0, 0.0, 1.0],
+            "SomeFutureColor": [0.0, 0.0, 0.0, 0.0]
+        }
+    }"#;
+    let style: Style = serde_json::from_str(json).unwrap();
+    let default = Style::default();
+    assert_eq!(style.alpha, 0.5);
+    assert_eq!(style.frame_padding, [3.0, 4.0]);
+    assert_eq!(style.color_button_position, Direction::Left);
+    assert_eq!(style.window_padding, default.window_padding);
+    assert_eq!(style.window_rounding, default.window_rounding);
+    assert_eq!(style[StyleColor::Text], [1.0, 0.0, 0.0, 1.0]);
+    assert_eq!(style[StyleColor::WindowBg], default[StyleColor::WindowBg]);
+
+    let style: Style = serde_json::from_str("{}").unwrap();
+    assert_eq!(style.colors, default.colors);
+}
+
 #[test]
 #[cfg(test)]
 fn test_style_memory_layout() {
@@ -165,6 +165,10 @@ bitflags! {
     pub struct TableColumnFlags: u32 {
         // Input configuration flags
 
+        /// Overriding/master disable flag: hide column, won't show in context menu (unlike
+        /// calling [`Ui::table_set_enabled`](crate::Ui::table_set_enabled), which manipulates
+        /// the user accessible state).
+        const DISABLED = sys::ImGuiTableColumnFlags_Disabled;
         /// Default as a hidden/disabled column.
         const DEFAULT_HIDE = sys::ImGuiTableColumnFlags_DefaultHide;
         /// Default as a sorting column.
@@ -193,6 +197,9 @@ bitflags! {
         const NO_SORT_ASCENDING = sys::ImGuiTableColumnFlags_NoSortAscending;
         /// Disable ability to sort in the descending direction.
         const NO_SORT_DESCENDING = sys::ImGuiTableColumnFlags_NoSortDescending;
+        /// [`Ui::table_headers_row`](crate::Ui::table_headers_row) will not submit label for this
+        /// column. Convenient for some small columns. Name will still appear in context menu.
+        const NO_HEADER_LABEL = sys::ImGuiTableColumnFlags_NoHeaderLabel;
         /// Disable header text width contribution to automatic column width.
         const NO_HEADER_WIDTH = sys::ImGuiTableColumnFlags_NoHeaderWidth;
         /// Make the initial sort direction Ascending when first sorting on this column (default).
@@ -664,10 +671,8 @@ impl Ui {
 
     /// Gets the flags on the current column in the current table.
     pub fn table_column_flags(&self) -> TableColumnFlags {
-        unsafe {
-            TableColumnFlags::from_bits(sys::igTableGetColumnFlags(-1) as u32)
-                .expect("bad column flags")
-        }
+        // imgui may also report internal flags, which we don't expose
+        unsafe { TableColumnFlags::from_bits_truncate(sys::igTableGetColumnFlags(-1) as u32) }
     }
 
     /// Gets the flags on the given column in the current table. To get the current column's
@@ -675,11 +680,26 @@ impl Ui {
     /// [table_column_flags](Self::table_column_flags).
     pub fn table_column_flags_with_column(&self, column_n: usize) -> TableColumnFlags {
         unsafe {
-            TableColumnFlags::from_bits(sys::igTableGetColumnFlags(column_n as i32) as u32)
-                .expect("bad column flags")
+            TableColumnFlags::from_bits_truncate(sys::igTableGetColumnFlags(column_n as i32) as u32)
         }
     }
 
+    /// Returns true if the given column is enabled, ie not hidden by the user (via the
+    /// context menu) or by [table_set_enabled_with_column](Self::table_set_enabled_with_column).
+    pub fn table_column_is_enabled(&self, column_n: usize) -> bool {
+        self.table_column_flags_with_column(column_n)
+            .contains(TableColumnFlags::IS_ENABLED)
+    }
+
+    /// Gets the index of the column hovered by the mouse in the current table, or `None` if
+    /// no column is hovered.
+    pub fn table_hovered_column(&self) -> Option<usize> {
+        (0..self.table_column_count()).find(|&column_n| {
+            self.table_column_flags_with_column(column_n)
+                .contains(TableColumnFlags::IS_HOVERED)
+        })
+    }
+
     /// Sets the given background color for this column. See [TableBgTarget]
     /// for more information on how colors work for tables.
     ///
@@ -716,6 +736,9 @@ impl Ui {
     /// this themselves by right-clicking in headers, or right-clicking in columns body
     /// if [TableFlags::CONTEXT_MENU_IN_BODY].
     ///
+    /// The table must have been created with [TableFlags::HIDEABLE]. The change takes
+    /// effect on the next frame.
+    ///
     /// Use [table_set_enabled_with_column](Self::table_set_enabled_with_column) to set
     /// for arbitrary indices.
     pub fn table_set_enabled(&self, enabled: bool) {
@@ -727,6 +750,9 @@ impl Ui {
     /// Set to false to hide the column. Users can use the context menu to change
     /// this themselves by right-clicking in headers, or right-clicking in columns body
     /// if [TableFlags::CONTEXT_MENU_IN_BODY].
+    ///
+    /// The table must have been created with [TableFlags::HIDEABLE]. The change takes
+    /// effect on the next frame.
     pub fn table_set_enabled_with_column(&self, enabled: bool, column_idx: usize) {
         unsafe { sys::igTableSetColumnEnabled(column_idx as i32, enabled) }
     }
@@ -781,6 +807,60 @@ impl<Name: AsRef<str>> TableColumnSetup<Name> {
 /// check if you should sort your data, sort your data using [specs] for information
 /// on how to sort it, and then [set_sorted] to indicate that the data is sorted.
 ///
+/// When the table has [TableFlags::SORT_MULTI], the specs are given in sort order: the first
+/// spec is the primary sort criteria, the second breaks ties of the first, and so on.
+///
+/// # Example
+/// ```no_run
+/// # use imgui::*;
+/// # use std::cmp::Ordering;
+/// # let mut ctx = Context::create();
+/// # { let ui = ctx.frame();
+/// struct File {
+///     name: String,
+///     size: u64,
+/// }
+/// let mut files: Vec<File> = Vec::new();
+///
+/// if let Some(_t) = ui.begin_table_with_flags(
+///     "files",
+///     2,
+///     TableFlags::SORTABLE | TableFlags::SORT_MULTI,
+/// ) {
+///     ui.table_setup_column("Name");
+///     ui.table_setup_column("Size");
+///     ui.table_headers_row();
+///
+///     if let Some(sort_specs) = ui.table_sort_specs_mut() {
+///         sort_specs.conditional_sort(|specs| {
+///             files.sort_by(|a, b| {
+///                 // compare by each spec in turn, until one of them tells the files apart
+///                 specs.iter().fold(Ordering::Equal, |ordering, spec| {
+///                     ordering.then_with(|| {
+///                         let ordering = match spec.column_idx() {
+///                             0 => a.name.cmp(&b.name),
+///                             _ => a.size.cmp(&b.size),
+///                         };
+///                         match spec.sort_direction() {
+///                             Some(TableSortDirection::Descending) => ordering.reverse(),
+///                             _ => ordering,
+///                         }
+///                     })
+///                 })
+///             });
+///         });
+///     }
+///
+///     for file in &files {
+///         ui.table_next_column();
+///         ui.text(&file.name);
+///         ui.table_next_column();
+///         ui.text(file.size.to_string());
+///     }
+/// }
+/// # };
+/// ```
+///
 /// [conditional_sort]: Self::conditional_sort
 /// [should_sort]: Self::should_sort
 /// [specs]: Self::specs
@@ -836,14 +916,32 @@ impl TableSortSpecsMut<'_> {
 /// but is generally == 1.
 ///
 /// Consume this struct as an iterator.
+#[derive(Copy, Clone)]
 pub struct Specs<'a>(&'a [sys::ImGuiTableColumnSortSpecs]);
 
 impl<'a> Specs<'a> {
     pub fn iter(self) -> impl Iterator<Item = TableColumnSortSpecs<'a>> {
         self.0.iter().map(TableColumnSortSpecs)
     }
+
+    /// Returns the number of columns the table is sorted on.
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    /// Returns true if the table is not sorted on any column. This can only happen when
+    /// [TableFlags::SORT_TRISTATE] is set.
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Gets the spec at the given position in sort order, where 0 is the primary sort criteria.
+    pub fn get(&self, sort_order: usize) -> Option<TableColumnSortSpecs<'a>> {
+        self.0.get(sort_order).map(TableColumnSortSpecs)
+    }
 }
 
+#[derive(Copy, Clone)]
 pub struct TableColumnSortSpecs<'a>(&'a sys::ImGuiTableColumnSortSpecs);
 impl<'a> TableColumnSortSpecs<'a> {
     /// User id of the column (if specified by a TableSetupColumn() call)
@@ -884,3 +982,93 @@ create_token!(
     /// Ends the table.
     drop { sys::igEndTable() }
 );
+
+#[test]
+fn test_table_multi_sort_specs() {
+    let (_guard, mut ctx) = crate::test::test_ctx_initialized();
+    let ui = ctx.frame();
+    let _window = ui.window("Example").begin();
+
+    let _table = ui
+        .begin_table_with_flags("sorted", 3, TableFlags::SORTABLE | TableFlags::SORT_MULTI)
+        .unwrap();
+    for (name, flags) in [
+        ("One", TableColumnFlags::empty()),
+        (
+            "Two",
+            TableColumnFlags::DEFAULT_SORT | TableColumnFlags::PREFER_SORT_DESCENDING,
+        ),
+        ("Three", TableColumnFlags::DEFAULT_SORT),
+    ] {
+        ui.table_setup_column_with(TableColumnSetup {
+            flags,
+            ..TableColumnSetup::new(name)
+        });
+    }
+    ui.table_headers_row();
+
+    let mut sort_specs = ui.table_sort_specs_mut().unwrap();
+    assert!(sort_specs.should_sort());
+
+    let specs = sort_specs.specs();
+    assert_eq!(specs.len(), 2);
+    let primary = specs.get(0).unwrap();
+    assert_eq!(primary.column_idx(), 1);
+    assert_eq!(primary.sort_order(), 0);
+    assert_eq!(
+        primary.sort_direction(),
+        Some(TableSortDirection::Descending)
+    );
+    let secondary = specs.get(1).unwrap();
+    assert_eq!(secondary.column_idx(), 2);
+    assert_eq!(secondary.sort_order(), 1);
+    assert_eq!(
+        secondary.sort_direction(),
+        Some(TableSortDirection::Ascending)
+    );
+    assert!(specs.get(2).is_none());
+
+    sort_specs.set_sorted();
+    assert!(!sort_specs.should_sort());
+}
+
+#[test]
+fn test_table_disable_column() {
+    let (_guard, mut ctx) = crate::test::test_ctx_initialized();
+
+    let table_frame = |ctx: &mut crate::Context, disable: bool| -> Vec<TableColumnFlags> {
+        let ui = ctx.frame();
+        let _window = ui.window("Example").begin();
+        let _table = ui
+            .begin_table_with_flags("columns", 3, TableFlags::HIDEABLE)
+            .unwrap();
+        ui.table_setup_column("One");
+        ui.table_setup_column_with(TableColumnSetup {
+            flags: TableColumnFlags::WIDTH_STRETCH,
+            init_width_or_weight: 2.0,
+            user_id: ui.new_id(2),
+            ..TableColumnSetup::new("Two")
+        });
+        ui.table_setup_column("Three");
+        ui.table_headers_row();
+        if disable {
+            ui.table_set_enabled_with_column(false, 1);
+        }
+        assert_eq!(ui.table_column_count(), 3);
+        (0..3)
+            .map(|column_n| ui.table_column_flags_with_column(column_n))
+            .collect()
+    };
+
+    let flags = table_frame(&mut ctx, true);
+    assert!(flags
+        .iter()
+        .all(|f| f.contains(TableColumnFlags::IS_ENABLED)));
+    ctx.render();
+
+    let flags = table_frame(&mut ctx, false);
+    assert!(flags[0].contains(TableColumnFlags::IS_ENABLED));
+    assert!(!flags[1].contains(TableColumnFlags::IS_ENABLED));
+    assert!(!flags[1].contains(TableColumnFlags::IS_VISIBLE));
+    assert!(flags[2].contains(TableColumnFlags::IS_ENABLED));
+}
@@ -0,0 +1,449 @@
+//! Ready-made styles, and smooth transitions between them.
+//!
+//! ```no_run
+//! # use imgui::*;
+//! # use imgui::themes::Theme;
+//! # let mut ctx = Context::create();
+//! Theme::high_contrast().apply(ctx.style_mut());
+//!
+//! // Halfway through an animated switch from dark to light
+//! *ctx.style_mut() = Theme::lerp(&Theme::dark(), &Theme::light(), 0.5);
+//! ```
+
+use crate::style::{Style, StyleColor};
+
+/// A complete style, colors and sizes, with a name to show in theme pickers
+#[derive(Debug, Copy, Clone)]
+pub struct Theme {
+    pub name: &'static str,
+    pub style: Style,
+}
+
+impl Theme {
+    /// Creates a theme from a style
+    pub fn new(name: &'static str, style: Style) -> Theme {
+        Theme { name, style }
+    }
+
+    /// Returns all the built-in themes.
+    pub fn presets() -> [Theme; 5] {
+        [
+            Theme::dark(),
+            Theme::light(),
+            Theme::classic(),
+            Theme::high_contrast(),
+            Theme::comfy(),
+        ]
+    }
+
+    /// The default style of Dear ImGui
+    pub fn dark() -> Theme {
+        Theme::new("Dark", Style::default())
+    }
+
+    /// Dear ImGui's light colors, with the borders they are meant to be used with
+    pub fn light() -> Theme {
+        let mut style = Style::default();
+        style.use_light_colors();
+        style.window_border_size = 1.0;
+        style.frame_border_size = 1.0;
+        style.popup_border_size = 1.0;
+        Theme::new("Light", style)
+    }
+
+    /// Dear ImGui's classic colors
+    pub fn classic() -> Theme {
+        let mut style = Style::default();
+        style.use_classic_colors();
+        Theme::new("Classic", style)
+    }
+
+    /// White text on black, with opaque backgrounds, thick borders and a yellow highlight for
+    /// the focused and active elements.
+    pub fn high_contrast() -> Theme {
+        const BLACK: [f32; 4] = [0.0, 0.0, 0.0, 1.0];
+        const WHITE: [f32; 4] = [1.0, 1.0, 1.0, 1.0];
+        const YELLOW: [f32; 4] = [1.0, 1.0, 0.0, 1.0];
+        const GRAY: [f32; 4] = [0.15, 0.15, 0.15, 1.0];
+        const BLUE: [f32; 4] = [0.0, 0.25, 0.5, 1.0];
+        const BLUE_HOVERED: [f32; 4] = [0.0, 0.4, 0.8, 1.0];
+        const BLUE_ACTIVE: [f32; 4] = [0.0, 0.55, 1.0, 1.0];
+
+        let mut style = Style {
+            window_border_size: 2.0,
+            child_border_size: 2.0,
+            popup_border_size: 2.0,
+            frame_border_size: 1.0,
+            tab_border_size: 1.0,
+            grab_min_size: 14.0,
+            scrollbar_size: 16.0,
+            ..Style::default()
+        };
+        set_colors(
+            &mut style,
+            &[
+                (StyleColor::Text, WHITE),
+                (StyleColor::TextDisabled, [0.75, 0.75, 0.75, 1.0]),
+                (StyleColor::WindowBg, BLACK),
+                (StyleColor::ChildBg, BLACK),
+                (StyleColor::PopupBg, BLACK),
+                (StyleColor::Border, WHITE),
+                (StyleColor::BorderShadow, [0.0, 0.0, 0.0, 0.0]),
+                (StyleColor::FrameBg, GRAY),
+                (StyleColor::FrameBgHovered, BLUE),
+                (StyleColor::FrameBgActive, BLUE_HOVERED),
+                (StyleColor::TitleBg, GRAY),
+                (StyleColor::TitleBgActive, BLUE),
+                (StyleColor::TitleBgCollapsed, BLACK),
+                (StyleColor::MenuBarBg, GRAY),
+                (StyleColor::ScrollbarBg, BLACK),
+                (StyleColor::ScrollbarGrab, [0.6, 0.6, 0.6, 1.0]),
+                (StyleColor::ScrollbarGrabHovered, WHITE),
+                (StyleColor::ScrollbarGrabActive, YELLOW),
+                (StyleColor::CheckMark, YELLOW),
+                (StyleColor::SliderGrab, WHITE),
+                (StyleColor::SliderGrabActive, YELLOW),
+                (StyleColor::Button, BLUE),
+                (StyleColor::ButtonHovered, BLUE_HOVERED),
+                (StyleColor::ButtonActive, BLUE_ACTIVE),
+                (StyleColor::Header, BLUE),
+                (StyleColor::HeaderHovered, BLUE_HOVERED),
+                (StyleColor::HeaderActive, BLUE_ACTIVE),
+                (StyleColor::Separator, WHITE),
+                (StyleColor::SeparatorHovered, BLUE_HOVERED),
+                (StyleColor::SeparatorActive, YELLOW),
+                (StyleColor::ResizeGrip, WHITE),
+                (StyleColor::ResizeGripHovered, BLUE_HOVERED),
+                (StyleColor::ResizeGripActive, YELLOW),
+                (StyleColor::Tab, GRAY),
+                (StyleColor::TabHovered, BLUE_HOVERED),
+                (StyleColor::TabActive, BLUE),
+                (StyleColor::TabUnfocused, GRAY),
+                (StyleColor::TabUnfocusedActive, BLUE),
+                (StyleColor::PlotLines, WHITE),
+                (StyleColor::PlotLinesHovered, YELLOW),
+                (StyleColor::PlotHistogram, WHITE),
+                (StyleColor::PlotHistogramHovered, YELLOW),
+                (StyleColor::TableHeaderBg, GRAY),
+                (StyleColor::TableBorderStrong, WHITE),
+                (StyleColor::TableBorderLight, [0.6, 0.6, 0.6, 1.0]),
+                (StyleColor::TableRowBg, BLACK),
+                (StyleColor::TableRowBgAlt, [0.1, 0.1, 0.1, 1.0]),
+                (StyleColor::TextSelectedBg, BLUE_HOVERED),
+                (StyleColor::DragDropTarget, YELLOW),
+                (StyleColor::NavHighlight, YELLOW),
+                (StyleColor::NavWindowingHighlight, YELLOW),
+                (StyleColor::NavWindowingDimBg, [0.0, 0.0, 0.0, 0.8]),
+                (StyleColor::ModalWindowDimBg, [0.0, 0.0, 0.0, 0.8]),
+            ],
+        );
+        Theme::new("High contrast", style)
+    }
+
+    /// Rounded corners, generous spacing and muted slate colors
+    pub fn comfy() -> Theme {
+        const TEXT: [f32; 4] = [0.9, 0.92, 0.95, 1.0];
+        const BACKGROUND: [f32; 4] = [0.11, 0.12, 0.15, 1.0];
+        const SURFACE: [f32; 4] = [0.17, 0.19, 0.23, 1.0];
+        const SURFACE_HOVERED: [f32; 4] = [0.23, 0.26, 0.32, 1.0];
+        const SURFACE_ACTIVE: [f32; 4] = [0.28, 0.32, 0.4, 1.0];
+        const ACCENT: [f32; 4] = [0.4, 0.62, 0.87, 1.0];
+        const ACCENT_HOVERED: [f32; 4] = [0.5, 0.7, 0.92, 1.0];
+
+        let mut style = Style {
+            window_padding: [12.0, 12.0],
+            window_rounding: 10.0,
+            window_border_size: 0.0,
+            window_title_align: [0.5, 0.5],
+            child_rounding: 8.0,
+            popup_rounding: 8.0,
+            frame_padding: [8.0, 5.0],
+            frame_rounding: 6.0,
+            item_spacing: [10.0, 8.0],
+            item_inner_spacing: [6.0, 6.0],
+            cell_padding: [6.0, 4.0],
+            indent_spacing: 22.0,
+            scrollbar_size: 14.0,
+            scrollbar_rounding: 12.0,
+            grab_min_size: 12.0,
+            grab_rounding: 6.0,
+            tab_rounding: 6.0,
+            ..Style::default()
+        };
+        set_colors(
+            &mut style,
+            &[
+                (StyleColor::Text, TEXT),
+                (StyleColor::TextDisabled, [0.5, 0.53, 0.58, 1.0]),
+                (StyleColor::WindowBg, BACKGROUND),
+                (StyleColor::ChildBg, [0.0, 0.0, 0.0, 0.0]),
+                (StyleColor::PopupBg, [0.13, 0.14, 0.18, 0.98]),
+                (StyleColor::Border, [0.25, 0.28, 0.34, 0.6]),
+                (StyleColor::FrameBg, SURFACE),
+                (StyleColor::FrameBgHovered, SURFACE_HOVERED),
+                (StyleColor::FrameBgActive, SURFACE_ACTIVE),
+                (StyleColor::TitleBg, BACKGROUND),
+                (StyleColor::TitleBgActive, SURFACE),
+                (StyleColor::TitleBgCollapsed, BACKGROUND),
+                (StyleColor::MenuBarBg, SURFACE),
+                (StyleColor::ScrollbarBg, [0.0, 0.0, 0.0, 0.0]),
+                (StyleColor::ScrollbarGrab, SURFACE_HOVERED),
+                (StyleColor::ScrollbarGrabHovered, SURFACE_ACTIVE),
+                (StyleColor::ScrollbarGrabActive, ACCENT),
+                (StyleColor::CheckMark, ACCENT_HOVERED),
+                (StyleColor::SliderGrab, ACCENT),
+                (StyleColor::SliderGrabActive, ACCENT_HOVERED),
+                (StyleColor::Button, SURFACE_HOVERED),
+                (StyleColor::ButtonHovered, SURFACE_ACTIVE),
+                (StyleColor::ButtonActive, ACCENT),
+                (StyleColor::Header, SURFACE),
+                (StyleColor::HeaderHovered, SURFACE_HOVERED),
+                (StyleColor::HeaderActive, SURFACE_ACTIVE),
+                (StyleColor::Separator, [0.25, 0.28, 0.34, 1.0]),
+                (StyleColor::SeparatorHovered, ACCENT),
+                (StyleColor::SeparatorActive, ACCENT_HOVERED),
+                (StyleColor::ResizeGrip, [0.4, 0.62, 0.87, 0.2]),
+                (StyleColor::ResizeGripHovered, [0.4, 0.62, 0.87, 0.6]),
+                (StyleColor::ResizeGripActive, ACCENT),
+                (StyleColor::Tab, SURFACE),
+                (StyleColor::TabHovered, SURFACE_ACTIVE),
+                (StyleColor::TabActive, SURFACE_HOVERED),
+                (StyleColor::TabUnfocused, BACKGROUND),
+                (StyleColor::TabUnfocusedActive, SURFACE),
+                (StyleColor::PlotLines, ACCENT),
+                (StyleColor::PlotLinesHovered, ACCENT_HOVERED),
+                (StyleColor::PlotHistogram, ACCENT),
+                (StyleColor::PlotHistogramHovered, ACCENT_HOVERED),
+                (StyleColor::TableHeaderBg, SURFACE),
+                (StyleColor::TableBorderStrong, [0.25, 0.28, 0.34, 1.0]),
+                (StyleColor::TableBorderLight, [0.2, 0.22, 0.27, 1.0]),
+                (StyleColor::TableRowBg, [0.0, 0.0, 0.0, 0.0]),
+                (StyleColor::TableRowBgAlt, [1.0, 1.0, 1.0, 0.03]),
+                (StyleColor::TextSelectedBg, [0.4, 0.62, 0.87, 0.35]),
+                (StyleColor::NavHighlight, ACCENT_HOVERED),
+            ],
+        );
+        Theme::new("Comfy", style)
+    }
+
+    /// Replaces the whole style with the theme's.
+    ///
+    /// Sizes are replaced too, so [`Style::scale_all_sizes`] has to be called again on high
+    /// DPI displays.
+    pub fn apply(&self, style: &mut Style) {
+        *style = self.style;
+    }
+
+    /// Returns the style between two themes, at `t` between 0.0 (`a`) and 1.0 (`b`), e.g. to
+    /// animate a theme switch.
+    ///
+    /// Sizes are interpolated linearly and colors in linear RGB, which avoids the dark middle
+    /// of a naive mix between sRGB values. Settings which can't be interpolated, such as
+    /// `anti_aliased_lines` or `window_menu_button_position`, switch from `a` to `b` halfway.
+    pub fn lerp(a: &Theme, b: &Theme, t: f32) -> Style {
+        if t <= 0.0 {
+            return a.style;
+        } else if t >= 1.0 {
+            return b.style;
+        }
+
+        let (a, b) = (&a.style, &b.style);
+        let mut style = if t < 0.5 { *a } else { *b };
+        macro_rules! lerp_fields {
+            ($($field:ident),* $(,)?) => {
+                $(style.$field = Lerp::lerp(a.$field, b.$field, t);)*
+            };
+        }
+        lerp_fields!(
+            alpha,
+            disabled_alpha,
+            window_padding,
+            window_rounding,
+            window_border_size,
+            window_min_size,
+            window_title_align,
+            child_rounding,
+            child_border_size,
+            popup_rounding,
+            popup_border_size,
+            frame_padding,
+            frame_rounding,
+            frame_border_size,
+            item_spacing,
+            item_inner_spacing,
+            cell_padding,
+            touch_extra_padding,
+            indent_spacing,
+            columns_min_spacing,
+            scrollbar_size,
+            scrollbar_rounding,
+            grab_min_size,
+            grab_rounding,
+            log_slider_deadzone,
+            tab_rounding,
+            tab_border_size,
+            tab_min_width_for_close_button,
+            button_text_align,
+            selectable_text_align,
+            display_window_padding,
+            display_safe_area_padding,
+            mouse_cursor_scale,
+            curve_tessellation_tol,
+            circle_tesselation_max_error,
+        );
+        for (color, (from, to)) in style.colors.iter_mut().zip(a.colors.iter().zip(&b.colors)) {
+            *color = lerp_color(*from, *to, t);
+        }
+        style
+    }
+}
+
+trait Lerp: Copy {
+    fn lerp(a: Self, b: Self, t: f32) -> Self;
+}
+
+impl Lerp for f32 {
+    fn lerp(a: f32, b: f32, t: f32) -> f32 {
+        a + (b - a) * t
+    }
+}
+
+impl Lerp for [f32; 2] {
+    fn lerp(a: [f32; 2], b: [f32; 2], t: f32) -> [f32; 2] {
+        [f32::lerp(a[0], b[0], t), f32::lerp(a[1], b[1], t)]
+    }
+}
+
+fn set_colors(style: &mut Style, colors: &[(StyleColor, [f32; 4])]) {
+    for &(color, value) in colors {
+        style[color] = value;
+    }
+}
+
+fn srgb_to_linear(c: f32) -> f32 {
+    if c <= 0.04045 {
+        c / 12.92
+    } else {
+        ((c + 0.055) / 1.055).powf(2.4)
+    }
+}
+
+fn linear_to_srgb(c: f32) -> f32 {
+    if c <= 0.003_130_8 {
+        c * 12.92
+    } else {
+        1.055 * c.powf(1.0 / 2.4) - 0.055
+    }
+}
+
+/// Interpolates the RGB components in linear space, and alpha as is
+fn lerp_color(a: [f32; 4], b: [f32; 4], t: f32) -> [f32; 4] {
+    let rgb = |i: usize| linear_to_srgb(f32::lerp(srgb_to_linear(a[i]), srgb_to_linear(b[i]), t));
+    [rgb(0), rgb(1), rgb(2), f32::lerp(a[3], b[3], t)]
+}
+
+#[test]
+fn test_theme_presets() {
+    // Defining values of each preset, to catch accidental changes
+    let snapshot = [
+        (
+            "Dark",
+            0.0,
+            1.0,
+            [0.06, 0.06, 0.06, 0.94],
+            [1.0, 1.0, 1.0, 1.0],
+        ),
+        (
+            "Light",
+            0.0,
+            1.0,
+            [0.94, 0.94, 0.94, 1.0],
+            [0.0, 0.0, 0.0, 1.0],
+        ),
+        (
+            "Classic",
+            0.0,
+            1.0,
+            [0.0, 0.0, 0.0, 0.85],
+            [0.9, 0.9, 0.9, 1.0],
+        ),
+        (
+            "High contrast",
+            0.0,
+            2.0,
+            [0.0, 0.0, 0.0, 1.0],
+            [1.0, 1.0, 1.0, 1.0],
+        ),
+        (
+            "Comfy",
+            6.0,
+            0.0,
+            [0.11, 0.12, 0.15, 1.0],
+            [0.9, 0.92, 0.95, 1.0],
+        ),
+    ];
+    let presets = Theme::presets();
+    assert_eq!(presets.len(), snapshot.len());
+    for (theme, (name, frame_rounding, window_border_size, window_bg, text)) in
+        presets.iter().zip(snapshot)
+    {
+        assert_eq!(theme.name, name);
+        let style = &theme.style;
+        assert_eq!(style.frame_rounding, frame_rounding, "{}", name);
+        assert_eq!(style.window_border_size, window_border_size, "{}", name);
+        assert_eq!(style[StyleColor::WindowBg], window_bg, "{}", name);
+        assert_eq!(style[StyleColor::Text], text, "{}", name);
+        for color in style.colors.iter().flatten() {
+            assert!((0.0..=1.0).contains(color), "{}", name);
+        }
+    }
+
+    let mut style = Style::default();
+    Theme::comfy().apply(&mut style);
+    assert_eq!(style.window_rounding, 10.0);
+    assert_eq!(style.colors, Theme::comfy().style.colors);
+}
+
+#[test]
+fn test_theme_lerp() {
+    use crate::Direction;
+
+    let mut a = Style {
+        window_padding: [0.0, 10.0],
+        window_rounding: 0.0,
+        anti_aliased_lines: true,
+        window_menu_button_position: Direction::Left,
+        ..Style::default()
+    };
+    a[StyleColor::Text] = [0.0, 0.0, 0.0, 0.0];
+    let mut b = a;
+    b.window_padding = [10.0, 20.0];
+    b.window_rounding = 4.0;
+    b.anti_aliased_lines = false;
+    b.window_menu_button_position = Direction::Right;
+    b[StyleColor::Text] = [1.0, 1.0, 1.0, 1.0];
+    let (a, b) = (Theme::new("A", a), Theme::new("B", b));
+
+    let start = Theme::lerp(&a, &b, 0.0);
+    assert_eq!(start.window_padding, [0.0, 10.0]);
+    assert!(start.anti_aliased_lines);
+    assert_eq!(start.colors, a.style.colors);
+
+    let middle = Theme::lerp(&a, &b, 0.5);
+    assert_eq!(middle.window_padding, [5.0, 15.0]);
+    assert_eq!(middle.window_rounding, 2.0);
+    assert!(!middle.anti_aliased_lines);
+    assert_eq!(middle.window_menu_button_position, Direction::Right);
+    // Half of the light intensity, which is lighter than 0.5 in sRGB
+    let text = middle[StyleColor::Text];
+    assert!((text[0] - 0.7354).abs() < 1e-3, "{:?}", text);
+    assert_eq!(text[0], text[1]);
+    assert_eq!(text[3], 0.5);
+    // Colors which are the same in both themes don't change
+    assert!((middle[StyleColor::WindowBg][0] - a.style[StyleColor::WindowBg][0]).abs() < 1e-6);
+
+    let end = Theme::lerp(&a, &b, 1.0);
+    assert_eq!(end.window_padding, [10.0, 20.0]);
+    assert!(!end.anti_aliased_lines);
+    assert_eq!(end.colors, b.style.colors);
+}
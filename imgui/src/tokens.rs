@@ -17,7 +17,7 @@ macro_rules! create_token {
         $(#[$end_meta:meta])*
         drop { $on_drop:expr }
     ) => {
-        #[must_use]
+        #[must_use = "if unused, the token is dropped right away, which ends what it tracks"]
         $(#[$struct_meta])*
         pub struct $token_name<'a>($crate::__core::marker::PhantomData<&'a $crate::Ui>);
 
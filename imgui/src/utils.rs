@@ -5,6 +5,7 @@ use crate::input::mouse::MouseButton;
 use crate::math::MintVec2;
 use crate::style::StyleColor;
 use crate::sys;
+use crate::Id;
 use crate::Style;
 use crate::Ui;
 
@@ -21,11 +22,28 @@ bitflags! {
         /// Return true even if the item is disabled
         const ALLOW_WHEN_DISABLED = sys::ImGuiHoveredFlags_AllowWhenDisabled;
         const RECT_ONLY = sys::ImGuiHoveredFlags_RectOnly;
+        /// Disable using keyboard/gamepad navigation state when active, always query the mouse
+        const NO_NAV_OVERRIDE = sys::ImGuiHoveredFlags_NoNavOverride;
+        /// Return true after [`Io::hover_delay_normal`](crate::Io::hover_delay_normal) elapsed
         const DELAY_NORMAL = sys::ImGuiHoveredFlags_DelayNormal;
+        /// Return true after [`Io::hover_delay_short`](crate::Io::hover_delay_short) elapsed
         const DELAY_SHORT = sys::ImGuiHoveredFlags_DelayShort;
+        /// Don't share the delay timer with the previously hovered item, so moving between
+        /// items restarts the delay
+        const NO_SHARED_DELAY = sys::ImGuiHoveredFlags_NoSharedDelay;
     }
 }
 
+impl ItemHoveredFlags {
+    /// Flags for deciding whether to show a tooltip for the hovered item, as used by
+    /// [`Ui::item_tooltip`].
+    ///
+    /// Newer versions of Dear ImGui make this configurable through the style and also wait for
+    /// the mouse to stop moving; with the bundled version, this is the same as
+    /// [`DELAY_SHORT`](Self::DELAY_SHORT).
+    pub const FOR_TOOLTIP: ItemHoveredFlags = ItemHoveredFlags::DELAY_SHORT;
+}
+
 /// # Item/widget utilities
 impl Ui {
     /// Returns `true` if the last item is hovered
@@ -48,6 +66,11 @@ impl Ui {
     pub fn is_item_focused(&self) -> bool {
         unsafe { sys::igIsItemFocused() }
     }
+    /// Returns the id of the last item, or `Id(0)` if it doesn't have one (e.g. text).
+    #[doc(alias = "GetItemID")]
+    pub fn item_id(&self) -> Id {
+        Id(unsafe { sys::igGetItemID() })
+    }
     /// Returns `true` if the last item is being clicked by `MouseButton::Left`.
     ///
     /// This is the same as [is_item_clicked_with_button](Self::is_item_clicked_with_button)
@@ -209,3 +232,44 @@ impl Ui {
         &*(sys::igGetStyle() as *const Style)
     }
 }
+
+#[test]
+fn test_item_tooltip_delay() {
+    let (_guard, mut ctx) = crate::test::test_ctx_initialized();
+    assert_eq!(
+        ItemHoveredFlags::FOR_TOOLTIP.bits(),
+        sys::ImGuiHoveredFlags_DelayShort
+    );
+    assert_eq!(
+        ItemHoveredFlags::NO_SHARED_DELAY.bits(),
+        sys::ImGuiHoveredFlags_NoSharedDelay
+    );
+
+    let short_delay = ctx.io().hover_delay_short;
+    ctx.io_mut().delta_time = short_delay / 2.5;
+
+    // Returns whether the tooltip was shown, and the button's rectangle
+    let mut frame = |mouse_pos: [f32; 2]| {
+        ctx.io_mut().add_mouse_pos_event(mouse_pos);
+        let result = {
+            let ui = ctx.frame();
+            let _window = ui
+                .window("Example")
+                .position([0.0, 0.0], crate::Condition::Always)
+                .size([200.0, 100.0], crate::Condition::Always)
+                .begin();
+            ui.button("Hover me");
+            let mut shown = false;
+            ui.item_tooltip(|| shown = true);
+            (shown, ui.item_rect_min(), ui.item_rect_max())
+        };
+        ctx.render();
+        result
+    };
+
+    let (_, min, max) = frame([-1.0, -1.0]);
+    let inside = [(min[0] + max[0]) / 2.0, (min[1] + max[1]) / 2.0];
+    let shown: Vec<_> = (0..5).map(|_| frame(inside).0).collect();
+    assert_eq!(shown, [false, false, false, true, true]);
+    assert!(!frame([-1.0, -1.0]).0);
+}
@@ -170,6 +170,48 @@ bitflags! {
     }
 }
 
+/// Groups of flags of which Dear ImGui accepts at most one option at a time.
+const DISPLAY_GROUP: (ColorEditFlags, &str) = (
+    ColorEditFlags::from_bits_truncate(
+        ColorEditFlags::DISPLAY_RGB.bits()
+            | ColorEditFlags::DISPLAY_HSV.bits()
+            | ColorEditFlags::DISPLAY_HEX.bits(),
+    ),
+    "display",
+);
+const INPUT_GROUP: (ColorEditFlags, &str) = (
+    ColorEditFlags::from_bits_truncate(
+        ColorEditFlags::INPUT_RGB.bits() | ColorEditFlags::INPUT_HSV.bits(),
+    ),
+    "input",
+);
+const PICKER_GROUP: (ColorEditFlags, &str) = (
+    ColorEditFlags::from_bits_truncate(
+        ColorEditFlags::PICKER_HUE_BAR.bits() | ColorEditFlags::PICKER_HUE_WHEEL.bits(),
+    ),
+    "picker",
+);
+const FORMAT_GROUP: (ColorEditFlags, &str) = (
+    ColorEditFlags::from_bits_truncate(ColorEditFlags::UINT8.bits() | ColorEditFlags::FLOAT.bits()),
+    "format",
+);
+
+/// Panics if more than one flag of any of the given groups is set.
+///
+/// Dear ImGui only checks this with an `IM_ASSERT`, which aborts the process (or is compiled out
+/// entirely), so catch it on the Rust side with a readable message instead.
+fn assert_exclusive_flags(flags: ColorEditFlags, groups: &[(ColorEditFlags, &str)]) {
+    for &(mask, name) in groups {
+        let set = flags & mask;
+        assert!(
+            set.bits().count_ones() <= 1,
+            "only one {} option can be selected at a time, got {:?}",
+            name,
+            set
+        );
+    }
+}
+
 /// Builder for a color editor widget.
 ///
 /// # Examples
@@ -327,8 +369,13 @@ where
     /// Builds the color editor.
     ///
     /// Returns true if the color value was changed.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the flags select more than one display or input mode.
     pub fn build(mut self) -> bool {
         self.flags.insert(ColorEditFlags::NO_ALPHA);
+        assert_exclusive_flags(self.flags, &[DISPLAY_GROUP, INPUT_GROUP]);
 
         let as_vec3: MintVec3 = (*self.value).into();
         let mut as_vec3: [f32; 3] = as_vec3.into();
@@ -547,7 +594,12 @@ where
     /// Builds the color editor.
     ///
     /// Returns true if the color value was changed.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the flags select more than one display or input mode.
     pub fn build(self) -> bool {
+        assert_exclusive_flags(self.flags, &[DISPLAY_GROUP, INPUT_GROUP]);
         let as_vec4: MintVec4 = (*self.value).into();
         let mut as_vec4: [f32; 4] = as_vec4.into();
 
@@ -729,18 +781,27 @@ where
         self
     }
     /// Enables/disables displaying the value as RGB.
+    ///
+    /// Unlike color editors, a picker can show several of these at once. If none are enabled,
+    /// all of them are shown.
     #[inline]
     pub fn display_rgb(mut self, value: bool) -> Self {
         self.flags.set(ColorEditFlags::DISPLAY_RGB, value);
         self
     }
     /// Enables/disables displaying the value as HSV.
+    ///
+    /// Unlike color editors, a picker can show several of these at once. If none are enabled,
+    /// all of them are shown.
     #[inline]
     pub fn display_hsv(mut self, value: bool) -> Self {
         self.flags.set(ColorEditFlags::DISPLAY_HSV, value);
         self
     }
     /// Enables/disables displaying the value as hex.
+    ///
+    /// Unlike color editors, a picker can show several of these at once. If none are enabled,
+    /// all of them are shown.
     #[inline]
     pub fn display_hex(mut self, value: bool) -> Self {
         self.flags.set(ColorEditFlags::DISPLAY_HEX, value);
@@ -772,8 +833,13 @@ where
     /// Builds the color picker.
     ///
     /// Returns true if the color value was changed.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the flags select more than one picker or input mode.
     pub fn build(mut self) -> bool {
         self.flags.insert(ColorEditFlags::NO_ALPHA);
+        assert_exclusive_flags(self.flags, &[PICKER_GROUP, INPUT_GROUP]);
         let mut value: [f32; 3] = (*self.value).into().into();
         let changed = unsafe {
             sys::igColorPicker3(
@@ -954,18 +1020,27 @@ where
         self
     }
     /// Enables/disables displaying the value as RGB.
+    ///
+    /// Unlike color editors, a picker can show several of these at once. If none are enabled,
+    /// all of them are shown.
     #[inline]
     pub fn display_rgb(mut self, value: bool) -> Self {
         self.flags.set(ColorEditFlags::DISPLAY_RGB, value);
         self
     }
     /// Enables/disables displaying the value as HSV.
+    ///
+    /// Unlike color editors, a picker can show several of these at once. If none are enabled,
+    /// all of them are shown.
     #[inline]
     pub fn display_hsv(mut self, value: bool) -> Self {
         self.flags.set(ColorEditFlags::DISPLAY_HSV, value);
         self
     }
     /// Enables/disables displaying the value as hex.
+    ///
+    /// Unlike color editors, a picker can show several of these at once. If none are enabled,
+    /// all of them are shown.
     #[inline]
     pub fn display_hex(mut self, value: bool) -> Self {
         self.flags.set(ColorEditFlags::DISPLAY_HEX, value);
@@ -1003,7 +1078,12 @@ where
     /// Builds the color picker.
     ///
     /// Returns true if the color value was changed.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the flags select more than one picker or input mode.
     pub fn build(self) -> bool {
+        assert_exclusive_flags(self.flags, &[PICKER_GROUP, INPUT_GROUP]);
         let mut value: [f32; 4] = (*self.value).into().into();
         let ref_color = self.ref_color.map(|c| c.as_ptr()).unwrap_or(ptr::null());
 
@@ -1223,8 +1303,97 @@ impl Ui {
     /// settings, unless you use .options(false) in your widget builders.
     #[doc(alias = "SetColorEditOptions")]
     pub fn set_color_edit_options(&self, flags: ColorEditFlags) {
+        assert_exclusive_flags(
+            flags,
+            &[DISPLAY_GROUP, INPUT_GROUP, PICKER_GROUP, FORMAT_GROUP],
+        );
         unsafe {
             sys::igSetColorEditOptions(flags.bits() as i32);
         }
     }
 }
+
+#[test]
+fn test_color_picker4_flags() {
+    let (_guard, mut ctx) = crate::test::test_ctx_initialized();
+    let ui = ctx.frame();
+    let mut color = [0.0, 0.0, 0.0, 1.0];
+
+    let picker = ui
+        .color_picker4_config("picker", &mut color)
+        .mode(ColorPickerMode::HueWheel)
+        .preview(ColorPreview::HalfAlpha)
+        .display_rgb(true)
+        .display_hex(true)
+        .reference_color([1.0, 0.0, 0.0, 1.0]);
+    assert_eq!(
+        picker.flags,
+        ColorEditFlags::PICKER_HUE_WHEEL
+            | ColorEditFlags::ALPHA_PREVIEW_HALF
+            | ColorEditFlags::DISPLAY_RGB
+            | ColorEditFlags::DISPLAY_HEX
+    );
+    assert_eq!(picker.ref_color, Some([1.0, 0.0, 0.0, 1.0]));
+
+    // Later settings of the same group replace earlier ones
+    let picker = picker
+        .mode(ColorPickerMode::HueBar)
+        .preview(ColorPreview::Alpha)
+        .display_rgb(false)
+        .input_mode(ColorEditInputMode::Hsv);
+    assert_eq!(
+        picker.flags,
+        ColorEditFlags::PICKER_HUE_BAR
+            | ColorEditFlags::ALPHA_PREVIEW
+            | ColorEditFlags::DISPLAY_HEX
+            | ColorEditFlags::INPUT_HSV
+    );
+
+    let picker = picker.preview(ColorPreview::Opaque);
+    assert!(!picker
+        .flags
+        .intersects(ColorEditFlags::ALPHA_PREVIEW | ColorEditFlags::ALPHA_PREVIEW_HALF));
+}
+
+#[test]
+fn test_color_edit_display_mode_is_exclusive() {
+    let (_guard, mut ctx) = crate::test::test_ctx_initialized();
+    let ui = ctx.frame();
+    let mut color = [0.0, 0.0, 0.0, 1.0];
+
+    let edit = ui
+        .color_edit4_config("edit", &mut color)
+        .display_mode(ColorEditDisplayMode::Hsv)
+        .display_mode(ColorEditDisplayMode::Hex)
+        .format(ColorFormat::Float);
+    assert_eq!(
+        edit.flags,
+        ColorEditFlags::DISPLAY_HEX | ColorEditFlags::FLOAT
+    );
+}
+
+#[test]
+fn test_exclusive_flags() {
+    assert_exclusive_flags(
+        ColorEditFlags::PICKER_HUE_WHEEL
+            | ColorEditFlags::DISPLAY_RGB
+            | ColorEditFlags::DISPLAY_HEX,
+        &[PICKER_GROUP, INPUT_GROUP],
+    );
+    assert_exclusive_flags(ColorEditFlags::empty(), &[DISPLAY_GROUP, FORMAT_GROUP]);
+
+    let result = std::panic::catch_unwind(|| {
+        assert_exclusive_flags(
+            ColorEditFlags::PICKER_HUE_BAR | ColorEditFlags::PICKER_HUE_WHEEL,
+            &[PICKER_GROUP],
+        )
+    });
+    assert!(result.is_err());
+    let result = std::panic::catch_unwind(|| {
+        assert_exclusive_flags(
+            ColorEditFlags::DISPLAY_RGB | ColorEditFlags::DISPLAY_HSV,
+            &[DISPLAY_GROUP],
+        )
+    });
+    assert!(result.is_err());
+}
@@ -2,7 +2,7 @@ use bitflags::bitflags;
 use std::borrow::Cow;
 
 use crate::sys;
-use crate::Ui;
+use crate::{ListClipper, Ui};
 
 // TODO: support size constraints
 
@@ -349,6 +349,106 @@ impl Ui {
         result
     }
 
+    /// Builds a combo box for choosing from `len` items, whose labels are produced on demand.
+    ///
+    /// Unlike [`Ui::combo`], no slice of items is needed: `label_fn` is only called for the
+    /// current item (to show the preview) and, while the combo box is open, for the items
+    /// which are visible. This makes it suitable for very long lists.
+    ///
+    /// ```no_run
+    /// # use imgui::*;
+    /// # use std::borrow::Cow;
+    /// # let mut ctx = Context::create();
+    /// # { let ui = ctx.frame();
+    /// let mut current = 0;
+    /// // no `Vec<String>` is built for the 100k items
+    /// ui.combo_with("Entity", &mut current, 100_000, |i| {
+    ///     Cow::Owned(format!("Entity #{}", i))
+    /// });
+    /// # };
+    /// ```
+    #[doc(alias = "Combo")]
+    pub fn combo_with<'a, L>(
+        &self,
+        label: impl AsRef<str>,
+        current_item: &mut usize,
+        len: usize,
+        label_fn: L,
+    ) -> bool
+    where
+        L: Fn(usize) -> Cow<'a, str>,
+    {
+        self.combo_with_flags(label, current_item, len, ComboBoxFlags::empty(), label_fn)
+    }
+
+    /// Builds a combo box for choosing from `len` items, whose labels are produced on demand.
+    ///
+    /// See [`Ui::combo_with`].
+    #[doc(alias = "Combo")]
+    pub fn combo_with_flags<'a, L>(
+        &self,
+        label: impl AsRef<str>,
+        current_item: &mut usize,
+        len: usize,
+        flags: ComboBoxFlags,
+        label_fn: L,
+    ) -> bool
+    where
+        L: Fn(usize) -> Cow<'a, str>,
+    {
+        let preview_value = if *current_item < len {
+            Some(label_fn(*current_item))
+        } else {
+            None
+        };
+
+        let cmbx = ComboBox {
+            label,
+            preview_value,
+            flags,
+            ui: self,
+        }
+        .begin();
+
+        match cmbx {
+            Some(_cb) => self.clipped_selectables(current_item, len, &label_fn),
+            None => false,
+        }
+    }
+
+    /// Submits a selectable for each visible item, for [`Ui::combo_with`] and
+    /// [`Ui::list_box_with`].
+    pub(crate) fn clipped_selectables<'a, L>(
+        &self,
+        current_item: &mut usize,
+        len: usize,
+        label_fn: &L,
+    ) -> bool
+    where
+        L: Fn(usize) -> Cow<'a, str>,
+    {
+        let mut result = false;
+        let mut clipper = ListClipper::new(len.min(i32::MAX as usize) as i32).begin(self);
+        if *current_item < len {
+            // so the current item can be focused and scrolled to, even when clipped
+            clipper.include_item_by_index(*current_item as i32);
+        }
+        for idx in clipper.iter() {
+            let idx = idx as usize;
+            let text = label_fn(idx);
+            let selected = idx == *current_item;
+            let _id = self.push_id_usize(idx);
+            if self.selectable_config(&text).selected(selected).build() {
+                *current_item = idx;
+                result = true;
+            }
+            if selected {
+                self.set_item_default_focus();
+            }
+        }
+        result
+    }
+
     /// Builds a simple combo box for choosing from a slice of strings
     ///
     /// This is useful if you already have a list of strings to choose
@@ -72,7 +72,10 @@ impl Image {
     }
 }
 
-/// Builder for an image button widget
+/// Builder for an image button widget.
+///
+/// The button is identified by its string id rather than by its texture, so several buttons can
+/// show the same texture.
 #[derive(Copy, Clone, Debug)]
 #[must_use]
 pub struct ImageButton<'ui, StrId> {
@@ -81,6 +84,7 @@ pub struct ImageButton<'ui, StrId> {
     size: [f32; 2],
     uv0: [f32; 2],
     uv1: [f32; 2],
+    frame_padding: Option<[f32; 2]>,
     bg_col: [f32; 4],
     tint_col: [f32; 4],
     ui: &'ui Ui,
@@ -130,6 +134,7 @@ impl<'ui, StrId: AsRef<str>> ImageButton<'ui, StrId> {
             size: size.into().into(),
             uv0: [0.0, 0.0],
             uv1: [1.0, 1.0],
+            frame_padding: None,
             bg_col: [0.0, 0.0, 0.0, 0.0],
             tint_col: [1.0, 1.0, 1.0, 1.0],
             ui,
@@ -162,10 +167,26 @@ impl<'ui, StrId: AsRef<str>> ImageButton<'ui, StrId> {
         self.tint_col = tint_col.into().into();
         self
     }
+    /// Sets the padding around the image (default: `Style::frame_padding`).
+    ///
+    /// `[0.0, 0.0]` removes the frame around the image.
+    pub fn frame_padding(mut self, frame_padding: impl Into<MintVec2>) -> Self {
+        self.frame_padding = Some(frame_padding.into().into());
+        self
+    }
     /// Builds the image button
+    ///
+    /// Returns true if the button was clicked.
     pub fn build(self) -> bool {
         unsafe {
-            sys::igImageButton(
+            if let Some(frame_padding) = self.frame_padding {
+                sys::igPushStyleVar_Vec2(
+                    sys::ImGuiStyleVar_FramePadding as i32,
+                    frame_padding.into(),
+                );
+            }
+
+            let res = sys::igImageButton(
                 self.ui.scratch_txt(self.str_id),
                 self.texture_id.id() as *mut c_void,
                 self.size.into(),
@@ -173,7 +194,13 @@ impl<'ui, StrId: AsRef<str>> ImageButton<'ui, StrId> {
                 self.uv1.into(),
                 self.bg_col.into(),
                 self.tint_col.into(),
-            )
+            );
+
+            if self.frame_padding.is_some() {
+                sys::igPopStyleVar(1);
+            }
+
+            res
         }
     }
 }
@@ -248,6 +275,11 @@ impl ImageButtonDeprecated {
 }
 
 impl Ui {
+    /// Renders a button showing an image, identified by the given string id.
+    ///
+    /// Returns true if the button was clicked. Use
+    /// [image_button_config](Self::image_button_config) for a builder with additional options.
+    #[doc(alias = "ImageButton")]
     pub fn image_button(
         &self,
         str_id: impl AsRef<str>,
@@ -260,6 +292,7 @@ impl Ui {
             size: size.into().into(),
             uv0: [0.0, 0.0],
             uv1: [1.0, 1.0],
+            frame_padding: None,
             bg_col: [0.0, 0.0, 0.0, 0.0],
             tint_col: [1.0, 1.0, 1.0, 1.0],
             ui: self,
@@ -267,6 +300,11 @@ impl Ui {
         .build()
     }
 
+    /// Constructs a new image button builder.
+    ///
+    /// The texture id can come from any renderer, e.g. a font atlas or a texture registered
+    /// with the renderer's texture map.
+    #[doc(alias = "ImageButton")]
     pub fn image_button_config<IdStr: AsRef<str>>(
         &self,
         str_id: IdStr,
@@ -279,9 +317,50 @@ impl Ui {
             size: size.into().into(),
             uv0: [0.0, 0.0],
             uv1: [1.0, 1.0],
+            frame_padding: None,
             bg_col: [0.0, 0.0, 0.0, 0.0],
             tint_col: [1.0, 1.0, 1.0, 1.0],
             ui: self,
         }
     }
 }
+
+#[test]
+fn test_image_buttons_sharing_a_texture() {
+    let (_guard, mut ctx) = crate::test::test_ctx_initialized();
+    let texture_id = TextureId::new(1);
+
+    // Returns which buttons were clicked, and their rectangles
+    let mut frame = |mouse_pos: [f32; 2], mouse_down: bool| {
+        let io = ctx.io_mut();
+        io.add_mouse_pos_event(mouse_pos);
+        io.add_mouse_button_event(crate::MouseButton::Left, mouse_down);
+        let result = {
+            let ui = ctx.frame();
+            let _window = ui
+                .window("Example")
+                .position([0.0, 0.0], crate::Condition::Always)
+                .size([200.0, 200.0], crate::Condition::Always)
+                .begin();
+            let first = ui.image_button("first", texture_id, [32.0, 32.0]);
+            let first_rect = [ui.item_rect_min(), ui.item_rect_max()];
+            let second = ui
+                .image_button_config("second", texture_id, [32.0, 32.0])
+                .frame_padding([0.0, 0.0])
+                .build();
+            let second_rect = [ui.item_rect_min(), ui.item_rect_max()];
+            ([first, second], [first_rect, second_rect])
+        };
+        ctx.render();
+        result
+    };
+
+    let (_, rects) = frame([-1.0, -1.0], false);
+    let center = |[min, max]: [[f32; 2]; 2]| [(min[0] + max[0]) / 2.0, (min[1] + max[1]) / 2.0];
+
+    assert_eq!(frame(center(rects[1]), true).0, [false, false]);
+    assert_eq!(frame(center(rects[1]), false).0, [false, true]);
+
+    assert_eq!(frame(center(rects[0]), true).0, [false, false]);
+    assert_eq!(frame(center(rects[0]), false).0, [true, false]);
+}
@@ -68,6 +68,25 @@ create_token!(
 
 /// # Convenience functions
 impl<T: AsRef<str>> ListBox<T> {
+    /// Builds a list box for choosing from `len` items, whose labels are produced on demand.
+    ///
+    /// `label_fn` is only called for the visible items. See [`Ui::list_box_with`].
+    pub fn build_with<'a, L>(
+        self,
+        ui: &Ui,
+        current_item: &mut usize,
+        len: usize,
+        label_fn: L,
+    ) -> bool
+    where
+        L: Fn(usize) -> Cow<'a, str>,
+    {
+        match self.begin(ui) {
+            Some(_lb) => ui.clipped_selectables(current_item, len, &label_fn),
+            None => false,
+        }
+    }
+
     /// Builds a simple list box for choosing from a slice of values
     pub fn build_simple<V, L>(
         self,
@@ -94,3 +113,27 @@ impl<T: AsRef<str>> ListBox<T> {
         result
     }
 }
+
+#[test]
+fn test_list_box_with_only_formats_visible_items() {
+    let (_guard, mut ctx) = crate::test::test_ctx_initialized();
+    let calls = std::cell::Cell::new(0);
+    let mut current = 50_000;
+
+    for _ in 0..2 {
+        {
+            let ui = ctx.frame();
+            let _window = ui.window("Example").begin();
+            calls.set(0);
+            ui.list_box_with("Items", &mut current, 100_000, |i| {
+                calls.set(calls.get() + 1);
+                Cow::Owned(format!("Item {}", i))
+            });
+        }
+        ctx.render();
+    }
+
+    assert!(calls.get() > 0);
+    assert!(calls.get() < 100, "{} labels formatted", calls.get());
+    assert_eq!(current, 50_000);
+}
@@ -1,5 +1,6 @@
 // use crate::string::ImStr;
 use crate::sys;
+use crate::KeyChord;
 use crate::Ui;
 
 /// # Widgets: Menus
@@ -132,6 +133,7 @@ impl Ui {
         MenuItem {
             label,
             shortcut: None,
+            chord: None,
             selected: false,
             enabled: true,
             ui: self,
@@ -145,6 +147,7 @@ impl Ui {
 pub struct MenuItem<'ui, Label, Shortcut = &'static str> {
     label: Label,
     shortcut: Option<Shortcut>,
+    chord: Option<KeyChord>,
     selected: bool,
     enabled: bool,
     ui: &'ui Ui,
@@ -157,6 +160,7 @@ impl<'ui, Label: AsRef<str>> MenuItem<'ui, Label> {
         MenuItem {
             label,
             shortcut: None,
+            chord: None,
             selected: false,
             enabled: true,
             ui,
@@ -167,7 +171,8 @@ impl<'ui, Label: AsRef<str>> MenuItem<'ui, Label> {
 impl<'ui, Label: AsRef<str>, Shortcut: AsRef<str>> MenuItem<'ui, Label, Shortcut> {
     /// Sets the menu item shortcut.
     ///
-    /// Shortcuts are displayed for convenience only and are not automatically handled.
+    /// Shortcuts are displayed for convenience only and are not automatically handled. See
+    /// [`shortcut_chord`](Self::shortcut_chord) for a shortcut that activates the item.
     #[inline]
     pub fn shortcut<Shortcut2: AsRef<str>>(
         self,
@@ -176,6 +181,38 @@ impl<'ui, Label: AsRef<str>, Shortcut: AsRef<str>> MenuItem<'ui, Label, Shortcut
         MenuItem {
             label: self.label,
             shortcut: Some(shortcut),
+            chord: self.chord,
+            selected: self.selected,
+            enabled: self.enabled,
+            ui: self.ui,
+        }
+    }
+    /// Sets a keyboard shortcut that activates the menu item, and displays its name (e.g.
+    /// "Ctrl+S") as the item's shortcut text.
+    ///
+    /// When the chord is pressed (see [`Ui::is_key_chord_pressed`]), [`build`](Self::build)
+    /// returns true just like when the item is clicked, unless the item is disabled.
+    ///
+    /// Items of a closed menu aren't built, so their chords can't be seen by the menu items
+    /// themselves. Check them with [`Ui::is_key_chord_pressed`] when the menu is closed:
+    ///
+    /// ```no_run
+    /// # use imgui::*;
+    /// # let mut ctx = Context::create();
+    /// # let ui = ctx.frame();
+    /// const SAVE: KeyChord = KeyChord::new(Key::S).ctrl();
+    ///
+    /// let save = match ui.begin_menu("File") {
+    ///     Some(_menu) => ui.menu_item_config("Save").shortcut_chord(SAVE).build(),
+    ///     None => ui.is_key_chord_pressed(SAVE),
+    /// };
+    /// ```
+    #[inline]
+    pub fn shortcut_chord(self, chord: KeyChord) -> MenuItem<'ui, Label, String> {
+        MenuItem {
+            label: self.label,
+            shortcut: Some(self.ui.key_chord_name(chord)),
+            chord: Some(chord),
             selected: self.selected,
             enabled: self.enabled,
             ui: self.ui,
@@ -199,12 +236,28 @@ impl<'ui, Label: AsRef<str>, Shortcut: AsRef<str>> MenuItem<'ui, Label, Shortcut
     }
     /// Builds the menu item.
     ///
-    /// Returns true if the menu item is activated.
+    /// Returns true if the menu item is activated, by clicking it or with its
+    /// [shortcut chord](Self::shortcut_chord).
     #[doc(alias = "MenuItemBool")]
     pub fn build(self) -> bool {
-        unsafe {
+        let chord_pressed = match self.chord {
+            Some(chord) => self.enabled && self.ui.is_key_chord_pressed(chord),
+            None => false,
+        };
+        let clicked = unsafe {
             let (label, shortcut) = self.ui.scratch_txt_with_opt(self.label, self.shortcut);
             sys::igMenuItem_Bool(label, shortcut, self.selected, self.enabled)
+        };
+        clicked || chord_pressed
+    }
+
+    /// Builds the menu item and runs a closure if it is activated.
+    /// Returns the result of the closure, if it is called.
+    pub fn build_with<R, F: FnOnce() -> R>(self, f: F) -> Option<R> {
+        if self.build() {
+            Some(f())
+        } else {
+            None
         }
     }
 
@@ -246,3 +299,31 @@ create_token!(
     /// Ends a menu
     drop { sys::igEndMenu() }
 );
+
+#[test]
+fn test_menu_item_shortcut_chord() {
+    let (_guard, mut ctx) = crate::test::test_ctx_initialized();
+    let save = KeyChord::new(crate::Key::S).ctrl();
+    ctx.io_mut().add_key_event(crate::Key::ModCtrl, true);
+    ctx.io_mut().add_key_event(crate::Key::S, true);
+
+    let ui = ctx.frame();
+    let _window = ui.window("Example").begin();
+    assert!(ui.menu_item_config("Save").shortcut_chord(save).build());
+    assert_eq!(
+        ui.menu_item_config("Save again")
+            .shortcut_chord(save)
+            .build_with(|| 42),
+        Some(42)
+    );
+    assert!(!ui
+        .menu_item_config("Disabled")
+        .shortcut_chord(save)
+        .enabled(false)
+        .build());
+    assert!(!ui.menu_item_config("Other").shortcut("Ctrl+S").build());
+    assert!(!ui
+        .menu_item_config("Open")
+        .shortcut_chord(KeyChord::new(crate::Key::O).ctrl())
+        .build());
+}
@@ -16,12 +16,25 @@ use crate::Ui;
 ///     .overlay_text("Progress!")
 ///     .build(&ui);
 /// ```
+///
+/// When the progress is unknown, an animated indeterminate progress bar can be used instead:
+///
+/// ```no_run
+/// # use imgui::*;
+/// # let mut imgui = Context::create();
+/// # let ui = imgui.frame();
+/// ProgressBar::indeterminate()
+///     .overlay_text("Working...")
+///     .build(&ui);
+/// ```
 #[derive(Copy, Clone, Debug)]
 #[must_use]
 pub struct ProgressBar<T = &'static str> {
     fraction: f32,
+    indeterminate: bool,
     size: [f32; 2],
     overlay_text: Option<T>,
+    show_overlay: bool,
 }
 
 impl ProgressBar {
@@ -35,22 +48,53 @@ impl ProgressBar {
     pub fn new(fraction: f32) -> Self {
         ProgressBar {
             fraction,
+            indeterminate: false,
             size: [-1.0, 0.0],
             overlay_text: None,
+            show_overlay: true,
+        }
+    }
+
+    /// Creates an indeterminate progress bar, for when the progress is unknown.
+    ///
+    /// Instead of filling up, a section of the bar moves along it, animated with
+    /// [`Ui::time`]. By default, no overlay text is shown.
+    ///
+    /// Like in newer versions of Dear ImGui, passing a negative fraction to
+    /// [`ProgressBar::new`] also draws an indeterminate progress bar, whose animation phase is
+    /// given by the fraction (e.g. `-ui.time()`).
+    #[inline]
+    pub fn indeterminate() -> Self {
+        ProgressBar {
+            indeterminate: true,
+            ..ProgressBar::new(0.0)
         }
     }
 }
 
 impl<T: AsRef<str>> ProgressBar<T> {
     /// Sets an optional text that will be drawn over the progress bar.
+    ///
+    /// By default, the progress is shown as a percentage.
     pub fn overlay_text<T2: AsRef<str>>(self, overlay_text: T2) -> ProgressBar<T2> {
         ProgressBar {
             fraction: self.fraction,
+            indeterminate: self.indeterminate,
             size: self.size,
             overlay_text: Some(overlay_text),
+            show_overlay: self.show_overlay,
         }
     }
 
+    /// Enables/disables the overlay text, including the default percentage.
+    ///
+    /// Default: enabled
+    #[inline]
+    pub fn show_overlay(mut self, value: bool) -> Self {
+        self.show_overlay = value;
+        self
+    }
+
     /// Sets the size of the progress bar.
     ///
     /// Negative values will automatically align to the end of the axis, zero will let the progress
@@ -63,12 +107,85 @@ impl<T: AsRef<str>> ProgressBar<T> {
 
     /// Builds the progress bar
     pub fn build(self, ui: &Ui) {
+        let fraction = if self.indeterminate {
+            -(ui.time() as f32)
+        } else {
+            self.fraction
+        };
+        // The default overlay shows the percentage, which is meaningless when indeterminate
+        let overlay = if self.show_overlay && (self.overlay_text.is_some() || fraction >= 0.0) {
+            ui.scratch_txt_opt(self.overlay_text)
+        } else {
+            ui.scratch_txt("")
+        };
+        if fraction >= 0.0 {
+            unsafe { sys::igProgressBar(fraction, self.size.into(), overlay) };
+            return;
+        }
+
+        // Dear ImGui 1.89 has no indeterminate progress bars, so draw an empty one and add the
+        // moving section ourselves
         unsafe {
-            sys::igProgressBar(
-                self.fraction,
-                self.size.into(),
-                ui.scratch_txt_opt(self.overlay_text),
+            sys::igProgressBar(0.0, self.size.into(), overlay);
+            if !ui.is_item_visible() {
+                return;
+            }
+            let style = ui.style();
+            let border = style.frame_border_size;
+            let [min_x, min_y] = ui.item_rect_min();
+            let [max_x, max_y] = ui.item_rect_max();
+            let (min_x, max_x) = (min_x + border, max_x - border);
+            let (start, end) = indeterminate_range(fraction);
+            if end <= start {
+                return;
+            }
+            sys::ImDrawList_AddRectFilled(
+                sys::igGetWindowDrawList(),
+                [min_x + (max_x - min_x) * start, min_y + border].into(),
+                [min_x + (max_x - min_x) * end, max_y - border].into(),
+                sys::igGetColorU32_Col(sys::ImGuiCol_PlotHistogram as i32, 1.0),
+                style.frame_rounding,
+                0,
             );
         }
     }
 }
+
+/// Width of the moving section of an indeterminate progress bar, as a fraction of the bar
+const INDETERMINATE_WIDTH: f32 = 0.2;
+
+/// Returns the start and end fractions of the section filled by an indeterminate progress bar,
+/// given its negative fraction.
+///
+/// The section enters on the left, moves to the right by one bar width per unit of `-fraction`,
+/// and leaves on the right, matching Dear ImGui's own indeterminate progress bars.
+fn indeterminate_range(fraction: f32) -> (f32, f32) {
+    let start = (-fraction).rem_euclid(1.0) * (1.0 + INDETERMINATE_WIDTH) - INDETERMINATE_WIDTH;
+    let end = (start + INDETERMINATE_WIDTH).clamp(0.0, 1.0);
+    (start.clamp(0.0, 1.0), end)
+}
+
+#[test]
+fn test_indeterminate_range() {
+    fn assert_range(fraction: f32, expected: (f32, f32)) {
+        let (start, end) = indeterminate_range(fraction);
+        assert!(
+            (start - expected.0).abs() < 1e-5 && (end - expected.1).abs() < 1e-5,
+            "fraction {}: got {:?}, expected {:?}",
+            fraction,
+            (start, end),
+            expected
+        );
+    }
+
+    // Entering on the left
+    assert_range(-0.0, (0.0, 0.0));
+    assert_range(-0.1, (0.0, 0.12));
+    // Fully inside
+    assert_range(-0.5, (0.4, 0.6));
+    // Leaving on the right
+    assert_range(-0.9, (0.88, 1.0));
+    // The animation loops every unit
+    assert_range(-1.5, (0.4, 0.6));
+    assert_range(-12.5, (0.4, 0.6));
+}
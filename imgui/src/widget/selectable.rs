@@ -21,6 +21,14 @@ bitflags!(
     }
 );
 
+impl SelectableFlags {
+    /// Hit testing to allow subsequent widgets to overlap this one.
+    ///
+    /// Alias for [`Self::ALLOW_ITEM_OVERLAP`], under the name used by newer versions of Dear
+    /// ImGui.
+    pub const ALLOW_OVERLAP: Self = Self::ALLOW_ITEM_OVERLAP;
+}
+
 impl Ui {
     /// Constructs a new simple selectable.
     ///
@@ -91,6 +99,9 @@ impl<'ui, T: AsRef<str>> Selectable<'ui, T> {
     }
     /// Enables/disables full column span (text will still fit in the current column).
     ///
+    /// In a table, this makes the selectable cover the whole row. It should be submitted in the
+    /// first column of the row, so the columns to its right are drawn over it.
+    ///
     /// Default: disabled
     pub fn span_all_columns(mut self, value: bool) -> Self {
         self.flags.set(SelectableFlags::SPAN_ALL_COLUMNS, value);
@@ -103,6 +114,16 @@ impl<'ui, T: AsRef<str>> Selectable<'ui, T> {
         self.flags.set(SelectableFlags::ALLOW_DOUBLE_CLICK, value);
         self
     }
+    /// Enables/disables allowing subsequent widgets to overlap this selectable.
+    ///
+    /// Combined with [`span_all_columns`](Self::span_all_columns), this lets widgets in the
+    /// other columns of a table row (e.g. buttons) still be clicked.
+    ///
+    /// Default: disabled
+    pub fn allow_overlap(mut self, value: bool) -> Self {
+        self.flags.set(SelectableFlags::ALLOW_OVERLAP, value);
+        self
+    }
     /// Enables/disables the selectable.
     ///
     /// When disabled, it cannot be selected and the text uses the disabled text color.
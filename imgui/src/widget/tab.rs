@@ -41,13 +41,26 @@ bitflags! {
 bitflags! {
     #[repr(transparent)]
     pub struct TabItemFlags: u32 {
+        /// Display a dot next to the title and don't close the tab when clicking its close
+        /// button; the closure is left to the application (e.g. after a "save changes?" prompt).
         const UNSAVED_DOCUMENT = sys::ImGuiTabItemFlags_UnsavedDocument;
+        /// Make the tab selected when it is submitted this frame.
+        ///
+        /// See [`TabItem::set_selected`].
         const SET_SELECTED = sys::ImGuiTabItemFlags_SetSelected;
+        /// Disable closing the tab with the middle mouse button.
         const NO_CLOSE_WITH_MIDDLE_MOUSE_BUTTON = sys::ImGuiTabItemFlags_NoCloseWithMiddleMouseButton;
+        /// Don't push the tab's ID on the ID stack while its contents are built.
         const NO_PUSH_ID = sys::ImGuiTabItemFlags_NoPushId;
+        /// Disable the tooltip for this tab.
         const NO_TOOLTIP = sys::ImGuiTabItemFlags_NoTooltip;
+        /// Disable reordering this tab, or having other tabs cross over it.
         const NO_REORDER = sys::ImGuiTabItemFlags_NoReorder;
+        /// Place the tab at the start of the tab bar, left of the regular tabs. Leading tabs
+        /// can't be reordered.
         const LEADING = sys::ImGuiTabItemFlags_Leading;
+        /// Place the tab at the end of the tab bar, right of the regular tabs. Trailing tabs
+        /// can't be reordered.
         const TRAILING = sys::ImGuiTabItemFlags_Trailing;
     }
 }
@@ -143,6 +156,18 @@ impl<'a, T: AsRef<str>> TabItem<'a, T> {
         self
     }
 
+    /// Select this tab when it is submitted, e.g. on the frame a document is opened.
+    ///
+    /// This should only be enabled for a single frame, otherwise the user can't select any
+    /// other tab.
+    ///
+    /// Disabled by default
+    #[inline]
+    pub fn set_selected(mut self, value: bool) -> Self {
+        self.flags.set(TabItemFlags::SET_SELECTED, value);
+        self
+    }
+
     #[must_use]
     pub fn begin(self, ui: &Ui) -> Option<TabItemToken<'_>> {
         ui.tab_item_with_flags(self.label, self.opened, self.flags)
@@ -215,6 +240,14 @@ impl Ui {
     }
 
     /// Creates a new tab item and returns a token if its contents are visible.
+    ///
+    /// Whether or not the tab is selected, the tab itself is the last item once this returns,
+    /// so the item queries apply to it: [`is_item_activated`](Self::is_item_activated) tells
+    /// when the user starts clicking (or dragging) it, and
+    /// [`item_rect_min`](Self::item_rect_min) gives its position in the tab bar. With
+    /// [`TabBarFlags::REORDERABLE`], sorting the tabs by that position gives the order
+    /// the user arranged them in. The layout is updated at the start of each frame's tab
+    /// bar, so positions are only valid once the tabs have been submitted for a frame.
     pub fn tab_item_with_flags(
         &self,
         label: impl AsRef<str>,
@@ -235,4 +268,69 @@ impl Ui {
             None
         }
     }
+
+    /// Creates a tab that behaves like a button, e.g. a "+" button to open a new document.
+    /// Returns true when clicked.
+    ///
+    /// This must be called inside a tab bar. It is usually combined with
+    /// [`TabItemFlags::TRAILING`] or [`TabItemFlags::LEADING`] to keep it at one end of the
+    /// tab bar.
+    #[doc(alias = "TabItemButton")]
+    pub fn tab_item_button(&self, label: impl AsRef<str>, flags: TabItemFlags) -> bool {
+        unsafe { sys::igTabItemButton(self.scratch_txt(label), flags.bits() as i32) }
+    }
+
+    /// Notifies the current tab bar that a tab was closed by the application, e.g. after
+    /// the user confirmed closing a tab with [`TabItemFlags::UNSAVED_DOCUMENT`].
+    ///
+    /// This must be called inside a tab bar, before submitting the tab it closes, to avoid a
+    /// frame of flicker. Tab bars otherwise assume a tab that isn't submitted anymore was
+    /// closed only on the next frame.
+    #[doc(alias = "SetTabItemClosed")]
+    pub fn set_tab_item_closed(&self, label: impl AsRef<str>) {
+        unsafe { sys::igSetTabItemClosed(self.scratch_txt(label)) }
+    }
+}
+
+#[test]
+fn test_tab_item_order_and_selection() {
+    let (_guard, mut ctx) = crate::test::test_ctx_initialized();
+
+    let mut positions = Vec::new();
+    let mut selected = None;
+    for frame in 0..3 {
+        {
+            let ui = ctx.frame();
+            let _window = ui
+                .window("Example")
+                .size([400.0, 200.0], crate::Condition::Always)
+                .begin();
+            let _tab_bar = ui
+                .tab_bar_with_flags("tabs", TabBarFlags::REORDERABLE)
+                .expect("tab bar should be visible");
+
+            positions.clear();
+            for label in ["one", "two", "three"] {
+                let tab = TabItem::new(label)
+                    .set_selected(frame == 1 && label == "two")
+                    .begin(ui);
+                positions.push((ui.item_rect_min()[0], label));
+                if tab.is_some() {
+                    selected = Some(label);
+                }
+            }
+            ui.tab_item_button("+", TabItemFlags::TRAILING | TabItemFlags::NO_TOOLTIP);
+            let button_x = ui.item_rect_min()[0];
+
+            if frame > 0 {
+                assert!(positions.iter().all(|&(x, _)| x < button_x));
+            }
+        }
+        ctx.render();
+    }
+
+    positions.sort_by(|a, b| a.0.partial_cmp(&b.0).unwrap());
+    let order: Vec<_> = positions.iter().map(|&(_, label)| label).collect();
+    assert_eq!(order, ["one", "two", "three"]);
+    assert_eq!(selected, Some("two"));
 }
@@ -3,7 +3,7 @@ use std::os::raw::c_char;
 use crate::math::MintVec4;
 // use crate::string::ImStr;
 use crate::style::StyleColor;
-use crate::Ui;
+use crate::{MouseCursor, Ui};
 
 static FMT: &[u8] = b"%s\0";
 
@@ -53,4 +53,127 @@ impl Ui {
     pub fn bullet_text(&self, text: impl AsRef<str>) {
         unsafe { sys::igBulletText(fmt_ptr(), self.scratch_txt(text)) }
     }
+    /// Renders hyperlink-style text, which is underlined and can be clicked.
+    ///
+    /// Like other widgets, anything after `##` in the label is used for the id but not shown.
+    /// Returns true if the link was clicked.
+    #[doc(alias = "TextLink")]
+    pub fn text_link(&self, label: impl AsRef<str>) -> bool {
+        // Dear ImGui 1.89 has no TextLink, so build it from an invisible button, with the same
+        // visuals as the upstream widget
+        let label = label.as_ref();
+        let text = label.find("##").map_or(label, |end| &label[..end]);
+        let size = self.calc_text_size(text);
+        let [min_x, min_y] = self.cursor_screen_pos();
+        // invisible buttons can't be empty
+        let clicked = self.invisible_button(label, [size[0].max(1.0), size[1].max(1.0)]);
+        let hovered = self.is_item_hovered();
+        let held = self.is_item_active();
+        if hovered {
+            self.set_mouse_cursor(Some(MouseCursor::Hand));
+        }
+
+        let text_color = self.style_color(StyleColor::HeaderActive);
+        let line_color = text_link_line_color(text_color, hovered, held);
+        unsafe {
+            let draw_list = sys::igGetWindowDrawList();
+            let font = sys::igGetFont();
+            let font_scale = sys::igGetFontSize() / (*font).FontSize;
+            let line_y = min_y + size[1] + ((*font).Descent * font_scale * 0.2).floor();
+            sys::ImDrawList_AddLine(
+                draw_list,
+                [min_x, line_y].into(),
+                [min_x + size[0], line_y].into(),
+                sys::igGetColorU32_Vec4(line_color.into()),
+                1.0,
+            );
+            let start = text.as_ptr() as *const c_char;
+            sys::ImDrawList_AddText_Vec2(
+                draw_list,
+                [min_x, min_y].into(),
+                sys::igGetColorU32_Vec4(text_color.into()),
+                start,
+                start.add(text.len()),
+            );
+        }
+        clicked
+    }
+    /// Renders a [text link](Self::text_link) that opens the given URL when clicked, and shows
+    /// it in a tooltip when hovered.
+    ///
+    /// The URL is opened with the callback set by
+    /// [`Context::set_open_in_shell_callback`](crate::Context::set_open_in_shell_callback). By
+    /// default, nothing happens unless the `open-url` feature is enabled. Returns true if the
+    /// link was clicked.
+    #[doc(alias = "TextLinkOpenURL")]
+    pub fn text_link_open_url(&self, label: impl AsRef<str>, url: impl AsRef<str>) -> bool {
+        let url = url.as_ref();
+        let clicked = self.text_link(label);
+        if clicked {
+            self.open_in_shell.open(url);
+        }
+        if self.is_item_hovered() {
+            self.tooltip_text(url);
+        }
+        clicked
+    }
+}
+
+/// Returns the underline color of a text link, which is dimmed unless hovered
+fn text_link_line_color(text_color: [f32; 4], hovered: bool, held: bool) -> [f32; 4] {
+    let [r, g, b, a] = text_color;
+    let (mut h, mut s, mut v) = (0.0, 0.0, 0.0);
+    unsafe { sys::igColorConvertRGBtoHSV(r, g, b, &mut h, &mut s, &mut v) };
+    if hovered || held {
+        v = (v + if held { 0.4 } else { 0.3 }).min(1.0);
+        h = (h + 0.02) % 1.0;
+    } else {
+        v *= 0.5;
+    }
+    let (mut r, mut g, mut b) = (0.0, 0.0, 0.0);
+    unsafe { sys::igColorConvertHSVtoRGB(h, s, v, &mut r, &mut g, &mut b) };
+    [r, g, b, a]
+}
+
+#[test]
+fn test_text_link_hit_testing() {
+    let (_guard, mut ctx) = crate::test::test_ctx_initialized();
+    let opened = std::rc::Rc::new(std::cell::RefCell::new(Vec::new()));
+    let handler_opened = std::rc::Rc::clone(&opened);
+    ctx.set_open_in_shell_callback(move |url| handler_opened.borrow_mut().push(url.to_owned()));
+
+    // Returns whether the link was clicked, and its rectangle
+    let mut frame = |mouse_pos: [f32; 2], mouse_down: bool| {
+        let io = ctx.io_mut();
+        io.add_mouse_pos_event(mouse_pos);
+        io.add_mouse_button_event(crate::MouseButton::Left, mouse_down);
+        let result = {
+            let ui = ctx.frame();
+            let _window = ui
+                .window("Example")
+                .position([0.0, 0.0], crate::Condition::Always)
+                .size([200.0, 100.0], crate::Condition::Always)
+                .begin();
+            let clicked = ui.text_link_open_url("Example link##id", "https://example.com");
+            (clicked, ui.item_rect_min(), ui.item_rect_max())
+        };
+        ctx.render();
+        result
+    };
+
+    let (_, min, max) = frame([-1.0, -1.0], false);
+    let width = max[0] - min[0];
+    assert!((width - frame([-1.0, -1.0], false).2[0] + min[0]).abs() < f32::EPSILON);
+    let inside = [min[0] + 2.0, (min[1] + max[1]) / 2.0];
+    let outside = [max[0] + 10.0, (min[1] + max[1]) / 2.0];
+
+    // A click outside of the text does nothing
+    assert!(!frame(outside, true).0);
+    assert!(!frame(outside, false).0);
+    assert!(opened.borrow().is_empty());
+
+    // A click inside opens the link once the button is released
+    assert!(!frame(inside, true).0);
+    assert!(frame(inside, false).0);
+    assert_eq!(*opened.borrow(), ["https://example.com"]);
 }
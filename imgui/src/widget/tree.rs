@@ -3,7 +3,7 @@ use std::os::raw::{c_char, c_void};
 
 // use crate::string::ImStr;
 use crate::sys;
-use crate::{Condition, Ui};
+use crate::{Condition, Id, Ui};
 
 bitflags!(
     /// Flags for tree nodes
@@ -120,6 +120,18 @@ impl Ui {
             ui: self,
         }
     }
+
+    /// Opens or closes the tree node with the given id, as if the user had clicked on it.
+    ///
+    /// Unlike [`TreeNode::opened`], this doesn't need to be called right before the tree node is
+    /// built, which makes it useful for "expand all"/"collapse all" buttons. The state is
+    /// stored in the current window, so this must be called in the same window and with the
+    /// same ID stack as the tree node, with an id obtained from e.g. [`Ui::new_id_str`].
+    pub fn tree_node_set_open(&self, id: Id, open: bool) {
+        unsafe {
+            sys::ImGuiStorage_SetInt(sys::igGetStateStorage(), id.0, open as i32);
+        }
+    }
 }
 
 /// Builder for a tree node widget
@@ -266,6 +278,24 @@ impl<'a, T: AsRef<str>, L: AsRef<str>> TreeNode<'a, T, L> {
         self
     }
 
+    /// Extends the hit box to the right-most edge, even if not framed.
+    ///
+    /// Disabled by default.
+    pub fn span_avail_width(mut self, value: bool) -> Self {
+        self.flags.set(TreeNodeFlags::SPAN_AVAIL_WIDTH, value);
+        self
+    }
+
+    /// Extends the hit box to the left-most and right-most edges, bypassing the indented area.
+    ///
+    /// Inside a table, this makes the hover and selection highlight cover the whole column.
+    ///
+    /// Disabled by default.
+    pub fn span_full_width(mut self, value: bool) -> Self {
+        self.flags.set(TreeNodeFlags::SPAN_FULL_WIDTH, value);
+        self
+    }
+
     /// Left direction may move to this tree node from any of its child.
     ///
     /// Disabled by default.
@@ -513,3 +543,21 @@ impl Ui {
             .build_with_close_button(self, opened)
     }
 }
+
+#[test]
+fn test_tree_node_set_open() {
+    let (_guard, mut ctx) = crate::test::test_ctx_initialized();
+    let ui = ctx.frame();
+    let _window = ui.window("Example").begin();
+
+    assert!(ui.tree_node("closed").is_none());
+
+    ui.tree_node_set_open(ui.new_id_str("node"), true);
+    let node = ui.tree_node("node").expect("tree node should be open");
+    ui.tree_node_set_open(ui.new_id_str("child"), true);
+    assert!(ui.tree_node("child").is_some());
+    node.pop();
+
+    ui.tree_node_set_open(ui.new_id_str("node"), false);
+    assert!(ui.tree_node("node").is_none());
+}
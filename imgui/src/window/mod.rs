@@ -5,6 +5,8 @@ use std::ptr;
 use crate::math::MintVec2;
 use crate::sys;
 use crate::{Condition, Ui};
+#[cfg(feature = "docking")]
+use crate::{DockId, WindowClass};
 
 pub(crate) mod child_window;
 pub(crate) mod content_region;
@@ -159,6 +161,21 @@ impl Ui {
         unsafe { sys::igGetWindowSize(&mut out) };
         out.into()
     }
+    /// Returns true if the current window is docked
+    #[cfg(feature = "docking")]
+    #[doc(alias = "IsWindowDocked")]
+    pub fn is_window_docked(&self) -> bool {
+        unsafe { sys::igIsWindowDocked() }
+    }
+    /// Returns the dock node of the current window, or `None` if it isn't docked
+    #[cfg(feature = "docking")]
+    #[doc(alias = "GetWindowDockID")]
+    pub fn window_dock_id(&self) -> Option<DockId> {
+        match unsafe { sys::igGetWindowDockID() } {
+            0 => None,
+            id => Some(DockId(id)),
+        }
+    }
 }
 
 /// Builder for a window
@@ -180,6 +197,10 @@ pub struct Window<'ui, 'a, Label> {
     collapsed_cond: Condition,
     focused: bool,
     bg_alpha: f32,
+    #[cfg(feature = "docking")]
+    window_class: Option<WindowClass>,
+    #[cfg(feature = "docking")]
+    dock_id: Option<(DockId, Condition)>,
 }
 
 impl<'ui, 'a, Label: AsRef<str>> Window<'ui, 'a, Label> {
@@ -202,6 +223,10 @@ impl<'ui, 'a, Label: AsRef<str>> Window<'ui, 'a, Label> {
             collapsed_cond: Condition::Never,
             focused: false,
             bg_alpha: f32::NAN,
+            #[cfg(feature = "docking")]
+            window_class: None,
+            #[cfg(feature = "docking")]
+            dock_id: None,
         }
     }
     /// Enables the window close button, which sets the passed boolean to false when clicked
@@ -491,6 +516,24 @@ impl<'ui, 'a, Label: AsRef<str>> Window<'ui, 'a, Label> {
         self.flags |= WindowFlags::NO_INPUTS;
         self
     }
+    /// Sets the class of the window, which restricts where it can dock.
+    #[cfg(feature = "docking")]
+    #[doc(alias = "SetNextWindowClass")]
+    #[inline]
+    pub fn window_class(mut self, window_class: &WindowClass) -> Self {
+        self.window_class = Some(*window_class);
+        self
+    }
+    /// Docks the window into a dock node, such as a dockspace, under the given condition.
+    ///
+    /// This ignores the window classes, which only restrict docking by dragging windows.
+    #[cfg(feature = "docking")]
+    #[doc(alias = "SetNextWindowDockID")]
+    #[inline]
+    pub fn dock_id(mut self, dock_id: DockId, condition: Condition) -> Self {
+        self.dock_id = Some((dock_id, condition));
+        self
+    }
     /// Creates a window and starts appending to it.
     ///
     /// Returns `Some(WindowToken)` if the window is visible. After content has been
@@ -534,6 +577,14 @@ impl<'ui, 'a, Label: AsRef<str>> Window<'ui, 'a, Label> {
         if self.bg_alpha.is_finite() {
             unsafe { sys::igSetNextWindowBgAlpha(self.bg_alpha) };
         }
+        #[cfg(feature = "docking")]
+        if let Some(window_class) = &self.window_class {
+            unsafe { sys::igSetNextWindowClass(&window_class.raw()) };
+        }
+        #[cfg(feature = "docking")]
+        if let Some((dock_id, condition)) = self.dock_id {
+            unsafe { sys::igSetNextWindowDockID(dock_id.0, condition as i32) };
+        }
         let should_render = unsafe {
             sys::igBegin(
                 self.ui.scratch_txt(self.name),